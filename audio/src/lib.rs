@@ -1,14 +1,45 @@
 use glam::{Mat4, Vec3};
-use noren::{rdb::audio::AudioClip, DB};
+use noren::{rdb::audio::AudioClip, NorenError, DB};
 use resource_pool::{Handle, Pool};
-use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source};
-use std::io::{BufReader, Cursor, Read, Seek};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, Source, StreamError};
+use std::collections::HashMap;
+use std::io::Cursor;
 use std::mem::MaybeUninit;
-use std::{ffi::c_void, ptr::NonNull, sync::Arc};
+use std::{
+    ffi::c_void,
+    ptr::NonNull,
+    sync::{Arc, Mutex},
+};
 use tracing::info;
 
-trait AudioReadSeek: Read + Seek + Send + Sync + 'static {}
-impl<T: Read + Seek + Send + Sync + 'static> AudioReadSeek for T {}
+#[derive(Debug)]
+pub enum AudioError {
+    /// No database has been attached; holds the requested path.
+    DatabaseUnavailable(String),
+    /// The database has no clip at the requested path.
+    ClipNotFound(String, NorenError),
+    /// Every source slot is in use.
+    PoolFull,
+    /// The clip at the requested path could not be decoded.
+    Undecodable(String),
+}
+
+impl std::fmt::Display for AudioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AudioError::DatabaseUnavailable(path) => {
+                write!(f, "Audio database unavailable; cannot load clip '{path}'")
+            }
+            AudioError::ClipNotFound(path, err) => {
+                write!(f, "Failed to load audio clip '{path}': {err:?}")
+            }
+            AudioError::PoolFull => write!(f, "No free audio source slots"),
+            AudioError::Undecodable(path) => write!(f, "Failed to decode audio clip '{path}'"),
+        }
+    }
+}
+
+impl std::error::Error for AudioError {}
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 #[repr(C)]
@@ -26,6 +57,10 @@ pub struct AudioEngineInfo {
     pub channels: u32,
     pub backend: AudioBackend,
     pub debug_mode: bool,
+    /// Lowest and highest playback speed handed to the backend. Pitch after
+    /// Doppler is clamped into this range; zero or negative pitch plays at
+    /// the minimum.
+    pub pitch_range: [f32; 2],
 }
 
 impl Default for AudioEngineInfo {
@@ -35,6 +70,7 @@ impl Default for AudioEngineInfo {
             channels: 2,
             backend: AudioBackend::Dummy,
             debug_mode: false,
+            pitch_range: [0.25, 4.0],
         }
     }
 }
@@ -43,6 +79,7 @@ impl Default for AudioEngineInfo {
 pub struct Bus {
     volume: f32,
     parent: Option<Handle<Bus>>,
+    effect: BusEffect,
 }
 
 impl Bus {
@@ -50,7 +87,478 @@ impl Bus {
         Self {
             volume: 1.0,
             parent,
+            effect: BusEffect::None,
+        }
+    }
+}
+
+/// Effect applied to every source routed through a bus.
+///
+/// Effects are resolved for each source when the engine mixes, so a change
+/// also reaches sources that are already playing. Streams hand out their
+/// encoded bytes untouched and are not affected.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[repr(C)]
+pub enum BusEffect {
+    #[default]
+    None,
+    /// Comb-filter reverb. `room_size` and `damping` are in `0..=1`; `wet`
+    /// is the reverb's share of the output.
+    Reverb {
+        room_size: f32,
+        damping: f32,
+        wet: f32,
+    },
+    /// One-pole low-pass filter.
+    LowPass { cutoff_hz: f32 },
+}
+
+/// Comb filter lengths in samples at 44.1 kHz, taken from Freeverb.
+const REVERB_COMB_TUNING: [usize; 4] = [1116, 1188, 1277, 1356];
+
+impl BusEffect {
+    /// Process interleaved samples in place.
+    fn apply(&self, samples: &mut [f32], sample_rate: u32, channels: u16) {
+        let mut chain = EffectChain::new(vec![*self], sample_rate, channels);
+        for (index, sample) in samples.iter_mut().enumerate() {
+            *sample = chain.process(*sample, index);
+        }
+    }
+}
+
+/// Running state of one [`BusEffect`] over interleaved samples.
+enum EffectState {
+    None,
+    LowPass {
+        alpha: f32,
+        state: Vec<f32>,
+    },
+    Reverb {
+        feedback: f32,
+        damping: f32,
+        wet: f32,
+        /// Per channel: comb buffer, write index and damping filter state.
+        combs: Vec<Vec<(Vec<f32>, usize, f32)>>,
+    },
+}
+
+impl EffectState {
+    fn new(effect: BusEffect, sample_rate: u32, channels: usize) -> Self {
+        match effect {
+            BusEffect::None => EffectState::None,
+            BusEffect::LowPass { cutoff_hz } => {
+                let dt = 1.0 / sample_rate.max(1) as f32;
+                let rc = 1.0 / (std::f32::consts::TAU * cutoff_hz.max(1.0));
+                EffectState::LowPass {
+                    alpha: dt / (rc + dt),
+                    state: vec![0.0; channels],
+                }
+            }
+            BusEffect::Reverb {
+                room_size,
+                damping,
+                wet,
+            } => {
+                let scale = sample_rate as f32 / 44_100.0;
+                let combs: Vec<Vec<(Vec<f32>, usize, f32)>> = (0..channels)
+                    .map(|_| {
+                        REVERB_COMB_TUNING
+                            .iter()
+                            .map(|&len| (vec![0.0; ((len as f32 * scale) as usize).max(1)], 0, 0.0))
+                            .collect()
+                    })
+                    .collect();
+                EffectState::Reverb {
+                    feedback: 0.7 + 0.28 * room_size.clamp(0.0, 1.0),
+                    damping: damping.clamp(0.0, 1.0),
+                    wet: wet.clamp(0.0, 1.0),
+                    combs,
+                }
+            }
+        }
+    }
+
+    fn process(&mut self, sample: f32, channel: usize) -> f32 {
+        match self {
+            EffectState::None => sample,
+            EffectState::LowPass { alpha, state } => {
+                let y = &mut state[channel];
+                *y += *alpha * (sample - *y);
+                *y
+            }
+            EffectState::Reverb {
+                feedback,
+                damping,
+                wet,
+                combs,
+            } => {
+                let mut out = 0.0;
+                for (buffer, index, filtered) in combs[channel].iter_mut() {
+                    let delayed = buffer[*index];
+                    *filtered = delayed * (1.0 - *damping) + *filtered * *damping;
+                    buffer[*index] = sample + *filtered * *feedback;
+                    *index = (*index + 1) % buffer.len();
+                    out += delayed;
+                }
+                out /= REVERB_COMB_TUNING.len() as f32;
+                sample * (1.0 - *wet) + out * *wet
+            }
+        }
+    }
+}
+
+/// Effects of a bus chain run sample by sample, innermost bus first.
+struct EffectChain {
+    effects: Vec<BusEffect>,
+    states: Vec<EffectState>,
+    channels: usize,
+}
+
+impl EffectChain {
+    fn new(effects: Vec<BusEffect>, sample_rate: u32, channels: u16) -> Self {
+        let channels = channels.max(1) as usize;
+        let states = effects
+            .iter()
+            .map(|&effect| EffectState::new(effect, sample_rate, channels))
+            .collect();
+        Self {
+            effects,
+            states,
+            channels,
+        }
+    }
+
+    /// Process the interleaved sample at `index`.
+    fn process(&mut self, sample: f32, index: usize) -> f32 {
+        let channel = index % self.channels;
+        self.states
+            .iter_mut()
+            .fold(sample, |sample, state| state.process(sample, channel))
+    }
+}
+
+/// Bus effects resolved for a source by the last mix, shared with its Rodio
+/// sink so already-queued audio picks up changes.
+type SharedBusEffects = Arc<Mutex<Vec<BusEffect>>>;
+
+/// Samples between checks of [`SharedBusEffects`] by a playing sink.
+const BUS_EFFECT_POLL_SAMPLES: usize = 512;
+
+/// Directional attenuation around the listener's forward (-Z) axis.
+///
+/// Angles are measured from the forward axis in radians. Sources within
+/// `inner_rad` play at full volume, sources beyond `outer_rad` are scaled by
+/// `outer_gain`, and the gain is interpolated in between.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ListenerCone {
+    pub inner_rad: f32,
+    pub outer_rad: f32,
+    pub outer_gain: f32,
+}
+
+impl Default for ListenerCone {
+    fn default() -> Self {
+        Self {
+            inner_rad: std::f32::consts::PI,
+            outer_rad: std::f32::consts::PI,
+            outer_gain: 1.0,
+        }
+    }
+}
+
+impl ListenerCone {
+    fn gain(&self, angle: f32) -> f32 {
+        if angle <= self.inner_rad {
+            1.0
+        } else if angle >= self.outer_rad {
+            self.outer_gain
+        } else {
+            let t = (angle - self.inner_rad) / (self.outer_rad - self.inner_rad);
+            1.0 + (self.outer_gain - 1.0) * t
+        }
+    }
+}
+
+/// Format and length of a decoded clip.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClipMetadata {
+    pub duration_secs: f32,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl ClipMetadata {
+    fn probe(data: &Arc<[u8]>) -> Option<Self> {
+        let decoder = Decoder::new(Cursor::new(Arc::clone(data))).ok()?;
+        let sample_rate = decoder.sample_rate();
+        let channels = decoder.channels();
+        if sample_rate == 0 || channels == 0 {
+            return None;
+        }
+        let duration_secs = match decoder.total_duration() {
+            Some(duration) => duration.as_secs_f32(),
+            // Formats without a length in the header have to be counted.
+            None => decoder.count() as f32 / (sample_rate as f32 * channels as f32),
+        };
+        Some(Self {
+            duration_secs,
+            sample_rate,
+            channels,
+        })
+    }
+}
+
+/// Linearly resample interleaved `samples` from `from` Hz to `to` Hz.
+fn resample_linear(samples: &[i16], channels: u16, from: u32, to: u32) -> Vec<i16> {
+    let channels = channels.max(1) as usize;
+    let frames = samples.len() / channels;
+    if frames == 0 || from == 0 || to == 0 || from == to {
+        return samples.to_vec();
+    }
+    let out_frames = (frames as u64 * to as u64 / from as u64) as usize;
+    let step = from as f64 / to as f64;
+    let mut out = Vec::with_capacity(out_frames * channels);
+    for frame in 0..out_frames {
+        let position = frame as f64 * step;
+        let index = (position as usize).min(frames - 1);
+        let next = (index + 1).min(frames - 1);
+        let t = (position - index as f64) as f32;
+        for channel in 0..channels {
+            let a = samples[index * channels + channel] as f32;
+            let b = samples[next * channels + channel] as f32;
+            out.push((a + (b - a) * t).round() as i16);
+        }
+    }
+    out
+}
+
+/// Interleaved PCM samples of a fully decoded clip.
+struct DecodedClip {
+    sample_rate: u32,
+    channels: u16,
+    samples: Arc<[i16]>,
+    rms: f32,
+}
+
+impl DecodedClip {
+    /// Decode `data`, resampling to `target_rate` when the clip was recorded
+    /// at a different rate. A `target_rate` of zero keeps the native rate.
+    fn decode(data: &Arc<[u8]>, target_rate: u32) -> Option<Self> {
+        let decoder = Decoder::new(Cursor::new(Arc::clone(data))).ok()?;
+        let mut sample_rate = decoder.sample_rate();
+        let channels = decoder.channels();
+        let mut samples: Arc<[i16]> = decoder.collect();
+        if target_rate != 0 && sample_rate != target_rate {
+            samples = resample_linear(&samples, channels, sample_rate, target_rate).into();
+            sample_rate = target_rate;
+        }
+        let rms = if samples.is_empty() {
+            0.0
+        } else {
+            let sum: f64 = samples
+                .iter()
+                .map(|&x| (x as f64 / i16::MAX as f64).powi(2))
+                .sum();
+            (sum / samples.len() as f64).sqrt() as f32
+        };
+        Some(Self {
+            sample_rate,
+            channels,
+            samples,
+            rms,
+        })
+    }
+
+    fn duration_secs(&self) -> f32 {
+        self.samples.len() as f32 / (self.sample_rate.max(1) as f32 * self.channels.max(1) as f32)
+    }
+
+    fn pass(&self) -> ClipPass {
+        ClipPass::new(self.channels, self.sample_rate, Arc::clone(&self.samples))
+    }
+
+    /// RMS of the clip after `effects`, for metering.
+    fn rms_with_effects(&self, effects: &[BusEffect]) -> f32 {
+        if effects.is_empty() || self.samples.is_empty() {
+            return self.rms;
+        }
+        let mut samples: Vec<f32> = self
+            .samples
+            .iter()
+            .map(|&x| x as f32 / i16::MAX as f32)
+            .collect();
+        for effect in effects {
+            effect.apply(&mut samples, self.sample_rate, self.channels);
+        }
+        let sum: f64 = samples
+            .iter()
+            .map(|&y| (y.clamp(-1.0, 1.0) as f64).powi(2))
+            .sum();
+        (sum / self.samples.len() as f64).sqrt() as f32
+    }
+}
+
+/// Rodio source playing shared clip samples once. [`ClipPass::restart`]
+/// queues another pass without copying the samples.
+struct ClipPass {
+    channels: u16,
+    sample_rate: u32,
+    samples: Arc<[i16]>,
+    position: usize,
+}
+
+impl ClipPass {
+    fn new(channels: u16, sample_rate: u32, samples: Arc<[i16]>) -> Self {
+        Self {
+            channels,
+            sample_rate,
+            samples,
+            position: 0,
+        }
+    }
+
+    /// A fresh pass from the first sample.
+    fn restart(&self) -> Self {
+        Self::new(self.channels, self.sample_rate, Arc::clone(&self.samples))
+    }
+}
+
+impl Iterator for ClipPass {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        let sample = self.samples.get(self.position).copied()?;
+        self.position += 1;
+        Some(sample)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.samples.len() - self.position;
+        (remaining, Some(remaining))
+    }
+}
+
+impl Source for ClipPass {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<std::time::Duration> {
+        let frames = self.samples.len() / self.channels.max(1) as usize;
+        Some(std::time::Duration::from_secs_f64(
+            frames as f64 / self.sample_rate.max(1) as f64,
+        ))
+    }
+}
+
+/// Rodio source running a clip pass through the bus effects its source was
+/// last mixed with.
+struct BusEffectsSource {
+    pass: ClipPass,
+    effects: SharedBusEffects,
+    chain: EffectChain,
+    position: usize,
+}
+
+impl BusEffectsSource {
+    fn new(pass: ClipPass, effects: &SharedBusEffects) -> Self {
+        let chain = EffectChain::new(Vec::new(), pass.sample_rate, pass.channels);
+        let mut source = Self {
+            pass,
+            effects: Arc::clone(effects),
+            chain,
+            position: 0,
+        };
+        source.sync_effects();
+        source
+    }
+
+    fn sync_effects(&mut self) {
+        let Ok(effects) = self.effects.try_lock() else {
+            return;
+        };
+        if *effects != self.chain.effects {
+            self.chain =
+                EffectChain::new(effects.clone(), self.pass.sample_rate, self.pass.channels);
+        }
+    }
+}
+
+impl Iterator for BusEffectsSource {
+    type Item = i16;
+
+    fn next(&mut self) -> Option<i16> {
+        if self.position % BUS_EFFECT_POLL_SAMPLES == 0 {
+            self.sync_effects();
+        }
+        let sample = self.pass.next()?;
+        let index = self.position;
+        self.position += 1;
+        if self.chain.states.is_empty() {
+            return Some(sample);
         }
+        let y = self.chain.process(sample as f32 / i16::MAX as f32, index);
+        Some((y.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pass.size_hint()
+    }
+}
+
+impl Source for BusEffectsSource {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        self.pass.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.pass.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<std::time::Duration> {
+        self.pass.total_duration()
+    }
+}
+
+/// Time constant for the falloff of [`AudioEngine::source_level`].
+pub const LEVEL_DECAY_SECS: f32 = 0.3;
+
+struct Listener {
+    position: Vec3,
+    forward: Vec3,
+    right: Vec3,
+    cone: ListenerCone,
+}
+
+impl Listener {
+    /// Distance and cone gain for an emitter, plus the unit direction from the
+    /// emitter to the listener.
+    fn spatialize(&self, transform: &Mat4) -> (f32, Vec3) {
+        let dir = self.position - transform.transform_point3(Vec3::ZERO);
+        let dist = dir.length();
+        let dir_norm = if dist > 0.0 { dir / dist } else { Vec3::ZERO };
+
+        // Simple inverse-distance attenuation.
+        let attenuation = 1.0 / (1.0 + dist);
+        let cone_gain = if dist > 0.0 && self.forward != Vec3::ZERO {
+            let cos_angle = self.forward.dot(-dir_norm).clamp(-1.0, 1.0);
+            self.cone.gain(cos_angle.acos())
+        } else {
+            1.0
+        };
+        (attenuation * cone_gain, dir_norm)
     }
 }
 
@@ -63,6 +571,7 @@ pub struct AudioEngine {
     info: AudioEngineInfo,
     listener_transform: Mat4,
     listener_velocity: Vec3,
+    listener_cone: ListenerCone,
     sources: Pool<AudioSourceSlot>,
     streams: Pool<StreamingSource>,
     buses: Pool<Bus>,
@@ -70,6 +579,9 @@ pub struct AudioEngine {
     music_bus: Handle<Bus>,
     effects_bus: Handle<Bus>,
     finished_callbacks: Vec<(FinishedCallback, *mut c_void)>,
+    clip_cache: HashMap<String, Arc<DecodedClip>>,
+    cache_on_play: bool,
+    decode_count: usize,
     rodio_stream: Option<OutputStream>,
     rodio_handle: Option<OutputStreamHandle>,
     db: Option<NonNull<DB>>,
@@ -77,6 +589,15 @@ pub struct AudioEngine {
 
 impl AudioEngine {
     pub fn new(info: &AudioEngineInfo) -> Self {
+        Self::with_output(info, OutputStream::try_default)
+    }
+
+    /// Build the engine, opening the Rodio output with `open_output` when
+    /// that backend is requested. Falls back to `Dummy` if it fails.
+    fn with_output(
+        info: &AudioEngineInfo,
+        open_output: impl FnOnce() -> Result<(OutputStream, OutputStreamHandle), StreamError>,
+    ) -> Self {
         info!(
             "Initializing Audio Engine: {} Hz, {} channels",
             info.sample_rate, info.channels
@@ -88,7 +609,7 @@ impl AudioEngine {
 
         let mut info_copy = *info;
         let (rodio_stream, rodio_handle) = if info.backend == AudioBackend::Rodio {
-            match OutputStream::try_default() {
+            match open_output() {
                 Ok((stream, handle)) => (Some(stream), Some(handle)),
                 Err(e) => {
                     info!("Failed to initialize Rodio backend: {}", e);
@@ -104,6 +625,7 @@ impl AudioEngine {
             info: info_copy,
             listener_transform: Mat4::IDENTITY,
             listener_velocity: Vec3::ZERO,
+            listener_cone: ListenerCone::default(),
             sources: Default::default(),
             streams: Default::default(),
             buses,
@@ -111,6 +633,9 @@ impl AudioEngine {
             music_bus,
             effects_bus,
             finished_callbacks: Vec::new(),
+            clip_cache: HashMap::new(),
+            cache_on_play: true,
+            decode_count: 0,
             rodio_stream,
             rodio_handle,
             db: None,
@@ -125,21 +650,41 @@ impl AudioEngine {
         self.info.debug_mode = enabled;
     }
 
+    /// Load a clip from the database, returning a default handle if it
+    /// can't be loaded. See [`Self::try_create_source`] for the reason.
     pub fn create_source(&mut self, path: &str) -> Handle<AudioSource> {
+        self.try_create_source(path).unwrap_or_else(|err| {
+            info!("{}", err);
+            Handle::default()
+        })
+    }
+
+    pub fn try_create_source(&mut self, path: &str) -> Result<Handle<AudioSource>, AudioError> {
+        let Some(mut db) = self.db else {
+            return Err(AudioError::DatabaseUnavailable(path.to_string()));
+        };
+
+        let clip = unsafe { db.as_mut().audio_mut().fetch_clip(path) }
+            .map_err(|err| AudioError::ClipNotFound(path.to_string(), err))?;
+        self.sources
+            .insert(AudioSourceSlot::new(AudioSource::new_clip(
+                clip,
+                self.effects_bus,
+            )))
+            .map(to_public_source_handle)
+            .ok_or(AudioError::PoolFull)
+    }
+
+    /// Play the clip at `path` once at `position`. The source is destroyed
+    /// when it finishes, so the returned handle only stays valid until then.
+    pub fn play_one_shot(&mut self, path: &str, position: Vec3) -> Handle<AudioSource> {
         let Some(mut db) = self.db else {
-            info!("Audio database unavailable; cannot load clip '{}'", path);
+            info!("Audio database unavailable; cannot play clip '{}'", path);
             return Handle::default();
         };
 
         match unsafe { db.as_mut().audio_mut().fetch_clip(path) } {
-            Ok(clip) => self
-                .sources
-                .insert(AudioSourceSlot::new(AudioSource::new_clip(
-                    clip,
-                    self.effects_bus,
-                )))
-                .map(to_public_source_handle)
-                .unwrap_or_default(),
+            Ok(clip) => self.play_one_shot_clip(clip, position),
             Err(err) => {
                 info!("Failed to load audio clip '{}': {:?}", path, err);
                 Handle::default()
@@ -147,6 +692,31 @@ impl AudioEngine {
         }
     }
 
+    /// [`Self::play_one_shot`] for a clip that is already loaded.
+    pub fn play_one_shot_clip(&mut self, clip: AudioClip, position: Vec3) -> Handle<AudioSource> {
+        let source = AudioSource::new_clip(clip, self.effects_bus);
+        self.play_one_shot_source(source, position)
+    }
+
+    fn play_one_shot_source(
+        &mut self,
+        mut source: AudioSource,
+        position: Vec3,
+    ) -> Handle<AudioSource> {
+        source.one_shot = true;
+        source.transform = Mat4::from_translation(position);
+        let Some(h) = self
+            .sources
+            .insert(AudioSourceSlot::new(source))
+            .map(to_public_source_handle)
+        else {
+            info!("{}", AudioError::PoolFull);
+            return Handle::default();
+        };
+        self.play(h);
+        h
+    }
+
     pub fn initialize_database(&mut self, db: &mut DB) {
         self.db = Some(NonNull::new(db).expect("audio db ptr"));
     }
@@ -155,6 +725,12 @@ impl AudioEngine {
         self.info.backend
     }
 
+    /// Settings the engine is running with. `backend` reflects any fallback
+    /// taken during initialization.
+    pub fn info(&self) -> AudioEngineInfo {
+        self.info
+    }
+
     pub fn destroy_source(&mut self, h: Handle<AudioSource>) {
         if self.info.backend == AudioBackend::Rodio {
             if let Some(s) = self.sources.get_mut_ref(to_slot_handle(h)) {
@@ -183,42 +759,122 @@ impl AudioEngine {
             .map(|s| s.as_ref().state)
     }
 
+    /// Length of the source's clip in seconds, or `None` if it cannot be decoded.
+    pub fn clip_duration(&mut self, h: Handle<AudioSource>) -> Option<f32> {
+        self.clip_metadata(h).map(|m| m.duration_secs)
+    }
+
+    /// Decode the clip header once and cache its format on the source.
+    pub fn clip_metadata(&mut self, h: Handle<AudioSource>) -> Option<ClipMetadata> {
+        let s = self.get_source_mut(h)?;
+        if let Some(metadata) = s.metadata {
+            return metadata;
+        }
+        let metadata = match &s.source {
+            AudioSourceData::Clip { data, .. } => ClipMetadata::probe(data),
+        };
+        s.metadata = Some(metadata);
+        metadata
+    }
+
     pub fn play(&mut self, h: Handle<AudioSource>) {
+        let Some(AudioSourceData::Clip { name, data }) =
+            self.get_source_mut(h).map(|s| s.source.clone())
+        else {
+            return;
+        };
+        let decoded = self.decoded_clip(&name, &data, self.cache_on_play);
+        let pitch_range = self.info.pitch_range;
+        let effects = self
+            .sources
+            .get_ref(to_slot_handle(h))
+            .map(|s| collect_bus_effects(&self.buses, s.as_ref().bus))
+            .unwrap_or_default();
         let backend = self.info.backend;
         let handle_clone = self.rodio_handle.clone();
         if let Some(s) = self.get_source_mut(h) {
+            // Undecodable clips are metered as full scale.
+            s.signal_rms = decoded
+                .as_ref()
+                .map_or(1.0, |d| d.rms_with_effects(&effects));
+            s.duration_secs = decoded.as_ref().map(|d| d.duration_secs());
+            s.clip = decoded.clone();
+            if let Ok(mut bus_effects) = s.bus_effects.lock() {
+                *bus_effects = effects;
+            }
+            if s.state != PlaybackState::Paused {
+                s.elapsed_secs = 0.0;
+            }
+            s.stop_fade = None;
             if backend == AudioBackend::Rodio {
-                if let Some(handle) = handle_clone {
-                    let reader: Option<Box<dyn AudioReadSeek>> = match &s.source {
-                        AudioSourceData::Clip { data, .. } => {
-                            Some(Box::new(Cursor::new(Arc::clone(data))) as Box<dyn AudioReadSeek>)
-                        }
-                    };
-                    if let Some(reader) = reader {
-                        let decoder = Decoder::new(BufReader::new(reader)).ok();
-                        let Some(decoder) = decoder else {
-                            s.state = PlaybackState::Playing;
-                            return;
-                        };
-                        if let Ok(sink) = Sink::try_new(&handle) {
-                            if s.looping {
-                                sink.append(decoder.repeat_infinite());
-                            } else {
-                                sink.append(decoder);
-                            }
-                            sink.set_volume(s.volume);
-                            sink.play();
-                            s.sink = Some(sink);
-                            s.state = PlaybackState::Playing;
-                            return;
-                        }
+                if let (Some(handle), Some(decoded)) = (handle_clone, decoded) {
+                    if let Ok(sink) = Sink::try_new(&handle) {
+                        let pass = decoded.pass();
+                        s.loop_pass = s.looping.then(|| pass.restart());
+                        sink.append(BusEffectsSource::new(pass, &s.bus_effects));
+                        sink.set_volume(s.volume);
+                        sink.set_speed(clamp_pitch(s.effective_pitch, pitch_range));
+                        sink.play();
+                        s.sink = Some(sink);
+                        s.state = PlaybackState::Playing;
+                        s.auto_paused = false;
+                        return;
                     }
                 }
             }
             s.state = PlaybackState::Playing;
+            s.auto_paused = false;
         }
     }
 
+    /// Decode a clip from the database ahead of time so playback can start
+    /// without decoding. Returns `false` if the clip could not be loaded.
+    pub fn preload(&mut self, path: &str) -> bool {
+        let Some(mut db) = self.db else {
+            info!("Audio database unavailable; cannot preload clip '{}'", path);
+            return false;
+        };
+
+        match unsafe { db.as_mut().audio_mut().fetch_clip(path) } {
+            Ok(clip) => {
+                let data = Arc::from(clip.data.into_boxed_slice());
+                self.decoded_clip(&clip.name, &data, true).is_some()
+            }
+            Err(err) => {
+                info!("Failed to preload audio clip '{}': {:?}", path, err);
+                false
+            }
+        }
+    }
+
+    /// Whether clips decoded by `play` are kept for later plays. Preloaded
+    /// clips are always cached.
+    pub fn set_cache_on_play(&mut self, enabled: bool) {
+        self.cache_on_play = enabled;
+    }
+
+    pub fn clear_clip_cache(&mut self) {
+        self.clip_cache.clear();
+    }
+
+    fn decoded_clip(
+        &mut self,
+        name: &str,
+        data: &Arc<[u8]>,
+        cache: bool,
+    ) -> Option<Arc<DecodedClip>> {
+        if let Some(decoded) = self.clip_cache.get(name) {
+            return Some(Arc::clone(decoded));
+        }
+        self.decode_count += 1;
+        let decoded = Arc::new(DecodedClip::decode(data, self.info.sample_rate)?);
+        if cache {
+            self.clip_cache
+                .insert(name.to_string(), Arc::clone(&decoded));
+        }
+        Some(decoded)
+    }
+
     pub fn pause(&mut self, h: Handle<AudioSource>) {
         let backend = self.info.backend;
         if let Some(s) = self.get_source_mut(h) {
@@ -228,9 +884,57 @@ impl AudioEngine {
                 }
             }
             s.state = PlaybackState::Paused;
+            s.auto_paused = false;
+        }
+    }
+
+    /// Pause every playing source, remembering them for [`Self::resume_all`].
+    pub fn pause_all(&mut self) {
+        for h in self.source_handles() {
+            if self.get_state(h) == Some(PlaybackState::Playing) {
+                self.pause(h);
+                if let Some(s) = self.get_source_mut(h) {
+                    s.auto_paused = true;
+                }
+            }
         }
     }
 
+    /// Resume only the sources paused by [`Self::pause_all`].
+    pub fn resume_all(&mut self) {
+        let backend = self.info.backend;
+        for h in self.source_handles() {
+            let Some(s) = self.get_source_mut(h) else {
+                continue;
+            };
+            if !s.auto_paused || s.state != PlaybackState::Paused {
+                continue;
+            }
+            s.auto_paused = false;
+            if backend == AudioBackend::Rodio {
+                if let Some(sink) = &s.sink {
+                    sink.play();
+                    s.state = PlaybackState::Playing;
+                    continue;
+                }
+            }
+            self.play(h);
+        }
+    }
+
+    pub fn stop_all(&mut self) {
+        for h in self.source_handles() {
+            self.stop(h);
+        }
+    }
+
+    fn source_handles(&mut self) -> Vec<Handle<AudioSource>> {
+        let mut handles = Vec::new();
+        self.sources
+            .for_each_occupied_handle_mut(|h| handles.push(to_public_source_handle(h)));
+        handles
+    }
+
     pub fn stop(&mut self, h: Handle<AudioSource>) {
         let backend = self.info.backend;
         if let Some(s) = self.get_source_mut(h) {
@@ -238,18 +942,47 @@ impl AudioEngine {
                 if let Some(sink) = s.sink.take() {
                     sink.stop();
                 }
+                s.loop_pass = None;
             }
+            s.clip = None;
             let was_playing = s.state == PlaybackState::Playing;
             s.state = PlaybackState::Stopped;
+            s.auto_paused = false;
+            s.stop_fade = None;
             if was_playing {
                 self.notify_finished(h);
             }
         }
     }
 
+    /// Fade a playing source to silence over `duration_secs`, then stop it and
+    /// fire the finished callbacks. Sources that are not playing, or a zero
+    /// duration, stop immediately like [`Self::stop`].
+    pub fn stop_with_fade(&mut self, h: Handle<AudioSource>, duration_secs: f32) {
+        let Some(s) = self.get_source_mut(h) else {
+            return;
+        };
+        if s.state != PlaybackState::Playing || duration_secs <= 0.0 {
+            self.stop(h);
+            return;
+        }
+        s.stop_fade = Some(StopFade {
+            remaining_secs: duration_secs,
+            duration_secs,
+        });
+    }
+
+    /// Takes effect at the end of the current pass: a playing source that
+    /// stops looping finishes the pass it is on and then stops.
     pub fn set_looping(&mut self, h: Handle<AudioSource>, looping: bool) {
         if let Some(s) = self.get_source_mut(h) {
             s.looping = looping;
+            s.loop_pass = match (&s.sink, &s.clip) {
+                (Some(_), Some(clip)) if looping => {
+                    s.loop_pass.take().or_else(|| Some(clip.pass()))
+                }
+                _ => None,
+            };
         }
     }
 
@@ -277,6 +1010,24 @@ impl AudioEngine {
         }
     }
 
+    pub fn set_bus_effect(&mut self, h: Handle<Bus>, effect: BusEffect) {
+        if let Some(b) = self.buses.get_mut_ref(h) {
+            b.effect = effect;
+        }
+    }
+
+    /// Scale every bus by adjusting the root `master_bus`.
+    pub fn set_master_volume(&mut self, volume: f32) {
+        self.set_bus_volume(self.master_bus, volume);
+    }
+
+    pub fn master_volume(&self) -> f32 {
+        self.buses
+            .get_ref(self.master_bus)
+            .map(|b| b.volume)
+            .unwrap_or(1.0)
+    }
+
     pub fn register_finished_callback(&mut self, cb: FinishedCallback, user_data: *mut c_void) {
         self.finished_callbacks.push((cb, user_data));
     }
@@ -293,56 +1044,288 @@ impl AudioEngine {
         }
     }
 
+    /// Mix between 2D (`0.0`, no attenuation or panning) and fully
+    /// positional 3D playback (`1.0`, the default).
+    pub fn set_source_spatial_blend(&mut self, h: Handle<AudioSource>, blend: f32) {
+        if let Some(s) = self.get_source_mut(h) {
+            s.spatial_blend = blend.clamp(0.0, 1.0);
+        }
+    }
+
     pub fn set_listener_transform(&mut self, transform: &Mat4, velocity: Vec3) {
         self.listener_transform = *transform;
         self.listener_velocity = velocity;
     }
 
+    /// Attenuate sources outside a cone around the listener's forward axis.
+    pub fn set_listener_cone(&mut self, inner_rad: f32, outer_rad: f32, outer_gain: f32) {
+        let inner_rad = inner_rad.clamp(0.0, std::f32::consts::PI);
+        self.listener_cone = ListenerCone {
+            inner_rad,
+            outer_rad: outer_rad.clamp(inner_rad, std::f32::consts::PI),
+            outer_gain: outer_gain.max(0.0),
+        };
+    }
+
+    pub fn listener_cone(&self) -> ListenerCone {
+        self.listener_cone
+    }
+
+    /// Volume after bus, distance and cone attenuation as of the last mix.
+    pub fn effective_volume(&self, h: Handle<AudioSource>) -> Option<f32> {
+        self.sources
+            .get_ref(to_slot_handle(h))
+            .map(|s| s.as_ref().effective_volume)
+    }
+
+    /// Playback speed after Doppler and clamping as of the last mix.
+    pub fn effective_pitch(&self, h: Handle<AudioSource>) -> Option<f32> {
+        self.sources
+            .get_ref(to_slot_handle(h))
+            .map(|s| s.as_ref().effective_pitch)
+    }
+
+    /// Stereo pan from -1 (left) to 1 (right) as of the last mix.
+    pub fn effective_pan(&self, h: Handle<AudioSource>) -> Option<f32> {
+        self.sources
+            .get_ref(to_slot_handle(h))
+            .map(|s| s.as_ref().effective_pan)
+    }
+
     pub fn create_stream(&mut self, path: &str) -> Handle<StreamingSource> {
+        self.try_create_stream(path).unwrap_or_else(|err| {
+            info!("{}", err);
+            Handle::default()
+        })
+    }
+
+    /// Loads a stream, decoded and resampled to
+    /// [`AudioEngineInfo::sample_rate`].
+    pub fn try_create_stream(&mut self, path: &str) -> Result<Handle<StreamingSource>, AudioError> {
         let Some(mut db) = self.db else {
-            info!("Audio database unavailable; cannot load stream '{}'", path);
-            return Handle::default();
+            return Err(AudioError::DatabaseUnavailable(path.to_string()));
         };
 
-        match unsafe { db.as_mut().audio_mut().fetch_clip(path) } {
-            Ok(clip) => self
-                .streams
-                .insert(StreamingSource::new_clip(clip))
-                .unwrap_or_default(),
-            Err(err) => {
-                info!("Failed to load audio stream '{}': {:?}", path, err);
-                Handle::default()
+        let clip = unsafe { db.as_mut().audio_mut().fetch_clip(path) }
+            .map_err(|err| AudioError::ClipNotFound(path.to_string(), err))?;
+        let data = Arc::from(clip.data.into_boxed_slice());
+        self.insert_stream(path, clip.name, &data)
+    }
+
+    fn insert_stream(
+        &mut self,
+        path: &str,
+        name: String,
+        data: &Arc<[u8]>,
+    ) -> Result<Handle<StreamingSource>, AudioError> {
+        let stream = StreamingSource::decode(name, data, self.music_bus, self.info.sample_rate)
+            .ok_or_else(|| AudioError::Undecodable(path.to_string()))?;
+        self.streams.insert(stream).ok_or(AudioError::PoolFull)
+    }
+
+    /// Channels interleaved in the samples [`AudioEngine::update_stream`]
+    /// writes.
+    pub fn stream_channels(&self, h: Handle<StreamingSource>) -> Option<u16> {
+        self.streams.get_ref(h).map(|s| s.channels)
+    }
+
+    pub fn set_stream_transform(
+        &mut self,
+        h: Handle<StreamingSource>,
+        transform: &Mat4,
+        velocity: Vec3,
+    ) {
+        if let Some(s) = self.streams.get_mut_ref(h) {
+            s.transform = *transform;
+            s.velocity = velocity;
+        }
+    }
+
+    pub fn set_stream_volume(&mut self, h: Handle<StreamingSource>, volume: f32) {
+        if let Some(s) = self.streams.get_mut_ref(h) {
+            s.volume = volume;
+        }
+    }
+
+    /// Loop the stream back to its start when it runs out of data. A looping
+    /// stream never reports finished.
+    pub fn set_stream_looping(&mut self, h: Handle<StreamingSource>, looping: bool) {
+        if let Some(s) = self.streams.get_mut_ref(h) {
+            s.looping = looping;
+            if looping {
+                s.finished = false;
             }
         }
     }
 
-    pub fn update_stream(&mut self, h: Handle<StreamingSource>, out: &mut [u8]) -> usize {
+    pub fn set_stream_bus(&mut self, h: Handle<StreamingSource>, bus: Handle<Bus>) {
+        if let Some(s) = self.streams.get_mut_ref(h) {
+            s.bus = bus;
+        }
+    }
+
+    /// Volume after bus, distance and cone attenuation as of the last mix.
+    pub fn stream_effective_volume(&self, h: Handle<StreamingSource>) -> Option<f32> {
+        self.streams.get_ref(h).map(|s| s.effective_volume)
+    }
+
+    /// Copies the next bytes of the stream into `out` as interleaved 16-bit
+    /// little-endian PCM at [`AudioEngineInfo::sample_rate`]. Released
+    /// handles write nothing and report finished.
+    pub fn update_stream(&mut self, h: Handle<StreamingSource>, out: &mut [u8]) -> StreamStatus {
         if let Some(stream) = self.streams.get_mut_ref(h) {
-            stream.pop_into(out)
+            let bytes = stream.pop_into(out);
+            StreamStatus {
+                bytes,
+                finished: stream.finished,
+            }
         } else {
-            0
+            StreamStatus {
+                bytes: 0,
+                finished: true,
+            }
         }
     }
 
-    pub fn update(&mut self, _dt: f32) {
-        self.mix();
+    /// Whether the stream has handed out all of its data. Released handles
+    /// count as finished.
+    pub fn is_stream_finished(&self, h: Handle<StreamingSource>) -> bool {
+        self.streams.get_ref(h).map_or(true, |s| s.finished)
+    }
+
+    /// Smoothed output level of a source as of the last mix.
+    ///
+    /// Without access to the device's mixed samples this is the clip's RMS
+    /// scaled by the source's effective volume. The meter rises immediately
+    /// and decays over roughly [`LEVEL_DECAY_SECS`].
+    pub fn source_level(&self, h: Handle<AudioSource>) -> Option<f32> {
+        self.sources
+            .get_ref(to_slot_handle(h))
+            .map(|s| s.as_ref().level)
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.mix(dt);
+        self.advance_stop_fades(dt);
+        self.poll_sinks();
+        self.advance_without_output(dt);
+        self.release_finished_one_shots();
+    }
+
+    /// Stop sources whose fade from [`Self::stop_with_fade`] has run out.
+    fn advance_stop_fades(&mut self, dt: f32) {
+        for h in self.source_handles() {
+            let Some(fade) = self.get_source_mut(h).and_then(|s| s.stop_fade.as_mut()) else {
+                continue;
+            };
+            fade.remaining_secs -= dt.max(0.0);
+            if fade.remaining_secs <= 0.0 {
+                self.stop(h);
+            }
+        }
     }
 
-    fn mix(&mut self) {
-        let listener_pos = self.listener_transform.transform_point3(Vec3::ZERO);
+    /// Track playback time for backends without a device so that
+    /// non-looping sources still finish once their clip has elapsed.
+    fn advance_without_output(&mut self, dt: f32) {
+        if self.info.backend == AudioBackend::Rodio {
+            return;
+        }
+        for h in self.source_handles() {
+            let Some(s) = self.get_source_mut(h) else {
+                continue;
+            };
+            let Some(duration) = s.duration_secs.filter(|d| *d > 0.0) else {
+                continue;
+            };
+            if s.state != PlaybackState::Playing {
+                continue;
+            }
+            s.elapsed_secs += dt.max(0.0) * s.effective_pitch;
+            if s.elapsed_secs < duration {
+                continue;
+            }
+            if s.looping {
+                s.elapsed_secs %= duration;
+                continue;
+            }
+            s.clip = None;
+            s.state = PlaybackState::Stopped;
+            self.notify_finished(h);
+        }
+    }
+
+    fn release_finished_one_shots(&mut self) {
+        for h in self.source_handles() {
+            let finished = self
+                .get_source_mut(h)
+                .is_some_and(|s| s.one_shot && s.state == PlaybackState::Stopped);
+            if finished {
+                self.destroy_source(h);
+            }
+        }
+    }
+
+    /// Queue another pass on drained looping sinks and stop the rest.
+    fn poll_sinks(&mut self) {
+        if self.info.backend != AudioBackend::Rodio {
+            return;
+        }
+        for h in self.source_handles() {
+            let Some(s) = self.get_source_mut(h) else {
+                continue;
+            };
+            let Some(sink) = s.sink.as_ref().filter(|sink| sink.empty()) else {
+                continue;
+            };
+            if s.state != PlaybackState::Playing {
+                continue;
+            }
+            if let (true, Some(pass)) = (s.looping, &s.loop_pass) {
+                sink.append(BusEffectsSource::new(pass.restart(), &s.bus_effects));
+                continue;
+            }
+            s.sink = None;
+            s.loop_pass = None;
+            s.clip = None;
+            s.state = PlaybackState::Stopped;
+            self.notify_finished(h);
+        }
+    }
+
+    fn mix(&mut self, dt: f32) {
+        let listener = Listener {
+            position: self.listener_transform.transform_point3(Vec3::ZERO),
+            forward: self
+                .listener_transform
+                .transform_vector3(Vec3::NEG_Z)
+                .normalize_or_zero(),
+            right: self
+                .listener_transform
+                .transform_vector3(Vec3::X)
+                .normalize_or_zero(),
+            cone: self.listener_cone,
+        };
         let listener_vel = self.listener_velocity;
+        let pitch_range = self.info.pitch_range;
         let buses_ptr: *const Pool<Bus> = &self.buses;
         self.sources.for_each_occupied_mut(|slot| {
             let s = slot.as_mut();
-            let src_pos = s.transform.transform_point3(Vec3::ZERO);
-            let dir = listener_pos - src_pos;
-            let dist = dir.length();
-            let dir_norm = if dist > 0.0 { dir / dist } else { Vec3::ZERO };
-
-            // Simple inverse-distance attenuation.
-            let attenuation = 1.0 / (1.0 + dist);
+            let (gain, dir_norm) = listener.spatialize(&s.transform);
             let bus_volume = unsafe { compute_bus_volume(&*buses_ptr, s.bus) };
-            s.effective_volume = s.volume * bus_volume * attenuation;
+            s.set_bus_effects(unsafe { collect_bus_effects(&*buses_ptr, s.bus) });
+            // Blend from the raw 2D volume and centred pan to the 3D values.
+            let blend = s.spatial_blend;
+            let pan = listener.right.dot(-dir_norm);
+            s.effective_volume = s.volume * bus_volume * (1.0 + (gain - 1.0) * blend);
+            s.effective_pan = pan * blend;
+            if let Some(fade) = &s.stop_fade {
+                let fade_gain = fade.gain();
+                s.effective_volume *= fade_gain;
+                if let Some(sink) = &s.sink {
+                    sink.set_volume(s.volume * fade_gain);
+                }
+            }
 
             // Doppler effect using the relative velocity along the line-of-sight.
             let rel_vel = (s.velocity - listener_vel).dot(dir_norm);
@@ -353,7 +1336,27 @@ impl AudioEngine {
             } else {
                 1.0
             };
-            s.effective_pitch = s.pitch * doppler;
+            s.effective_pitch = clamp_pitch(s.pitch * doppler, pitch_range);
+            if let Some(sink) = &s.sink {
+                sink.set_speed(s.effective_pitch);
+            }
+
+            let target = if s.state == PlaybackState::Playing {
+                s.signal_rms * s.effective_volume
+            } else {
+                0.0
+            };
+            if target >= s.level {
+                s.level = target;
+            } else {
+                let decay = (-dt.max(0.0) / LEVEL_DECAY_SECS).exp();
+                s.level = target + (s.level - target) * decay;
+            }
+        });
+        self.streams.for_each_occupied_mut(|s| {
+            let (gain, _) = listener.spatialize(&s.transform);
+            let bus_volume = unsafe { compute_bus_volume(&*buses_ptr, s.bus) };
+            s.effective_volume = s.volume * bus_volume * gain;
         });
     }
 
@@ -380,12 +1383,46 @@ pub struct AudioSource {
     volume: f32,
     pitch: f32,
     state: PlaybackState,
+    auto_paused: bool,
     transform: Mat4,
     velocity: Vec3,
+    spatial_blend: f32,
     effective_volume: f32,
+    effective_pan: f32,
     effective_pitch: f32,
+    signal_rms: f32,
+    level: f32,
+    /// Length of the decoded clip, known once it has been played.
+    duration_secs: Option<f32>,
+    /// Playback position tracked when there is no output device.
+    elapsed_secs: f32,
+    /// Destroyed by the engine once playback finishes.
+    one_shot: bool,
+    /// Fade-out in progress from [`AudioEngine::stop_with_fade`].
+    stop_fade: Option<StopFade>,
     bus: Handle<Bus>,
     sink: Option<Sink>,
+    /// One pass of the clip, re-appended when a looping sink drains. Only
+    /// held while the source loops.
+    loop_pass: Option<ClipPass>,
+    /// Decoded clip of the current playback.
+    clip: Option<Arc<DecodedClip>>,
+    /// Effects of the source's bus chain as of the last mix.
+    bus_effects: SharedBusEffects,
+    metadata: Option<Option<ClipMetadata>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct StopFade {
+    remaining_secs: f32,
+    duration_secs: f32,
+}
+
+impl StopFade {
+    /// Volume scale, falling linearly from one to zero over the fade.
+    fn gain(&self) -> f32 {
+        (self.remaining_secs / self.duration_secs).clamp(0.0, 1.0)
+    }
 }
 
 struct AudioSourceSlot {
@@ -419,22 +1456,50 @@ enum AudioSourceData {
 
 impl AudioSource {
     fn new_clip(clip: AudioClip, bus: Handle<Bus>) -> Self {
+        Self::from_data(clip.name, Arc::from(clip.data.into_boxed_slice()), bus)
+    }
+
+    fn from_data(name: String, data: Arc<[u8]>, bus: Handle<Bus>) -> Self {
         Self {
-            source: AudioSourceData::Clip {
-                name: clip.name,
-                data: Arc::from(clip.data.into_boxed_slice()),
-            },
+            source: AudioSourceData::Clip { name, data },
             looping: false,
             volume: 1.0,
             pitch: 1.0,
             state: PlaybackState::Stopped,
+            auto_paused: false,
             transform: Mat4::IDENTITY,
             velocity: Vec3::ZERO,
+            spatial_blend: 1.0,
             effective_volume: 1.0,
+            effective_pan: 0.0,
             effective_pitch: 1.0,
+            signal_rms: 1.0,
+            level: 0.0,
+            duration_secs: None,
+            elapsed_secs: 0.0,
+            one_shot: false,
+            stop_fade: None,
             bus,
             sink: None,
+            loop_pass: None,
+            clip: None,
+            bus_effects: SharedBusEffects::default(),
+            metadata: None,
+        }
+    }
+
+    /// Route playback through `effects`, re-metering the clip if they changed.
+    fn set_bus_effects(&mut self, effects: Vec<BusEffect>) {
+        let Ok(mut current) = self.bus_effects.lock() else {
+            return;
+        };
+        if *current == effects {
+            return;
+        }
+        if let Some(clip) = &self.clip {
+            self.signal_rms = clip.rms_with_effects(&effects);
         }
+        *current = effects;
     }
 }
 
@@ -446,11 +1511,31 @@ fn to_public_source_handle(handle: Handle<AudioSourceSlot>) -> Handle<AudioSourc
     Handle::new(handle.slot, handle.generation)
 }
 
+/// Result of pulling bytes from a [`StreamingSource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamStatus {
+    /// Bytes written into the output buffer.
+    pub bytes: usize,
+    /// The stream reached its end; later pulls write nothing.
+    pub finished: bool,
+}
+
 pub struct StreamingSource {
     #[allow(dead_code)]
     name: String,
-    data: Arc<[u8]>,
+    channels: u16,
+    /// Interleaved PCM at the engine's sample rate.
+    samples: Arc<[i16]>,
+    /// Byte offset into `samples`.
     cursor: usize,
+    finished: bool,
+    looping: bool,
+    volume: f32,
+    transform: Mat4,
+    #[allow(dead_code)]
+    velocity: Vec3,
+    effective_volume: f32,
+    bus: Handle<Bus>,
 }
 
 fn compute_bus_volume(buses: &Pool<Bus>, h: Handle<Bus>) -> f32 {
@@ -465,25 +1550,604 @@ fn compute_bus_volume(buses: &Pool<Bus>, h: Handle<Bus>) -> f32 {
     }
 }
 
+/// Clamp a playback speed into `(min, max)`. NaN, zero and negative values
+/// map to `min`.
+fn clamp_pitch(pitch: f32, [min, max]: [f32; 2]) -> f32 {
+    if pitch > min {
+        pitch.min(max)
+    } else {
+        min
+    }
+}
+
+/// Effects from `h` up to the root bus, innermost first.
+fn collect_bus_effects(buses: &Pool<Bus>, h: Handle<Bus>) -> Vec<BusEffect> {
+    let mut effects = Vec::new();
+    let mut current = Some(h);
+    while let Some(bus) = current.and_then(|h| buses.get_ref(h)) {
+        if bus.effect != BusEffect::None {
+            effects.push(bus.effect);
+        }
+        current = bus.parent;
+    }
+    effects
+}
+
 impl StreamingSource {
-    fn new_clip(clip: AudioClip) -> Self {
-        Self {
-            name: clip.name,
-            data: Arc::from(clip.data.into_boxed_slice()),
+    /// Decode `data`, resampling to `sample_rate` when the clip was recorded
+    /// at a different rate.
+    fn decode(name: String, data: &Arc<[u8]>, bus: Handle<Bus>, sample_rate: u32) -> Option<Self> {
+        let decoded = DecodedClip::decode(data, sample_rate)?;
+        Some(Self {
+            name,
+            channels: decoded.channels,
+            samples: decoded.samples,
             cursor: 0,
-        }
+            finished: false,
+            looping: false,
+            volume: 1.0,
+            transform: Mat4::IDENTITY,
+            velocity: Vec3::ZERO,
+            effective_volume: 1.0,
+            bus,
+        })
     }
 
     fn pop_into(&mut self, out: &mut [u8]) -> usize {
+        let len = self.samples.len() * 2;
         let mut count = 0;
         while count < out.len() {
-            let Some(v) = self.data.get(self.cursor) else {
+            if self.looping && self.cursor >= len && len > 0 {
+                self.cursor = 0;
+            }
+            let Some(sample) = self.samples.get(self.cursor / 2) else {
                 break;
             };
-            out[count] = *v;
+            out[count] = sample.to_le_bytes()[self.cursor % 2];
             count += 1;
             self.cursor += 1;
         }
+        self.finished = !self.looping && self.cursor >= len;
         count
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insert_source(engine: &mut AudioEngine, data: &[u8]) -> Handle<AudioSource> {
+        let source =
+            AudioSource::from_data("test".to_string(), Arc::from(data), engine.effects_bus);
+        engine
+            .sources
+            .insert(AudioSourceSlot::new(source))
+            .map(to_public_source_handle)
+            .unwrap()
+    }
+
+    fn wav_bytes(sample_rate: u32, channels: u16, frames: u32) -> Vec<u8> {
+        let data_len = frames * channels as u32 * 2;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16u32.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&channels.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+        bytes.extend_from_slice(&(channels * 2).to_le_bytes());
+        bytes.extend_from_slice(&16u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_len.to_le_bytes());
+        bytes.resize(bytes.len() + data_len as usize, 0);
+        bytes
+    }
+
+    fn pcm_wav(sample_rate: u32, channels: u16, samples: &[i16]) -> Vec<u8> {
+        let frames = samples.len() as u32 / channels as u32;
+        let mut bytes = wav_bytes(sample_rate, channels, frames);
+        let start = bytes.len() - samples.len() * 2;
+        for (i, sample) in samples.iter().enumerate() {
+            bytes[start + i * 2..start + i * 2 + 2].copy_from_slice(&sample.to_le_bytes());
+        }
+        bytes
+    }
+
+    fn insert_stream(engine: &mut AudioEngine, samples: &[i16]) -> Handle<StreamingSource> {
+        let data = Arc::from(pcm_wav(engine.info.sample_rate, 1, samples));
+        engine
+            .insert_stream("audio/music.wav", "music".to_string(), &data)
+            .unwrap()
+    }
+
+    fn place(engine: &mut AudioEngine, h: Handle<AudioSource>, position: Vec3) {
+        engine.set_source_transform(h, &Mat4::from_translation(position), Vec3::ZERO);
+    }
+
+    #[test]
+    fn listener_cone_attenuates_by_angle() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        engine.set_listener_cone(0.5, 1.5, 0.25);
+        let ahead = insert_source(&mut engine, &[]);
+        let edge = insert_source(&mut engine, &[]);
+        let behind = insert_source(&mut engine, &[]);
+
+        place(&mut engine, ahead, Vec3::new(0.0, 0.0, -5.0));
+        place(
+            &mut engine,
+            edge,
+            Vec3::new(5.0 * 1.0f32.sin(), 0.0, -5.0 * 1.0f32.cos()),
+        );
+        place(&mut engine, behind, Vec3::new(0.0, 0.0, 5.0));
+        engine.update(0.0);
+
+        let distance_gain = 1.0 / 6.0;
+        let volume = |h| engine.effective_volume(h).unwrap() / distance_gain;
+        assert!((volume(ahead) - 1.0).abs() < 1e-4);
+        // Halfway between the inner and outer cone.
+        assert!((volume(edge) - 0.625).abs() < 1e-4);
+        assert!((volume(behind) - 0.25).abs() < 1e-4);
+    }
+
+    #[test]
+    fn missing_clip_reports_an_error() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        assert!(matches!(
+            engine.try_create_source("audio/missing.wav"),
+            Err(AudioError::DatabaseUnavailable(_))
+        ));
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = DB::new(&noren::DBInfo {
+            base_dir: dir.path().to_str().unwrap(),
+            layout_file: None,
+            pooled_geometry_uploads: false,
+        })
+        .unwrap();
+        engine.initialize_database(&mut db);
+        let Err(AudioError::ClipNotFound(path, _)) = engine.try_create_source("audio/missing.wav")
+        else {
+            panic!("expected a missing clip");
+        };
+        assert_eq!(path, "audio/missing.wav");
+        assert!(!engine.create_source("audio/missing.wav").valid());
+    }
+
+    #[test]
+    #[ignore = "needs an audio output device"]
+    fn disabling_looping_stops_after_the_current_pass() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo {
+            backend: AudioBackend::Rodio,
+            ..Default::default()
+        });
+        // 0.1 s per pass.
+        let clip = insert_source(&mut engine, &wav_bytes(8000, 1, 800));
+        let run = |engine: &mut AudioEngine, seconds: f32| {
+            for _ in 0..(seconds / 0.01) as u32 {
+                std::thread::sleep(std::time::Duration::from_millis(10));
+                engine.update(0.01);
+            }
+        };
+
+        engine.set_looping(clip, true);
+        engine.play(clip);
+        run(&mut engine, 0.35);
+        assert_eq!(engine.get_state(clip), Some(PlaybackState::Playing));
+
+        engine.set_looping(clip, false);
+        run(&mut engine, 0.25);
+        assert_eq!(engine.get_state(clip), Some(PlaybackState::Stopped));
+    }
+
+    #[test]
+    fn failed_rodio_init_reports_dummy_backend() {
+        let engine = AudioEngine::with_output(
+            &AudioEngineInfo {
+                backend: AudioBackend::Rodio,
+                ..Default::default()
+            },
+            || Err(StreamError::NoDevice),
+        );
+        assert_eq!(engine.backend(), AudioBackend::Dummy);
+        assert_eq!(engine.info().backend, AudioBackend::Dummy);
+    }
+
+    #[test]
+    fn clip_duration_reads_wav_length() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let clip = insert_source(&mut engine, &wav_bytes(8000, 2, 12000));
+
+        let duration = engine.clip_duration(clip).unwrap();
+        assert!((duration - 1.5).abs() < 1e-3);
+        let metadata = engine.clip_metadata(clip).unwrap();
+        assert_eq!(metadata.sample_rate, 8000);
+        assert_eq!(metadata.channels, 2);
+    }
+
+    #[test]
+    fn clip_duration_is_none_for_undecodable_data() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let clip = insert_source(&mut engine, b"not audio");
+        assert_eq!(engine.clip_duration(clip), None);
+    }
+
+    #[test]
+    fn preloaded_clip_decodes_once() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let wav: Arc<[u8]> = Arc::from(wav_bytes(8000, 1, 800));
+        assert!(engine.decoded_clip("test", &wav, true).is_some());
+        assert_eq!(engine.decode_count, 1);
+
+        let clip = insert_source(&mut engine, &wav);
+        engine.play(clip);
+        engine.stop(clip);
+        engine.play(clip);
+        assert_eq!(engine.decode_count, 1);
+        assert_eq!(engine.get_state(clip), Some(PlaybackState::Playing));
+    }
+
+    #[test]
+    fn clips_are_resampled_to_the_engine_rate() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo {
+            sample_rate: 48_000,
+            ..Default::default()
+        });
+        let frequency = 440.0;
+        let native_rate = 44_100;
+        let mut wav = wav_bytes(native_rate, 1, native_rate);
+        let header = wav.len() - native_rate as usize * 2;
+        for (i, bytes) in wav[header..].chunks_mut(2).enumerate() {
+            let phase = std::f32::consts::TAU * frequency * i as f32 / native_rate as f32;
+            let sample = (phase.sin() * 0.5 * i16::MAX as f32) as i16;
+            bytes.copy_from_slice(&sample.to_le_bytes());
+        }
+
+        let decoded = engine.decoded_clip("tone", &Arc::from(wav), true).unwrap();
+        assert_eq!(decoded.sample_rate, 48_000);
+        assert_eq!(decoded.samples.len(), 48_000);
+        let rising_crossings = decoded
+            .samples
+            .windows(2)
+            .filter(|pair| pair[0] < 0 && pair[1] >= 0)
+            .count();
+        assert!((rising_crossings as f32 - frequency).abs() <= 1.0);
+    }
+
+    #[test]
+    fn clip_passes_share_the_decoded_samples() {
+        let data: Arc<[u8]> = Arc::from(wav_bytes(8000, 2, 400).as_slice());
+        let decoded = DecodedClip::decode(&data, 0).unwrap();
+
+        let mut pass = decoded.pass();
+        assert!(Arc::ptr_eq(&pass.samples, &decoded.samples));
+        assert_eq!(pass.by_ref().count(), 800);
+
+        let again = pass.restart();
+        assert!(Arc::ptr_eq(&again.samples, &decoded.samples));
+        assert_eq!(again.count(), 800);
+    }
+
+    #[test]
+    fn uncached_plays_decode_every_time() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        engine.set_cache_on_play(false);
+        let clip = insert_source(&mut engine, &wav_bytes(8000, 1, 800));
+        engine.play(clip);
+        engine.play(clip);
+        assert_eq!(engine.decode_count, 2);
+    }
+
+    #[test]
+    fn master_volume_scales_leaf_sources() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let source = insert_source(&mut engine, &[]);
+        place(&mut engine, source, Vec3::new(0.0, 0.0, -1.0));
+        engine.update(0.0);
+        let full = engine.effective_volume(source).unwrap();
+
+        engine.set_master_volume(0.5);
+        assert_eq!(engine.master_volume(), 0.5);
+        engine.update(0.0);
+        let halved = engine.effective_volume(source).unwrap();
+        assert!((halved - full * 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn resume_all_restores_previously_playing_sources() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let playing = insert_source(&mut engine, &[]);
+        let paused = insert_source(&mut engine, &[]);
+        let stopped = insert_source(&mut engine, &[]);
+        engine.play(playing);
+        engine.play(paused);
+        engine.pause(paused);
+
+        engine.pause_all();
+        assert_eq!(engine.get_state(playing), Some(PlaybackState::Paused));
+
+        engine.resume_all();
+        assert_eq!(engine.get_state(playing), Some(PlaybackState::Playing));
+        assert_eq!(engine.get_state(paused), Some(PlaybackState::Paused));
+        assert_eq!(engine.get_state(stopped), Some(PlaybackState::Stopped));
+
+        engine.stop_all();
+        for h in [playing, paused, stopped] {
+            assert_eq!(engine.get_state(h), Some(PlaybackState::Stopped));
+        }
+    }
+
+    #[test]
+    fn louder_source_reports_higher_level() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let loud = insert_source(&mut engine, &[]);
+        let quiet = insert_source(&mut engine, &[]);
+        let silent = insert_source(&mut engine, &[]);
+        engine.set_volume(quiet, 0.25);
+        engine.play(loud);
+        engine.play(quiet);
+        engine.update(1.0 / 60.0);
+
+        let level = |h| engine.source_level(h).unwrap();
+        assert!(level(loud) > level(quiet));
+        assert!(level(quiet) > 0.0);
+        assert_eq!(level(silent), 0.0);
+
+        engine.stop(loud);
+        let before = engine.source_level(loud).unwrap();
+        engine.update(1.0 / 60.0);
+        let after = engine.source_level(loud).unwrap();
+        assert!(after < before && after > 0.0);
+    }
+
+    #[test]
+    fn positioned_stream_is_attenuated_by_distance() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let stream = insert_stream(&mut engine, &[0]);
+        engine.set_stream_volume(stream, 0.5);
+
+        engine.update(0.0);
+        assert!((engine.stream_effective_volume(stream).unwrap() - 0.5).abs() < 1e-6);
+
+        engine.set_stream_transform(
+            stream,
+            &Mat4::from_translation(Vec3::new(0.0, 0.0, -3.0)),
+            Vec3::ZERO,
+        );
+        engine.update(0.0);
+        let expected = 0.5 / 4.0;
+        assert!((engine.stream_effective_volume(stream).unwrap() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn stream_reports_finished_after_last_read() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let stream = insert_stream(&mut engine, &[1, 2, 3]);
+        assert!(!engine.is_stream_finished(stream));
+
+        let mut out = [0u8; 4];
+        let mut total = 0;
+        let status = loop {
+            let status = engine.update_stream(stream, &mut out);
+            total += status.bytes;
+            if status.finished {
+                break status;
+            }
+        };
+        assert_eq!(total, 6);
+        assert_eq!(status.bytes, 2);
+        assert!(engine.is_stream_finished(stream));
+        assert_eq!(
+            engine.update_stream(stream, &mut out),
+            StreamStatus {
+                bytes: 0,
+                finished: true,
+            }
+        );
+    }
+
+    #[test]
+    fn streams_are_resampled_to_the_engine_rate() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo {
+            sample_rate: 48_000,
+            ..Default::default()
+        });
+        let data = Arc::from(wav_bytes(22_050, 2, 2_205));
+        let stream = engine
+            .insert_stream("audio/music.wav", "music".to_string(), &data)
+            .unwrap();
+        assert_eq!(engine.stream_channels(stream), Some(2));
+
+        let mut out = vec![0u8; 32 * 1024];
+        let status = engine.update_stream(stream, &mut out);
+        assert_eq!(status.bytes, 4_800 * 2 * 2);
+        assert!(status.finished);
+    }
+
+    #[test]
+    fn looping_stream_wraps_without_gaps() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let stream = insert_stream(&mut engine, &[1, 2, 3]);
+        engine.set_stream_looping(stream, true);
+
+        let mut out = [0u8; 8];
+        let status = engine.update_stream(stream, &mut out);
+        assert_eq!(status.bytes, 8);
+        assert!(!status.finished);
+        assert_eq!(out, [1, 0, 2, 0, 3, 0, 1, 0]);
+
+        engine.set_stream_looping(stream, false);
+        let status = engine.update_stream(stream, &mut out);
+        assert_eq!(status.bytes, 4);
+        assert!(status.finished);
+    }
+
+    #[test]
+    fn looping_stream_seam_skips_the_file_header() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let samples = [100, -200, 300, -400];
+        let stream = insert_stream(&mut engine, &samples);
+        engine.set_stream_looping(stream, true);
+
+        let mut out = [0u8; 24];
+        assert_eq!(engine.update_stream(stream, &mut out).bytes, out.len());
+        let played: Vec<i16> = out
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        let expected: Vec<i16> = samples.iter().copied().cycle().take(12).collect();
+        assert_eq!(played, expected);
+        assert!(!out.windows(4).any(|w| w == b"RIFF" || w == b"data"));
+    }
+
+    fn tone_rms(frequency: f32, effect: BusEffect) -> f32 {
+        let sample_rate = 48_000;
+        let mut samples: Vec<f32> = (0..sample_rate)
+            .map(|i| (std::f32::consts::TAU * frequency * i as f32 / sample_rate as f32).sin())
+            .collect();
+        effect.apply(&mut samples, sample_rate, 1);
+        // Skip the filter's settling time.
+        let tail = &samples[sample_rate as usize / 2..];
+        (tail.iter().map(|x| x * x).sum::<f32>() / tail.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn low_pass_bus_effect_attenuates_high_tones() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let low_pass = BusEffect::LowPass { cutoff_hz: 500.0 };
+        engine.set_bus_effect(engine.effects_bus, low_pass);
+        assert_eq!(
+            collect_bus_effects(&engine.buses, engine.effects_bus),
+            vec![low_pass]
+        );
+        assert!(collect_bus_effects(&engine.buses, engine.music_bus).is_empty());
+
+        let unfiltered = tone_rms(8_000.0, BusEffect::None);
+        let low = tone_rms(100.0, low_pass);
+        let high = tone_rms(8_000.0, low_pass);
+        assert!(low > unfiltered * 0.9);
+        assert!(high < unfiltered * 0.1);
+    }
+
+    #[test]
+    fn bus_effect_reaches_a_source_playing_through_the_bus() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        // 0.1 s of an 8 kHz tone, looped, routed through the effects bus.
+        let mut bytes = wav_bytes(48_000, 1, 4_800);
+        let data_start = bytes.len() - 4_800 * 2;
+        for (i, sample) in bytes[data_start..].chunks_mut(2).enumerate() {
+            let x = (std::f32::consts::TAU * 8_000.0 * i as f32 / 48_000.0).sin();
+            sample.copy_from_slice(&((x * i16::MAX as f32) as i16).to_le_bytes());
+        }
+        let clip = insert_source(&mut engine, &bytes);
+        engine.set_looping(clip, true);
+        engine.play(clip);
+        engine.update(0.01);
+        let dry = engine.source_level(clip).unwrap();
+        assert!(dry > 0.5, "{dry}");
+
+        // The filter sits on the root bus; the source only reaches it by
+        // being routed through the effects bus.
+        engine.set_bus_effect(engine.master_bus, BusEffect::LowPass { cutoff_hz: 500.0 });
+        for _ in 0..100 {
+            engine.update(0.05);
+        }
+        assert_eq!(engine.get_state(clip), Some(PlaybackState::Playing));
+        let wet = engine.source_level(clip).unwrap();
+        assert!(wet < dry * 0.1, "{wet} vs {dry}");
+
+        engine.set_bus_effect(engine.master_bus, BusEffect::None);
+        engine.update(0.01);
+        assert!((engine.source_level(clip).unwrap() - dry).abs() < 1e-3);
+    }
+
+    #[test]
+    fn extreme_pitch_is_clamped_to_the_configured_range() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo {
+            pitch_range: [0.5, 2.0],
+            ..Default::default()
+        });
+        let source = insert_source(&mut engine, &[]);
+
+        engine.set_pitch(source, 100.0);
+        engine.update(0.0);
+        assert_eq!(engine.effective_pitch(source), Some(2.0));
+
+        engine.set_pitch(source, -3.0);
+        engine.update(0.0);
+        assert_eq!(engine.effective_pitch(source), Some(0.5));
+    }
+
+    #[test]
+    fn spatial_blend_controls_distance_attenuation() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let ui = insert_source(&mut engine, &[]);
+        let world = insert_source(&mut engine, &[]);
+        engine.set_source_spatial_blend(ui, 0.0);
+        for h in [ui, world] {
+            place(&mut engine, h, Vec3::new(4.0, 0.0, 0.0));
+        }
+        engine.update(0.0);
+
+        assert!((engine.effective_volume(ui).unwrap() - 1.0).abs() < 1e-6);
+        assert_eq!(engine.effective_pan(ui), Some(0.0));
+        assert!((engine.effective_volume(world).unwrap() - 0.2).abs() < 1e-6);
+        assert!((engine.effective_pan(world).unwrap() - 1.0).abs() < 1e-6);
+
+        engine.set_source_spatial_blend(ui, 0.5);
+        engine.update(0.0);
+        assert!((engine.effective_volume(ui).unwrap() - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn one_shot_source_is_released_after_it_finishes() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        // 0.1 s at 8 kHz.
+        let source = AudioSource::from_data(
+            "one_shot".to_string(),
+            Arc::from(wav_bytes(8000, 1, 800)),
+            engine.effects_bus,
+        );
+        let h = engine.play_one_shot_source(source, Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(engine.get_state(h), Some(PlaybackState::Playing));
+
+        engine.update(0.05);
+        assert_eq!(engine.get_state(h), Some(PlaybackState::Playing));
+
+        engine.update(0.06);
+        assert_eq!(engine.get_state(h), None);
+        assert!(engine.source_handles().is_empty());
+    }
+
+    extern "C" fn count_finished(_: Handle<AudioSource>, user_data: *mut c_void) {
+        unsafe { *(user_data as *mut u32) += 1 };
+    }
+
+    #[test]
+    fn stop_with_fade_keeps_playing_until_the_fade_ends() {
+        let mut engine = AudioEngine::new(&AudioEngineInfo::default());
+        let mut finished = 0u32;
+        engine.register_finished_callback(count_finished, &mut finished as *mut u32 as *mut c_void);
+        // 1 s at 8 kHz, longer than the fade.
+        let clip = insert_source(&mut engine, &wav_bytes(8000, 1, 8000));
+        engine.play(clip);
+        engine.update(0.0);
+        let full = engine.effective_volume(clip).unwrap();
+
+        engine.stop_with_fade(clip, 0.2);
+        engine.update(0.1);
+        assert_eq!(engine.get_state(clip), Some(PlaybackState::Playing));
+        engine.update(0.0);
+        let faded = engine.effective_volume(clip).unwrap();
+        assert!(
+            (faded - full * 0.5).abs() < 1e-4,
+            "volume {faded} of {full}"
+        );
+        assert_eq!(finished, 0);
+
+        engine.update(0.15);
+        assert_eq!(engine.get_state(clip), Some(PlaybackState::Stopped));
+        assert_eq!(finished, 1);
+    }
+}
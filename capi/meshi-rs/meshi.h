@@ -10,6 +10,7 @@ extern "C" {
 
 typedef void (*MeshiEventCallback)(struct MeshiEvent*, void*);
 typedef void (*MeshiAudioFinishedCallback)(MeshiAudioSourceHandle, void*);
+typedef void (*MeshiPhysicsContactCallback)(const MeshiContactInfo*, void*);
 typedef const struct MeshiPluginApi* (*MeshiPluginGetApiFn)(void);
 typedef void* (*MeshiSymbolLoader)(const char* name);
 
@@ -59,12 +60,6 @@ typedef struct MeshiPluginApi {
     void (*audio_set_looping)(struct MeshiEngine* engine, MeshiAudioSourceHandle h, int32_t looping);
     void (*audio_set_volume)(struct MeshiEngine* engine, MeshiAudioSourceHandle h, float volume);
     void (*audio_set_pitch)(struct MeshiEngine* engine, MeshiAudioSourceHandle h, float pitch);
-    MeshiAudioSourceHandle (*audio_create_stream)(struct MeshiEngine* engine, const char* path);
-    size_t (*audio_update_stream)(
-        struct MeshiEngine* engine,
-        MeshiAudioSourceHandle h,
-        uint8_t* out_samples,
-        size_t max);
     void (*audio_set_source_transform)(
         struct MeshiEngine* engine,
         MeshiAudioSourceHandle h,
@@ -74,6 +69,12 @@ typedef struct MeshiPluginApi {
         struct MeshiEngine* engine,
         const MeshiMat4* transform,
         MeshiVec3 velocity);
+    MeshiAudioSourceHandle (*audio_create_stream)(struct MeshiEngine* engine, const char* path);
+    size_t (*audio_update_stream)(
+        struct MeshiEngine* engine,
+        MeshiAudioSourceHandle h,
+        uint8_t* out_samples,
+        size_t max);
     void (*audio_set_bus_volume)(struct MeshiEngine* engine, MeshiAudioBusHandle h, float volume);
     void (*audio_register_finished_callback)(struct MeshiEngine* engine, void* user_data, MeshiAudioFinishedCallback cb);
     void (*physx_set_gravity)(struct MeshiEngine* engine, float gravity_mps);
@@ -98,6 +99,51 @@ typedef struct MeshiPluginApi {
         struct MeshiEngine* engine,
         const MeshiRenderObjectHandle* render_handle,
         const MeshiRigidBodyHandle* physics_handle);
+    int32_t (*gfx_try_create_mesh_object)(
+        struct MeshiEngine* render,
+        const MeshiMeshObjectInfo* info,
+        MeshiRenderObjectHandle* out_handle);
+    int32_t (*gfx_try_create_render_object)(
+        struct MeshiEngine* render,
+        const MeshiRenderObjectInfo* info,
+        MeshiRenderObjectHandle* out_handle);
+    void (*gfx_set_light_position)(struct MeshiEngine* render, MeshiLightHandle h, MeshiVec3 position);
+    void (*gfx_set_transforms_batch)(
+        struct MeshiEngine* render,
+        const MeshiRenderObjectHandle* handles,
+        const MeshiMat4* transforms,
+        size_t count);
+    void (*physx_register_contact_callback)(struct MeshiEngine* engine, void* user_data, MeshiPhysicsContactCallback cb);
+    int32_t (*gfx_frame_dump)(struct MeshiEngine* render, MeshiImage* out_image);
+    void (*audio_set_master_volume)(struct MeshiEngine* engine, float volume);
+    int32_t (*physx_set_rigid_body_gravity)(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, int32_t enabled);
+    int32_t (*audio_stream_finished)(struct MeshiEngine* engine, MeshiAudioSourceHandle h);
+    void (*audio_set_stream_looping)(struct MeshiEngine* engine, MeshiAudioSourceHandle h, int32_t looping);
+    MeshiAudioBackend (*audio_get_backend)(struct MeshiEngine* engine);
+    void (*audio_get_info)(struct MeshiEngine* engine, MeshiAudioEngineInfo* out_info);
+    void (*gfx_set_primary_camera)(struct MeshiEngine* render, MeshiCameraHandle camera_handle);
+    MeshiCameraHandle (*gfx_get_primary_camera)(struct MeshiEngine* render);
+    int32_t (*gfx_set_object_mesh)(struct MeshiEngine* render, MeshiRenderObjectHandle h, const char* mesh);
+    int32_t (*gfx_set_object_material)(struct MeshiEngine* render, MeshiRenderObjectHandle h, const char* material);
+    size_t (*gfx_object_count)(struct MeshiEngine* render);
+    size_t (*gfx_light_count)(struct MeshiEngine* render);
+    size_t (*gfx_camera_count)(struct MeshiEngine* render);
+    int32_t (*physx_get_rigid_body_state)(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, MeshiRigidBodyState* out_state);
+    int32_t (*physx_set_rigid_body_state)(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, const MeshiRigidBodyState* state);
+    MeshiAudioSourceHandle (*audio_play_one_shot)(struct MeshiEngine* engine, const char* path, MeshiVec3 position);
+    void (*gfx_set_display_size)(
+        struct MeshiEngine* render,
+        MeshiDisplayHandle display_handle,
+        uint32_t width,
+        uint32_t height);
+    int32_t (*gfx_get_display_size)(
+        struct MeshiEngine* render,
+        MeshiDisplayHandle display_handle,
+        uint32_t* out_width,
+        uint32_t* out_height);
+    void (*gfx_set_display_fullscreen)(struct MeshiEngine* render, MeshiDisplayHandle display_handle, int32_t value);
+    int32_t (*physx_set_rigid_body_velocity)(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, const MeshiVec3* velocity);
+    void (*physx_set_paused)(struct MeshiEngine* engine, int32_t paused);
 } MeshiPluginApi;
 
 // Engine
@@ -112,6 +158,7 @@ const struct MeshiPluginApi* meshi_plugin_get_api(void);
 
 // Audio
 MeshiAudioSourceHandle meshi_audio_create_source(struct MeshiEngine* engine, const char* path);
+MeshiAudioSourceHandle meshi_audio_play_one_shot(struct MeshiEngine* engine, const char* path, MeshiVec3 position);
 void meshi_audio_destroy_source(struct MeshiEngine* engine, MeshiAudioSourceHandle h);
 void meshi_audio_play(struct MeshiEngine* engine, MeshiAudioSourceHandle h);
 void meshi_audio_pause(struct MeshiEngine* engine, MeshiAudioSourceHandle h);
@@ -126,6 +173,8 @@ size_t meshi_audio_update_stream(
     MeshiAudioSourceHandle h,
     uint8_t* out_samples,
     size_t max);
+int32_t meshi_audio_stream_finished(struct MeshiEngine* engine, MeshiAudioSourceHandle h);
+void meshi_audio_set_stream_looping(struct MeshiEngine* engine, MeshiAudioSourceHandle h, int32_t looping);
 void meshi_audio_set_source_transform(
     struct MeshiEngine* engine,
     MeshiAudioSourceHandle h,
@@ -136,18 +185,42 @@ void meshi_audio_set_listener_transform(
     const MeshiMat4* transform,
     MeshiVec3 velocity);
 void meshi_audio_set_bus_volume(struct MeshiEngine* engine, MeshiAudioBusHandle h, float volume);
+void meshi_audio_set_master_volume(struct MeshiEngine* engine, float volume);
+MeshiAudioBackend meshi_audio_get_backend(struct MeshiEngine* engine);
+void meshi_audio_get_info(struct MeshiEngine* engine, MeshiAudioEngineInfo* out_info);
 void meshi_audio_register_finished_callback(struct MeshiEngine* engine, void* user_data, MeshiAudioFinishedCallback cb);
 
 // Graphics
 MESHI_DEPRECATED
 MeshiRenderObjectHandle meshi_gfx_create_mesh_object(struct MeshiEngine* render, const MeshiMeshObjectInfo* info);
 MeshiRenderObjectHandle meshi_gfx_create_render_object(struct MeshiEngine* render, const MeshiRenderObjectInfo* info);
+// Returns a MeshiResult code. out_handle is only written on success;
+// engines without an asset database report DatabaseUnavailable.
+MESHI_DEPRECATED
+int32_t meshi_gfx_try_create_mesh_object(
+    struct MeshiEngine* render,
+    const MeshiMeshObjectInfo* info,
+    MeshiRenderObjectHandle* out_handle);
+int32_t meshi_gfx_try_create_render_object(
+    struct MeshiEngine* render,
+    const MeshiRenderObjectInfo* info,
+    MeshiRenderObjectHandle* out_handle);
 void meshi_gfx_release_render_object(struct MeshiEngine* render, const MeshiRenderObjectHandle* h);
+// Rebind an object's mesh or material in place. Returns a MeshiResult code;
+// the object is unchanged on failure.
+int32_t meshi_gfx_set_object_mesh(struct MeshiEngine* render, MeshiRenderObjectHandle h, const char* mesh);
+int32_t meshi_gfx_set_object_material(struct MeshiEngine* render, MeshiRenderObjectHandle h, const char* material);
 void meshi_gfx_set_transform(struct MeshiEngine* render, MeshiRenderObjectHandle h, const MeshiMat4* transform);
+void meshi_gfx_set_transforms_batch(
+    struct MeshiEngine* render,
+    const MeshiRenderObjectHandle* handles,
+    const MeshiMat4* transforms,
+    size_t count);
 MeshiLightHandle meshi_gfx_create_light(struct MeshiEngine* render, const MeshiLightInfo* info);
 void meshi_gfx_release_light(struct MeshiEngine* render, const MeshiLightHandle* h);
 void meshi_gfx_set_light_transform(struct MeshiEngine* render, MeshiLightHandle h, const MeshiMat4* transform);
 void meshi_gfx_set_light_info(struct MeshiEngine* render, MeshiLightHandle h, const MeshiLightInfo* info);
+void meshi_gfx_set_light_position(struct MeshiEngine* render, MeshiLightHandle h, MeshiVec3 position);
 MeshiDisplayHandle meshi_gfx_register_display(struct MeshiEngine* render, const MeshiDisplayInfo* info);
 void meshi_gfx_attach_camera_to_display(
     struct MeshiEngine* render,
@@ -162,12 +235,34 @@ void meshi_gfx_set_camera_projection(
     struct MeshiEngine* render,
     MeshiCameraHandle camera_handle,
     const MeshiMat4* transform);
+// A default camera handle in the camera calls above targets the primary camera.
+void meshi_gfx_set_primary_camera(struct MeshiEngine* render, MeshiCameraHandle camera_handle);
+MeshiCameraHandle meshi_gfx_get_primary_camera(struct MeshiEngine* render);
 void meshi_gfx_capture_mouse(struct MeshiEngine* render, int32_t value);
+// Resizing the primary display also resizes the renderer output.
+void meshi_gfx_set_display_size(
+    struct MeshiEngine* render,
+    MeshiDisplayHandle display_handle,
+    uint32_t width,
+    uint32_t height);
+int32_t meshi_gfx_get_display_size(
+    struct MeshiEngine* render,
+    MeshiDisplayHandle display_handle,
+    uint32_t* out_width,
+    uint32_t* out_height);
+void meshi_gfx_set_display_fullscreen(struct MeshiEngine* render, MeshiDisplayHandle display_handle, int32_t value);
+// Pixels stay valid until the next meshi_update or meshi_gfx_frame_dump call.
+int32_t meshi_gfx_frame_dump(struct MeshiEngine* render, MeshiImage* out_image);
+// Live renderer resource counts, for tooling and leak checks.
+size_t meshi_gfx_object_count(struct MeshiEngine* render);
+size_t meshi_gfx_light_count(struct MeshiEngine* render);
+size_t meshi_gfx_camera_count(struct MeshiEngine* render);
 
 
 // Physics
 struct MeshiEngine* meshi_get_physics_system(struct MeshiEngine* engine);
 void meshi_physx_set_gravity(struct MeshiEngine* engine, float gravity_mps);
+void meshi_physx_set_paused(struct MeshiEngine* engine, int32_t paused);
 MeshiMaterialHandle meshi_physx_create_material(struct MeshiEngine* engine, const MeshiMaterialInfo* info);
 void meshi_physx_release_material(struct MeshiEngine* engine, const MeshiMaterialHandle* h);
 MeshiRigidBodyHandle meshi_physx_create_rigid_body(struct MeshiEngine* engine, const MeshiRigidBodyInfo* info);
@@ -177,8 +272,14 @@ int32_t meshi_physx_set_rigid_body_transform(struct MeshiEngine* engine, const M
 int32_t meshi_physx_get_rigid_body_status(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, MeshiActorStatus* out_status);
 // Returns the current velocity of a rigid body or a zero vector on failure.
 MeshiVec3 meshi_physx_get_rigid_body_velocity(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h);
+int32_t meshi_physx_get_rigid_body_state(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, MeshiRigidBodyState* out_state);
+int32_t meshi_physx_set_rigid_body_state(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, const MeshiRigidBodyState* state);
+int32_t meshi_physx_set_rigid_body_velocity(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, const MeshiVec3* velocity);
 int32_t meshi_physx_set_collision_shape(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, const MeshiCollisionShape* shape);
+// Takes effect on the next simulation step.
+int32_t meshi_physx_set_rigid_body_gravity(struct MeshiEngine* engine, const MeshiRigidBodyHandle* h, int32_t enabled);
 size_t meshi_physx_get_contacts(struct MeshiEngine* engine, MeshiContactInfo* out_contacts, size_t max);
+void meshi_physx_register_contact_callback(struct MeshiEngine* engine, void* user_data, MeshiPhysicsContactCallback cb);
 MeshiCollisionShape meshi_physx_collision_shape_sphere(float radius);
 MeshiCollisionShape meshi_physx_collision_shape_box(MeshiVec3 dimensions);
 MeshiCollisionShape meshi_physx_collision_shape_capsule(float half_height, float radius);
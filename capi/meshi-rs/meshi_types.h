@@ -47,6 +47,18 @@ struct MeshiPhysicsSimulation;
 struct MeshiMaterial;
 struct MeshiRigidBody;
 
+enum class MeshiResult : std::int32_t {
+    Success = 0,
+    NullPointer = 1,
+    GpuError = 2,
+    DatabaseError = 3,
+    AssetError = 4,
+    ShaderError = 5,
+    Unknown = 6,
+    Unavailable = 7,
+    DatabaseUnavailable = 8,
+};
+
 struct MeshiEngineInfo {
     const char* application_name = nullptr;
     const char* application_location = nullptr;
@@ -67,6 +79,13 @@ struct MeshiDisplayInfo {
     MeshiWindowInfo window;
 };
 
+struct MeshiImage {
+    std::uint32_t width = 0;
+    std::uint32_t height = 0;
+    std::uint32_t format = 0;
+    const std::uint8_t* pixels = nullptr;
+};
+
 struct MeshiRenderObjectInfo {
     const char* mesh = nullptr;
     const char* material = nullptr;
@@ -83,6 +102,12 @@ enum class MeshiLightType : std::uint32_t {
     RectArea = 3,
 };
 
+enum class MeshiLightFalloff : std::uint32_t {
+    InverseSquare = 0,
+    Linear = 1,
+    Smooth = 2,
+};
+
 enum class MeshiLightFlags : std::uint32_t {
     None = 0,
     CastsShadows = 1 << 0,
@@ -113,6 +138,8 @@ struct MeshiLightInfo {
 
     float rect_half_width;
     float rect_half_height;
+
+    MeshiLightFalloff falloff;
 };
 
 enum class MeshiEventType : std::uint32_t {
@@ -136,6 +163,7 @@ enum class MeshiEventSource : std::uint32_t {
     MouseButton = 3,
     Gamepad = 4,
     Window = 5,
+    MouseMotion = 6,
 };
 
 enum class MeshiKeyCode : std::uint32_t {
@@ -288,6 +316,7 @@ struct MeshiMaterialInfo {
     float dynamic_friction_m;
     float static_friction_m;
     float restitution;
+    float density;
 };
 
 struct MeshiForceApplyInfo {
@@ -298,6 +327,8 @@ enum class MeshiCollisionShapeType : std::uint32_t {
     Sphere = 0,
     Box = 1,
     Capsule = 2,
+    Plane = 3,
+    Heightfield = 4,
 };
 
 enum class MeshiPlaybackState : std::uint32_t {
@@ -306,6 +337,20 @@ enum class MeshiPlaybackState : std::uint32_t {
     Paused = 2,
 };
 
+enum class MeshiAudioBackend : std::uint32_t {
+    Dummy = 0,
+    Cpal = 1,
+    Rodio = 2,
+};
+
+struct MeshiAudioEngineInfo {
+    std::uint32_t sample_rate;
+    std::uint32_t channels;
+    MeshiAudioBackend backend;
+    bool debug_mode;
+    float pitch_range[2];
+};
+
 struct alignas(16) MeshiCollisionShape {
     MeshiVec3 dimensions;
     float radius;
@@ -320,6 +365,7 @@ struct MeshiRigidBodyInfo {
     MeshiQuat initial_rotation;
     std::uint32_t has_gravity;
     MeshiCollisionShape collision_shape;
+    float mass;
 };
 
 struct MeshiActorStatus {
@@ -327,11 +373,19 @@ struct MeshiActorStatus {
     MeshiQuat rotation;
 };
 
+struct MeshiRigidBodyState {
+    MeshiVec3 position;
+    MeshiQuat rotation;
+    MeshiVec3 linear_velocity;
+    MeshiVec3 angular_velocity;
+};
+
 struct MeshiContactInfo {
     MeshiHandle a;
     MeshiHandle b;
     MeshiVec3 normal;
     float penetration;
+    float impulse;
 };
 
 using MeshiRenderObjectHandle = MeshiHandle;
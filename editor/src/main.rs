@@ -1,6 +1,7 @@
 mod project;
 mod render_backend;
 mod runtime;
+mod scene;
 mod terrain;
 mod ui;
 
@@ -12,6 +13,7 @@ mod editor {
     use crate::{
         project::ProjectManager,
         runtime::{RuntimeBridge, RuntimeControlState, RuntimeLogLevel},
+        scene::Scene,
         ui::{EditorUi, UiAction},
     };
     use eframe::{Frame, NativeOptions};
@@ -63,6 +65,10 @@ mod editor {
         }
     }
 
+    fn scene_path(project_root: &Path) -> PathBuf {
+        project_root.join("scenes").join("main.scene.json")
+    }
+
     fn open_in_system_editor(path: &Path) -> io::Result<()> {
         if !path.exists() {
             return Err(io::Error::new(
@@ -154,6 +160,42 @@ mod editor {
                                 .log_message(RuntimeLogLevel::Info, "Opened project.");
                         }
                     }
+                    UiAction::SaveScene => match active_project_root.as_deref() {
+                        Some(root) => {
+                            let path = scene_path(root);
+                            if let Err(err) = self.runtime.scene().save(&path) {
+                                self.runtime.log_message(
+                                    RuntimeLogLevel::Error,
+                                    format!("Failed to save scene: {}", err),
+                                );
+                            } else {
+                                self.runtime.log_message(
+                                    RuntimeLogLevel::Info,
+                                    format!("Saved scene to {}.", path.display()),
+                                );
+                            }
+                        }
+                        None => self
+                            .runtime
+                            .log_message(RuntimeLogLevel::Error, "No active project to save into."),
+                    },
+                    UiAction::OpenScene => match active_project_root.as_deref() {
+                        Some(root) => match Scene::load(&scene_path(root)) {
+                            Ok(scene) => {
+                                self.runtime.set_database_root(Some(&root.join("database")));
+                                self.runtime.load_scene(scene);
+                                self.runtime
+                                    .log_message(RuntimeLogLevel::Info, "Opened scene.");
+                            }
+                            Err(err) => self.runtime.log_message(
+                                RuntimeLogLevel::Error,
+                                format!("Failed to open scene: {}", err),
+                            ),
+                        },
+                        None => self
+                            .runtime
+                            .log_message(RuntimeLogLevel::Error, "No active project to open from."),
+                    },
                     UiAction::OpenProjectFile(path) => {
                         if let Err(err) = open_in_system_editor(&path) {
                             self.runtime.log_message(
@@ -192,6 +234,9 @@ mod editor {
                     }
                 }
             }
+            if let Some(transform) = output.transform_edit {
+                self.runtime.set_selected_transform(&transform);
+            }
             let delta_time = ctx.input(|input| input.unstable_dt);
             self.runtime.configure_hot_reload(
                 self.project_manager.active_project_root().as_deref(),
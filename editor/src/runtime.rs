@@ -1,6 +1,10 @@
+use crate::scene::{Scene, SceneObject};
 use dashi::{Handle, SampleCount};
 use glam::{Mat4, Vec3};
-use meshi_graphics::{DisplayInfo, RenderEngine, RenderEngineInfo, RendererSelect, WindowInfo};
+use meshi_graphics::{
+    BillboardInfo, BillboardType, Camera, DB, DBInfo, DisplayInfo, Light, RenderEngine,
+    RenderEngineInfo, RenderObject, RenderObjectInfo, RendererSelect, WindowInfo,
+};
 use std::{
     fs,
     io::{self, BufRead},
@@ -36,12 +40,30 @@ impl RuntimeControlState {
             false
         }
     }
+
+    /// Returns the simulation delta for this frame, or `None` while paused.
+    /// A pending step request advances exactly one frame and is cleared.
+    pub fn advance(&mut self, delta_time: f32) -> Option<f32> {
+        let stepped = self.consume_step();
+        (self.playing || stepped).then(|| delta_time.max(MIN_FRAME_DELTA))
+    }
 }
 
+/// Smallest delta fed to the engine so a single step always moves the world.
+const MIN_FRAME_DELTA: f32 = 1.0 / 240.0;
+
 pub struct RuntimeBridge {
     engine: Option<RenderEngine>,
     display: Option<Handle<meshi_graphics::Display>>,
     camera: Option<Handle<meshi_graphics::Camera>>,
+    scene: Scene,
+    scene_objects: Vec<Handle<RenderObject>>,
+    scene_lights: Vec<Handle<Light>>,
+    selected_object: Option<Handle<RenderObject>>,
+    /// Index into `scene.objects` of the selection, `None` for the placeholder.
+    selected_scene_index: Option<usize>,
+    selected_transform: Mat4,
+    redraw_requested: bool,
     viewport_size: [u32; 2],
     last_frame: Option<RuntimeFrame>,
     template_root: PathBuf,
@@ -55,6 +77,8 @@ pub struct RuntimeBridge {
     build_thread: Option<JoinHandle<()>>,
     child: Option<Child>,
     hot_reload: HotReloadState,
+    /// Declared after `engine`, which keeps a pointer into it.
+    database: Option<Box<DB>>,
 }
 
 impl RuntimeBridge {
@@ -67,6 +91,13 @@ impl RuntimeBridge {
             engine: None,
             display: None,
             camera: None,
+            scene: Scene::default(),
+            scene_objects: Vec::new(),
+            scene_lights: Vec::new(),
+            selected_object: None,
+            selected_scene_index: None,
+            selected_transform: Mat4::IDENTITY,
+            redraw_requested: false,
             viewport_size: [0, 0],
             last_frame: None,
             template_root,
@@ -80,6 +111,29 @@ impl RuntimeBridge {
             build_thread: None,
             child: None,
             hot_reload: HotReloadState::default(),
+            database: None,
+        }
+    }
+
+    /// Open the asset database scene meshes and materials are resolved from.
+    /// The preview engine is rebuilt against it on the next tick.
+    pub fn set_database_root(&mut self, database_root: Option<&Path>) {
+        // The engine points into the database, so it goes first.
+        self.engine = None;
+        self.database = None;
+        let Some(base_dir) = database_root.and_then(Path::to_str) else {
+            return;
+        };
+        match DB::new(&DBInfo {
+            base_dir,
+            layout_file: None,
+            pooled_geometry_uploads: false,
+        }) {
+            Ok(database) => self.database = Some(Box::new(database)),
+            Err(err) => self.push_log(
+                RuntimeLogLevel::Warn,
+                format!("Failed to open asset database at {}: {:?}", base_dir, err),
+            ),
         }
     }
 
@@ -96,6 +150,49 @@ impl RuntimeBridge {
         }
     }
 
+    /// Apply an inspector transform to the selected entity and redraw the
+    /// preview on the next tick.
+    pub fn set_selected_transform(&mut self, transform: &Mat4) {
+        self.selected_transform = *transform;
+        if let (Some(engine), Some(object)) = (self.engine.as_mut(), self.selected_object) {
+            engine.set_object_transform(object, transform);
+        }
+        if let Some(object) = self
+            .selected_scene_index
+            .and_then(|index| self.scene.objects.get_mut(index))
+        {
+            object.transform = transform.to_cols_array();
+        }
+        self.redraw_requested = true;
+    }
+
+    pub fn scene(&self) -> &Scene {
+        &self.scene
+    }
+
+    /// Replace the current scene and repopulate the preview engine with it.
+    pub fn load_scene(&mut self, scene: Scene) {
+        self.scene = scene;
+        let Some(mut engine) = self.engine.take() else {
+            return;
+        };
+        for object in self.scene_objects.drain(..) {
+            engine.release_object(object);
+        }
+        for light in self.scene_lights.drain(..) {
+            engine.release_light(light);
+        }
+        if let Some(object) = self.selected_object.take() {
+            engine.release_object(object);
+        }
+        if let Some(camera) = self.camera {
+            self.apply_scene_camera(&mut engine, camera);
+        }
+        self.populate_scene(&mut engine);
+        self.engine = Some(engine);
+        self.redraw_requested = true;
+    }
+
     pub fn latest_frame(&self) -> Option<&RuntimeFrame> {
         self.last_frame.as_ref()
     }
@@ -149,19 +246,16 @@ impl RuntimeBridge {
             self.recreate_engine(viewport_pixels);
         }
 
-        let should_step = controls.consume_step();
-        let should_render = controls.playing || should_step || size_changed;
+        let advance = controls.advance(delta_time);
+        let should_render =
+            advance.is_some() || size_changed || std::mem::take(&mut self.redraw_requested);
         let Some(engine) = self.engine.as_mut() else {
             return false;
         };
 
         if should_render {
-            let frame_delta = if controls.playing || should_step {
-                delta_time.max(1.0 / 240.0)
-            } else {
-                0.0
-            };
-            engine.update(frame_delta);
+            // Redraws while paused render the world without advancing it.
+            engine.update(advance.unwrap_or(0.0));
 
             let Some(display) = self.display else {
                 return false;
@@ -196,8 +290,12 @@ impl RuntimeBridge {
             skybox_cubemap_entry: None,
             debug_mode: false,
             shadow_cascades: Default::default(),
+            anisotropy: 1,
         };
         let mut engine = RenderEngine::new(&info).expect("Failed to create RenderEngine");
+        if let Some(database) = self.database.as_mut() {
+            engine.initialize_database(database);
+        }
         let mut display_info = DisplayInfo::default();
         display_info.window = WindowInfo {
             title: "Meshi Editor Viewport".to_string(),
@@ -206,21 +304,146 @@ impl RuntimeBridge {
         };
         let display = engine.register_cpu_display(display_info);
 
-        let camera = engine.register_camera(&Mat4::from_translation(Vec3::new(0.0, 0.0, 5.0)));
-        engine.set_camera_perspective(
-            camera,
-            60f32.to_radians(),
-            viewport_pixels[0] as f32,
-            viewport_pixels[1] as f32,
-            0.1,
-            2000.0,
-        );
+        let camera = engine.register_camera(&Mat4::IDENTITY);
         engine.attach_camera_to_display(display, camera);
+        self.viewport_size = viewport_pixels;
+        self.apply_scene_camera(&mut engine, camera);
+        self.populate_scene(&mut engine);
 
         self.engine = Some(engine);
         self.display = Some(display);
         self.camera = Some(camera);
-        self.viewport_size = viewport_pixels;
+    }
+
+    fn apply_scene_camera(&self, engine: &mut RenderEngine, camera: Handle<Camera>) {
+        let scene_camera = &self.scene.camera;
+        engine.set_camera_transform(camera, &Mat4::from_cols_array(&scene_camera.transform));
+        engine.set_camera_perspective(
+            camera,
+            scene_camera.fov_y_deg.to_radians(),
+            self.viewport_size[0] as f32,
+            self.viewport_size[1] as f32,
+            scene_camera.near,
+            scene_camera.far,
+        );
+    }
+
+    /// Register the scene's objects and lights with a freshly created engine.
+    /// Without a loaded scene a placeholder object stands in as the selection.
+    fn populate_scene(&mut self, engine: &mut RenderEngine) {
+        self.scene_objects.clear();
+        self.scene_lights.clear();
+        self.selected_object = None;
+        self.selected_scene_index = None;
+
+        let objects = self.scene.objects.clone();
+        for (index, object) in objects.iter().enumerate() {
+            let Some(handle) = self.register_scene_object(engine, object) else {
+                continue;
+            };
+            let transform = Mat4::from_cols_array(&object.transform);
+            engine.set_object_transform(handle, &transform);
+            self.scene_objects.push(handle);
+            if self.selected_scene_index.is_none() {
+                self.selected_object = Some(handle);
+                self.selected_scene_index = Some(index);
+                self.selected_transform = transform;
+            }
+        }
+
+        let lights = self.scene.lights.clone();
+        for light in &lights {
+            match engine.register_light(&light.world_light_info()) {
+                Ok(handle) => self.scene_lights.push(handle),
+                Err(err) => self.push_log(
+                    RuntimeLogLevel::Warn,
+                    format!("Skipping invalid scene light: {}", err),
+                ),
+            }
+        }
+
+        if self.selected_object.is_none() {
+            self.selected_object = self.register_placeholder(engine);
+            if let Some(handle) = self.selected_object {
+                engine.set_object_transform(handle, &self.selected_transform);
+            }
+        }
+    }
+
+    /// Register `object`'s mesh and material from the asset database. Falls
+    /// back to a placeholder when the mesh cannot be resolved.
+    fn register_scene_object(
+        &mut self,
+        engine: &mut RenderEngine,
+        object: &SceneObject,
+    ) -> Option<Handle<RenderObject>> {
+        let Some(database) = self.database.as_mut() else {
+            self.push_log(
+                RuntimeLogLevel::Warn,
+                format!(
+                    "No asset database is open for mesh '{}' of '{}'. Using a placeholder.",
+                    object.mesh, object.name
+                ),
+            );
+            return self.register_placeholder(engine);
+        };
+        let model = match database.fetch_gpu_model(&object.mesh) {
+            Ok(model) => model,
+            Err(err) => {
+                self.push_log(
+                    RuntimeLogLevel::Warn,
+                    format!(
+                        "Unknown mesh '{}' for '{}': {:?}. Using a placeholder.",
+                        object.mesh, object.name, err
+                    ),
+                );
+                return self.register_placeholder(engine);
+            }
+        };
+        let handle = match engine.register_object(&RenderObjectInfo::Model(model.into())) {
+            Ok(handle) => handle,
+            Err(err) => {
+                self.push_log(
+                    RuntimeLogLevel::Warn,
+                    format!(
+                        "Failed to create '{}' from mesh '{}': {}. Using a placeholder.",
+                        object.name, object.mesh, err
+                    ),
+                );
+                return self.register_placeholder(engine);
+            }
+        };
+        if let Some(material) = &object.material {
+            if let Err(err) = engine.set_object_material(handle, material) {
+                self.push_log(
+                    RuntimeLogLevel::Warn,
+                    format!(
+                        "Unknown material '{}' for '{}': {}. Keeping the mesh's material.",
+                        material, object.name, err
+                    ),
+                );
+            }
+        }
+        Some(handle)
+    }
+
+    fn register_placeholder(&mut self, engine: &mut RenderEngine) -> Option<Handle<RenderObject>> {
+        let placeholder = RenderObjectInfo::Billboard(BillboardInfo {
+            texture_id: 0,
+            material: None,
+            billboard_type: BillboardType::AxisAligned,
+            target: Vec3::ZERO,
+        });
+        match engine.register_object(&placeholder) {
+            Ok(handle) => Some(handle),
+            Err(err) => {
+                self.push_log(
+                    RuntimeLogLevel::Warn,
+                    format!("Failed to create preview object: {}", err),
+                );
+                None
+            }
+        }
     }
 
     fn start_build(&mut self, project_root: Option<&Path>, action: RuntimeBuildAction) {
@@ -631,3 +854,36 @@ fn spawn_output_reader<T: io::Read + Send + 'static>(
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paused_controls_do_not_advance() {
+        let mut controls = RuntimeControlState::default();
+        assert_eq!(controls.advance(0.016), None);
+    }
+
+    #[test]
+    fn step_request_advances_exactly_once() {
+        let mut controls = RuntimeControlState::default();
+        controls.request_step();
+        assert_eq!(controls.advance(0.016), Some(0.016));
+        assert_eq!(controls.advance(0.016), None);
+        assert!(!controls.consume_step());
+    }
+
+    #[test]
+    fn playing_controls_advance_every_frame() {
+        let mut controls = RuntimeControlState {
+            playing: true,
+            ..Default::default()
+        };
+        assert_eq!(controls.advance(0.016), Some(0.016));
+        assert_eq!(controls.advance(0.0), Some(MIN_FRAME_DELTA));
+
+        controls.playing = false;
+        assert_eq!(controls.advance(0.016), None);
+    }
+}
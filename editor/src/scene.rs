@@ -0,0 +1,228 @@
+use glam::{Mat4, Vec3};
+use meshi_ffi_structs::{LightFalloff, LightInfo, LightType};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::Path};
+
+/// Editor scene description saved alongside a project. Transforms are stored
+/// as column-major `Mat4` arrays.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Scene {
+    #[serde(default)]
+    pub objects: Vec<SceneObject>,
+    #[serde(default)]
+    pub lights: Vec<SceneLight>,
+    #[serde(default)]
+    pub camera: SceneCamera,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneObject {
+    pub name: String,
+    pub mesh: String,
+    #[serde(default)]
+    pub material: Option<String>,
+    #[serde(default = "identity_transform")]
+    pub transform: [f32; 16],
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneLight {
+    pub info: SceneLightInfo,
+    #[serde(default = "identity_transform")]
+    pub transform: [f32; 16],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SceneLightKind {
+    Directional,
+    Point,
+    Spot,
+    RectArea,
+}
+
+/// Serializable mirror of [`LightInfo`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneLightInfo {
+    pub kind: SceneLightKind,
+    #[serde(default)]
+    pub flags: u32,
+    pub color: [f32; 3],
+    pub intensity: f32,
+    #[serde(default)]
+    pub range: f32,
+    #[serde(default)]
+    pub position: [f32; 3],
+    #[serde(default)]
+    pub direction: [f32; 3],
+    #[serde(default)]
+    pub spot_inner_angle_rad: f32,
+    #[serde(default)]
+    pub spot_outer_angle_rad: f32,
+    #[serde(default)]
+    pub rect_half_width: f32,
+    #[serde(default)]
+    pub rect_half_height: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SceneCamera {
+    pub transform: [f32; 16],
+    pub fov_y_deg: f32,
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Default for SceneCamera {
+    fn default() -> Self {
+        Self {
+            transform: Mat4::from_translation(Vec3::new(0.0, 0.0, 5.0)).to_cols_array(),
+            fov_y_deg: 60.0,
+            near: 0.1,
+            far: 2000.0,
+        }
+    }
+}
+
+impl Scene {
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let data = serde_json::to_string_pretty(self)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        fs::write(path, data)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+impl SceneLight {
+    /// The light with its position and direction moved into world space.
+    pub fn world_light_info(&self) -> LightInfo {
+        let transform = Mat4::from_cols_array(&self.transform);
+        let mut info = self.info.to_light_info();
+        let position = transform.transform_point3(Vec3::new(info.pos_x, info.pos_y, info.pos_z));
+        let direction = transform.transform_vector3(Vec3::new(info.dir_x, info.dir_y, info.dir_z));
+        [info.pos_x, info.pos_y, info.pos_z] = position.to_array();
+        [info.dir_x, info.dir_y, info.dir_z] = direction.to_array();
+        info
+    }
+}
+
+impl SceneLightInfo {
+    pub fn to_light_info(&self) -> LightInfo {
+        LightInfo {
+            ty: match self.kind {
+                SceneLightKind::Directional => LightType::Directional,
+                SceneLightKind::Point => LightType::Point,
+                SceneLightKind::Spot => LightType::Spot,
+                SceneLightKind::RectArea => LightType::RectArea,
+            },
+            flags: self.flags,
+            intensity: self.intensity,
+            range: self.range,
+            color_r: self.color[0],
+            color_g: self.color[1],
+            color_b: self.color[2],
+            pos_x: self.position[0],
+            pos_y: self.position[1],
+            pos_z: self.position[2],
+            dir_x: self.direction[0],
+            dir_y: self.direction[1],
+            dir_z: self.direction[2],
+            spot_inner_angle_rad: self.spot_inner_angle_rad,
+            spot_outer_angle_rad: self.spot_outer_angle_rad,
+            rect_half_width: self.rect_half_width,
+            rect_half_height: self.rect_half_height,
+            falloff: LightFalloff::default(),
+        }
+    }
+}
+
+impl From<&LightInfo> for SceneLightInfo {
+    fn from(info: &LightInfo) -> Self {
+        Self {
+            kind: match info.ty {
+                LightType::Directional => SceneLightKind::Directional,
+                LightType::Point => SceneLightKind::Point,
+                LightType::Spot => SceneLightKind::Spot,
+                LightType::RectArea => SceneLightKind::RectArea,
+            },
+            flags: info.flags,
+            color: [info.color_r, info.color_g, info.color_b],
+            intensity: info.intensity,
+            range: info.range,
+            position: [info.pos_x, info.pos_y, info.pos_z],
+            direction: [info.dir_x, info.dir_y, info.dir_z],
+            spot_inner_angle_rad: info.spot_inner_angle_rad,
+            spot_outer_angle_rad: info.spot_outer_angle_rad,
+            rect_half_width: info.rect_half_width,
+            rect_half_height: info.rect_half_height,
+        }
+    }
+}
+
+fn identity_transform() -> [f32; 16] {
+    Mat4::IDENTITY.to_cols_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_scene() -> Scene {
+        Scene {
+            objects: vec![
+                SceneObject {
+                    name: "Floor".to_string(),
+                    mesh: "model/plane".to_string(),
+                    material: Some("material/concrete".to_string()),
+                    transform: Mat4::from_scale(Vec3::new(10.0, 1.0, 10.0)).to_cols_array(),
+                },
+                SceneObject {
+                    name: "Crate".to_string(),
+                    mesh: "model/cube".to_string(),
+                    material: None,
+                    transform: Mat4::from_translation(Vec3::new(1.5, 0.5, -2.0)).to_cols_array(),
+                },
+            ],
+            lights: vec![SceneLight {
+                info: SceneLightInfo::from(&LightInfo::point(
+                    Vec3::new(0.0, 3.0, 0.0),
+                    Vec3::new(1.0, 0.9, 0.7),
+                    4.0,
+                    12.0,
+                )),
+                transform: identity_transform(),
+            }],
+            camera: SceneCamera::default(),
+        }
+    }
+
+    #[test]
+    fn scene_round_trips_through_disk() {
+        let scene = sample_scene();
+        let path = std::env::temp_dir()
+            .join(format!("meshi-editor-scene-{}", std::process::id()))
+            .join("main.scene.json");
+
+        scene.save(&path).unwrap();
+        let loaded = Scene::load(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+
+        assert_eq!(loaded, scene);
+    }
+
+    #[test]
+    fn light_info_survives_conversion() {
+        let light = &sample_scene().lights[0];
+        let info = light.info.to_light_info();
+        assert_eq!(SceneLightInfo::from(&info), light.info);
+        assert_eq!(light.world_light_info().pos_y, 3.0);
+    }
+}
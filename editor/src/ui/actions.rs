@@ -10,6 +10,8 @@ pub enum UiAction {
     OpenProject,
     OpenWorkspace,
     SaveAll,
+    SaveScene,
+    OpenScene,
     OpenProjectFile(PathBuf),
 }
 
@@ -21,3 +23,5 @@ pub const ACTION_CREATE_PROJECT: u32 = 5;
 pub const ACTION_OPEN_PROJECT: u32 = 6;
 pub const ACTION_OPEN_WORKSPACE: u32 = 7;
 pub const ACTION_SAVE_ALL: u32 = 8;
+pub const ACTION_SAVE_SCENE: u32 = 9;
+pub const ACTION_OPEN_SCENE: u32 = 10;
@@ -0,0 +1,59 @@
+use glam::{EulerRot, Mat4, Quat, Vec3};
+
+/// Transform values edited through the inspector. Rotation is stored in
+/// degrees to match the DragValue widgets.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InspectorTransform {
+    pub position: [f32; 3],
+    pub rotation_deg: [f32; 3],
+    pub scale: [f32; 3],
+}
+
+impl Default for InspectorTransform {
+    fn default() -> Self {
+        Self {
+            position: [0.0; 3],
+            rotation_deg: [0.0; 3],
+            scale: [1.0; 3],
+        }
+    }
+}
+
+impl InspectorTransform {
+    /// Compose a scale, XYZ Euler rotation and translation into a matrix.
+    pub fn compose(&self) -> Mat4 {
+        let [rx, ry, rz] = self.rotation_deg.map(f32::to_radians);
+        Mat4::from_scale_rotation_translation(
+            Vec3::from_array(self.scale),
+            Quat::from_euler(EulerRot::XYZ, rx, ry, rz),
+            Vec3::from_array(self.position),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_transform_is_identity() {
+        assert_eq!(InspectorTransform::default().compose(), Mat4::IDENTITY);
+    }
+
+    #[test]
+    fn compose_applies_scale_then_rotation_then_translation() {
+        let transform = InspectorTransform {
+            position: [1.0, 2.0, 3.0],
+            rotation_deg: [0.0, 90.0, 0.0],
+            scale: [2.0, 2.0, 2.0],
+        };
+        let matrix = transform.compose();
+
+        let point = matrix.transform_point3(Vec3::X);
+        assert!(point.abs_diff_eq(Vec3::new(1.0, 2.0, 1.0), 1e-5));
+        let (scale, rotation, translation) = matrix.to_scale_rotation_translation();
+        assert!(scale.abs_diff_eq(Vec3::splat(2.0), 1e-5));
+        assert!(rotation.abs_diff_eq(Quat::from_rotation_y(90f32.to_radians()), 1e-5));
+        assert_eq!(translation, Vec3::new(1.0, 2.0, 3.0));
+    }
+}
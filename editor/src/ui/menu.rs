@@ -4,7 +4,8 @@ use crate::project::ProjectManager;
 
 use super::actions::{
     ACTION_BUILD_AND_RUN, ACTION_BUILD_PROJECT, ACTION_CREATE_PROJECT, ACTION_GENERATE_BINDINGS,
-    ACTION_OPEN_PROJECT, ACTION_OPEN_WORKSPACE, ACTION_REBUILD_ALL, ACTION_SAVE_ALL, UiAction,
+    ACTION_OPEN_PROJECT, ACTION_OPEN_SCENE, ACTION_OPEN_WORKSPACE, ACTION_REBUILD_ALL,
+    ACTION_SAVE_ALL, ACTION_SAVE_SCENE, UiAction,
 };
 
 pub fn create_menu_bar() -> MenuBar {
@@ -73,6 +74,17 @@ pub fn refresh_file_menu(menu_bar: &mut MenuBar, project_manager: &ProjectManage
             item
         },
         MenuItem::separator(),
+        {
+            let mut item = MenuItem::new("Open Scene...");
+            item.action_id = Some(ACTION_OPEN_SCENE);
+            item
+        },
+        {
+            let mut item = MenuItem::new("Save Scene");
+            item.action_id = Some(ACTION_SAVE_SCENE);
+            item
+        },
+        MenuItem::separator(),
         MenuItem::new("Recent Projects").with_submenu(recent_menu_items),
     ];
 
@@ -121,6 +133,8 @@ pub fn action_for_menu_item(action_id: u32) -> Option<UiAction> {
         ACTION_OPEN_PROJECT => Some(UiAction::OpenProject),
         ACTION_OPEN_WORKSPACE => Some(UiAction::OpenWorkspace),
         ACTION_SAVE_ALL => Some(UiAction::SaveAll),
+        ACTION_SAVE_SCENE => Some(UiAction::SaveScene),
+        ACTION_OPEN_SCENE => Some(UiAction::OpenScene),
         _ => None,
     }
 }
@@ -1,4 +1,5 @@
 mod actions;
+mod inspector;
 mod menu;
 mod panels;
 mod project_tree;
@@ -21,6 +22,7 @@ pub struct EditorUi {
     viewport: [f32; 2],
     runtime_texture: Option<egui::TextureHandle>,
     runtime_texture_size: [usize; 2],
+    inspector_transform: InspectorTransform,
 }
 
 impl EditorUi {
@@ -31,6 +33,7 @@ impl EditorUi {
             viewport: [1280.0, 720.0],
             runtime_texture: None,
             runtime_texture_size: [0, 0],
+            inspector_transform: InspectorTransform::default(),
         }
     }
 
@@ -122,6 +125,7 @@ impl EditorUi {
         menu::refresh_build_menu(&mut self.menu_bar);
 
         let mut action = None;
+        let mut transform_edit = None;
         egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 for menu in &self.menu_bar.menus {
@@ -150,7 +154,9 @@ impl EditorUi {
             .resizable(false)
             .default_width(320.0)
             .show(ctx, |ui| {
-                panels::draw_inspector_panel(ui);
+                if panels::draw_inspector_panel(ui, &mut self.inspector_transform) {
+                    transform_edit = Some(self.inspector_transform.compose());
+                }
             });
 
         let viewport_pixels = [
@@ -180,6 +186,7 @@ impl EditorUi {
                 pixels: viewport_pixels,
             },
             action,
+            transform_edit,
         }
     }
 
@@ -275,6 +282,7 @@ impl EditorUi {
             position: [layout.position[0] + 12.0, layout.position[1] + 20.0],
             color: [0.92, 0.94, 0.98, 1.0],
             scale: 1.0,
+            clip_rect: None,
         });
 
         for (index, line) in lines.iter().enumerate() {
@@ -286,6 +294,7 @@ impl EditorUi {
                 ],
                 color: [0.78, 0.8, 0.86, 1.0],
                 scale: 0.9,
+                clip_rect: None,
             });
         }
     }
@@ -303,6 +312,8 @@ pub struct ViewportMetrics {
 pub struct UiFrameOutput {
     pub metrics: ViewportMetrics,
     pub action: Option<UiAction>,
+    /// Composed inspector transform, set when it was edited this frame.
+    pub transform_edit: Option<glam::Mat4>,
 }
 
 fn quad_from_pixels(
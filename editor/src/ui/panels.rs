@@ -3,7 +3,7 @@ use crate::{
     ui::project_tree::ProjectTreeEntry,
 };
 
-use super::actions::UiAction;
+use super::{actions::UiAction, inspector::InspectorTransform};
 
 /// Central place for panel widgets.
 /// Add new GUI elements by creating a `draw_*_panel` function in this module
@@ -27,17 +27,37 @@ pub fn draw_project_panel(ui: &mut egui::Ui, entries: Vec<ProjectTreeEntry>) ->
     action
 }
 
-pub fn draw_inspector_panel(ui: &mut egui::Ui) {
+/// Draws the inspector and returns `true` when the transform was edited.
+pub fn draw_inspector_panel(ui: &mut egui::Ui, transform: &mut InspectorTransform) -> bool {
     ui.heading("Inspector");
     ui.separator();
-    for line in [
-        "Selection Parameters",
-        "Transform",
-        "Mesh / Material",
-        "Script Bindings",
-    ] {
+    ui.label("Selection Parameters");
+
+    let mut changed = false;
+    ui.collapsing("Transform", |ui| {
+        egui::Grid::new("inspector_transform")
+            .num_columns(4)
+            .show(ui, |ui| {
+                changed |= drag_vec3(ui, "Position", &mut transform.position, 0.05);
+                changed |= drag_vec3(ui, "Rotation", &mut transform.rotation_deg, 1.0);
+                changed |= drag_vec3(ui, "Scale", &mut transform.scale, 0.01);
+            });
+    });
+
+    for line in ["Mesh / Material", "Script Bindings"] {
         ui.label(line);
     }
+    changed
+}
+
+fn drag_vec3(ui: &mut egui::Ui, label: &str, values: &mut [f32; 3], speed: f64) -> bool {
+    ui.label(label);
+    let mut changed = false;
+    for value in values.iter_mut() {
+        changed |= ui.add(egui::DragValue::new(value).speed(speed)).changed();
+    }
+    ui.end_row();
+    changed
 }
 
 pub fn draw_viewport_panel(
@@ -25,6 +25,8 @@ pub enum EventSource {
     MouseButton = 3,
     Gamepad = 4,
     Window = 5,
+    /// Relative pointer motion, reported while the mouse is captured.
+    MouseMotion = 6,
 }
 
 #[repr(C)]
@@ -214,6 +216,35 @@ impl Event {
     pub unsafe fn key(&self) -> KeyCode {
         self.payload.press.key
     }
+
+    /// Build a keyboard event, e.g. for feeding synthetic input to GUI state.
+    pub fn key_event(event_type: EventType, key: KeyCode) -> Self {
+        Self {
+            event_type,
+            source: EventSource::Key,
+            payload: Payload {
+                press: PressPayload {
+                    key,
+                    previous: EventType::Unknown,
+                },
+            },
+            timestamp: 0,
+        }
+    }
+
+    /// Build a window resize event carrying the new extent in pixels.
+    pub fn window_resized(width: u32, height: u32) -> Self {
+        Self {
+            event_type: EventType::WindowResized,
+            source: EventSource::Window,
+            payload: Payload {
+                motion2d: Motion2DPayload {
+                    motion: vec2(width as f32, height as f32),
+                },
+            },
+            timestamp: 0,
+        }
+    }
 }
 
 use glam::{vec2, Vec2};
@@ -616,6 +647,19 @@ pub fn from_winit_event(event: &WEvent<'_, ()>) -> Option<Event> {
                 timestamp: 0,
             })
         }
+        WEvent::DeviceEvent {
+            event: DeviceEvent::MouseMotion { delta },
+            ..
+        } => Some(Event {
+            event_type: EventType::Motion2D,
+            source: EventSource::MouseMotion,
+            payload: Payload {
+                motion2d: Motion2DPayload {
+                    motion: vec2(delta.0 as f32, delta.1 as f32),
+                },
+            },
+            timestamp: 0,
+        }),
         WEvent::DeviceEvent {
             event: DeviceEvent::Button { state, .. },
             ..
@@ -667,4 +711,16 @@ mod tests {
         assert_eq!(KeyCode::from(VK::NumpadAdd), KeyCode::NumpadAdd);
         assert_eq!(KeyCode::from(VK::AbntC1), KeyCode::Undefined);
     }
+
+    #[test]
+    fn converts_raw_mouse_motion_to_relative_motion() {
+        let event = WEvent::DeviceEvent {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            event: DeviceEvent::MouseMotion { delta: (3.0, -2.0) },
+        };
+        let converted = from_winit_event(&event).expect("mouse motion converts");
+        assert_eq!(converted.event_type(), EventType::Motion2D);
+        assert_eq!(converted.source(), EventSource::MouseMotion);
+        assert_eq!(unsafe { converted.motion2d() }, vec2(3.0, -2.0));
+    }
 }
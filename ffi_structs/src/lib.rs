@@ -39,6 +39,20 @@ pub enum LightType {
     RectArea    = 3,
 }
 
+/// How a point, spot or area light fades out towards its `range`.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LightFalloff {
+    /// Physically based inverse-square falloff, windowed to reach zero at
+    /// `range`.
+    #[default]
+    InverseSquare = 0,
+    /// Brightness drops linearly to zero at `range`.
+    Linear = 1,
+    /// Smoothstep from full brightness to zero at `range`.
+    Smooth = 2,
+}
+
 bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -49,6 +63,50 @@ bitflags::bitflags! {
     }
 }
 
+/// Reasons a [`LightInfo`] cannot be turned into a usable light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LightError {
+    /// Directional and spot lights need a non-zero direction.
+    ZeroDirection,
+    /// A spot light's inner cone angle is wider than its outer angle.
+    InvertedSpotCone {
+        inner: f32,
+        outer: f32,
+    },
+    NegativeRange(f32),
+    NegativeIntensity(f32),
+    /// Area lights need a non-zero width and height.
+    EmptyArea {
+        half_width: f32,
+        half_height: f32,
+    },
+}
+
+impl std::fmt::Display for LightError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LightError::ZeroDirection => write!(f, "Light direction has zero length"),
+            LightError::InvertedSpotCone { inner, outer } => write!(
+                f,
+                "Spot light inner angle {inner} exceeds outer angle {outer}"
+            ),
+            LightError::NegativeRange(range) => write!(f, "Light range {range} is negative"),
+            LightError::NegativeIntensity(intensity) => {
+                write!(f, "Light intensity {intensity} is negative")
+            }
+            LightError::EmptyArea {
+                half_width,
+                half_height,
+            } => write!(
+                f,
+                "Area light extent {half_width}x{half_height} must be non-zero"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LightError {}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug)]
 pub struct LightInfo {
@@ -75,6 +133,161 @@ pub struct LightInfo {
 
     pub rect_half_width: f32,
     pub rect_half_height: f32,
+
+    pub falloff: LightFalloff,
+}
+
+impl LightInfo {
+    /// A shadow-casting directional light. Position, range and spot/area
+    /// parameters are zeroed.
+    pub fn directional(direction: Vec3, color: Vec3, intensity: f32) -> Self {
+        Self {
+            ty: LightType::Directional,
+            flags: LightFlags::CASTS_SHADOWS.bits(),
+            dir_x: direction.x,
+            dir_y: direction.y,
+            dir_z: direction.z,
+            ..Self::base(color, intensity, 0.0)
+        }
+    }
+
+    /// A point light at `position`. Direction and spot/area parameters are zeroed.
+    pub fn point(position: Vec3, color: Vec3, intensity: f32, range: f32) -> Self {
+        Self {
+            ty: LightType::Point,
+            pos_x: position.x,
+            pos_y: position.y,
+            pos_z: position.z,
+            ..Self::base(color, intensity, range)
+        }
+    }
+
+    /// A shadow-casting spot light with cone angles in radians.
+    pub fn spot(
+        position: Vec3,
+        direction: Vec3,
+        color: Vec3,
+        intensity: f32,
+        range: f32,
+        inner_angle_rad: f32,
+        outer_angle_rad: f32,
+    ) -> Self {
+        Self {
+            ty: LightType::Spot,
+            flags: LightFlags::CASTS_SHADOWS.bits(),
+            pos_x: position.x,
+            pos_y: position.y,
+            pos_z: position.z,
+            dir_x: direction.x,
+            dir_y: direction.y,
+            dir_z: direction.z,
+            spot_inner_angle_rad: inner_angle_rad,
+            spot_outer_angle_rad: outer_angle_rad,
+            ..Self::base(color, intensity, range)
+        }
+    }
+
+    /// A rectangular area light facing `direction`.
+    pub fn rect_area(
+        position: Vec3,
+        direction: Vec3,
+        color: Vec3,
+        intensity: f32,
+        range: f32,
+        half_width: f32,
+        half_height: f32,
+    ) -> Self {
+        Self {
+            ty: LightType::RectArea,
+            pos_x: position.x,
+            pos_y: position.y,
+            pos_z: position.z,
+            dir_x: direction.x,
+            dir_y: direction.y,
+            dir_z: direction.z,
+            rect_half_width: half_width,
+            rect_half_height: half_height,
+            ..Self::base(color, intensity, range)
+        }
+    }
+
+    pub fn direction(&self) -> Vec3 {
+        vec3(self.dir_x, self.dir_y, self.dir_z)
+    }
+
+    /// Check that the fields used by this light type describe a usable light.
+    pub fn validate(&self) -> Result<(), LightError> {
+        if self.intensity < 0.0 {
+            return Err(LightError::NegativeIntensity(self.intensity));
+        }
+        if self.range < 0.0 {
+            return Err(LightError::NegativeRange(self.range));
+        }
+
+        match self.ty {
+            LightType::Directional => {
+                if self.direction().length_squared() <= f32::EPSILON {
+                    return Err(LightError::ZeroDirection);
+                }
+            }
+            LightType::Spot => {
+                if self.direction().length_squared() <= f32::EPSILON {
+                    return Err(LightError::ZeroDirection);
+                }
+                if self.spot_inner_angle_rad > self.spot_outer_angle_rad {
+                    return Err(LightError::InvertedSpotCone {
+                        inner: self.spot_inner_angle_rad,
+                        outer: self.spot_outer_angle_rad,
+                    });
+                }
+            }
+            LightType::RectArea => {
+                if self.rect_half_width <= 0.0 || self.rect_half_height <= 0.0 {
+                    return Err(LightError::EmptyArea {
+                        half_width: self.rect_half_width,
+                        half_height: self.rect_half_height,
+                    });
+                }
+            }
+            LightType::Point => {}
+        }
+
+        Ok(())
+    }
+
+    /// Copy of this light with a unit-length direction, when it has one.
+    pub fn normalized(&self) -> Self {
+        let direction = self.direction().normalize_or_zero();
+        Self {
+            dir_x: direction.x,
+            dir_y: direction.y,
+            dir_z: direction.z,
+            ..*self
+        }
+    }
+
+    fn base(color: Vec3, intensity: f32, range: f32) -> Self {
+        Self {
+            ty: LightType::Point,
+            flags: LightFlags::NONE.bits(),
+            intensity,
+            range,
+            color_r: color.x,
+            color_g: color.y,
+            color_b: color.z,
+            pos_x: 0.0,
+            pos_y: 0.0,
+            pos_z: 0.0,
+            dir_x: 0.0,
+            dir_y: 0.0,
+            dir_z: 0.0,
+            spot_inner_angle_rad: 0.0,
+            spot_outer_angle_rad: 0.0,
+            rect_half_width: 0.0,
+            rect_half_height: 0.0,
+            falloff: LightFalloff::InverseSquare,
+        }
+    }
 }
 
 #[repr(C)]
@@ -89,3 +302,159 @@ pub struct EventCallbackInfo {
     pub event_cb: extern "C" fn(*mut event::Event, *mut c_void),
     pub user_data: *mut c_void,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLOR: Vec3 = Vec3::new(0.5, 0.25, 1.0);
+
+    fn assert_color(info: &LightInfo) {
+        assert_eq!((info.color_r, info.color_g, info.color_b), (0.5, 0.25, 1.0));
+    }
+
+    #[test]
+    fn directional_zeroes_position_and_shape() {
+        let info = LightInfo::directional(Vec3::new(0.0, -1.0, 0.0), COLOR, 2.0);
+        assert_eq!(info.ty, LightType::Directional);
+        assert_eq!((info.dir_x, info.dir_y, info.dir_z), (0.0, -1.0, 0.0));
+        assert_eq!(info.intensity, 2.0);
+        assert_color(&info);
+        assert_eq!(
+            (info.pos_x, info.pos_y, info.pos_z, info.range),
+            (0.0, 0.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            (info.spot_inner_angle_rad, info.spot_outer_angle_rad),
+            (0.0, 0.0)
+        );
+        assert_eq!((info.rect_half_width, info.rect_half_height), (0.0, 0.0));
+    }
+
+    fn valid_spot() -> LightInfo {
+        LightInfo::spot(
+            Vec3::ZERO,
+            Vec3::new(0.0, -2.0, 0.0),
+            COLOR,
+            1.0,
+            5.0,
+            0.2,
+            0.4,
+        )
+    }
+
+    #[test]
+    fn validate_accepts_spot_and_normalizes_direction() {
+        let spot = valid_spot();
+        assert_eq!(spot.validate(), Ok(()));
+        assert_eq!(spot.normalized().direction(), Vec3::new(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn validate_rejects_zero_direction() {
+        let spot = LightInfo {
+            dir_y: 0.0,
+            ..valid_spot()
+        };
+        assert_eq!(spot.validate(), Err(LightError::ZeroDirection));
+
+        let directional = LightInfo::directional(Vec3::ZERO, COLOR, 1.0);
+        assert_eq!(directional.validate(), Err(LightError::ZeroDirection));
+    }
+
+    #[test]
+    fn validate_rejects_inverted_spot_cone() {
+        let spot = LightInfo {
+            spot_inner_angle_rad: 0.6,
+            ..valid_spot()
+        };
+        assert_eq!(
+            spot.validate(),
+            Err(LightError::InvertedSpotCone {
+                inner: 0.6,
+                outer: 0.4
+            })
+        );
+    }
+
+    #[test]
+    fn validate_rejects_negative_range_and_intensity() {
+        let point = LightInfo::point(Vec3::ZERO, COLOR, 1.0, -1.0);
+        assert_eq!(point.validate(), Err(LightError::NegativeRange(-1.0)));
+
+        let point = LightInfo::point(Vec3::ZERO, COLOR, -2.0, 1.0);
+        assert_eq!(point.validate(), Err(LightError::NegativeIntensity(-2.0)));
+    }
+
+    #[test]
+    fn validate_rejects_empty_area() {
+        let rect = LightInfo::rect_area(Vec3::ZERO, Vec3::NEG_Y, COLOR, 1.0, 5.0, 0.5, 0.0);
+        assert_eq!(
+            rect.validate(),
+            Err(LightError::EmptyArea {
+                half_width: 0.5,
+                half_height: 0.0
+            })
+        );
+    }
+
+    #[test]
+    fn point_zeroes_direction_and_shape() {
+        let info = LightInfo::point(Vec3::new(1.0, 2.0, 3.0), COLOR, 4.0, 6.0);
+        assert_eq!(info.ty, LightType::Point);
+        assert_eq!((info.pos_x, info.pos_y, info.pos_z), (1.0, 2.0, 3.0));
+        assert_eq!((info.intensity, info.range), (4.0, 6.0));
+        assert_color(&info);
+        assert_eq!((info.dir_x, info.dir_y, info.dir_z), (0.0, 0.0, 0.0));
+        assert_eq!(
+            (info.spot_inner_angle_rad, info.spot_outer_angle_rad),
+            (0.0, 0.0)
+        );
+        assert_eq!((info.rect_half_width, info.rect_half_height), (0.0, 0.0));
+        assert_eq!(info.flags, LightFlags::NONE.bits());
+    }
+
+    #[test]
+    fn spot_sets_cone_and_zeroes_rect() {
+        let info = LightInfo::spot(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(0.0, 0.0, -1.0),
+            COLOR,
+            9.0,
+            8.0,
+            0.2,
+            0.5,
+        );
+        assert_eq!(info.ty, LightType::Spot);
+        assert_eq!((info.pos_x, info.pos_y, info.pos_z), (1.0, 2.0, 3.0));
+        assert_eq!((info.dir_x, info.dir_y, info.dir_z), (0.0, 0.0, -1.0));
+        assert_eq!(
+            (info.spot_inner_angle_rad, info.spot_outer_angle_rad),
+            (0.2, 0.5)
+        );
+        assert_color(&info);
+        assert_eq!((info.rect_half_width, info.rect_half_height), (0.0, 0.0));
+    }
+
+    #[test]
+    fn rect_area_sets_extent_and_zeroes_cone() {
+        let info = LightInfo::rect_area(
+            Vec3::new(1.0, 2.0, 3.0),
+            Vec3::new(0.0, -1.0, 0.0),
+            COLOR,
+            6.0,
+            5.0,
+            0.6,
+            0.4,
+        );
+        assert_eq!(info.ty, LightType::RectArea);
+        assert_eq!((info.pos_x, info.pos_y, info.pos_z), (1.0, 2.0, 3.0));
+        assert_eq!((info.dir_x, info.dir_y, info.dir_z), (0.0, -1.0, 0.0));
+        assert_eq!((info.rect_half_width, info.rect_half_height), (0.6, 0.4));
+        assert_color(&info);
+        assert_eq!(
+            (info.spot_inner_angle_rad, info.spot_outer_angle_rad),
+            (0.0, 0.0)
+        );
+    }
+}
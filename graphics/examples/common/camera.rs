@@ -162,3 +162,155 @@ impl CameraController {
         Mat4::look_to_rh(self.position, forward, Vec3::Y).inverse()
     }
 }
+
+#[derive(Debug, Clone, Copy)]
+pub struct OrbitCameraSettings {
+    pub sensitivity: f32,
+    pub zoom_speed: f32,
+    pub pitch_limit: f32,
+    pub min_distance: f32,
+    pub max_distance: f32,
+}
+
+impl Default for OrbitCameraSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: 0.006,
+            zoom_speed: 0.1,
+            pitch_limit: 1.54,
+            min_distance: 0.5,
+            max_distance: 500.0,
+        }
+    }
+}
+
+/// Eye position `distance` away from `target`. Zero yaw and pitch place the
+/// eye on +Z looking back down -Z; positive pitch raises it above the target.
+pub fn orbit_eye(target: Vec3, yaw: f32, pitch: f32, distance: f32) -> Vec3 {
+    target
+        + Vec3::new(
+            yaw.sin() * pitch.cos(),
+            pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        ) * distance
+}
+
+pub struct OrbitCameraController {
+    target: Vec3,
+    distance: f32,
+    yaw: f32,
+    pitch: f32,
+    settings: OrbitCameraSettings,
+    dragging: bool,
+    mouse_delta: Vec2,
+    scroll: f32,
+    last_cursor_pos: Option<Vec2>,
+    window_focused: bool,
+}
+
+impl OrbitCameraController {
+    pub fn new(target: Vec3, distance: f32) -> Self {
+        Self {
+            target,
+            distance,
+            yaw: 0.0,
+            pitch: 0.0,
+            settings: OrbitCameraSettings::default(),
+            dragging: false,
+            mouse_delta: Vec2::ZERO,
+            scroll: 0.0,
+            last_cursor_pos: None,
+            window_focused: true,
+        }
+    }
+
+    pub fn settings_mut(&mut self) -> &mut OrbitCameraSettings {
+        &mut self.settings
+    }
+
+    pub fn set_target(&mut self, target: Vec3) {
+        self.target = target;
+    }
+
+    pub fn handle_event(&mut self, event: &Event) {
+        match (event.source(), event.event_type()) {
+            (EventSource::MouseButton, EventType::Pressed | EventType::Released) => {
+                self.dragging = event.event_type() == EventType::Pressed;
+            }
+            (EventSource::Mouse, EventType::CursorMoved) => {
+                let position = unsafe { event.motion2d() };
+                if self.window_focused && self.dragging {
+                    if let Some(last) = self.last_cursor_pos {
+                        self.mouse_delta += position - last;
+                    }
+                }
+                self.last_cursor_pos = Some(position);
+            }
+            (EventSource::Mouse, EventType::Motion2D) => {
+                self.scroll += unsafe { event.motion2d() }.y;
+            }
+            (EventSource::Window, EventType::WindowFocused) => {
+                self.window_focused = true;
+                self.last_cursor_pos = None;
+            }
+            (EventSource::Window, EventType::WindowUnfocused) => {
+                self.window_focused = false;
+                self.dragging = false;
+                self.last_cursor_pos = None;
+                self.mouse_delta = Vec2::ZERO;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn update(&mut self, _dt: f32) -> Mat4 {
+        let mouse_delta = self.mouse_delta;
+        self.mouse_delta = Vec2::ZERO;
+        let scroll = self.scroll;
+        self.scroll = 0.0;
+
+        // Dragging right swings the eye left so the scene follows the cursor.
+        self.yaw -= mouse_delta.x * self.settings.sensitivity;
+        self.pitch = (self.pitch + mouse_delta.y * self.settings.sensitivity)
+            .clamp(-self.settings.pitch_limit, self.settings.pitch_limit);
+        self.distance = (self.distance * (1.0 - scroll * self.settings.zoom_speed))
+            .clamp(self.settings.min_distance, self.settings.max_distance);
+
+        let eye = orbit_eye(self.target, self.yaw, self.pitch, self.distance);
+        Mat4::look_at_rh(eye, self.target, Vec3::Y).inverse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orbit_eye_follows_yaw_pitch_and_distance() {
+        let target = Vec3::new(1.0, 2.0, 3.0);
+        let eye = orbit_eye(target, 0.0, 0.0, 5.0);
+        assert!(eye.abs_diff_eq(target + Vec3::new(0.0, 0.0, 5.0), 1.0e-5));
+
+        let eye = orbit_eye(target, std::f32::consts::FRAC_PI_2, 0.0, 5.0);
+        assert!(eye.abs_diff_eq(target + Vec3::new(5.0, 0.0, 0.0), 1.0e-5));
+
+        let eye = orbit_eye(target, 0.0, std::f32::consts::FRAC_PI_2, 2.0);
+        assert!(eye.abs_diff_eq(target + Vec3::new(0.0, 2.0, 0.0), 1.0e-5));
+    }
+
+    #[test]
+    fn orbit_update_faces_the_target() {
+        let mut orbit = OrbitCameraController::new(Vec3::ZERO, 5.0);
+        let transform = orbit.update(0.016);
+        assert!(
+            transform
+                .transform_point3(Vec3::ZERO)
+                .abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 1.0e-5)
+        );
+        assert!(
+            transform
+                .transform_vector3(Vec3::NEG_Z)
+                .abs_diff_eq(Vec3::NEG_Z, 1.0e-5)
+        );
+    }
+}
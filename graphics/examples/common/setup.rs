@@ -62,6 +62,7 @@ pub fn init(
         skybox_cubemap_entry: Some(DEFAULT_CUBEMAP_ENTRY.to_string()),
         debug_mode: false,
         shadow_cascades: Default::default(),
+        anisotropy: 1,
     })
     .unwrap();
 
@@ -51,6 +51,7 @@ fn main() {
                     texture_id: 0,
                     material: None,
                     billboard_type: ty,
+                    target: Vec3::ZERO,
                 }))
                 .unwrap();
             let translation = Mat4::from_translation(Vec3::new(
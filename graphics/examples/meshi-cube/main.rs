@@ -40,7 +40,7 @@ fn main() {
         .expect("Expected a quad-like model in the database");
     let quad = setup
         .engine
-        .register_object(&RenderObjectInfo::Model(quad_model))
+        .register_object(&RenderObjectInfo::Model(quad_model.into()))
         .unwrap();
 
     let translation = Mat4::from_translation(Vec3::new(0.0, 0.25, -2.5));
@@ -2,7 +2,7 @@ use std::ffi::c_void;
 
 use glam::*;
 use meshi_ffi_structs::event::*;
-use meshi_ffi_structs::{LightFlags, LightInfo, LightType};
+use meshi_ffi_structs::{LightInfo, LightType};
 use meshi_graphics::*;
 use meshi_utils::timer::Timer;
 use std::env::*;
@@ -12,111 +12,7 @@ mod common_camera;
 #[path = "../common/setup.rs"]
 mod common_setup;
 
-use common_camera::CameraController;
-
-fn directional_light(direction: Vec3, color: Vec3, intensity: f32) -> LightInfo {
-    LightInfo {
-        ty: LightType::Directional,
-        flags: LightFlags::CASTS_SHADOWS.bits(),
-        intensity,
-        range: 0.0,
-        color_r: color.x,
-        color_g: color.y,
-        color_b: color.z,
-        pos_x: 0.0,
-        pos_y: 0.0,
-        pos_z: 0.0,
-        dir_x: direction.x,
-        dir_y: direction.y,
-        dir_z: direction.z,
-        spot_inner_angle_rad: 0.0,
-        spot_outer_angle_rad: 0.0,
-        rect_half_width: 0.0,
-        rect_half_height: 0.0,
-    }
-}
-
-fn point_light(position: Vec3, color: Vec3, intensity: f32, range: f32) -> LightInfo {
-    LightInfo {
-        ty: LightType::Point,
-        flags: LightFlags::NONE.bits(),
-        intensity,
-        range,
-        color_r: color.x,
-        color_g: color.y,
-        color_b: color.z,
-        pos_x: position.x,
-        pos_y: position.y,
-        pos_z: position.z,
-        dir_x: 0.0,
-        dir_y: 0.0,
-        dir_z: 0.0,
-        spot_inner_angle_rad: 0.0,
-        spot_outer_angle_rad: 0.0,
-        rect_half_width: 0.0,
-        rect_half_height: 0.0,
-    }
-}
-
-fn spot_light(
-    position: Vec3,
-    direction: Vec3,
-    color: Vec3,
-    intensity: f32,
-    range: f32,
-    inner_angle_rad: f32,
-    outer_angle_rad: f32,
-) -> LightInfo {
-    LightInfo {
-        ty: LightType::Spot,
-        flags: LightFlags::CASTS_SHADOWS.bits(),
-        intensity,
-        range,
-        color_r: color.x,
-        color_g: color.y,
-        color_b: color.z,
-        pos_x: position.x,
-        pos_y: position.y,
-        pos_z: position.z,
-        dir_x: direction.x,
-        dir_y: direction.y,
-        dir_z: direction.z,
-        spot_inner_angle_rad: inner_angle_rad,
-        spot_outer_angle_rad: outer_angle_rad,
-        rect_half_width: 0.0,
-        rect_half_height: 0.0,
-    }
-}
-
-fn rect_area_light(
-    position: Vec3,
-    direction: Vec3,
-    color: Vec3,
-    intensity: f32,
-    range: f32,
-    half_width: f32,
-    half_height: f32,
-) -> LightInfo {
-    LightInfo {
-        ty: LightType::RectArea,
-        flags: LightFlags::NONE.bits(),
-        intensity,
-        range,
-        color_r: color.x,
-        color_g: color.y,
-        color_b: color.z,
-        pos_x: position.x,
-        pos_y: position.y,
-        pos_z: position.z,
-        dir_x: direction.x,
-        dir_y: direction.y,
-        dir_z: direction.z,
-        spot_inner_angle_rad: 0.0,
-        spot_outer_angle_rad: 0.0,
-        rect_half_width: half_width,
-        rect_half_height: half_height,
-    }
-}
+use common_camera::OrbitCameraController;
 
 fn light_marker_position(light: &LightInfo) -> Option<Vec3> {
     match light.ty {
@@ -170,16 +66,21 @@ fn main() {
 
     let model_handle = setup
         .engine
-        .register_object(&RenderObjectInfo::Model(model))
+        .register_object(&RenderObjectInfo::Model(model.into()))
         .unwrap();
 
     let translation = Mat4::from_translation(Vec3::new(0.0, -0.2, -2.8));
     setup.engine.set_object_transform(model_handle, &translation);
 
     let lights = [
-        directional_light(Vec3::new(-0.2, -70.0, -0.3), Vec3::splat(1.0), 1.4),
-        point_light(Vec3::new(1.0, 0.3, -60.0), Vec3::new(1.0, 0.2, 0.2), 7.0, 6.0),
-        spot_light(
+        LightInfo::directional(Vec3::new(-0.2, -70.0, -0.3), Vec3::splat(1.0), 1.4),
+        LightInfo::point(
+            Vec3::new(1.0, 0.3, -60.0),
+            Vec3::new(1.0, 0.2, 0.2),
+            7.0,
+            6.0,
+        ),
+        LightInfo::spot(
             Vec3::new(-20.1, 1.1, -2.3),
             Vec3::new(0.2, -1.0, -0.2),
             Vec3::new(0.2, 0.8, 1.0),
@@ -188,7 +89,7 @@ fn main() {
             12.0_f32.to_radians(),
             28.0_f32.to_radians(),
         ),
-        rect_area_light(
+        LightInfo::rect_area(
             Vec3::new(0.2, 1.2, -30.0),
             Vec3::new(0.0, -2.0, 0.0),
             Vec3::new(0.9, 0.8, 0.5),
@@ -200,7 +101,10 @@ fn main() {
     ];
 
     for light in &lights {
-        setup.engine.register_light(light);
+        setup
+            .engine
+            .register_light(light)
+            .expect("example lights are valid");
     }
 
     let marker_model = setup
@@ -216,7 +120,7 @@ fn main() {
         if let Some(position) = light_marker_position(light) {
             let handle = setup
                 .engine
-                .register_object(&RenderObjectInfo::Model(marker_model.clone()))
+                .register_object(&RenderObjectInfo::Model(marker_model.clone().into()))
                 .unwrap();
             let marker_transform =
                 Mat4::from_translation(position) * Mat4::from_scale(Vec3::splat(4.0));
@@ -228,13 +132,13 @@ fn main() {
     struct AppData {
         running: bool,
         paused: bool,
-        camera: CameraController,
+        camera: OrbitCameraController,
     }
 
     let mut data = AppData {
         running: true,
         paused: false,
-        camera: CameraController::new(Vec3::ZERO, setup.window_size),
+        camera: OrbitCameraController::new(Vec3::new(0.0, -0.2, -2.8), 8.0),
     };
 
     extern "C" fn callback(event: *mut Event, data: *mut c_void) {
@@ -43,6 +43,7 @@ fn main() {
         .register_object(&RenderObjectInfo::SkinnedModel(SkinnedModelInfo {
             model,
             animation: AnimationState::default(),
+            bounds: None,
         }))
         .unwrap();
 
@@ -146,7 +147,7 @@ fn main() {
                     time_seconds: 0.0,
                     speed: 1.0,
                     looping: true,
-                },
+                    },
             );
             if let Some(name) = animation_names.get(data.animation_index) {
                 setup.engine.set_text(
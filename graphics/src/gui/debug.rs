@@ -1295,6 +1295,7 @@ impl DebugGui {
             position: [debug_title_bar_pos.x + 12.0, debug_title_bar_pos.y + 6.0],
             color: Vec4::new(0.92, 0.95, 1.0, 1.0).to_array(),
             scale: 0.95,
+            clip_rect: None,
         });
 
         let tab_height = 26.0 * ui_scale;
@@ -1327,6 +1328,7 @@ impl DebugGui {
                 position: [tab_pos.x + 10.0 * ui_scale, tab_pos.y + 6.0 * ui_scale],
                 color: Vec4::new(0.9, 0.93, 0.98, 1.0).to_array(),
                 scale: 0.85,
+                clip_rect: None,
             });
         }
 
@@ -1368,6 +1370,7 @@ impl DebugGui {
                     position: [tab_pos.x + 10.0 * ui_scale, tab_pos.y + 4.0 * ui_scale],
                     color: Vec4::new(0.9, 0.93, 0.98, 1.0).to_array(),
                     scale: 0.8,
+                    clip_rect: None,
                 });
             }
             text_start = vec2(
@@ -1451,6 +1454,7 @@ impl DebugGui {
             ),
             content_clip_rect,
         ));
+        gui.push_clip_rect(content_clip_rect);
         let line_height = 18.0 * ui_scale;
         let radial_metrics = RadialButtonMetrics {
             item_height: (20.0 * ui_scale).clamp(18.0, 26.0),
@@ -1526,6 +1530,7 @@ impl DebugGui {
                 position: [text_start.x, line_y],
                 color: Vec4::new(0.75, 0.8, 0.9, 1.0).to_array(),
                 scale: if index == 0 { 0.9 } else { 0.85 },
+                clip_rect: None,
             });
         }
         let mut slider_start_y =
@@ -1543,6 +1548,7 @@ impl DebugGui {
                         position: [text_start.x, title_y],
                         color: Vec4::new(0.8, 0.84, 0.92, 1.0).to_array(),
                         scale: 0.82,
+                        clip_rect: None,
                     });
                 }
                 let current_value = group.value.round();
@@ -1589,6 +1595,7 @@ impl DebugGui {
                     position: [text_start.x, empty_y],
                     color: Vec4::new(0.7, 0.75, 0.85, 1.0).to_array(),
                     scale: 0.85,
+                    clip_rect: None,
                 });
             }
         } else if !debug_sliders.is_empty() {
@@ -1635,6 +1642,7 @@ impl DebugGui {
                     position: label_pos,
                     color: label_color.to_array(),
                     scale: 0.82 * ui_scale,
+                    clip_rect: None,
                 });
 
                 let input_pos = vec2(
@@ -1684,6 +1692,7 @@ impl DebugGui {
                     ],
                     color: input_text_color.to_array(),
                     scale: 0.8 * ui_scale,
+                    clip_rect: None,
                 });
 
                 debug_int_input_layout.items.push(DebugIntInputLayoutItem {
@@ -1694,6 +1703,8 @@ impl DebugGui {
             }
         }
 
+        gui.pop_clip_rect();
+
         if max_scroll > 0.0 && content_height > 0.0 {
             let track_pos = vec2(
                 debug_panel_position.x + content_width + scrollbar_gap,
@@ -1752,6 +1763,7 @@ impl DebugGui {
             ],
             color: Vec4::new(0.82, 0.86, 0.92, 1.0).to_array(),
             scale: 0.8,
+            clip_rect: None,
         });
 
         let reset_color = if reset_button_hovered {
@@ -1772,6 +1784,7 @@ impl DebugGui {
             ],
             color: Vec4::new(0.9, 0.93, 0.98, 1.0).to_array(),
             scale: 0.75,
+            clip_rect: None,
         });
 
         let close_color = if close_button_hovered {
@@ -1792,6 +1805,7 @@ impl DebugGui {
             ],
             color: Vec4::new(0.95, 0.9, 0.92, 1.0).to_array(),
             scale: 0.75,
+            clip_rect: None,
         });
 
         if let Some(tooltip_text) = tooltip_text {
@@ -1828,6 +1842,7 @@ impl DebugGui {
                 ],
                 color: Vec4::new(0.9, 0.93, 0.98, 1.0).to_array(),
                 scale: 0.78 * ui_scale,
+                clip_rect: None,
             });
         }
 
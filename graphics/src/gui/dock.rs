@@ -1,6 +1,8 @@
 use glam::Vec2;
 use serde::{Deserialize, Serialize};
 
+use super::{GuiId, PanelState};
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum DockSplitDirection {
     Horizontal,
@@ -302,6 +304,221 @@ impl DockArea {
     }
 }
 
+/// Region of the root area a [`Panel`](super::Panel) can be docked into.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PanelDock {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Center,
+}
+
+/// Share of the root area given to each edge dock. Top and bottom span the
+/// full width; left and right fill the height between them.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PanelDockRatios {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl Default for PanelDockRatios {
+    fn default() -> Self {
+        Self {
+            left: 0.2,
+            right: 0.2,
+            top: 0.15,
+            bottom: 0.25,
+        }
+    }
+}
+
+/// Docks panels into the edges and center of a root region.
+///
+/// Docks without panels collapse so the center takes their space. Panels
+/// sharing a dock split it evenly, stacked vertically on the sides and
+/// side by side along the top and bottom.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PanelDockLayout {
+    pub ratios: PanelDockRatios,
+    /// Width of the band inside each root edge where dropping a dragged
+    /// panel docks it to that edge.
+    pub edge_drop_width: f32,
+    panels: Vec<(u64, PanelDock)>,
+}
+
+impl Default for PanelDockLayout {
+    fn default() -> Self {
+        Self {
+            ratios: PanelDockRatios::default(),
+            edge_drop_width: 32.0,
+            panels: Vec::new(),
+        }
+    }
+}
+
+impl PanelDockLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Dock `panel`, moving it if it was already docked elsewhere.
+    pub fn assign(&mut self, panel: GuiId, dock: PanelDock) {
+        self.panels.retain(|(id, _)| *id != panel.value());
+        self.panels.push((panel.value(), dock));
+    }
+
+    pub fn remove(&mut self, panel: GuiId) {
+        self.panels.retain(|(id, _)| *id != panel.value());
+    }
+
+    pub fn dock_of(&self, panel: GuiId) -> Option<PanelDock> {
+        self.panels
+            .iter()
+            .find(|(id, _)| *id == panel.value())
+            .map(|(_, dock)| *dock)
+    }
+
+    fn occupied(&self, dock: PanelDock) -> bool {
+        self.panels.iter().any(|(_, d)| *d == dock)
+    }
+
+    /// Area of `dock` within `root`. Empty docks have zero size.
+    pub fn dock_rect(&self, root: DockRect, dock: PanelDock) -> DockRect {
+        let size = root.size();
+        let share = |dock: PanelDock, ratio: f32| {
+            if self.occupied(dock) {
+                ratio.clamp(0.0, 0.45)
+            } else {
+                0.0
+            }
+        };
+        let top = size.y * share(PanelDock::Top, self.ratios.top);
+        let bottom = size.y * share(PanelDock::Bottom, self.ratios.bottom);
+        let left = size.x * share(PanelDock::Left, self.ratios.left);
+        let right = size.x * share(PanelDock::Right, self.ratios.right);
+        let middle_y = root.min.y + top;
+        let middle_height = size.y - top - bottom;
+        match dock {
+            PanelDock::Top => DockRect::from_position_size(root.min, vec2(size.x, top)),
+            PanelDock::Bottom => DockRect::from_position_size(
+                vec2(root.min.x, root.max.y - bottom),
+                vec2(size.x, bottom),
+            ),
+            PanelDock::Left => {
+                DockRect::from_position_size(vec2(root.min.x, middle_y), vec2(left, middle_height))
+            }
+            PanelDock::Right => DockRect::from_position_size(
+                vec2(root.max.x - right, middle_y),
+                vec2(right, middle_height),
+            ),
+            PanelDock::Center => DockRect::from_position_size(
+                vec2(root.min.x + left, middle_y),
+                vec2(size.x - left - right, middle_height),
+            ),
+        }
+    }
+
+    /// Area of `panel` within `root`, or `None` if it isn't docked.
+    pub fn panel_rect(&self, root: DockRect, panel: GuiId) -> Option<DockRect> {
+        let dock = self.dock_of(panel)?;
+        let siblings: Vec<u64> = self
+            .panels
+            .iter()
+            .filter(|(_, d)| *d == dock)
+            .map(|(id, _)| *id)
+            .collect();
+        let index = siblings.iter().position(|id| *id == panel.value())?;
+        let rect = self.dock_rect(root, dock);
+        let size = rect.size();
+        let count = siblings.len() as f32;
+        Some(match dock {
+            PanelDock::Top | PanelDock::Bottom => {
+                let width = size.x / count;
+                DockRect::from_position_size(
+                    vec2(rect.min.x + width * index as f32, rect.min.y),
+                    vec2(width, size.y),
+                )
+            }
+            PanelDock::Left | PanelDock::Right | PanelDock::Center => {
+                let height = size.y / count;
+                DockRect::from_position_size(
+                    vec2(rect.min.x, rect.min.y + height * index as f32),
+                    vec2(size.x, height),
+                )
+            }
+        })
+    }
+
+    /// Dock that a panel dropped at `cursor` would move to: an edge when
+    /// the cursor is within [`Self::edge_drop_width`] of it, otherwise the
+    /// center.
+    pub fn drop_target(&self, root: DockRect, cursor: Vec2) -> Option<PanelDock> {
+        if !root.contains(cursor) {
+            return None;
+        }
+        let edges = [
+            (cursor.x - root.min.x, PanelDock::Left),
+            (root.max.x - cursor.x, PanelDock::Right),
+            (cursor.y - root.min.y, PanelDock::Top),
+            (root.max.y - cursor.y, PanelDock::Bottom),
+        ];
+        let (distance, dock) = edges
+            .into_iter()
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap();
+        Some(if distance <= self.edge_drop_width {
+            dock
+        } else {
+            PanelDock::Center
+        })
+    }
+
+    /// Re-dock `panel` where its title bar was dropped. Returns `true` if its
+    /// dock changed.
+    pub fn drop_panel(&mut self, root: DockRect, panel: GuiId, cursor: Vec2) -> bool {
+        match self.drop_target(root, cursor) {
+            Some(dock) if self.dock_of(panel) != Some(dock) => {
+                self.assign(panel, dock);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Re-dock `panel` if its title drag ends this frame, then move `state`
+    /// into the panel's rect. Call before [`GuiContext::submit_panel`] so
+    /// the drag is still active on the release frame.
+    ///
+    /// [`GuiContext::submit_panel`]: super::GuiContext::submit_panel
+    pub fn update_panel(
+        &mut self,
+        root: DockRect,
+        panel: GuiId,
+        state: &mut PanelState,
+        cursor: Vec2,
+        mouse_released: bool,
+    ) {
+        if mouse_released && state.is_dragging() {
+            self.drop_panel(root, panel, cursor);
+        }
+        if let Some(rect) = self.panel_rect(root, panel) {
+            state.position = rect.min.to_array();
+            state.size = rect.size().to_array();
+        }
+    }
+
+    pub fn serialize(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn deserialize(data: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(data)
+    }
+}
+
 fn layout_node(node: &DockNode, rect: DockRect, tab_bar_height: f32, output: &mut Vec<DockGroupLayout>) {
     match node {
         DockNode::Split {
@@ -372,3 +589,76 @@ fn layout_node(node: &DockNode, rect: DockRect, tab_bar_height: f32, output: &mu
 fn vec2(x: f32, y: f32) -> Vec2 {
     Vec2::new(x, y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root() -> DockRect {
+        DockRect::from_position_size(vec2(0.0, 0.0), vec2(1000.0, 800.0))
+    }
+
+    fn area(rect: DockRect) -> f32 {
+        let size = rect.size();
+        size.x * size.y
+    }
+
+    fn overlap(a: DockRect, b: DockRect) -> f32 {
+        let width = (a.max.x.min(b.max.x) - a.min.x.max(b.min.x)).max(0.0);
+        let height = (a.max.y.min(b.max.y) - a.min.y.max(b.min.y)).max(0.0);
+        width * height
+    }
+
+    #[test]
+    fn docks_tile_the_root_without_overlap() {
+        let mut layout = PanelDockLayout::new();
+        let docks = [
+            PanelDock::Left,
+            PanelDock::Right,
+            PanelDock::Top,
+            PanelDock::Bottom,
+            PanelDock::Center,
+        ];
+        for (index, dock) in docks.iter().enumerate() {
+            layout.assign(GuiId::from(index), *dock);
+        }
+
+        let rects: Vec<DockRect> = (0..docks.len())
+            .map(|index| layout.panel_rect(root(), GuiId::from(index)).unwrap())
+            .collect();
+        let total: f32 = rects.iter().map(|rect| area(*rect)).sum();
+        assert!((total - area(root())).abs() < 1e-2);
+        for (i, a) in rects.iter().enumerate() {
+            assert!(area(*a) > 0.0);
+            for b in &rects[i + 1..] {
+                assert_eq!(overlap(*a, *b), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn dropping_a_panel_on_an_edge_redocks_it() {
+        let panel = GuiId::new(7);
+        let mut layout = PanelDockLayout::new();
+        layout.assign(panel, PanelDock::Center);
+
+        assert!(layout.drop_panel(root(), panel, vec2(990.0, 400.0)));
+        assert_eq!(layout.dock_of(panel), Some(PanelDock::Right));
+        let rect = layout.panel_rect(root(), panel).unwrap();
+        assert_eq!(rect.max.x, 1000.0);
+
+        assert!(!layout.drop_panel(root(), panel, vec2(995.0, 300.0)));
+        assert!(!layout.drop_panel(root(), panel, vec2(-10.0, 300.0)));
+        assert!(layout.drop_panel(root(), panel, vec2(500.0, 400.0)));
+        assert_eq!(layout.dock_of(panel), Some(PanelDock::Center));
+    }
+
+    #[test]
+    fn split_ratios_survive_serialization() {
+        let mut layout = PanelDockLayout::new();
+        layout.ratios.left = 0.3;
+        layout.assign(GuiId::new(1), PanelDock::Left);
+        let restored = PanelDockLayout::deserialize(&layout.serialize().unwrap()).unwrap();
+        assert_eq!(restored, layout);
+    }
+}
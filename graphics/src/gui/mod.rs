@@ -15,7 +15,9 @@ pub use icon_atlas::{
     ToolbarIconId,
 };
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 
 use crate::render::gui::{GuiMesh, GuiVertex};
@@ -35,6 +37,14 @@ impl GuiId {
     pub const fn value(self) -> u64 {
         self.0
     }
+
+    /// Id for a part of this widget, stable across frames for the same `key`.
+    pub fn child(self, key: impl Hash) -> Self {
+        let mut hasher = DefaultHasher::new();
+        self.0.hash(&mut hasher);
+        key.hash(&mut hasher);
+        Self(hasher.finish())
+    }
 }
 
 impl From<u32> for GuiId {
@@ -49,6 +59,24 @@ impl From<usize> for GuiId {
     }
 }
 
+/// Clipboard shortcut detected from the key events of the current frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuiClipboardCommand {
+    Cut,
+    Copy,
+    Paste,
+}
+
+/// Drag started from a pressed widget, carrying a caller-defined payload.
+#[derive(Debug, Clone, Copy)]
+struct GuiDrag {
+    source: GuiId,
+    payload: u64,
+    origin: Vec2,
+    /// Set once the cursor has moved past the drag threshold.
+    started: bool,
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct GuiInteraction {
     pub hovered: bool,
@@ -57,6 +85,15 @@ pub struct GuiInteraction {
     pub clicked: bool,
 }
 
+/// Modifier keys held this frame, for matching keyboard shortcuts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub meta: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct GuiInput {
     pub cursor: Vec2,
@@ -64,11 +101,26 @@ pub struct GuiInput {
     pub mouse_down: bool,
     pub mouse_pressed: bool,
     pub mouse_released: bool,
+    /// Cursor distance in pixels a pressed draggable must travel before a
+    /// drag begins.
+    pub drag_threshold: f32,
     hot: Option<GuiId>,
+    /// Widgets under the cursor this frame, in submission order; the last
+    /// one is top-most.
+    hover_stack: Vec<GuiId>,
+    /// Widgets that sat under a later-submitted hovered widget last frame.
+    occluded: HashSet<GuiId>,
     active: Option<GuiId>,
     focused: Option<GuiId>,
     last_key_pressed: Option<KeyCode>,
     keys_down: HashSet<KeyCode>,
+    clipboard_command: Option<GuiClipboardCommand>,
+    paste_requested: bool,
+    clipboard: String,
+    copied_text: Option<String>,
+    focusables: Vec<GuiId>,
+    focus_order: Vec<GuiId>,
+    drag: Option<GuiDrag>,
 }
 
 impl Default for GuiInput {
@@ -79,25 +131,47 @@ impl Default for GuiInput {
             mouse_down: false,
             mouse_pressed: false,
             mouse_released: false,
+            drag_threshold: 4.0,
             hot: None,
+            hover_stack: Vec::new(),
+            occluded: HashSet::new(),
             active: None,
             focused: None,
             last_key_pressed: None,
             keys_down: HashSet::new(),
+            clipboard_command: None,
+            paste_requested: false,
+            clipboard: String::new(),
+            copied_text: None,
+            focusables: Vec::new(),
+            focus_order: Vec::new(),
+            drag: None,
         }
     }
 }
 
 impl GuiInput {
     pub fn begin_frame(&mut self) {
+        self.end_frame();
         if !self.mouse_down {
             self.active = None;
+            // Drop targets had the release frame to claim the payload.
+            self.drag = None;
         }
         self.scroll_delta = Vec2::ZERO;
         self.mouse_pressed = false;
         self.mouse_released = false;
         self.hot = None;
+        self.occluded.clear();
+        if let Some((_, below)) = self.hover_stack.split_last() {
+            self.occluded.extend(below.iter().copied());
+        }
+        self.hover_stack.clear();
         self.last_key_pressed = None;
+        self.clipboard_command = None;
+        if !self.focusables.is_empty() {
+            self.focus_order = std::mem::take(&mut self.focusables);
+        }
     }
 
     pub fn handle_event(&mut self, event: &Event) {
@@ -125,6 +199,20 @@ impl GuiInput {
                     let key = event.key();
                     self.keys_down.insert(key);
                     self.last_key_pressed = Some(key);
+                    if self.command_down() {
+                        match key {
+                            KeyCode::C => self.clipboard_command = Some(GuiClipboardCommand::Copy),
+                            KeyCode::X => self.clipboard_command = Some(GuiClipboardCommand::Cut),
+                            KeyCode::V => {
+                                self.clipboard_command = Some(GuiClipboardCommand::Paste);
+                                self.paste_requested = true;
+                            }
+                            _ => {}
+                        }
+                    } else if key == KeyCode::Tab {
+                        let reverse = self.keys_down.contains(&KeyCode::Shift);
+                        self.advance_focus(reverse);
+                    }
                 }
                 (EventSource::Key, EventType::Released) => {
                     let key = event.key();
@@ -134,6 +222,9 @@ impl GuiInput {
                     self.focused = None;
                     self.active = None;
                     self.hot = None;
+                    self.hover_stack.clear();
+                    self.occluded.clear();
+                    self.drag = None;
                     self.keys_down.clear();
                 }
                 _ => {}
@@ -141,7 +232,36 @@ impl GuiInput {
         }
     }
 
+    /// Settle the frame just submitted: the top-most (last submitted) hovered
+    /// widget becomes hot and, on the frame the mouse was pressed, active and
+    /// focused. Widgets below it may have claimed these while the frame was
+    /// being built, before the widgets above them were known.
+    ///
+    /// [`GuiInput::begin_frame`] calls this first, so calling it sooner only
+    /// makes the result visible earlier.
+    pub fn end_frame(&mut self) {
+        let Some(&top) = self.hover_stack.last() else {
+            return;
+        };
+        self.hot = Some(top);
+        if self.mouse_pressed {
+            self.active = Some(top);
+            self.focused = Some(top);
+            self.drag = self.drag.filter(|drag| drag.source == top);
+        }
+    }
+
+    /// Resolve hover and click state for widget `id`. When widgets overlap,
+    /// only the top-most (last submitted) one is treated as hovered. Earlier
+    /// widgets cannot see later ones, so the state returned here uses the
+    /// previous frame's overlap; [`GuiInput::end_frame`] settles the hot and
+    /// active widget once the whole frame is known.
     pub fn interact(&mut self, id: GuiId, hovered: bool) -> GuiInteraction {
+        self.register_focusable(id);
+        if hovered {
+            self.hover_stack.push(id);
+        }
+        let hovered = hovered && !self.occluded.contains(&id);
         if hovered {
             self.hot = Some(id);
         }
@@ -171,6 +291,56 @@ impl GuiInput {
         }
     }
 
+    /// Offer `payload` for dragging from widget `id`. Draggable widgets call
+    /// this every frame after [`GuiInput::interact`]; the drag begins once the
+    /// widget is pressed and the cursor moves past `drag_threshold`.
+    ///
+    /// Returns `true` while a drag from `id` is in progress.
+    pub fn begin_drag(&mut self, id: GuiId, payload: u64) -> bool {
+        if let Some(drag) = self.drag.as_mut().filter(|drag| drag.source == id) {
+            if !drag.started && self.cursor.distance(drag.origin) >= self.drag_threshold {
+                drag.started = true;
+            }
+            return drag.started;
+        }
+
+        if self.mouse_down && self.active == Some(id) {
+            self.drag = Some(GuiDrag {
+                source: id,
+                payload,
+                origin: self.cursor,
+                started: false,
+            });
+        }
+        false
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some_and(|drag| drag.started)
+    }
+
+    /// Payload of the drag in progress, for drawing drag previews.
+    pub fn drag_payload(&self) -> Option<u64> {
+        self.drag
+            .filter(|drag| drag.started)
+            .map(|drag| drag.payload)
+    }
+
+    /// Accept drops onto `rect`. Returns the payload on the frame the mouse
+    /// is released over the target; a widget never receives its own drag.
+    pub fn drop_target(&mut self, id: GuiId, rect: MenuRect) -> Option<u64> {
+        let drag = self.drag.filter(|drag| drag.started && drag.source != id)?;
+        if !rect.contains([self.cursor.x, self.cursor.y]) {
+            return None;
+        }
+        self.hot = Some(id);
+        if !self.mouse_released {
+            return None;
+        }
+        self.drag = None;
+        Some(drag.payload)
+    }
+
     pub fn hot(&self) -> Option<GuiId> {
         self.hot
     }
@@ -187,6 +357,15 @@ impl GuiInput {
         self.keys_down.contains(&key)
     }
 
+    pub fn modifiers(&self) -> Modifiers {
+        Modifiers {
+            ctrl: self.keys_down.contains(&KeyCode::Control),
+            shift: self.keys_down.contains(&KeyCode::Shift),
+            alt: self.keys_down.contains(&KeyCode::Alt),
+            meta: self.keys_down.contains(&KeyCode::Meta),
+        }
+    }
+
     pub fn last_key_pressed(&self) -> Option<KeyCode> {
         self.last_key_pressed
     }
@@ -198,6 +377,78 @@ impl GuiInput {
     pub fn set_focus(&mut self, id: GuiId) {
         self.focused = Some(id);
     }
+
+    /// Add a widget to this frame's Tab traversal order. Widgets should call this
+    /// in submission order every frame.
+    pub fn register_focusable(&mut self, id: GuiId) {
+        if !self.focusables.contains(&id) {
+            self.focusables.push(id);
+        }
+    }
+
+    /// Move focus to the next registered widget (or the previous one when
+    /// `reverse` is set), wrapping around at either end.
+    pub fn advance_focus(&mut self, reverse: bool) {
+        // Key events usually arrive before widgets re-register, so fall back to
+        // the order recorded last frame.
+        let order = if self.focusables.is_empty() {
+            &self.focus_order
+        } else {
+            &self.focusables
+        };
+        if order.is_empty() {
+            return;
+        }
+
+        let len = order.len();
+        let next = match self
+            .focused
+            .and_then(|id| order.iter().position(|entry| *entry == id))
+        {
+            Some(index) if reverse => (index + len - 1) % len,
+            Some(index) => (index + 1) % len,
+            None if reverse => len - 1,
+            None => 0,
+        };
+        self.focused = Some(order[next]);
+    }
+
+    /// Whether Control (or Meta on macOS) is currently held.
+    pub fn command_down(&self) -> bool {
+        self.keys_down.contains(&KeyCode::Control) || self.keys_down.contains(&KeyCode::Meta)
+    }
+
+    pub fn clipboard_command(&self) -> Option<GuiClipboardCommand> {
+        self.clipboard_command
+    }
+
+    /// Returns `true` once per Ctrl+V press so the host can read the system
+    /// clipboard and stage it with [`GuiInput::set_clipboard`] before widgets run.
+    pub fn take_paste_request(&mut self) -> bool {
+        std::mem::take(&mut self.paste_requested)
+    }
+
+    /// Stage clipboard contents that widgets insert on paste.
+    pub fn set_clipboard(&mut self, text: String) {
+        self.clipboard = text;
+    }
+
+    pub fn clipboard(&self) -> &str {
+        &self.clipboard
+    }
+
+    /// Record text copied or cut by a widget. The staged clipboard is updated
+    /// too so pasting works without a host round-trip.
+    pub fn set_copied_text(&mut self, text: String) {
+        self.clipboard = text.clone();
+        self.copied_text = Some(text);
+    }
+
+    /// Text copied or cut since the last call, for the host to push to the
+    /// system clipboard.
+    pub fn take_copied_text(&mut self) -> Option<String> {
+        self.copied_text.take()
+    }
 }
 
 /// Primary GUI state owned by the renderer/user layer.
@@ -206,6 +457,15 @@ pub struct GuiContext {
     draws: Vec<GuiQueuedDraw>,
     text_draws: Vec<GuiTextDraw>,
     draw_sequence: u64,
+    panel_order: Vec<GuiId>,
+    current_panel: Option<GuiId>,
+    pending_panel_raise: Option<GuiId>,
+    /// Clip rects pushed by scroll areas and panels; the top one clips every
+    /// draw and text submitted until it is popped.
+    clip_stack: Vec<GuiClipRect>,
+    /// Last HSV edited per color picker, so hue and saturation survive
+    /// colors where RGB cannot encode them (black, greys).
+    color_picker_hsv: HashMap<GuiId, [f32; 3]>,
 }
 
 impl GuiContext {
@@ -215,6 +475,11 @@ impl GuiContext {
             draws: Vec::new(),
             text_draws: Vec::new(),
             draw_sequence: 0,
+            panel_order: Vec::new(),
+            current_panel: None,
+            pending_panel_raise: None,
+            clip_stack: Vec::new(),
+            color_picker_hsv: HashMap::new(),
         }
     }
 
@@ -222,19 +487,125 @@ impl GuiContext {
     pub fn register_layer(&mut self, _layer: GuiLayer) {}
 
     /// Submit a draw call to be collected for this frame.
-    pub fn submit_draw(&mut self, draw: GuiDraw) {
+    ///
+    /// Draws submitted after [`GuiContext::submit_panel`] belong to that panel
+    /// until [`GuiContext::end_panel`] and follow its z-order.
+    pub fn submit_draw(&mut self, mut draw: GuiDraw) {
+        draw.clip_rect = self.clip_to_stack(draw.clip_rect);
         let order = self.draw_sequence;
         self.draw_sequence = self.draw_sequence.wrapping_add(1);
-        self.draws.push(GuiQueuedDraw { order, draw });
+        self.draws.push(GuiQueuedDraw {
+            order,
+            panel: self.current_panel,
+            draw,
+        });
+    }
+
+    /// Stop associating submitted draws with the last submitted panel.
+    pub fn end_panel(&mut self) {
+        self.current_panel = None;
+    }
+
+    /// Clip every draw and text submitted until the matching
+    /// [`GuiContext::pop_clip_rect`] to `rect`, intersected with any clip
+    /// already on the stack.
+    pub fn push_clip_rect(&mut self, rect: GuiClipRect) {
+        let clip = self.clip_to_stack(Some(rect)).unwrap_or(rect);
+        self.clip_stack.push(clip);
+    }
+
+    pub fn pop_clip_rect(&mut self) {
+        self.clip_stack.pop();
+    }
+
+    /// The clip applied to submissions right now, if any.
+    pub fn current_clip_rect(&self) -> Option<GuiClipRect> {
+        self.clip_stack.last().copied()
+    }
+
+    fn clip_to_stack(&self, clip: Option<GuiClipRect>) -> Option<GuiClipRect> {
+        match (clip, self.current_clip_rect()) {
+            (Some(clip), Some(top)) => Some(clip.intersect(&top)),
+            (clip, top) => clip.or(top),
+        }
+    }
+
+    /// Panel ids ordered back-to-front.
+    pub fn panel_order(&self) -> &[GuiId] {
+        &self.panel_order
+    }
+
+    /// Move a panel to the top of the z-order so its draws sort last within
+    /// their layer.
+    pub fn bring_panel_to_front(&mut self, id: GuiId) {
+        self.panel_order.retain(|entry| *entry != id);
+        self.panel_order.push(id);
+    }
+
+    fn panel_rank(&self, id: GuiId) -> usize {
+        self.panel_order
+            .iter()
+            .position(|entry| *entry == id)
+            .map_or(0, |index| index + 1)
+    }
+
+    fn request_panel_raise(&mut self, id: GuiId) {
+        // Only the top-most panel under the cursor should be raised.
+        let rank = self.panel_rank(id);
+        let replace = self
+            .pending_panel_raise
+            .map_or(true, |pending| self.panel_rank(pending) < rank);
+        if replace {
+            self.pending_panel_raise = Some(id);
+        }
+    }
+
+    /// Submit a textured quad covering `rect` that samples the `uv` region of
+    /// `texture_id`.
+    ///
+    /// `rect` is in the same space as [`GuiQuad::positions`]; its `min` corner
+    /// samples `uv.min`. Pass [`GuiClipRect::FULL_UV`] to show the whole texture.
+    pub fn submit_image(
+        &mut self,
+        layer: GuiLayer,
+        texture_id: u32,
+        rect: GuiClipRect,
+        uv: GuiClipRect,
+        tint: [f32; 4],
+    ) {
+        let quad = GuiQuad {
+            positions: [
+                [rect.min[0], rect.min[1]],
+                [rect.max[0], rect.min[1]],
+                [rect.max[0], rect.max[1]],
+                [rect.min[0], rect.max[1]],
+            ],
+            uvs: [
+                [uv.min[0], uv.min[1]],
+                [uv.max[0], uv.min[1]],
+                [uv.max[0], uv.max[1]],
+                [uv.min[0], uv.max[1]],
+            ],
+            color: tint,
+        };
+        self.submit_draw(GuiDraw::new(layer, Some(texture_id), quad));
     }
 
-    /// Submit a text draw call to be collected for this frame.
-    pub fn submit_text(&mut self, draw: GuiTextDraw) {
+    /// Submit a text draw call to be collected for this frame. Its clip rect
+    /// is narrowed to the active clip stack.
+    pub fn submit_text(&mut self, mut draw: GuiTextDraw) {
+        draw.clip_rect = self.clip_to_stack(draw.clip_rect);
         self.text_draws.push(draw);
     }
 
-    /// Build a frame mesh by sorting by layer and grouping consecutive draws by texture id.
+    /// Build a frame mesh by sorting by layer and panel z-order and grouping consecutive
+    /// draws by texture id.
     pub fn build_frame(&mut self) -> GuiFrame {
+        self.current_panel = None;
+        if let Some(id) = self.pending_panel_raise.take() {
+            self.bring_panel_to_front(id);
+        }
+
         if self.draws.is_empty() && self.text_draws.is_empty() {
             self.draw_sequence = 0;
             return GuiFrame::default();
@@ -250,16 +621,22 @@ impl GuiContext {
             };
         }
 
-        let draws_sorted = self
-            .draws
-            .is_sorted_by(|a, b| (a.draw.layer, a.order) <= (b.draw.layer, b.order));
+        let panel_ranks: HashMap<GuiId, usize> = self
+            .panel_order
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index + 1))
+            .collect();
+        let sort_key = |queued: &GuiQueuedDraw| {
+            let rank = queued
+                .panel
+                .and_then(|id| panel_ranks.get(&id).copied())
+                .unwrap_or(0);
+            (queued.draw.layer, rank, queued.order)
+        };
+        let draws_sorted = self.draws.is_sorted_by(|a, b| sort_key(a) <= sort_key(b));
         if !draws_sorted {
-            self.draws.sort_by(|a, b| {
-                a.draw
-                    .layer
-                    .cmp(&b.draw.layer)
-                    .then_with(|| a.order.cmp(&b.order))
-            });
+            self.draws.sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
         }
 
         let mut batches: Vec<GuiBatchMesh> = Vec::with_capacity(self.draws.len());
@@ -529,6 +906,7 @@ impl GuiContext {
                         colors.disabled
                     },
                     scale: metrics.font_scale,
+                    clip_rect,
                 });
             }
 
@@ -548,6 +926,7 @@ impl GuiContext {
                             colors.disabled
                         },
                         scale: metrics.font_scale,
+                        clip_rect,
                     });
                 }
             }
@@ -669,6 +1048,7 @@ impl GuiContext {
                     position: label_pos,
                     color: if enabled { colors.label } else { colors.disabled },
                     scale: metrics.font_scale,
+                    clip_rect,
                 });
             }
 
@@ -684,99 +1064,634 @@ impl GuiContext {
         layout
     }
 
-    pub fn submit_panel(
+    /// Draw a strip of tab headers and commit a clicked tab to `active`.
+    ///
+    /// Clicking a closeable tab's × button reports it in
+    /// [`TabBarLayout::close_requested`] instead of selecting it.
+    pub fn submit_tab_bar(
         &mut self,
-        panel: &Panel,
-        state: &mut PanelState,
-        options: &PanelRenderOptions,
-    ) -> PanelLayout {
-        let viewport = options.viewport;
+        id: GuiId,
+        tabs: &[TabInfo],
+        active: &mut usize,
+        input: &mut GuiInput,
+        options: &TabBarRenderOptions,
+    ) -> TabBarLayout {
         let metrics = &options.metrics;
         let colors = &options.colors;
-        let interaction = options.interaction;
+        let viewport = options.viewport;
+        let cursor = [input.cursor.x, input.cursor.y];
+        let bar_size = [options.width, metrics.height];
 
-        let initial_display_size = panel_display_size(
-            state.size,
-            metrics.title_bar_height,
-            metrics.minimized_extra_height,
-            state.minimized,
-        );
-        let initial_title_bar_rect = MenuRect::from_position_size(
-            state.position,
-            [initial_display_size[0], metrics.title_bar_height],
-        );
-        let initial_grip_pos = [state.position[0] + metrics.grip_padding, state.position[1]];
-        let initial_grip_rect = MenuRect::from_position_size(initial_grip_pos, metrics.grip_size);
-        let initial_drag_rect = if metrics.grip_size[0] > 0.0 {
-            initial_grip_rect
-        } else {
-            initial_title_bar_rect
+        self.submit_draw(GuiDraw::new(
+            options.layer,
+            None,
+            quad_from_pixels(options.position, bar_size, colors.background, viewport),
+        ));
+
+        let mut layout = TabBarLayout {
+            rect: MenuRect::from_position_size(options.position, bar_size),
+            tabs: Vec::with_capacity(tabs.len()),
+            active: *active,
+            close_requested: None,
         };
+        let mut x = options.position[0];
+        for (index, tab) in tabs.iter().enumerate() {
+            let label_width = text_width(&tab.label, metrics.char_width);
+            let close_width = if tab.closeable {
+                metrics.close_gap + metrics.close_button_size[0]
+            } else {
+                0.0
+            };
+            let tab_rect = MenuRect::from_position_size(
+                [x, options.position[1]],
+                [
+                    metrics.padding * 2.0 + label_width + close_width,
+                    metrics.height,
+                ],
+            );
+            let close_rect = tab.closeable.then(|| {
+                MenuRect::from_position_size(
+                    [
+                        tab_rect.max[0] - metrics.padding - metrics.close_button_size[0],
+                        tab_rect.min[1] + (metrics.height - metrics.close_button_size[1]) * 0.5,
+                    ],
+                    metrics.close_button_size,
+                )
+            });
 
-        let button_size = metrics.button_size;
-        let button_gap = metrics.button_gap;
-        let button_y = state.position[1] + (metrics.title_bar_height - button_size[1]) * 0.5;
-        let buttons_right = state.position[0] + initial_display_size[0] - metrics.button_padding;
-        let close_button_pos = [buttons_right - button_size[0], button_y];
-        let minimize_button_pos = [buttons_right - button_size[0] * 2.0 - button_gap, button_y];
-        let initial_close_rect = MenuRect::from_position_size(close_button_pos, button_size);
-        let initial_minimize_rect = MenuRect::from_position_size(minimize_button_pos, button_size);
+            let over_close = close_rect.is_some_and(|rect| rect.contains(cursor));
+            let close = close_rect.map(|_| input.interact(id.child((index, "close")), over_close));
+            let header = input.interact(id.child(index), tab_rect.contains(cursor) && !over_close);
+            if header.clicked {
+                *active = index;
+            }
+            if close.is_some_and(|close| close.clicked) {
+                layout.close_requested = Some(index);
+            }
 
-        let resize_edge = if state.closed || state.minimized {
-            None
+            let tab_color = if index == *active {
+                colors.tab_active
+            } else if header.hovered {
+                colors.tab_hover
+            } else {
+                colors.tab
+            };
+            self.submit_draw(GuiDraw::new(
+                options.layer,
+                None,
+                quad_from_pixels(
+                    tab_rect.min,
+                    [tab_rect.max[0] - tab_rect.min[0], metrics.height],
+                    tab_color,
+                    viewport,
+                ),
+            ));
+            self.submit_text(GuiTextDraw {
+                text: tab.label.clone(),
+                position: [
+                    tab_rect.min[0] + metrics.padding,
+                    tab_rect.min[1] + metrics.text_offset[1],
+                ],
+                color: colors.label,
+                scale: metrics.font_scale,
+                clip_rect: None,
+            });
+
+            if let (Some(rect), Some(close)) = (close_rect, close) {
+                let close_color = if close.hovered {
+                    colors.close_button_hover
+                } else {
+                    colors.close_button
+                };
+                self.submit_draw(GuiDraw::new(
+                    options.layer,
+                    None,
+                    quad_from_pixels(rect.min, metrics.close_button_size, close_color, viewport),
+                ));
+                self.submit_text(GuiTextDraw {
+                    text: "×".to_string(),
+                    position: [rect.min[0] + 3.0, rect.min[1]],
+                    color: colors.label,
+                    scale: metrics.font_scale,
+                    clip_rect: None,
+                });
+            }
+
+            layout.tabs.push(TabLayout {
+                tab_rect,
+                close_rect,
+                hovered: header.hovered,
+            });
+            x = tab_rect.max[0] + metrics.tab_gap;
+        }
+
+        layout.active = *active;
+        layout
+    }
+
+    /// Draw a track filled to `fraction`, clamped to `[0, 1]`.
+    pub fn submit_progress_bar(
+        &mut self,
+        rect: MenuRect,
+        fraction: f32,
+        options: &ProgressRenderOptions,
+    ) -> ProgressBarLayout {
+        let fraction = if fraction.is_nan() {
+            0.0
         } else {
-            resize_edge_for_cursor(
-                interaction.cursor,
-                state.position,
-                initial_display_size,
-                metrics.resize_margin,
-            )
+            fraction.clamp(0.0, 1.0)
         };
+        let size = [rect.max[0] - rect.min[0], rect.max[1] - rect.min[1]];
+        let fill_rect = MenuRect::from_position_size(rect.min, [size[0] * fraction, size[1]]);
 
-        if interaction.mouse_pressed && !state.closed {
-            if options.allow_close && point_in_rect(interaction.cursor, initial_close_rect) {
-                state.closed = true;
-            } else if options.allow_minimize
-                && point_in_rect(interaction.cursor, initial_minimize_rect)
-            {
-                state.minimized = !state.minimized;
-            } else if let Some(edge) = resize_edge {
-                state.resize_active = Some(edge);
-                state.resize_anchor = interaction.cursor;
-                state.resize_start_pos = state.position;
-                state.resize_start_size = state.size;
-            } else if point_in_rect(interaction.cursor, initial_drag_rect) {
-                state.drag_active = true;
-                state.drag_offset = sub2(interaction.cursor, state.position);
-            }
+        self.submit_draw(GuiDraw::new(
+            options.layer,
+            None,
+            quad_from_pixels(rect.min, size, options.colors.track, options.viewport),
+        ));
+        if fraction > 0.0 {
+            self.submit_draw(GuiDraw::new(
+                options.layer,
+                None,
+                quad_from_pixels(
+                    fill_rect.min,
+                    [size[0] * fraction, size[1]],
+                    options.colors.fill,
+                    options.viewport,
+                ),
+            ));
         }
 
-        if !interaction.mouse_down {
-            state.drag_active = false;
-            state.resize_active = None;
+        if options.show_percentage {
+            let text = format!("{:.0}%", fraction * 100.0);
+            let metrics = &options.metrics;
+            let width = text_width(&text, metrics.char_width);
+            self.submit_text(GuiTextDraw {
+                position: [
+                    rect.min[0] + (size[0] - width) * 0.5,
+                    rect.min[1] + (size[1] - metrics.text_height) * 0.5,
+                ],
+                text,
+                color: options.colors.text,
+                scale: metrics.font_scale,
+                clip_rect: None,
+            });
         }
 
-        if state.drag_active && interaction.mouse_down {
-            state.position = sub2(interaction.cursor, state.drag_offset);
+        ProgressBarLayout {
+            track_rect: rect,
+            fill_rect,
+            fraction,
         }
+    }
 
-        if let Some(edge) = state.resize_active {
-            if interaction.mouse_down {
-                let delta = sub2(interaction.cursor, state.resize_anchor);
-                let (position, size) = apply_resize(
-                    edge,
-                    state.resize_start_pos,
-                    state.resize_start_size,
-                    delta,
-                    metrics.min_size,
-                );
-                state.position = position;
-                state.size = size;
-            }
+    /// Draw an indeterminate spinner: a ring of dots whose highlight
+    /// advances with `time` (seconds).
+    pub fn submit_spinner(&mut self, rect: MenuRect, time: f32, options: &ProgressRenderOptions) {
+        let metrics = &options.metrics;
+        let dots = metrics.spinner_dots.max(1);
+        let size = [rect.max[0] - rect.min[0], rect.max[1] - rect.min[1]];
+        let center = [rect.min[0] + size[0] * 0.5, rect.min[1] + size[1] * 0.5];
+        let dot = metrics.spinner_dot_size;
+        let radius = (size[0].min(size[1]) - dot) * 0.5;
+        let head = (time * metrics.spinner_speed).rem_euclid(1.0) * dots as f32;
+
+        for index in 0..dots {
+            let angle = index as f32 / dots as f32 * std::f32::consts::TAU;
+            // How far this dot trails the head, in dots; recent ones are brighter.
+            let behind = (head - index as f32).rem_euclid(dots as f32);
+            let intensity = 1.0 - behind / dots as f32;
+            let mut color = options.colors.fill;
+            color[3] *= intensity;
+            self.submit_draw(GuiDraw::new(
+                options.layer,
+                None,
+                quad_from_pixels(
+                    [
+                        center[0] + radius * angle.cos() - dot * 0.5,
+                        center[1] + radius * angle.sin() - dot * 0.5,
+                    ],
+                    [dot, dot],
+                    color,
+                    options.viewport,
+                ),
+            ));
         }
+    }
 
-        if state.closed {
-            return PanelLayout::closed(state.position, state.size);
+    /// Edit `color` in place with a saturation/value square, a hue strip and
+    /// an alpha slider. The returned interaction covers all three parts.
+    pub fn submit_color_picker(
+        &mut self,
+        id: GuiId,
+        color: &mut [f32; 4],
+        input: &mut GuiInput,
+        options: &ColorPickerRenderOptions,
+    ) -> GuiInteraction {
+        let metrics = &options.metrics;
+        let colors = &options.colors;
+        let viewport = options.viewport;
+        let layer = options.layer;
+        let rects = color_picker_rects(options);
+        let cursor = [input.cursor.x, input.cursor.y];
+
+        let rgb = [color[0], color[1], color[2]];
+        let mut hsv = match self.color_picker_hsv.get(&id) {
+            Some(&cached) if colors_match(hsv_to_rgb(cached), rgb) => cached,
+            _ => rgb_to_hsv(rgb),
+        };
+        let mut alpha = color[3].clamp(0.0, 1.0);
+
+        let square = input.interact(id.child("sv"), rects.square.contains(cursor));
+        let hue = input.interact(id.child("hue"), rects.hue.contains(cursor));
+        let alpha_slider = input.interact(id.child("alpha"), rects.alpha.contains(cursor));
+        if square.active {
+            let [s, v] = rect_fraction(rects.square, cursor);
+            hsv[1] = s;
+            hsv[2] = 1.0 - v;
+        }
+        if hue.active {
+            hsv[0] = rect_fraction(rects.hue, cursor)[1];
+        }
+        if alpha_slider.active {
+            alpha = rect_fraction(rects.alpha, cursor)[0];
+        }
+        if square.active || hue.active || alpha_slider.active {
+            let [r, g, b] = hsv_to_rgb(hsv);
+            *color = [r, g, b, alpha];
+        }
+        self.color_picker_hsv.insert(id, hsv);
+
+        self.submit_draw(GuiDraw::new(
+            layer,
+            None,
+            quad_from_pixels(options.position, rects.size, colors.background, viewport),
+        ));
+
+        // Gradients are approximated with flat cells since quads carry one color.
+        let cells = metrics.square_cells.max(1);
+        let cell = metrics.square_size / cells as f32;
+        for row in 0..cells {
+            for column in 0..cells {
+                let s = (column as f32 + 0.5) / cells as f32;
+                let v = 1.0 - (row as f32 + 0.5) / cells as f32;
+                let [r, g, b] = hsv_to_rgb([hsv[0], s, v]);
+                self.submit_draw(GuiDraw::new(
+                    layer,
+                    None,
+                    quad_from_pixels(
+                        [
+                            rects.square.min[0] + column as f32 * cell,
+                            rects.square.min[1] + row as f32 * cell,
+                        ],
+                        [cell, cell],
+                        [r, g, b, 1.0],
+                        viewport,
+                    ),
+                ));
+            }
+        }
+
+        let segments = metrics.hue_segments.max(1);
+        let segment = metrics.square_size / segments as f32;
+        for index in 0..segments {
+            let [r, g, b] = hsv_to_rgb([(index as f32 + 0.5) / segments as f32, 1.0, 1.0]);
+            self.submit_draw(GuiDraw::new(
+                layer,
+                None,
+                quad_from_pixels(
+                    [rects.hue.min[0], rects.hue.min[1] + index as f32 * segment],
+                    [metrics.strip_width, segment],
+                    [r, g, b, 1.0],
+                    viewport,
+                ),
+            ));
+        }
+
+        let alpha_width = rects.alpha.max[0] - rects.alpha.min[0];
+        let segment = alpha_width / segments as f32;
+        for index in 0..segments {
+            let a = (index as f32 + 0.5) / segments as f32;
+            self.submit_draw(GuiDraw::new(
+                layer,
+                None,
+                quad_from_pixels(
+                    [
+                        rects.alpha.min[0] + index as f32 * segment,
+                        rects.alpha.min[1],
+                    ],
+                    [segment, metrics.strip_width],
+                    [color[0], color[1], color[2], a],
+                    viewport,
+                ),
+            ));
+        }
+
+        let marker = metrics.marker_size;
+        let markers = [
+            [
+                rects.square.min[0] + hsv[1] * metrics.square_size,
+                rects.square.min[1] + (1.0 - hsv[2]) * metrics.square_size,
+            ],
+            [
+                rects.hue.min[0] + metrics.strip_width * 0.5,
+                rects.hue.min[1] + hsv[0] * metrics.square_size,
+            ],
+            [
+                rects.alpha.min[0] + alpha * alpha_width,
+                rects.alpha.min[1] + metrics.strip_width * 0.5,
+            ],
+        ];
+        for center in markers {
+            self.submit_draw(GuiDraw::new(
+                layer,
+                None,
+                quad_from_pixels(
+                    [center[0] - marker * 0.5, center[1] - marker * 0.5],
+                    [marker, marker],
+                    colors.marker,
+                    viewport,
+                ),
+            ));
+        }
+
+        GuiInteraction {
+            hovered: square.hovered || hue.hovered || alpha_slider.hovered,
+            active: square.active || hue.active || alpha_slider.active,
+            focused: square.focused || hue.focused || alpha_slider.focused,
+            clicked: square.clicked || hue.clicked || alpha_slider.clicked,
+        }
+    }
+
+    /// Draw one row of a tree. Clicking the arrow toggles `state.open`;
+    /// clicking the label selects the node. Callers draw children when the
+    /// response is open, one [`TreeRenderOptions::depth`] deeper.
+    pub fn submit_tree_node(
+        &mut self,
+        id: GuiId,
+        label: &str,
+        state: &mut TreeNodeState,
+        input: &mut GuiInput,
+        options: &TreeRenderOptions,
+    ) -> TreeNodeResponse {
+        let metrics = &options.metrics;
+        let colors = &options.colors;
+        let viewport = options.viewport;
+        let cursor = [input.cursor.x, input.cursor.y];
+
+        let row_rect =
+            MenuRect::from_position_size(options.position, [options.width, metrics.row_height]);
+        let indent = options.position[0] + options.depth as f32 * metrics.indent;
+        let arrow_rect = MenuRect::from_position_size(
+            [
+                indent,
+                options.position[1] + (metrics.row_height - metrics.arrow_size) * 0.5,
+            ],
+            [metrics.arrow_size, metrics.arrow_size],
+        );
+        let over_arrow = !state.leaf && arrow_rect.contains(cursor);
+
+        let mut toggled = false;
+        if !state.leaf {
+            let arrow = input.interact(id.child("arrow"), over_arrow);
+            if arrow.clicked {
+                state.open = !state.open;
+                toggled = true;
+            }
+        }
+        let row = input.interact(id, row_rect.contains(cursor) && !over_arrow);
+        let selected = row.clicked.then_some(id);
+
+        let row_color = if state.selected {
+            Some(colors.row_selected)
+        } else if row.hovered {
+            Some(colors.row_hover)
+        } else {
+            None
+        };
+        if let Some(row_color) = row_color {
+            self.submit_draw(GuiDraw::new(
+                options.layer,
+                None,
+                quad_from_pixels(
+                    row_rect.min,
+                    [options.width, metrics.row_height],
+                    row_color,
+                    viewport,
+                ),
+            ));
+        }
+
+        if !state.leaf {
+            self.submit_text(GuiTextDraw {
+                text: if state.open { "▾" } else { "▸" }.to_string(),
+                position: [arrow_rect.min[0], row_rect.min[1] + metrics.text_offset[1]],
+                color: colors.arrow,
+                scale: metrics.font_scale,
+                clip_rect: None,
+            });
+        }
+        self.submit_text(GuiTextDraw {
+            text: label.to_string(),
+            position: [
+                arrow_rect.max[0] + metrics.label_gap,
+                row_rect.min[1] + metrics.text_offset[1],
+            ],
+            color: colors.label,
+            scale: metrics.font_scale,
+            clip_rect: None,
+        });
+
+        TreeNodeResponse {
+            row_rect,
+            arrow_rect,
+            open: state.open && !state.leaf,
+            toggled,
+            selected,
+            hovered: row.hovered,
+        }
+    }
+
+    pub fn submit_text_edit(
+        &mut self,
+        state: &TextEditState,
+        options: &TextEditRenderOptions,
+    ) -> TextEditLayout {
+        let metrics = &options.metrics;
+        let colors = &options.colors;
+        let viewport = options.viewport;
+        let rect = MenuRect::from_position_size(options.position, options.size);
+
+        let background = if options.focused {
+            colors.background_focused
+        } else {
+            colors.background
+        };
+        self.submit_draw(GuiDraw::new(
+            options.layer,
+            None,
+            quad_from_pixels(options.position, options.size, background, viewport),
+        ));
+
+        let text_x = options.position[0] + metrics.padding[0];
+        let text_y = options.position[1] + metrics.text_offset[1];
+
+        if let Some(selection) = state.selection() {
+            let start_x = text_x + selection.start as f32 * metrics.char_width;
+            let width = (selection.end - selection.start) as f32 * metrics.char_width;
+            self.submit_draw(GuiDraw::new(
+                options.layer,
+                None,
+                quad_from_pixels(
+                    [start_x, options.position[1] + metrics.padding[1]],
+                    [width, options.size[1] - metrics.padding[1] * 2.0],
+                    colors.selection,
+                    viewport,
+                ),
+            ));
+        }
+
+        self.submit_text(GuiTextDraw {
+            text: state.text.clone(),
+            position: [text_x, text_y],
+            color: colors.text,
+            scale: metrics.font_scale,
+            clip_rect: None,
+        });
+
+        let caret_rect = MenuRect::from_position_size(
+            [
+                text_x + state.caret() as f32 * metrics.char_width,
+                options.position[1] + metrics.padding[1],
+            ],
+            [
+                metrics.caret_width,
+                (options.size[1] - metrics.padding[1] * 2.0).max(0.0),
+            ],
+        );
+        if options.focused {
+            self.submit_draw(GuiDraw::new(
+                options.layer,
+                None,
+                quad_from_pixels(
+                    caret_rect.min,
+                    [metrics.caret_width, caret_rect.max[1] - caret_rect.min[1]],
+                    colors.caret,
+                    viewport,
+                ),
+            ));
+        }
+
+        TextEditLayout { rect, caret_rect }
+    }
+
+    pub fn submit_panel(
+        &mut self,
+        panel: &Panel,
+        state: &mut PanelState,
+        options: &PanelRenderOptions,
+    ) -> PanelLayout {
+        let viewport = options.viewport;
+        let metrics = &options.metrics;
+        let colors = &options.colors;
+        let interaction = options.interaction;
+
+        let initial_display_size = panel_display_size(
+            state.size,
+            metrics.title_bar_height,
+            metrics.minimized_extra_height,
+            state.minimized,
+        );
+        let initial_title_bar_rect = MenuRect::from_position_size(
+            state.position,
+            [initial_display_size[0], metrics.title_bar_height],
+        );
+        let initial_grip_pos = [state.position[0] + metrics.grip_padding, state.position[1]];
+        let initial_grip_rect = MenuRect::from_position_size(initial_grip_pos, metrics.grip_size);
+        let initial_drag_rect = if metrics.grip_size[0] > 0.0 {
+            initial_grip_rect
+        } else {
+            initial_title_bar_rect
+        };
+
+        let button_size = metrics.button_size;
+        let button_gap = metrics.button_gap;
+        let button_y = state.position[1] + (metrics.title_bar_height - button_size[1]) * 0.5;
+        let buttons_right = state.position[0] + initial_display_size[0] - metrics.button_padding;
+        let close_button_pos = [buttons_right - button_size[0], button_y];
+        let minimize_button_pos = [buttons_right - button_size[0] * 2.0 - button_gap, button_y];
+        let initial_close_rect = MenuRect::from_position_size(close_button_pos, button_size);
+        let initial_minimize_rect = MenuRect::from_position_size(minimize_button_pos, button_size);
+
+        if !self.panel_order.contains(&panel.id) {
+            self.panel_order.push(panel.id);
+        }
+        self.current_panel = Some(panel.id);
+        if interaction.mouse_pressed
+            && !state.closed
+            && point_in_rect(
+                interaction.cursor,
+                MenuRect::from_position_size(state.position, initial_display_size),
+            )
+        {
+            self.request_panel_raise(panel.id);
+        }
+
+        let resize_edge = if state.closed || state.minimized {
+            None
+        } else {
+            resize_edge_for_cursor(
+                interaction.cursor,
+                state.position,
+                initial_display_size,
+                metrics.resize_margin,
+            )
+        };
+
+        if interaction.mouse_pressed && !state.closed {
+            if options.allow_close && point_in_rect(interaction.cursor, initial_close_rect) {
+                state.closed = true;
+            } else if options.allow_minimize
+                && point_in_rect(interaction.cursor, initial_minimize_rect)
+            {
+                state.minimized = !state.minimized;
+            } else if let Some(edge) = resize_edge {
+                state.resize_active = Some(edge);
+                state.resize_anchor = interaction.cursor;
+                state.resize_start_pos = state.position;
+                state.resize_start_size = state.size;
+            } else if point_in_rect(interaction.cursor, initial_drag_rect) {
+                state.drag_active = true;
+                state.drag_offset = sub2(interaction.cursor, state.position);
+            }
+        }
+
+        if !interaction.mouse_down {
+            state.drag_active = false;
+            state.resize_active = None;
+        }
+
+        if state.drag_active && interaction.mouse_down {
+            state.position = sub2(interaction.cursor, state.drag_offset);
+        }
+
+        if let Some(edge) = state.resize_active {
+            if interaction.mouse_down {
+                let delta = sub2(interaction.cursor, state.resize_anchor);
+                let (position, size) = apply_resize(
+                    edge,
+                    state.resize_start_pos,
+                    state.resize_start_size,
+                    delta,
+                    metrics.min_size,
+                );
+                state.position = position;
+                state.size = size;
+            }
+        }
+
+        if state.closed {
+            return PanelLayout::closed(state.position, state.size);
         }
 
         let display_size = panel_display_size(
@@ -898,6 +1813,7 @@ impl GuiContext {
                 position: [minimize_button_pos[0] + 6.0, minimize_button_pos[1] + 2.0],
                 color: colors.button_text,
                 scale: metrics.button_text_scale,
+                clip_rect: None,
             });
         }
 
@@ -912,6 +1828,7 @@ impl GuiContext {
                 position: [close_button_pos[0] + 5.0, close_button_pos[1] + 1.0],
                 color: colors.button_text,
                 scale: metrics.button_text_scale,
+                clip_rect: None,
             });
         }
 
@@ -923,6 +1840,7 @@ impl GuiContext {
             ],
             color: colors.title_text,
             scale: metrics.title_text_scale,
+            clip_rect: None,
         });
 
         if !state.minimized && metrics.resize_handle_size > 0.0 {
@@ -986,6 +1904,7 @@ impl GuiContext {
 #[derive(Debug, Clone, Copy)]
 struct GuiQueuedDraw {
     order: u64,
+    panel: Option<GuiId>,
     draw: GuiDraw,
 }
 
@@ -1045,6 +1964,8 @@ pub struct GuiTextDraw {
     pub position: [f32; 2],
     pub color: [f32; 4],
     pub scale: f32,
+    /// Pixel-space rect glyphs are clipped to. `None` draws unclipped.
+    pub clip_rect: Option<GuiClipRect>,
 }
 
 impl GuiQuad {
@@ -1089,6 +2010,12 @@ pub struct GuiClipRect {
 }
 
 impl GuiClipRect {
+    /// UV region covering an entire texture.
+    pub const FULL_UV: GuiClipRect = GuiClipRect {
+        min: [0.0, 0.0],
+        max: [1.0, 1.0],
+    };
+
     pub fn from_min_max(min: [f32; 2], max: [f32; 2]) -> Self {
         Self { min, max }
     }
@@ -1099,6 +2026,16 @@ impl GuiClipRect {
             max: [position[0] + size[0], position[1] + size[1]],
         }
     }
+
+    /// Overlap of both rects. Disjoint rects yield an empty rect.
+    pub fn intersect(&self, other: &GuiClipRect) -> GuiClipRect {
+        let min = [self.min[0].max(other.min[0]), self.min[1].max(other.min[1])];
+        let max = [
+            self.max[0].min(other.max[0]).max(min[0]),
+            self.max[1].min(other.max[1]).max(min[1]),
+        ];
+        Self { min, max }
+    }
 }
 
 /// A frame-ready GUI mesh plus batch metadata.
@@ -1199,6 +2136,7 @@ impl MenuBar {
                 position: text_pos,
                 color: colors.text,
                 scale: metrics.font_scale,
+                clip_rect: None,
             });
 
             cursor_x += tab_width + metrics.menu_gap;
@@ -1282,6 +2220,7 @@ impl MenuBar {
                             position: check_pos,
                             color: colors.checked_text,
                             scale: metrics.font_scale,
+                            clip_rect: None,
                         });
                     }
 
@@ -1293,6 +2232,7 @@ impl MenuBar {
                         position: label_pos,
                         color: text_color,
                         scale: metrics.font_scale,
+                        clip_rect: None,
                     });
 
                     if let Some(shortcut) = &item.shortcut {
@@ -1306,6 +2246,7 @@ impl MenuBar {
                             position: shortcut_pos,
                             color: text_color,
                             scale: metrics.font_scale,
+                            clip_rect: None,
                         });
                     }
 
@@ -1406,6 +2347,7 @@ impl MenuBar {
                                             position: check_pos,
                                             color: colors.checked_text,
                                             scale: metrics.font_scale,
+                                            clip_rect: None,
                                         });
                                     }
 
@@ -1421,6 +2363,7 @@ impl MenuBar {
                                         position: submenu_label_pos,
                                         color: submenu_text_color,
                                         scale: metrics.font_scale,
+                                        clip_rect: None,
                                     });
 
                                     if let Some(shortcut) = &submenu_item.shortcut {
@@ -1437,6 +2380,7 @@ impl MenuBar {
                                             position: shortcut_pos,
                                             color: submenu_text_color,
                                             scale: metrics.font_scale,
+                                            clip_rect: None,
                                         });
                                     }
 
@@ -1576,6 +2520,7 @@ impl MenuPopup {
                     position: check_pos,
                     color: colors.checked_text,
                     scale: metrics.font_scale,
+                    clip_rect: None,
                 });
             }
 
@@ -1586,6 +2531,7 @@ impl MenuPopup {
                 position: label_pos,
                 color: text_color,
                 scale: metrics.font_scale,
+                clip_rect: None,
             });
 
             if let Some(shortcut) = &item.shortcut {
@@ -1599,6 +2545,7 @@ impl MenuPopup {
                     position: shortcut_pos,
                     color: text_color,
                     scale: metrics.font_scale,
+                    clip_rect: None,
                 });
             }
 
@@ -1691,6 +2638,7 @@ impl MenuPopup {
                                     position: check_pos,
                                     color: colors.checked_text,
                                     scale: metrics.font_scale,
+                                    clip_rect: None,
                                 });
                             }
 
@@ -1706,6 +2654,7 @@ impl MenuPopup {
                                 position: submenu_label_pos,
                                 color: submenu_text_color,
                                 scale: metrics.font_scale,
+                                clip_rect: None,
                             });
 
                             if let Some(shortcut) = &submenu_item.shortcut {
@@ -1721,6 +2670,7 @@ impl MenuPopup {
                                     position: shortcut_pos,
                                     color: submenu_text_color,
                                     scale: metrics.font_scale,
+                                    clip_rect: None,
                                 });
                             }
 
@@ -1860,6 +2810,7 @@ impl CommandPalette {
                 ],
                 color: colors.placeholder_text,
                 scale: metrics.font_scale,
+                clip_rect: None,
             });
             None
         } else {
@@ -1875,6 +2826,7 @@ impl CommandPalette {
                 ],
                 color: colors.input_text,
                 scale: metrics.font_scale,
+                clip_rect: None,
             });
         }
 
@@ -1914,6 +2866,7 @@ impl CommandPalette {
                 ],
                 color: colors.empty_text,
                 scale: metrics.font_scale,
+                clip_rect: None,
             });
             return layout;
         }
@@ -1956,6 +2909,7 @@ impl CommandPalette {
                 ],
                 color: colors.item_text,
                 scale: metrics.font_scale,
+                clip_rect: None,
             });
 
             if let Some(shortcut) = item.shortcut.as_ref() {
@@ -1969,6 +2923,7 @@ impl CommandPalette {
                     position: shortcut_pos,
                     color: colors.shortcut_text,
                     scale: metrics.font_scale,
+                    clip_rect: None,
                 });
             }
 
@@ -2491,105 +3446,748 @@ impl RadialButton {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct RadialButtonRenderOptions {
+#[derive(Debug, Clone)]
+pub struct RadialButtonRenderOptions {
+    pub viewport: [f32; 2],
+    pub position: [f32; 2],
+    pub size: [f32; 2],
+    pub layer: GuiLayer,
+    pub metrics: RadialButtonMetrics,
+    pub colors: RadialButtonColors,
+    pub state: RadialButtonState,
+    pub clip_rect: Option<GuiClipRect>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RadialButtonState {
+    pub hovered: Option<u32>,
+    pub active: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RadialButtonMetrics {
+    pub item_height: f32,
+    pub item_gap: f32,
+    pub padding: [f32; 2],
+    pub button_size: [f32; 2],
+    pub indicator_size: [f32; 2],
+    pub label_gap: f32,
+    pub char_width: f32,
+    pub font_scale: f32,
+    pub text_offset: [f32; 2],
+}
+
+impl Default for RadialButtonMetrics {
+    fn default() -> Self {
+        Self {
+            item_height: 24.0,
+            item_gap: 8.0,
+            padding: [12.0, 8.0],
+            button_size: [16.0, 16.0],
+            indicator_size: [8.0, 8.0],
+            label_gap: 10.0,
+            char_width: 7.2,
+            font_scale: 0.9,
+            text_offset: [0.0, 6.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RadialButtonColors {
+    pub button: [f32; 4],
+    pub button_hover: [f32; 4],
+    pub button_active: [f32; 4],
+    pub button_selected: [f32; 4],
+    pub button_selected_hover: [f32; 4],
+    pub indicator: [f32; 4],
+    pub label: [f32; 4],
+    pub disabled: [f32; 4],
+}
+
+impl Default for RadialButtonColors {
+    fn default() -> Self {
+        Self {
+            button: [0.18, 0.2, 0.26, 0.9],
+            button_hover: [0.22, 0.26, 0.34, 0.95],
+            button_active: [0.3, 0.36, 0.46, 0.95],
+            button_selected: [0.24, 0.28, 0.38, 0.98],
+            button_selected_hover: [0.3, 0.36, 0.48, 1.0],
+            indicator: [0.85, 0.9, 1.0, 1.0],
+            label: [0.9, 0.93, 0.98, 1.0],
+            disabled: [0.4, 0.42, 0.46, 0.6],
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct RadialButtonLayout {
+    pub items: Vec<RadialButtonItemLayout>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct RadialButtonItemLayout {
+    pub id: u32,
+    pub item_rect: MenuRect,
+    pub button_rect: MenuRect,
+    pub selected: bool,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TabInfo {
+    pub label: String,
+    /// Show a × button that reports a close request.
+    pub closeable: bool,
+}
+
+impl TabInfo {
+    pub fn new(label: impl Into<String>, closeable: bool) -> Self {
+        Self {
+            label: label.into(),
+            closeable,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TabBarRenderOptions {
+    pub viewport: [f32; 2],
+    pub position: [f32; 2],
+    /// Width of the strip behind the tabs.
+    pub width: f32,
+    pub layer: GuiLayer,
+    pub metrics: TabBarMetrics,
+    pub colors: TabBarColors,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TabBarMetrics {
+    pub height: f32,
+    /// Horizontal space between a tab's edges and its contents.
+    pub padding: f32,
+    pub tab_gap: f32,
+    pub close_gap: f32,
+    pub close_button_size: [f32; 2],
+    pub char_width: f32,
+    pub font_scale: f32,
+    pub text_offset: [f32; 2],
+}
+
+impl Default for TabBarMetrics {
+    fn default() -> Self {
+        Self {
+            height: 28.0,
+            padding: 10.0,
+            tab_gap: 2.0,
+            close_gap: 8.0,
+            close_button_size: [14.0, 14.0],
+            char_width: 7.2,
+            font_scale: 0.9,
+            text_offset: [0.0, 7.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TabBarColors {
+    pub background: [f32; 4],
+    pub tab: [f32; 4],
+    pub tab_hover: [f32; 4],
+    pub tab_active: [f32; 4],
+    pub label: [f32; 4],
+    pub close_button: [f32; 4],
+    pub close_button_hover: [f32; 4],
+}
+
+impl Default for TabBarColors {
+    fn default() -> Self {
+        Self {
+            background: [0.1, 0.12, 0.16, 0.95],
+            tab: [0.16, 0.18, 0.24, 0.9],
+            tab_hover: [0.22, 0.26, 0.34, 0.95],
+            tab_active: [0.28, 0.32, 0.42, 1.0],
+            label: [0.9, 0.93, 0.98, 1.0],
+            close_button: [0.2, 0.22, 0.28, 0.0],
+            close_button_hover: [0.5, 0.24, 0.26, 0.95],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TabBarLayout {
+    pub rect: MenuRect,
+    pub tabs: Vec<TabLayout>,
+    /// Selected tab after this frame's clicks.
+    pub active: usize,
+    /// Tab whose close button was clicked this frame.
+    pub close_requested: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TabLayout {
+    pub tab_rect: MenuRect,
+    pub close_rect: Option<MenuRect>,
+    pub hovered: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressRenderOptions {
+    pub viewport: [f32; 2],
+    pub layer: GuiLayer,
+    /// Draw the fill as a centered percentage over the track.
+    pub show_percentage: bool,
+    pub metrics: ProgressMetrics,
+    pub colors: ProgressColors,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressMetrics {
+    pub char_width: f32,
+    pub text_height: f32,
+    pub font_scale: f32,
+    pub spinner_dots: u32,
+    pub spinner_dot_size: f32,
+    /// Spinner revolutions per second.
+    pub spinner_speed: f32,
+}
+
+impl Default for ProgressMetrics {
+    fn default() -> Self {
+        Self {
+            char_width: 7.2,
+            text_height: 14.0,
+            font_scale: 0.85,
+            spinner_dots: 8,
+            spinner_dot_size: 4.0,
+            spinner_speed: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressColors {
+    pub track: [f32; 4],
+    pub fill: [f32; 4],
+    pub text: [f32; 4],
+}
+
+impl Default for ProgressColors {
+    fn default() -> Self {
+        Self {
+            track: [0.12, 0.14, 0.18, 0.95],
+            fill: [0.32, 0.56, 0.92, 1.0],
+            text: [0.92, 0.94, 0.98, 1.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressBarLayout {
+    pub track_rect: MenuRect,
+    pub fill_rect: MenuRect,
+    /// Fraction actually drawn, after clamping.
+    pub fraction: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ColorPickerRenderOptions {
+    pub viewport: [f32; 2],
+    pub position: [f32; 2],
+    pub layer: GuiLayer,
+    pub metrics: ColorPickerMetrics,
+    pub colors: ColorPickerColors,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ColorPickerMetrics {
+    pub padding: f32,
+    /// Side length of the saturation/value square; the hue strip matches its height.
+    pub square_size: f32,
+    /// Thickness of the hue strip and alpha slider.
+    pub strip_width: f32,
+    pub gap: f32,
+    pub square_cells: u32,
+    pub hue_segments: u32,
+    pub marker_size: f32,
+}
+
+impl Default for ColorPickerMetrics {
+    fn default() -> Self {
+        Self {
+            padding: 8.0,
+            square_size: 160.0,
+            strip_width: 16.0,
+            gap: 8.0,
+            square_cells: 16,
+            hue_segments: 24,
+            marker_size: 6.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ColorPickerColors {
+    pub background: [f32; 4],
+    pub marker: [f32; 4],
+}
+
+impl Default for ColorPickerColors {
+    fn default() -> Self {
+        Self {
+            background: [0.12, 0.14, 0.18, 0.95],
+            marker: [0.95, 0.96, 0.98, 1.0],
+        }
+    }
+}
+
+struct ColorPickerRects {
+    size: [f32; 2],
+    square: MenuRect,
+    hue: MenuRect,
+    alpha: MenuRect,
+}
+
+fn color_picker_rects(options: &ColorPickerRenderOptions) -> ColorPickerRects {
+    let metrics = &options.metrics;
+    let origin = [
+        options.position[0] + metrics.padding,
+        options.position[1] + metrics.padding,
+    ];
+    let square = MenuRect::from_position_size(origin, [metrics.square_size; 2]);
+    let hue = MenuRect::from_position_size(
+        [square.max[0] + metrics.gap, origin[1]],
+        [metrics.strip_width, metrics.square_size],
+    );
+    let alpha = MenuRect::from_position_size(
+        [origin[0], square.max[1] + metrics.gap],
+        [hue.max[0] - origin[0], metrics.strip_width],
+    );
+    ColorPickerRects {
+        size: [
+            hue.max[0] + metrics.padding - options.position[0],
+            alpha.max[1] + metrics.padding - options.position[1],
+        ],
+        square,
+        hue,
+        alpha,
+    }
+}
+
+/// Cursor position inside `rect` as clamped `[0, 1]` fractions per axis.
+fn rect_fraction(rect: MenuRect, point: [f32; 2]) -> [f32; 2] {
+    let axis = |index: usize| {
+        let extent = rect.max[index] - rect.min[index];
+        if extent <= f32::EPSILON {
+            0.0
+        } else {
+            ((point[index] - rect.min[index]) / extent).clamp(0.0, 1.0)
+        }
+    };
+    [axis(0), axis(1)]
+}
+
+fn colors_match(a: [f32; 3], b: [f32; 3]) -> bool {
+    a.iter().zip(b).all(|(a, b)| (a - b).abs() <= 1.0e-4)
+}
+
+/// Convert RGB to HSV, all components in `[0, 1]`.
+fn rgb_to_hsv([r, g, b]: [f32; 3]) -> [f32; 3] {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+    let hue = if delta <= f32::EPSILON {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    let saturation = if max <= f32::EPSILON {
+        0.0
+    } else {
+        delta / max
+    };
+    [hue / 6.0, saturation, max]
+}
+
+/// Convert HSV to RGB, all components in `[0, 1]`.
+fn hsv_to_rgb([h, s, v]: [f32; 3]) -> [f32; 3] {
+    let sector = h.rem_euclid(1.0) * 6.0;
+    let chroma = v * s;
+    let x = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+    let m = v - chroma;
+    let [r, g, b] = match sector as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    };
+    [r + m, g + m, b + m]
+}
+
+/// Per-node tree state kept by the caller between frames.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TreeNodeState {
+    pub open: bool,
+    /// Draw the row highlighted; the caller decides which node is selected.
+    pub selected: bool,
+    /// Nodes without children draw no arrow and never open.
+    pub leaf: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TreeRenderOptions {
+    pub viewport: [f32; 2],
+    /// Top-left of the row; callers advance it by the row height per node.
+    pub position: [f32; 2],
+    pub width: f32,
+    /// Nesting level, indenting the arrow and label by `metrics.indent` each.
+    pub depth: u32,
+    pub layer: GuiLayer,
+    pub metrics: TreeMetrics,
+    pub colors: TreeColors,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TreeMetrics {
+    pub row_height: f32,
+    pub indent: f32,
+    pub arrow_size: f32,
+    pub label_gap: f32,
+    pub font_scale: f32,
+    pub text_offset: [f32; 2],
+}
+
+impl Default for TreeMetrics {
+    fn default() -> Self {
+        Self {
+            row_height: 22.0,
+            indent: 14.0,
+            arrow_size: 12.0,
+            label_gap: 4.0,
+            font_scale: 0.9,
+            text_offset: [0.0, 5.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TreeColors {
+    pub row_hover: [f32; 4],
+    pub row_selected: [f32; 4],
+    pub arrow: [f32; 4],
+    pub label: [f32; 4],
+}
+
+impl Default for TreeColors {
+    fn default() -> Self {
+        Self {
+            row_hover: [0.2, 0.24, 0.32, 0.8],
+            row_selected: [0.28, 0.4, 0.62, 0.95],
+            arrow: [0.7, 0.74, 0.82, 1.0],
+            label: [0.9, 0.93, 0.98, 1.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TreeNodeResponse {
+    pub row_rect: MenuRect,
+    pub arrow_rect: MenuRect,
+    /// Whether the caller should submit this node's children.
+    pub open: bool,
+    /// The arrow was clicked this frame.
+    pub toggled: bool,
+    /// Id of this node if its label was clicked this frame.
+    pub selected: Option<GuiId>,
+    pub hovered: bool,
+}
+
+/// Editable single-line text buffer with a caret and optional selection.
+///
+/// Caret and selection positions are measured in characters.
+#[derive(Debug, Clone, Default)]
+pub struct TextEditState {
+    pub text: String,
+    caret: usize,
+    selection_anchor: Option<usize>,
+}
+
+impl TextEditState {
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let caret = text.chars().count();
+        Self {
+            text,
+            caret,
+            selection_anchor: None,
+        }
+    }
+
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    pub fn set_caret(&mut self, caret: usize) {
+        self.caret = caret.min(self.char_count());
+        self.selection_anchor = None;
+    }
+
+    /// Select the characters in `range`, leaving the caret at its end.
+    pub fn select(&mut self, range: Range<usize>) {
+        let count = self.char_count();
+        self.selection_anchor = Some(range.start.min(count));
+        self.caret = range.end.min(count);
+    }
+
+    pub fn select_all(&mut self) {
+        self.select(0..self.char_count());
+    }
+
+    /// The current non-empty selection, ordered start to end.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.selection_anchor?;
+        if anchor == self.caret {
+            return None;
+        }
+        Some(anchor.min(self.caret)..anchor.max(self.caret))
+    }
+
+    pub fn selected_text(&self) -> Option<String> {
+        let selection = self.selection()?;
+        Some(
+            self.text
+                .chars()
+                .skip(selection.start)
+                .take(selection.end - selection.start)
+                .collect(),
+        )
+    }
+
+    /// Insert `text` at the caret, replacing any selection.
+    pub fn insert_str(&mut self, text: &str) {
+        self.delete_selection();
+        let byte_index = self.byte_index(self.caret);
+        self.text.insert_str(byte_index, text);
+        self.caret += text.chars().count();
+    }
+
+    /// Apply this frame's key presses and clipboard shortcuts. Returns `true`
+    /// when the text changed.
+    pub fn handle_input(&mut self, input: &mut GuiInput) -> bool {
+        if let Some(command) = input.clipboard_command() {
+            return match command {
+                GuiClipboardCommand::Copy => {
+                    if let Some(text) = self.selected_text() {
+                        input.set_copied_text(text);
+                    }
+                    false
+                }
+                GuiClipboardCommand::Cut => match self.selected_text() {
+                    Some(text) => {
+                        input.set_copied_text(text);
+                        self.delete_selection()
+                    }
+                    None => false,
+                },
+                GuiClipboardCommand::Paste => {
+                    if input.clipboard().is_empty() {
+                        false
+                    } else {
+                        let text = input.clipboard().to_string();
+                        self.insert_str(&text);
+                        true
+                    }
+                }
+            };
+        }
+
+        let Some(key) = input.last_key_pressed() else {
+            return false;
+        };
+
+        let shift_down = input.is_key_down(KeyCode::Shift);
+        if input.command_down() {
+            if key == KeyCode::A {
+                self.select_all();
+            }
+            return false;
+        }
+
+        match key {
+            KeyCode::Backspace => {
+                if self.delete_selection() {
+                    return true;
+                }
+                if self.caret == 0 {
+                    return false;
+                }
+                let byte_index = self.byte_index(self.caret - 1);
+                self.text.remove(byte_index);
+                self.caret -= 1;
+                true
+            }
+            KeyCode::Delete => {
+                if self.delete_selection() {
+                    return true;
+                }
+                if self.caret >= self.char_count() {
+                    return false;
+                }
+                let byte_index = self.byte_index(self.caret);
+                self.text.remove(byte_index);
+                true
+            }
+            KeyCode::ArrowLeft => {
+                self.move_caret(self.caret.saturating_sub(1), shift_down);
+                false
+            }
+            KeyCode::ArrowRight => {
+                self.move_caret((self.caret + 1).min(self.char_count()), shift_down);
+                false
+            }
+            KeyCode::Home => {
+                self.move_caret(0, shift_down);
+                false
+            }
+            KeyCode::End => {
+                self.move_caret(self.char_count(), shift_down);
+                false
+            }
+            _ => match keycode_to_char(key, shift_down) {
+                Some(ch) => {
+                    let mut buffer = [0u8; 4];
+                    self.insert_str(ch.encode_utf8(&mut buffer));
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    fn move_caret(&mut self, caret: usize, extend_selection: bool) {
+        if extend_selection {
+            if self.selection_anchor.is_none() {
+                self.selection_anchor = Some(self.caret);
+            }
+        } else {
+            self.selection_anchor = None;
+        }
+        self.caret = caret;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        let Some(selection) = self.selection() else {
+            self.selection_anchor = None;
+            return false;
+        };
+        let start = self.byte_index(selection.start);
+        let end = self.byte_index(selection.end);
+        self.text.replace_range(start..end, "");
+        self.caret = selection.start;
+        self.selection_anchor = None;
+        true
+    }
+
+    fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    fn byte_index(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(char_index)
+            .map(|(index, _)| index)
+            .unwrap_or(self.text.len())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TextEditRenderOptions {
     pub viewport: [f32; 2],
     pub position: [f32; 2],
     pub size: [f32; 2],
     pub layer: GuiLayer,
-    pub metrics: RadialButtonMetrics,
-    pub colors: RadialButtonColors,
-    pub state: RadialButtonState,
-    pub clip_rect: Option<GuiClipRect>,
-}
-
-#[derive(Debug, Clone, Copy, Default)]
-pub struct RadialButtonState {
-    pub hovered: Option<u32>,
-    pub active: Option<u32>,
+    pub focused: bool,
+    pub metrics: TextEditMetrics,
+    pub colors: TextEditColors,
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct RadialButtonMetrics {
-    pub item_height: f32,
-    pub item_gap: f32,
+pub struct TextEditMetrics {
     pub padding: [f32; 2],
-    pub button_size: [f32; 2],
-    pub indicator_size: [f32; 2],
-    pub label_gap: f32,
     pub char_width: f32,
     pub font_scale: f32,
     pub text_offset: [f32; 2],
+    pub caret_width: f32,
 }
 
-impl Default for RadialButtonMetrics {
+impl Default for TextEditMetrics {
     fn default() -> Self {
         Self {
-            item_height: 24.0,
-            item_gap: 8.0,
-            padding: [12.0, 8.0],
-            button_size: [16.0, 16.0],
-            indicator_size: [8.0, 8.0],
-            label_gap: 10.0,
-            char_width: 7.2,
-            font_scale: 0.9,
-            text_offset: [0.0, 6.0],
+            padding: [8.0, 4.0],
+            char_width: 7.5,
+            font_scale: 1.0,
+            text_offset: [0.0, 7.0],
+            caret_width: 1.5,
         }
     }
 }
 
 #[derive(Debug, Clone, Copy)]
-pub struct RadialButtonColors {
-    pub button: [f32; 4],
-    pub button_hover: [f32; 4],
-    pub button_active: [f32; 4],
-    pub button_selected: [f32; 4],
-    pub button_selected_hover: [f32; 4],
-    pub indicator: [f32; 4],
-    pub label: [f32; 4],
-    pub disabled: [f32; 4],
+pub struct TextEditColors {
+    pub background: [f32; 4],
+    pub background_focused: [f32; 4],
+    pub text: [f32; 4],
+    pub selection: [f32; 4],
+    pub caret: [f32; 4],
 }
 
-impl Default for RadialButtonColors {
+impl Default for TextEditColors {
     fn default() -> Self {
         Self {
-            button: [0.18, 0.2, 0.26, 0.9],
-            button_hover: [0.22, 0.26, 0.34, 0.95],
-            button_active: [0.3, 0.36, 0.46, 0.95],
-            button_selected: [0.24, 0.28, 0.38, 0.98],
-            button_selected_hover: [0.3, 0.36, 0.48, 1.0],
-            indicator: [0.85, 0.9, 1.0, 1.0],
-            label: [0.9, 0.93, 0.98, 1.0],
-            disabled: [0.4, 0.42, 0.46, 0.6],
+            background: [0.1, 0.11, 0.14, 0.95],
+            background_focused: [0.14, 0.16, 0.2, 0.98],
+            text: [0.92, 0.94, 0.98, 1.0],
+            selection: [0.26, 0.4, 0.62, 0.8],
+            caret: [0.95, 0.97, 1.0, 1.0],
         }
     }
 }
 
-#[derive(Debug, Default, Clone)]
-pub struct RadialButtonLayout {
-    pub items: Vec<RadialButtonItemLayout>,
-}
-
 #[derive(Debug, Clone, Copy)]
-pub struct RadialButtonItemLayout {
-    pub id: u32,
-    pub item_rect: MenuRect,
-    pub button_rect: MenuRect,
-    pub selected: bool,
-    pub enabled: bool,
+pub struct TextEditLayout {
+    pub rect: MenuRect,
+    pub caret_rect: MenuRect,
 }
 
 #[derive(Debug, Clone)]
 pub struct Panel {
+    pub id: GuiId,
     pub title: String,
 }
 
 impl Panel {
+    /// Create a panel whose id is derived from its title.
     pub fn new(title: impl Into<String>) -> Self {
+        let title = title.into();
+        let mut hasher = DefaultHasher::new();
+        title.hash(&mut hasher);
         Self {
-            title: title.into(),
+            id: GuiId::new(hasher.finish()),
+            title,
         }
     }
+
+    pub fn with_id(mut self, id: GuiId) -> Self {
+        self.id = id;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -2723,6 +4321,11 @@ impl PanelState {
             resize_start_size: [0.0, 0.0],
         }
     }
+
+    /// Whether the panel is being dragged by its title bar.
+    pub fn is_dragging(&self) -> bool {
+        self.drag_active
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -2908,6 +4511,122 @@ pub struct MenuPopupLayout {
     pub open_submenu: Option<MenuPopupSubmenuLayout>,
 }
 
+impl MenuBarLayout {
+    /// Hit-tests `input` against the laid-out tabs and items, updating
+    /// `state`'s hover and open menus, and returns the action id of an
+    /// enabled item clicked this frame.
+    pub fn clicked_action(&self, input: &GuiInput, state: &mut MenuBarState) -> Option<u32> {
+        let cursor = [input.cursor.x, input.cursor.y];
+        let hovered_tab = self
+            .menu_tabs
+            .iter()
+            .find(|tab| tab.rect.contains(cursor))
+            .map(|tab| tab.menu_index);
+        let hovered_item = self
+            .item_rects
+            .iter()
+            .filter(|item| item.rect.contains(cursor))
+            .max_by_key(|item| item.depth);
+
+        state.hovered_menu = hovered_tab;
+        state.hovered_item = hovered_item
+            .filter(|item| item.enabled)
+            .map(|item| (item.menu_index, item.item_index));
+
+        match (state.open_menu, hovered_item) {
+            (Some(open_menu), Some(item)) if item.depth == 0 => {
+                state.open_submenu =
+                    (item.enabled && item.has_submenu && item.menu_index == open_menu)
+                        .then_some((item.menu_index, item.item_index));
+            }
+            (Some(_), Some(_)) => {}
+            _ => state.open_submenu = None,
+        }
+
+        if state.open_menu.is_some()
+            && hovered_tab.is_some()
+            && !input.mouse_pressed
+            && !input.mouse_down
+        {
+            state.open_menu = hovered_tab;
+            state.open_submenu = None;
+        }
+
+        if !input.mouse_pressed {
+            return None;
+        }
+
+        if let Some(menu_index) = hovered_tab {
+            state.open_menu = if state.open_menu == Some(menu_index) {
+                None
+            } else {
+                Some(menu_index)
+            };
+            state.open_submenu = None;
+            return None;
+        }
+
+        if let Some(item) = hovered_item {
+            if !item.enabled || item.has_submenu {
+                return None;
+            }
+            state.open_menu = None;
+            state.open_submenu = None;
+            return item.action_id;
+        }
+
+        let in_open_menu = self
+            .open_menu
+            .is_some_and(|open_menu| open_menu.rect.contains(cursor))
+            || self
+                .open_submenu
+                .is_some_and(|open_submenu| open_submenu.rect.contains(cursor));
+        if !in_open_menu {
+            state.open_menu = None;
+            state.open_submenu = None;
+        }
+        None
+    }
+}
+
+impl MenuPopupLayout {
+    /// Hit-tests `input` against the laid-out popup items, updating `state`'s
+    /// hover and open submenu, and returns the action id of an enabled item
+    /// clicked this frame.
+    pub fn clicked_action(&self, input: &GuiInput, state: &mut MenuPopupState) -> Option<u32> {
+        let cursor = [input.cursor.x, input.cursor.y];
+        let hovered_item = self
+            .item_rects
+            .iter()
+            .filter(|item| item.rect.contains(cursor))
+            .max_by_key(|item| item.depth);
+
+        state.hovered_item = match hovered_item {
+            Some(item) if item.enabled => Some(MenuPopupItemRef {
+                item_index: item.item_index,
+                parent_item_index: item.parent_item_index,
+                depth: item.depth,
+            }),
+            _ => None,
+        };
+
+        match hovered_item {
+            Some(item) if item.depth == 0 => {
+                state.open_submenu = (item.enabled && item.has_submenu).then_some(item.item_index);
+            }
+            Some(_) => {}
+            None => state.open_submenu = None,
+        }
+
+        let item = hovered_item.filter(|_| input.mouse_pressed)?;
+        if !item.enabled || item.has_submenu {
+            return None;
+        }
+        state.open_submenu = None;
+        item.action_id
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct MenuTabLayout {
     pub menu_index: usize,
@@ -3238,3 +4957,698 @@ fn quad_from_pixels(
         color,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(input: &mut GuiInput, key: KeyCode) {
+        input.handle_event(&Event::key_event(EventType::Pressed, key));
+    }
+
+    fn release(input: &mut GuiInput, key: KeyCode) {
+        input.handle_event(&Event::key_event(EventType::Released, key));
+    }
+
+    fn panel_options(
+        cursor: [f32; 2],
+        mouse_pressed: bool,
+        background: [f32; 4],
+    ) -> PanelRenderOptions {
+        PanelRenderOptions {
+            viewport: [800.0, 600.0],
+            layer: GuiLayer::World,
+            interaction: PanelInteraction {
+                cursor,
+                mouse_pressed,
+                mouse_down: mouse_pressed,
+            },
+            metrics: PanelMetrics::default(),
+            colors: PanelColors {
+                background,
+                ..PanelColors::default()
+            },
+            allow_close: false,
+            allow_minimize: false,
+            show_shadow: false,
+            show_outline: false,
+        }
+    }
+
+    fn first_vertex_with_color(frame: &GuiFrame, color: [f32; 4]) -> usize {
+        frame
+            .batches
+            .iter()
+            .flat_map(|batch| batch.mesh.vertices.iter())
+            .position(|vertex| vertex.color == color)
+            .expect("panel background drawn")
+    }
+
+    #[test]
+    fn clicking_lower_panel_raises_it_above_top_panel() {
+        const LOWER: [f32; 4] = [1.0, 0.0, 0.0, 1.0];
+        const UPPER: [f32; 4] = [0.0, 1.0, 0.0, 1.0];
+        let lower = Panel::new("Lower");
+        let upper = Panel::new("Upper");
+        let mut lower_state = PanelState::new([0.0, 0.0], [300.0, 200.0]);
+        let mut upper_state = PanelState::new([200.0, 100.0], [300.0, 200.0]);
+        let mut gui = GuiContext::new();
+
+        gui.submit_panel(
+            &lower,
+            &mut lower_state,
+            &panel_options([0.0, 0.0], false, LOWER),
+        );
+        gui.submit_panel(
+            &upper,
+            &mut upper_state,
+            &panel_options([0.0, 0.0], false, UPPER),
+        );
+        let frame = gui.build_frame();
+        assert!(first_vertex_with_color(&frame, LOWER) < first_vertex_with_color(&frame, UPPER));
+
+        let click = [50.0, 150.0];
+        gui.submit_panel(&lower, &mut lower_state, &panel_options(click, true, LOWER));
+        gui.submit_panel(&upper, &mut upper_state, &panel_options(click, true, UPPER));
+        let frame = gui.build_frame();
+        assert!(first_vertex_with_color(&frame, UPPER) < first_vertex_with_color(&frame, LOWER));
+        assert_eq!(gui.panel_order(), &[upper.id, lower.id]);
+    }
+
+    #[test]
+    fn tab_cycles_focus_through_registered_widgets() {
+        let mut input = GuiInput::default();
+        let ids = [GuiId::new(1), GuiId::new(2), GuiId::new(3)];
+        let mut visited = Vec::new();
+
+        for _ in 0..4 {
+            input.begin_frame();
+            for id in ids {
+                input.register_focusable(id);
+            }
+            press(&mut input, KeyCode::Tab);
+            release(&mut input, KeyCode::Tab);
+            visited.push(input.focused().map(|id| id.value()));
+        }
+
+        assert_eq!(visited, vec![Some(1), Some(2), Some(3), Some(1)]);
+
+        press(&mut input, KeyCode::Shift);
+        press(&mut input, KeyCode::Tab);
+        assert_eq!(input.focused(), Some(ids[2]));
+    }
+
+    #[test]
+    fn submit_image_batches_by_texture_with_requested_uvs() {
+        let mut gui = GuiContext::new();
+        let rect = GuiClipRect::from_min_max([-0.5, 0.5], [0.5, -0.5]);
+        let uv = GuiClipRect::from_min_max([0.25, 0.5], [0.75, 1.0]);
+        gui.submit_image(GuiLayer::Overlay, 7, rect, uv, [1.0; 4]);
+        gui.submit_image(GuiLayer::Overlay, 9, rect, GuiClipRect::FULL_UV, [1.0; 4]);
+
+        let frame = gui.build_frame();
+        assert_eq!(frame.batches.len(), 2);
+
+        let image = &frame.batches[0];
+        assert_eq!(image.batch.texture_id, Some(7));
+        let uvs: Vec<[f32; 2]> = image.mesh.vertices.iter().map(|v| v.uv).collect();
+        assert_eq!(
+            uvs,
+            vec![[0.25, 0.5], [0.75, 0.5], [0.75, 1.0], [0.25, 1.0]]
+        );
+
+        let full = &frame.batches[1];
+        assert_eq!(full.batch.texture_id, Some(9));
+        let uvs: Vec<[f32; 2]> = full.mesh.vertices.iter().map(|v| v.uv).collect();
+        assert_eq!(uvs, vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+    }
+
+    fn tab_bar_options() -> TabBarRenderOptions {
+        TabBarRenderOptions {
+            viewport: [800.0, 600.0],
+            position: [0.0, 0.0],
+            width: 800.0,
+            layer: GuiLayer::Overlay,
+            metrics: TabBarMetrics::default(),
+            colors: TabBarColors::default(),
+        }
+    }
+
+    /// Press and release the mouse at `point` over two frames, returning the
+    /// layout from the release frame.
+    fn click_tab_bar(
+        gui: &mut GuiContext,
+        input: &mut GuiInput,
+        tabs: &[TabInfo],
+        active: &mut usize,
+        point: [f32; 2],
+    ) -> TabBarLayout {
+        let id = GuiId::new(42);
+        input.begin_frame();
+        input.cursor = Vec2::from(point);
+        input.mouse_pressed = true;
+        input.mouse_down = true;
+        gui.submit_tab_bar(id, tabs, active, input, &tab_bar_options());
+
+        input.begin_frame();
+        input.mouse_down = false;
+        input.mouse_released = true;
+        gui.submit_tab_bar(id, tabs, active, input, &tab_bar_options())
+    }
+
+    fn rect_center(rect: MenuRect) -> [f32; 2] {
+        [
+            (rect.min[0] + rect.max[0]) * 0.5,
+            (rect.min[1] + rect.max[1]) * 0.5,
+        ]
+    }
+
+    #[test]
+    fn clicking_a_tab_selects_it() {
+        let mut gui = GuiContext::new();
+        let mut input = GuiInput::default();
+        let tabs = [
+            TabInfo::new("Scene", false),
+            TabInfo::new("Assets", false),
+            TabInfo::new("Console", false),
+        ];
+        let mut active = 0;
+        let layout = gui.submit_tab_bar(
+            GuiId::new(42),
+            &tabs,
+            &mut active,
+            &mut input,
+            &tab_bar_options(),
+        );
+
+        let target = rect_center(layout.tabs[2].tab_rect);
+        let layout = click_tab_bar(&mut gui, &mut input, &tabs, &mut active, target);
+        assert_eq!(active, 2);
+        assert_eq!(layout.active, 2);
+        assert_eq!(layout.close_requested, None);
+    }
+
+    #[test]
+    fn clicking_a_close_button_reports_its_tab() {
+        let mut gui = GuiContext::new();
+        let mut input = GuiInput::default();
+        let tabs = [
+            TabInfo::new("Scene", true),
+            TabInfo::new("Assets", true),
+            TabInfo::new("Console", false),
+        ];
+        let mut active = 0;
+        let layout = gui.submit_tab_bar(
+            GuiId::new(42),
+            &tabs,
+            &mut active,
+            &mut input,
+            &tab_bar_options(),
+        );
+        assert!(layout.tabs[2].close_rect.is_none());
+
+        let target = rect_center(layout.tabs[1].close_rect.unwrap());
+        let layout = click_tab_bar(&mut gui, &mut input, &tabs, &mut active, target);
+        assert_eq!(layout.close_requested, Some(1));
+        assert_eq!(active, 0);
+    }
+
+    #[test]
+    fn progress_bar_fills_its_fraction_of_the_track() {
+        let mut gui = GuiContext::new();
+        let options = ProgressRenderOptions {
+            viewport: [800.0, 600.0],
+            layer: GuiLayer::Overlay,
+            show_percentage: true,
+            metrics: ProgressMetrics::default(),
+            colors: ProgressColors::default(),
+        };
+        let track = MenuRect::from_position_size([20.0, 40.0], [200.0, 16.0]);
+
+        let half = gui.submit_progress_bar(track, 0.5, &options);
+        assert_eq!(half.fill_rect.min, track.min);
+        assert_eq!(half.fill_rect.max, [120.0, 56.0]);
+
+        let over = gui.submit_progress_bar(track, 1.7, &options);
+        assert_eq!(over.fraction, 1.0);
+        assert_eq!(over.fill_rect.max, track.max);
+
+        let under = gui.submit_progress_bar(track, -0.3, &options);
+        assert_eq!(under.fraction, 0.0);
+        assert_eq!(under.fill_rect.max[0], track.min[0]);
+    }
+
+    #[test]
+    fn dragging_the_value_axis_darkens_the_color() {
+        let mut gui = GuiContext::new();
+        let mut input = GuiInput::default();
+        let options = ColorPickerRenderOptions {
+            viewport: [800.0, 600.0],
+            position: [10.0, 10.0],
+            layer: GuiLayer::Overlay,
+            metrics: ColorPickerMetrics::default(),
+            colors: ColorPickerColors::default(),
+        };
+        let square = color_picker_rects(&options).square;
+        let id = GuiId::new(7);
+        let mut color = [0.0, 1.0, 0.0, 1.0];
+
+        input.begin_frame();
+        input.cursor = Vec2::new(square.max[0], square.min[1]);
+        input.mouse_pressed = true;
+        input.mouse_down = true;
+        let pressed = gui.submit_color_picker(id, &mut color, &mut input, &options);
+        assert!(pressed.active);
+        assert!((color[1] - 1.0).abs() < 1.0e-4);
+
+        input.begin_frame();
+        input.cursor = Vec2::new(square.max[0], (square.min[1] + square.max[1]) * 0.5);
+        gui.submit_color_picker(id, &mut color, &mut input, &options);
+        assert!((color[1] - 0.5).abs() < 1.0e-4);
+        assert!(color[0].abs() < 1.0e-4 && color[2].abs() < 1.0e-4);
+
+        // Dragging past the square clamps to black without losing the hue.
+        input.begin_frame();
+        input.cursor = Vec2::new(square.max[0], square.max[1] + 50.0);
+        gui.submit_color_picker(id, &mut color, &mut input, &options);
+        assert_eq!(&color[..3], &[0.0, 0.0, 0.0]);
+
+        input.begin_frame();
+        input.cursor = Vec2::new(square.max[0], square.min[1]);
+        gui.submit_color_picker(id, &mut color, &mut input, &options);
+        assert!((color[1] - 1.0).abs() < 1.0e-4);
+        assert!(color[0].abs() < 1.0e-4 && color[2].abs() < 1.0e-4);
+        assert_eq!(color[3], 1.0);
+    }
+
+    #[test]
+    fn rgb_round_trips_through_hsv() {
+        let samples = [
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 1.0],
+            [0.5, 0.5, 0.5],
+            [1.0, 0.0, 0.0],
+            [0.2, 0.8, 0.4],
+            [0.1, 0.3, 0.9],
+            [0.9, 0.1, 0.7],
+            [0.95, 0.9, 0.05],
+        ];
+        for rgb in samples {
+            let back = hsv_to_rgb(rgb_to_hsv(rgb));
+            for channel in 0..3 {
+                assert!(
+                    (back[channel] - rgb[channel]).abs() < 1.0e-5,
+                    "{rgb:?} came back as {back:?}"
+                );
+            }
+        }
+    }
+
+    fn tree_options(position: [f32; 2], depth: u32) -> TreeRenderOptions {
+        TreeRenderOptions {
+            viewport: [800.0, 600.0],
+            position,
+            width: 240.0,
+            depth,
+            layer: GuiLayer::Overlay,
+            metrics: TreeMetrics::default(),
+            colors: TreeColors::default(),
+        }
+    }
+
+    /// Submit a parent and one child row for a frame, returning both responses.
+    fn submit_tree(
+        gui: &mut GuiContext,
+        input: &mut GuiInput,
+        states: &mut [TreeNodeState; 2],
+    ) -> (TreeNodeResponse, Option<TreeNodeResponse>) {
+        let [root_state, child_state] = states;
+        let root = gui.submit_tree_node(
+            GuiId::new(1),
+            "World",
+            root_state,
+            input,
+            &tree_options([0.0, 0.0], 0),
+        );
+        let child = root.open.then(|| {
+            gui.submit_tree_node(
+                GuiId::new(2),
+                "Camera",
+                child_state,
+                input,
+                &tree_options([0.0, root.row_rect.max[1]], 1),
+            )
+        });
+        (root, child)
+    }
+
+    fn click_tree(
+        gui: &mut GuiContext,
+        input: &mut GuiInput,
+        states: &mut [TreeNodeState; 2],
+        point: [f32; 2],
+    ) -> (TreeNodeResponse, Option<TreeNodeResponse>) {
+        input.begin_frame();
+        input.cursor = Vec2::from(point);
+        input.mouse_pressed = true;
+        input.mouse_down = true;
+        submit_tree(gui, input, states);
+
+        input.begin_frame();
+        input.mouse_down = false;
+        input.mouse_released = true;
+        submit_tree(gui, input, states)
+    }
+
+    #[test]
+    fn clicking_the_tree_arrow_toggles_open() {
+        let mut gui = GuiContext::new();
+        let mut input = GuiInput::default();
+        let mut states = [
+            TreeNodeState::default(),
+            TreeNodeState {
+                leaf: true,
+                ..Default::default()
+            },
+        ];
+        let (root, child) = submit_tree(&mut gui, &mut input, &mut states);
+        assert!(!root.open);
+        assert!(child.is_none());
+
+        let arrow = rect_center(root.arrow_rect);
+        let (root, child) = click_tree(&mut gui, &mut input, &mut states, arrow);
+        assert!(root.toggled);
+        assert!(root.open && states[0].open);
+        assert_eq!(root.selected, None);
+        let child = child.expect("open node should show its child");
+        assert!(child.arrow_rect.min[0] > root.arrow_rect.min[0]);
+
+        let (root, _) = click_tree(&mut gui, &mut input, &mut states, arrow);
+        assert!(!root.open && !states[0].open);
+    }
+
+    #[test]
+    fn clicking_a_tree_label_reports_its_id() {
+        let mut gui = GuiContext::new();
+        let mut input = GuiInput::default();
+        let mut states = [
+            TreeNodeState {
+                open: true,
+                ..Default::default()
+            },
+            TreeNodeState {
+                leaf: true,
+                ..Default::default()
+            },
+        ];
+        let (_, child) = submit_tree(&mut gui, &mut input, &mut states);
+        let child_row = child.unwrap().row_rect;
+
+        let (root, child) = click_tree(
+            &mut gui,
+            &mut input,
+            &mut states,
+            [child_row.min[0] + 120.0, rect_center(child_row)[1]],
+        );
+        assert_eq!(root.selected, None);
+        assert_eq!(child.unwrap().selected, Some(GuiId::new(2)));
+        assert!(states[0].open);
+    }
+
+    #[test]
+    fn dragging_onto_a_drop_target_delivers_the_payload() {
+        let mut input = GuiInput::default();
+        let source_id = GuiId::new(10);
+        let target_id = GuiId::new(11);
+        let source = MenuRect::from_position_size([10.0, 10.0], [80.0, 20.0]);
+        let target = MenuRect::from_position_size([200.0, 100.0], [120.0, 60.0]);
+        let payload = 0xa55e7;
+
+        let frame = |input: &mut GuiInput, cursor: [f32; 2]| {
+            input.cursor = Vec2::from(cursor);
+            input.interact(source_id, source.contains(cursor));
+            let dragging = input.begin_drag(source_id, payload);
+            (dragging, input.drop_target(target_id, target))
+        };
+
+        input.begin_frame();
+        input.mouse_pressed = true;
+        input.mouse_down = true;
+        assert_eq!(frame(&mut input, [20.0, 20.0]), (false, None));
+
+        // Jitter below the threshold is still a click, not a drag.
+        input.begin_frame();
+        assert_eq!(frame(&mut input, [22.0, 21.0]), (false, None));
+        assert!(!input.is_dragging());
+
+        input.begin_frame();
+        assert_eq!(frame(&mut input, [120.0, 60.0]), (true, None));
+        assert_eq!(input.drag_payload(), Some(payload));
+
+        input.begin_frame();
+        assert_eq!(frame(&mut input, [250.0, 120.0]), (true, None));
+
+        input.begin_frame();
+        input.mouse_down = false;
+        input.mouse_released = true;
+        assert_eq!(frame(&mut input, [250.0, 120.0]).1, Some(payload));
+        assert!(!input.is_dragging());
+
+        input.begin_frame();
+        assert!(input.drag_payload().is_none());
+    }
+
+    #[test]
+    fn ctrl_v_pastes_staged_clipboard_at_caret() {
+        let mut input = GuiInput::default();
+        let mut edit = TextEditState::new("hello world");
+        edit.set_caret(5);
+        input.set_clipboard(", big".to_string());
+
+        input.begin_frame();
+        press(&mut input, KeyCode::Control);
+        press(&mut input, KeyCode::V);
+
+        assert!(input.take_paste_request());
+        assert!(!input.take_paste_request());
+        assert!(edit.handle_input(&mut input));
+        assert_eq!(edit.text, "hello, big world");
+        assert_eq!(edit.caret(), 10);
+    }
+
+    #[test]
+    fn overlapping_widgets_only_hover_the_later_one() {
+        let mut input = GuiInput::default();
+        let back = GuiId::new(1);
+        let front = GuiId::new(2);
+
+        for _ in 0..2 {
+            input.begin_frame();
+            input.interact(back, true);
+            input.interact(front, true);
+        }
+
+        input.begin_frame();
+        input.mouse_pressed = true;
+        input.mouse_down = true;
+        let back_state = input.interact(back, true);
+        let front_state = input.interact(front, true);
+        assert!(!back_state.hovered && !back_state.active);
+        assert!(front_state.hovered && front_state.active);
+        assert_eq!(input.hot(), Some(front));
+
+        // Once the front widget is gone, the back one hovers again.
+        input.begin_frame();
+        input.mouse_pressed = false;
+        input.mouse_down = false;
+        input.interact(back, true);
+        input.begin_frame();
+        assert!(input.interact(back, true).hovered);
+    }
+
+    #[test]
+    fn first_overlapping_press_only_activates_the_later_widget() {
+        let mut input = GuiInput::default();
+        let back = GuiId::new(1);
+        let front = GuiId::new(2);
+
+        input.begin_frame();
+        input.mouse_pressed = true;
+        input.mouse_down = true;
+        input.interact(back, true);
+        input.interact(front, true);
+        input.end_frame();
+        assert_eq!(input.hot(), Some(front));
+        assert_eq!(input.active(), Some(front));
+        assert_eq!(input.focused(), Some(front));
+
+        input.begin_frame();
+        input.mouse_down = false;
+        input.mouse_released = true;
+        let back_state = input.interact(back, true);
+        let front_state = input.interact(front, true);
+        assert!(!back_state.hovered && !back_state.clicked);
+        assert!(front_state.clicked);
+
+        // The back widget is hot again as soon as the front one is gone.
+        input.begin_frame();
+        input.interact(back, true);
+        input.end_frame();
+        assert_eq!(input.hot(), Some(back));
+    }
+
+    #[test]
+    fn ctrl_press_and_release_toggles_modifier() {
+        let mut input = GuiInput::default();
+        assert_eq!(input.modifiers(), Modifiers::default());
+
+        press(&mut input, KeyCode::Control);
+        assert!(input.modifiers().ctrl);
+        assert!(!input.modifiers().shift);
+
+        release(&mut input, KeyCode::Control);
+        assert!(!input.modifiers().ctrl);
+    }
+
+    #[test]
+    fn ctrl_c_and_ctrl_x_copy_selection() {
+        let mut input = GuiInput::default();
+        let mut edit = TextEditState::new("hello world");
+        edit.select(6..11);
+
+        input.begin_frame();
+        press(&mut input, KeyCode::Control);
+        press(&mut input, KeyCode::C);
+        assert!(!edit.handle_input(&mut input));
+        assert_eq!(input.take_copied_text().as_deref(), Some("world"));
+        assert_eq!(edit.text, "hello world");
+
+        input.begin_frame();
+        press(&mut input, KeyCode::X);
+        assert!(edit.handle_input(&mut input));
+        assert_eq!(input.take_copied_text().as_deref(), Some("world"));
+        assert_eq!(edit.text, "hello ");
+
+        input.begin_frame();
+        release(&mut input, KeyCode::Control);
+        press(&mut input, KeyCode::V);
+        assert!(edit.handle_input(&mut input));
+        assert_eq!(edit.text, "hello v");
+    }
+
+    #[test]
+    fn text_under_clip_stack_carries_intersected_clip() {
+        let mut ctx = GuiContext::new();
+        ctx.push_clip_rect(GuiClipRect::from_min_max([0.0, 0.0], [100.0, 100.0]));
+        ctx.submit_text(GuiTextDraw {
+            text: "scrolled".to_string(),
+            position: [10.0, 90.0],
+            color: [1.0; 4],
+            scale: 1.0,
+            clip_rect: Some(GuiClipRect::from_min_max([50.0, 50.0], [150.0, 150.0])),
+        });
+        ctx.pop_clip_rect();
+        ctx.submit_text(GuiTextDraw {
+            text: "outside".to_string(),
+            position: [0.0, 0.0],
+            color: [1.0; 4],
+            scale: 1.0,
+            clip_rect: None,
+        });
+
+        let frame = ctx.build_frame();
+        assert_eq!(
+            frame.text_draws[0].clip_rect,
+            Some(GuiClipRect::from_min_max([50.0, 50.0], [100.0, 100.0]))
+        );
+        assert_eq!(frame.text_draws[1].clip_rect, None);
+    }
+
+    fn file_menu_items() -> Vec<MenuItem> {
+        vec![
+            MenuItem {
+                action_id: Some(1),
+                ..MenuItem::new("Open")
+            },
+            MenuItem {
+                enabled: false,
+                action_id: Some(2),
+                ..MenuItem::new("Save")
+            },
+        ]
+    }
+
+    fn click_at(point: [f32; 2]) -> GuiInput {
+        let mut input = GuiInput::default();
+        input.cursor = Vec2::from(point);
+        input.mouse_pressed = true;
+        input.mouse_down = true;
+        input
+    }
+
+    #[test]
+    fn clicking_a_menu_bar_item_returns_its_action() {
+        let mut ctx = GuiContext::new();
+        let menu_bar = MenuBar {
+            menus: vec![Menu {
+                label: "File".to_string(),
+                items: file_menu_items(),
+            }],
+        };
+        let mut state = MenuBarState {
+            open_menu: Some(0),
+            ..Default::default()
+        };
+        let layout = menu_bar.submit_to_draw_list(
+            &mut ctx,
+            &MenuBarRenderOptions {
+                viewport: [800.0, 600.0],
+                position: [0.0, 0.0],
+                layer: GuiLayer::Overlay,
+                metrics: MenuLayoutMetrics::default(),
+                colors: MenuColors::default(),
+                state,
+            },
+        );
+
+        let disabled = click_at(rect_center(layout.item_rects[1].rect));
+        assert_eq!(layout.clicked_action(&disabled, &mut state), None);
+        assert_eq!(state.open_menu, Some(0));
+        assert_eq!(state.hovered_item, None);
+
+        let enabled = click_at(rect_center(layout.item_rects[0].rect));
+        assert_eq!(layout.clicked_action(&enabled, &mut state), Some(1));
+        assert_eq!(state.open_menu, None);
+    }
+
+    #[test]
+    fn clicking_a_popup_item_returns_its_action() {
+        let mut ctx = GuiContext::new();
+        let popup = MenuPopup {
+            items: file_menu_items(),
+        };
+        let mut state = MenuPopupState::default();
+        let layout = popup.submit_to_draw_list(
+            &mut ctx,
+            &MenuPopupRenderOptions {
+                viewport: [800.0, 600.0],
+                anchor: MenuPopupAnchor::Position([100.0, 100.0]),
+                layer: GuiLayer::Overlay,
+                metrics: MenuLayoutMetrics::default(),
+                colors: MenuColors::default(),
+                state,
+            },
+        );
+
+        let disabled = click_at(rect_center(layout.item_rects[1].rect));
+        assert_eq!(layout.clicked_action(&disabled, &mut state), None);
+
+        let mut hover = click_at(rect_center(layout.item_rects[0].rect));
+        hover.mouse_pressed = false;
+        assert_eq!(layout.clicked_action(&hover, &mut state), None);
+        assert_eq!(state.hovered_item.map(|item| item.item_index), Some(0));
+
+        let enabled = click_at(rect_center(layout.item_rects[0].rect));
+        assert_eq!(layout.clicked_action(&enabled, &mut state), Some(1));
+    }
+}
@@ -11,16 +11,19 @@ use dashi::execution::CommandRing;
 use dashi::utils::Pool;
 use dashi::{
     AspectMask, Buffer, BufferInfo, BufferUsage, BufferView, CommandQueueInfo2, CommandStream,
-    Context, Display as DashiDisplay, DisplayInfo as DashiDisplayInfo, FRect2D, Format, Handle,
-    ImageInfo, ImageView, ImageViewType, MemoryVisibility, QueueType, Rect2D, SampleCount,
+    Context, Display as DashiDisplay, DisplayInfo as DashiDisplayInfo, FRect2D, Filter, Format,
+    Handle, ImageInfo, ImageView, ImageViewType, MemoryVisibility, QueueType, Rect2D, SampleCount,
     SubmitInfo, SubresourceRange, Viewport,
 };
 pub use furikake::types::AnimationState as FAnimationState;
 pub use furikake::types::{Camera, Light, Material};
-use glam::{Mat3, Mat4, Vec2, Vec3};
-use meshi_ffi_structs::{EventCallbackInfo, FFIImage, LightFlags, LightInfo, LightType, event};
+use glam::{Mat3, Mat4, Vec2, Vec3, Vec4};
+use meshi_ffi_structs::{
+    EventCallbackInfo, FFIImage, LightError, LightFalloff, LightFlags, LightInfo, LightType, event,
+};
 use meshi_utils::MeshiError;
 pub use noren::*;
+use render::debug_draw::{DebugLines, PHYSICS_DEBUG_COLOR};
 use render::deferred::DeferredRenderer;
 pub use render::environment::clouds::CloudRenderer;
 pub use render::environment::ocean::OceanFrameSettings;
@@ -49,6 +52,13 @@ struct CPUImageOutput {
     pixels: Vec<u8>,
 }
 
+/// Images backing [`RenderEngine::render_to_image`]. Renderer output is
+/// resolved at the renderer's resolution, then scaled into `output`.
+struct OffscreenTarget {
+    resolve: ImageView,
+    output: CPUImageOutput,
+}
+
 enum DisplayImpl {
     Window(Option<Box<DashiDisplay>>),
     CPUImage(CPUImageOutput),
@@ -57,11 +67,126 @@ enum DisplayImpl {
 pub struct Display {
     raw: DisplayImpl,
     scene: Handle<Camera>,
+    size: [u32; 2],
+    fullscreen: bool,
+    /// Size to restore when leaving fullscreen.
+    windowed_size: [u32; 2],
+}
+
+/// Perspective parameters kept so a camera's aspect can follow its display.
+#[derive(Clone, Copy)]
+struct CameraPerspective {
+    fov_y_radians: f32,
+    near: f32,
+    far: f32,
+}
+
+pub struct Sprite {
+    info: SpriteInfo,
+}
+
+fn apply_cursor_capture(window: &winit::window::Window, capture: bool) {
+    if let Err(err) = window.set_cursor_grab(capture) {
+        warn!("Failed to change cursor grab: {err:?}");
+    }
+    window.set_cursor_visible(!capture);
+    if capture {
+        // Start from the center so confining platforms have room to move.
+        let size = window.inner_size();
+        let center = winit::dpi::PhysicalPosition::new(size.width / 2, size.height / 2);
+        if let Err(err) = window.set_cursor_position(center) {
+            warn!("Failed to recenter cursor: {err:?}");
+        }
+    }
+}
+
+/// Raw mouse motion is only forwarded while the cursor is captured.
+fn is_uncaptured_motion(event: &event::Event, captured: bool) -> bool {
+    !captured && event.source() == event::EventSource::MouseMotion
+}
+
+fn make_color_view(
+    ctx: &mut Context,
+    debug_name: &str,
+    size: [u32; 2],
+    format: Format,
+) -> Result<ImageView, MeshiError> {
+    let img = ctx.make_image(&ImageInfo {
+        debug_name,
+        dim: [size[0], size[1], 1],
+        layers: 1,
+        format,
+        mip_levels: 1,
+        samples: SampleCount::S1,
+        initial_data: None,
+        ..Default::default()
+    })?;
+    Ok(ImageView {
+        img,
+        range: SubresourceRange::default(),
+        aspect: AspectMask::Color,
+        view_type: ImageViewType::Type2D,
+    })
+}
+
+/// A color image plus the CPU-visible buffer its pixels are copied into.
+fn make_cpu_image_output(
+    ctx: &mut Context,
+    debug_name: &str,
+    size: [u32; 2],
+    format: Format,
+) -> Result<CPUImageOutput, MeshiError> {
+    let img = make_color_view(ctx, &format!("{debug_name} Image"), size, format)?;
+    let byte_size = size[0] as usize * size[1] as usize * 4;
+    let staging = ctx.make_buffer(&BufferInfo {
+        debug_name: &format!("{debug_name} Staging"),
+        byte_size: byte_size as u32,
+        visibility: MemoryVisibility::CpuAndGpu,
+        usage: BufferUsage::ALL,
+        initial_data: None,
+    })?;
+    Ok(CPUImageOutput {
+        img,
+        staging,
+        width: size[0],
+        height: size[1],
+        format,
+        pixels: vec![0; byte_size],
+    })
+}
+
+/// Copy the staging buffer of `output` into its pixel storage. The GPU copy
+/// must already have completed.
+fn read_back_cpu_output(ctx: &mut Context, output: &mut CPUImageOutput) -> Option<FFIImage> {
+    let mapped = match ctx.map_buffer::<u8>(BufferView::new(output.staging)) {
+        Ok(mapped) => mapped,
+        Err(err) => {
+            warn!("Failed to map CPU display buffer: {err:?}");
+            return None;
+        }
+    };
+
+    if output.pixels.len() != mapped.len() {
+        output.pixels.resize(mapped.len(), 0);
+    }
+    output.pixels.copy_from_slice(mapped);
+
+    if let Err(err) = ctx.unmap_buffer(output.staging) {
+        warn!("Failed to unmap CPU display buffer: {err:?}");
+    }
+
+    Some(FFIImage {
+        width: output.width,
+        height: output.height,
+        format: output.format as u32,
+        pixels: output.pixels.as_ptr(),
+    })
 }
 
 pub struct RenderEngine {
     renderer: Box<dyn Renderer>,
     displays: Pool<Display>,
+    sprites: Pool<Sprite>,
     event_cb: Option<EventCallbackInfo>,
     blit_queue: CommandRing,
     event_loop: Option<winit::event_loop::EventLoop<()>>,
@@ -78,9 +203,22 @@ pub struct RenderEngine {
     skybox_settings: SkyboxFrameSettings,
     ocean_settings: OceanFrameSettings,
     cloud_settings: CloudSettings,
+    shadow_filter: ShadowFilter,
+    fog_settings: FogSettings,
+    bloom_settings: BloomSettings,
+    tonemap_settings: TonemapSettings,
+    debug_lines: DebugLines,
     terrain_settings: TerrainRenderSettings,
     light_cache: Vec<CachedLightEntry>,
     spot_shadow_light: Option<render::SpotShadowLight>,
+    headless: bool,
+    primary_display: Option<Handle<Display>>,
+    mouse_captured: bool,
+    queued_events: Vec<(Handle<Display>, event::Event)>,
+    cameras: HashSet<Handle<Camera>>,
+    camera_perspectives: HashMap<Handle<Camera>, CameraPerspective>,
+    offscreen: Option<OffscreenTarget>,
+    animation_callbacks: Vec<AnimationCallbackEntry>,
 }
 
 #[derive(Clone, Debug)]
@@ -112,21 +250,38 @@ struct CachedLightEntry {
     name: String,
 }
 
+struct AnimationCallbackEntry {
+    object: Handle<RenderObject>,
+    callback: AnimationCallback,
+    user_data: *mut c_void,
+}
+
 impl RenderEngine {
-    fn refresh_spot_shadow_light(&mut self) {
-        let mut selected: Option<render::SpotShadowLight> = None;
-        for entry in &self.light_cache {
-            let info = entry.info;
-            if info.ty == LightType::Spot && (info.flags & LightFlags::CASTS_SHADOWS.bits()) != 0 {
-                selected = Some(render::SpotShadowLight {
-                    handle: entry.handle,
-                    info,
-                });
-                break;
-            }
-        }
-        self.spot_shadow_light = selected;
-        self.renderer.set_spot_shadow_light(selected);
+    /// Pick the first shadow-casting spot and point light for the local
+    /// shadow maps.
+    fn refresh_shadow_lights(&mut self) {
+        let casts_shadows = |entry: &&CachedLightEntry, ty: LightType| {
+            entry.info.ty == ty && (entry.info.flags & LightFlags::CASTS_SHADOWS.bits()) != 0
+        };
+        let spot = self
+            .light_cache
+            .iter()
+            .find(|entry| casts_shadows(entry, LightType::Spot))
+            .map(|entry| render::SpotShadowLight {
+                handle: entry.handle,
+                info: entry.info,
+            });
+        let point = self
+            .light_cache
+            .iter()
+            .find(|entry| casts_shadows(entry, LightType::Point))
+            .map(|entry| render::PointShadowLight {
+                handle: entry.handle,
+                info: entry.info,
+            });
+        self.spot_shadow_light = spot;
+        self.renderer.set_spot_shadow_light(spot);
+        self.renderer.set_point_shadow_light(point);
     }
 
     fn update_cached_light_info(
@@ -213,6 +368,7 @@ impl RenderEngine {
             },
             sample_count,
             shadow_cascades: info.shadow_cascades,
+            anisotropy: info.anisotropy,
         };
 
         let renderer_select = info.renderer;
@@ -226,14 +382,11 @@ impl RenderEngine {
         } else {
             Some(winit::event_loop::EventLoop::new())
         };
-        let blit_queue = renderer
-            .context()
-            .make_command_ring(&CommandQueueInfo2 {
-                debug_name: "[BLIT]",
-                parent: None,
-                queue_type: QueueType::Graphics,
-            })
-            .expect("Failed to make render queue");
+        let blit_queue = renderer.context().make_command_ring(&CommandQueueInfo2 {
+            debug_name: "[BLIT]",
+            parent: None,
+            queue_type: QueueType::Graphics,
+        })?;
 
         let cloud_settings = renderer.cloud_settings();
         let terrain_settings = TerrainRenderSettings::default();
@@ -241,6 +394,7 @@ impl RenderEngine {
 
         Ok(Self {
             displays: Pool::new(8),
+            sprites: Pool::default(),
             renderer,
             db: None,
             event_cb: None,
@@ -258,9 +412,22 @@ impl RenderEngine {
             skybox_settings: SkyboxFrameSettings::default(),
             ocean_settings: OceanFrameSettings::default(),
             cloud_settings,
+            shadow_filter: ShadowFilter::default(),
+            fog_settings: FogSettings::default(),
+            bloom_settings: BloomSettings::default(),
+            tonemap_settings: TonemapSettings::default(),
+            debug_lines: DebugLines::default(),
             terrain_settings,
             light_cache: Vec::new(),
             spot_shadow_light: None,
+            headless: info.headless,
+            primary_display: None,
+            mouse_captured: false,
+            queued_events: Vec::new(),
+            cameras: HashSet::new(),
+            camera_perspectives: HashMap::new(),
+            offscreen: None,
+            animation_callbacks: Vec::new(),
         })
     }
 
@@ -268,6 +435,15 @@ impl RenderEngine {
         self.debug_mode
     }
 
+    pub fn headless(&self) -> bool {
+        self.headless
+    }
+
+    /// The first display registered with this engine, if any.
+    pub fn primary_display(&self) -> Option<Handle<Display>> {
+        self.primary_display
+    }
+
     pub fn set_debug_mode(&mut self, enabled: bool) {
         self.debug_mode = enabled;
     }
@@ -319,6 +495,19 @@ impl RenderEngine {
         self.renderer.set_ocean_settings(self.ocean_settings);
     }
 
+    /// Water surface height at world `(x, z)` for buoyancy queries, using the
+    /// Gerstner swell from the current ocean settings and the renderer's
+    /// configured cascades. `time` is the environment time in seconds.
+    /// Returns `0.0` while the ocean is disabled.
+    ///
+    /// Only the swell is evaluated; the FFT detail layered on top lives on the
+    /// GPU and is left out, so the result is the smooth mean surface.
+    pub fn ocean_height_at(&self, x: f32, z: f32, time: f32) -> f32 {
+        let patch_sizes = self.renderer.ocean_patch_sizes();
+        self.ocean_settings
+            .gerstner_height_at(patch_sizes, x, z, time)
+    }
+
     pub fn set_environment_lighting(&mut self, settings: EnvironmentLightingSettings) {
         self.sky_settings = settings.sky.clone();
         let sky_settings = self.sky_settings.clone();
@@ -347,8 +536,8 @@ impl RenderEngine {
             self.set_light_debug_name(sun_light, "Sun");
             self.set_light_debug_name(moon_light, "Moon");
         } else {
-            let sun_light = self.register_light(&sun_info);
-            let moon_light = self.register_light(&moon_info);
+            let sun_light = self.insert_light(&sun_info);
+            let moon_light = self.insert_light(&moon_info);
             self.set_light_debug_name(sun_light, "Sun");
             self.set_light_debug_name(moon_light, "Moon");
             self.environment_lighting = Some(EnvironmentLightingState {
@@ -365,9 +554,32 @@ impl RenderEngine {
         self.renderer.set_sky_settings(sky_settings);
     }
 
+    /// Direction, color, and intensity of the sky-driven sun light. The
+    /// direction points from the sun toward the scene.
+    pub fn resolved_sun_light(&self) -> (Vec3, Vec3, f32) {
+        resolved_celestial_lights(&self.resolved_environment_lighting())[0]
+    }
+
+    /// Direction, color, and intensity of the sky-driven moon light. The
+    /// direction points from the moon toward the scene.
+    pub fn resolved_moon_light(&self) -> (Vec3, Vec3, f32) {
+        resolved_celestial_lights(&self.resolved_environment_lighting())[1]
+    }
+
+    fn resolved_environment_lighting(&self) -> EnvironmentLightingSettings {
+        let mut settings = self
+            .environment_lighting
+            .as_ref()
+            .map(|state| state.settings.clone())
+            .unwrap_or_default();
+        settings.sky = self.sky_settings.clone();
+        settings
+    }
+
     pub fn shut_down(mut self) {
         info!("Shutting down render engine.");
         self.event_cb = None;
+        self.animation_callbacks.clear();
         self.event_loop = None;
 
         let mut displays = std::mem::take(&mut self.displays);
@@ -381,7 +593,14 @@ impl RenderEngine {
         self.renderer.shut_down();
     }
 
-    pub fn register_light(&mut self, info: &LightInfo) -> Handle<Light> {
+    /// Register a light after validating it. Directions are normalized before
+    /// the light is packed for the GPU.
+    pub fn register_light(&mut self, info: &LightInfo) -> Result<Handle<Light>, LightError> {
+        info.validate()?;
+        Ok(self.insert_light(&info.normalized()))
+    }
+
+    fn insert_light(&mut self, info: &LightInfo) -> Handle<Light> {
         let mut h = Handle::default();
 
         self.renderer
@@ -396,7 +615,7 @@ impl RenderEngine {
             .unwrap();
 
         self.update_cached_light_info(h, *info, Some(default_light_name(h)));
-        self.refresh_spot_shadow_light();
+        self.refresh_shadow_lights();
 
         h
     }
@@ -429,7 +648,7 @@ impl RenderEngine {
             .unwrap();
 
         self.update_cached_light_transform(handle, transform);
-        self.refresh_spot_shadow_light();
+        self.refresh_shadow_lights();
     }
 
     pub fn set_light_info(&mut self, handle: Handle<Light>, info: &LightInfo) {
@@ -447,7 +666,74 @@ impl RenderEngine {
             .unwrap();
 
         self.update_cached_light_info(handle, *info, None);
-        self.refresh_spot_shadow_light();
+        self.refresh_shadow_lights();
+    }
+
+    /// Move a light, re-packing it so the new position follows its light type.
+    pub fn set_light_position(&mut self, handle: Handle<Light>, position: Vec3) {
+        let Some(mut info) = self.light_info(handle) else {
+            return;
+        };
+
+        info.pos_x = position.x;
+        info.pos_y = position.y;
+        info.pos_z = position.z;
+        self.set_light_info(handle, &info);
+    }
+
+    /// Toggle [`LightFlags::CASTS_SHADOWS`] on a light. Only the first
+    /// shadow-casting spot and point light receive a shadow map.
+    pub fn set_light_casts_shadows(&mut self, handle: Handle<Light>, enabled: bool) {
+        let Some(mut info) = self.light_info(handle) else {
+            return;
+        };
+
+        let mut flags = LightFlags::from_bits_truncate(info.flags);
+        flags.set(LightFlags::CASTS_SHADOWS, enabled);
+        info.flags = flags.bits();
+        self.set_light_info(handle, &info);
+    }
+
+    /// Resolution of the cascade tiles, the spot shadow map and each point
+    /// shadow cube face. Clamped to `1..=4096`.
+    pub fn set_shadow_resolution(&mut self, resolution: u32) {
+        self.renderer.set_shadow_resolution(resolution);
+    }
+
+    pub fn shadow_filtering(&self) -> ShadowFilter {
+        self.shadow_filter
+    }
+
+    /// Filtering applied to shadow map lookups. Defaults to
+    /// [`ShadowFilterMode::Hard`], a single tap for crisp edges;
+    /// [`ShadowFilterMode::Pcf`] blends a `kernel_size` square of texels.
+    pub fn set_shadow_filtering(&mut self, filter: ShadowFilter) {
+        self.shadow_filter = filter;
+        self.renderer.set_shadow_filter(filter);
+    }
+
+    /// Anisotropy level the renderer's samplers were created with, after
+    /// clamping [`RenderEngineInfo::anisotropy`] to the device limit.
+    pub fn sampler_anisotropy(&self) -> u32 {
+        self.renderer.sampler_anisotropy()
+    }
+
+    /// The device's `maxSamplerAnisotropy`, or `1` when it lacks the
+    /// `samplerAnisotropy` feature.
+    pub fn sampler_anisotropy_limit(&self) -> u32 {
+        self.renderer.sampler_anisotropy_limit()
+    }
+
+    /// Spot and point shadow maps allocated for the most recently rendered view.
+    pub fn local_shadow_map_count(&self) -> usize {
+        self.renderer.local_shadow_map_count()
+    }
+
+    pub fn light_info(&self, handle: Handle<Light>) -> Option<LightInfo> {
+        self.light_cache
+            .iter()
+            .find(|entry| entry.handle == handle)
+            .map(|entry| entry.info)
     }
 
     pub fn release_light(&mut self, handle: Handle<Light>) {
@@ -466,7 +752,7 @@ impl RenderEngine {
             .unwrap();
 
         self.light_cache.retain(|entry| entry.handle != handle);
-        self.refresh_spot_shadow_light();
+        self.refresh_shadow_lights();
     }
 
     pub fn register_object(
@@ -492,6 +778,74 @@ impl RenderEngine {
         self.renderer.set_skinned_animation_state(handle, state);
     }
 
+    /// Crossfades a skinned object from its active clip to `to_clip` over
+    /// `blend_time` seconds. The target becomes the active clip once the
+    /// blend completes.
+    pub fn blend_skinned_animation(
+        &mut self,
+        handle: Handle<RenderObject>,
+        to_clip: u32,
+        blend_time: f32,
+    ) {
+        self.renderer
+            .blend_skinned_animation(handle, to_clip, blend_time);
+    }
+
+    /// Adds a named marker to one of a skinned object's clips. Animation
+    /// callbacks receive a [`AnimationEventKind::Marker`] event each time
+    /// playback of `clip_index` crosses `time_seconds`.
+    pub fn add_animation_marker(
+        &mut self,
+        handle: Handle<RenderObject>,
+        clip_index: u32,
+        name: &str,
+        time_seconds: f32,
+    ) {
+        self.renderer
+            .add_animation_marker(handle, clip_index, name, time_seconds);
+    }
+
+    /// Registers `callback` to receive playback events for a skinned object
+    /// during [`RenderEngine::update`]. Looping clips report each wrap and
+    /// non-looping clips report completion once, timed by the clip's own
+    /// length.
+    pub fn register_animation_callback(
+        &mut self,
+        handle: Handle<RenderObject>,
+        callback: AnimationCallback,
+        user_data: *mut c_void,
+    ) {
+        self.animation_callbacks.push(AnimationCallbackEntry {
+            object: handle,
+            callback,
+            user_data,
+        });
+    }
+
+    fn dispatch_animation_events(&mut self) {
+        let events = self.renderer.take_animation_events();
+        if self.animation_callbacks.is_empty() {
+            return;
+        }
+
+        for (object, pending) in events {
+            let event = AnimationEvent {
+                object,
+                kind: pending.kind,
+                clip_index: pending.clip_index,
+                marker: pending
+                    .marker
+                    .as_ref()
+                    .map_or(std::ptr::null(), |name| name.as_ptr()),
+            };
+            for entry in &self.animation_callbacks {
+                if entry.object == object {
+                    (entry.callback)(&event, entry.user_data);
+                }
+            }
+        }
+    }
+
     pub fn set_billboard_texture(&mut self, handle: Handle<RenderObject>, texture_id: u32) {
         self.renderer.set_billboard_texture(handle, texture_id);
     }
@@ -504,6 +858,11 @@ impl RenderEngine {
         self.renderer.set_billboard_material(handle, material);
     }
 
+    /// Point a [`BillboardType::TargetFacing`] billboard at `target`.
+    pub fn set_billboard_target(&mut self, handle: Handle<RenderObject>, target: Vec3) {
+        self.renderer.set_billboard_target(handle, target);
+    }
+
     pub fn cloud_settings(&self) -> CloudSettings {
         self.cloud_settings
     }
@@ -513,6 +872,33 @@ impl RenderEngine {
         self.renderer.set_cloud_settings(self.cloud_settings);
     }
 
+    pub fn fog(&self) -> FogSettings {
+        self.fog_settings
+    }
+
+    pub fn set_fog(&mut self, settings: FogSettings) {
+        self.fog_settings = settings;
+        self.renderer.set_fog(settings);
+    }
+
+    pub fn bloom(&self) -> BloomSettings {
+        self.bloom_settings
+    }
+
+    pub fn set_bloom(&mut self, settings: BloomSettings) {
+        self.bloom_settings = settings;
+        self.renderer.set_bloom(settings);
+    }
+
+    pub fn tonemap(&self) -> TonemapSettings {
+        self.tonemap_settings
+    }
+
+    pub fn set_tonemap(&mut self, settings: TonemapSettings) {
+        self.tonemap_settings = settings;
+        self.renderer.set_tonemap(settings);
+    }
+
     pub fn set_cloud_weather_map(&mut self, view: Option<ImageView>) {
         self.renderer.set_cloud_weather_map(view);
     }
@@ -535,6 +921,8 @@ impl RenderEngine {
     }
 
     pub fn release_object(&mut self, handle: Handle<RenderObject>) {
+        self.animation_callbacks
+            .retain(|entry| entry.object != handle);
         self.renderer.release_object(handle);
     }
 
@@ -562,6 +950,33 @@ impl RenderEngine {
         self.renderer.set_gui_visibility(handle, visible);
     }
 
+    /// Register a sprite drawn in the 2D overlay after the 3D scene.
+    pub fn register_sprite(&mut self, info: &SpriteInfo) -> Handle<Sprite> {
+        self.sprites
+            .insert(Sprite { info: info.clone() })
+            .unwrap_or_default()
+    }
+
+    pub fn set_sprite_info(&mut self, handle: Handle<Sprite>, info: &SpriteInfo) {
+        if let Some(sprite) = self.sprites.get_mut_ref(handle) {
+            sprite.info = info.clone();
+        }
+    }
+
+    /// Move and resize a sprite, in viewport pixels.
+    pub fn set_sprite_transform(&mut self, handle: Handle<Sprite>, position: Vec2, size: Vec2) {
+        if let Some(sprite) = self.sprites.get_mut_ref(handle) {
+            sprite.info.position = position;
+            sprite.info.size = size;
+        }
+    }
+
+    pub fn release_sprite(&mut self, handle: Handle<Sprite>) {
+        if self.sprites.get_ref(handle).is_some() {
+            self.sprites.release(handle);
+        }
+    }
+
     pub fn upload_gui_frame(&mut self, frame: gui::GuiFrame) {
         self.pending_gui_frame = Some(frame);
     }
@@ -574,68 +989,204 @@ impl RenderEngine {
         &mut self.gui_input
     }
 
+    /// Queue a line for this frame. Debug geometry is only collected in
+    /// debug mode and is cleared on every [`RenderEngine::update`].
+    pub fn debug_line(&mut self, a: Vec3, b: Vec3, color: Vec4) {
+        if self.debug_mode {
+            self.debug_lines.line(a, b, color);
+        }
+    }
+
+    pub fn debug_box(&mut self, min: Vec3, max: Vec3, color: Vec4) {
+        if self.debug_mode {
+            self.debug_lines.aabb(min, max, color);
+        }
+    }
+
+    pub fn debug_sphere(&mut self, center: Vec3, radius: f32, color: Vec4) {
+        if self.debug_mode {
+            self.debug_lines.sphere(center, radius, color);
+        }
+    }
+
+    /// Queue a wireframe of every rigid body's collision shape at its
+    /// current pose. Does nothing outside debug mode.
+    pub fn draw_physics_debug(&mut self, physics: &meshi_physics::PhysicsSimulation) {
+        if !self.debug_mode {
+            return;
+        }
+        for (shape, status) in physics.collision_shapes() {
+            self.debug_lines.collision_shape(
+                &shape,
+                status.position,
+                status.rotation,
+                PHYSICS_DEBUG_COLOR,
+            );
+        }
+    }
+
+    /// Number of debug line vertices queued for the next frame.
+    pub fn debug_vertex_count(&self) -> usize {
+        self.debug_lines.vertex_count()
+    }
+
     pub fn set_object_transform(&mut self, handle: Handle<RenderObject>, transform: &glam::Mat4) {
         self.renderer.set_object_transform(handle, transform);
     }
 
+    /// Rebinds `handle` to the material stored under `material_key` without
+    /// re-uploading its geometry. The previous material is kept on error.
+    pub fn set_object_material(
+        &mut self,
+        handle: Handle<RenderObject>,
+        material_key: &str,
+    ) -> Result<(), MeshiError> {
+        let Some(mut db) = self.db else {
+            return Err(MeshiError::Other(
+                "cannot set object material without a database".to_string(),
+            ));
+        };
+
+        let (_, material) = unsafe { db.as_mut() }.fetch_host_material(material_key)?;
+        let material = material.ok_or_else(|| {
+            MeshiError::Other(format!("material '{material_key}' has no GPU handle"))
+        })?;
+        self.renderer.set_object_material(handle, material)
+    }
+
+    pub fn object_material(&self, handle: Handle<RenderObject>) -> Option<Handle<Material>> {
+        self.renderer.object_material(handle)
+    }
+
+    /// Rebinds `handle` to the model stored under `mesh_key`, keeping the
+    /// handle and transform. The previous model is kept on error.
+    pub fn set_object_mesh(
+        &mut self,
+        handle: Handle<RenderObject>,
+        mesh_key: &str,
+    ) -> Result<(), MeshiError> {
+        let Some(mut db) = self.db else {
+            return Err(MeshiError::Other(
+                "cannot set object mesh without a database".to_string(),
+            ));
+        };
+
+        let model = unsafe { db.as_mut() }.fetch_gpu_model(mesh_key)?;
+        self.renderer.set_object_model(handle, &model)
+    }
+
+    /// Update many object transforms in one call. Invalid handles are skipped;
+    /// the dirty transforms are uploaded together on the next frame.
+    pub fn set_object_transforms(&mut self, transforms: &[(Handle<RenderObject>, Mat4)]) {
+        for (handle, transform) in transforms {
+            if handle.valid() {
+                self.renderer.set_object_transform(*handle, transform);
+            }
+        }
+    }
+
     pub fn object_transform(&self, handle: Handle<RenderObject>) -> glam::Mat4 {
         self.renderer.object_transform(handle)
     }
 
+    /// Number of draws submitted for an object each frame; one per mesh of a
+    /// model. Dead handles report `0`.
+    pub fn object_draw_count(&self, handle: Handle<RenderObject>) -> usize {
+        self.renderer.object_draw_count(handle)
+    }
+
+    /// World-space `(min, max)` bounds of an object, or `None` if the handle
+    /// does not refer to a live object.
+    pub fn object_bounds(&self, handle: Handle<RenderObject>) -> Option<(Vec3, Vec3)> {
+        let local = self.renderer.object_local_bounds(handle)?;
+        let world = local.transformed(&self.renderer.object_transform(handle));
+        Some((world.min, world.max))
+    }
+
+    /// Returns the nearest object under `screen_pos`, given in pixels from the
+    /// top-left corner of a viewport of size `viewport`.
+    pub fn pick_object(
+        &mut self,
+        camera: Handle<Camera>,
+        screen_pos: Vec2,
+        viewport: Vec2,
+    ) -> Option<Handle<RenderObject>> {
+        if !camera.valid() {
+            return None;
+        }
+
+        let view_projection = self.camera_projection(camera) * self.camera_view(camera);
+        let (origin, dir) = screen_ray(&view_projection, screen_pos, viewport)?;
+        let candidates = self
+            .renderer
+            .object_handles()
+            .into_iter()
+            .filter_map(|handle| {
+                let (min, max) = self.object_bounds(handle)?;
+                Some((handle, Aabb { min, max }))
+            });
+        nearest_hit(origin, dir, candidates)
+    }
+
     fn publish_events(&mut self) {
         use winit::event_loop::ControlFlow;
         use winit::platform::run_return::EventLoopExtRunReturn;
 
+        let mut events = std::mem::take(&mut self.queued_events);
+        let mut handles = Vec::new();
+        self.displays
+            .for_each_occupied_handle_mut(|handle| handles.push(handle));
+        for handle in handles {
+            let Some(dis) = self.displays.get_mut_ref(handle) else {
+                continue;
+            };
+            if let DisplayImpl::Window(Some(display)) = &mut dis.raw {
+                let event_loop = display.winit_event_loop();
+                event_loop.run_return(|event, _target, control_flow| {
+                    *control_flow = ControlFlow::Exit;
+                    if let Some(e) = event::from_winit_event(&event) {
+                        events.push((handle, e));
+                    }
+                });
+            }
+        }
+
         let mut triggered = false;
-        let debug_gui_ptr = &mut self.debug_gui as *mut DebugGui;
-        let gui_input_ptr = &mut self.gui_input as *mut gui::GuiInput;
-
-        if let Some(cb) = self.event_cb.as_mut() {
-            self.displays.for_each_occupied_mut(|dis| {
-                if let DisplayImpl::Window(Some(display)) = &mut dis.raw {
-                    let event_loop = display.winit_event_loop();
-                    event_loop.run_return(|event, _target, control_flow| {
-                        *control_flow = ControlFlow::Exit;
-                        if let Some(mut e) = event::from_winit_event(&event) {
-                            triggered = true;
-                            unsafe {
-                                (*debug_gui_ptr).handle_event(&e);
-                            }
-                            unsafe {
-                                (*gui_input_ptr).handle_event(&e);
-                            }
-                            let c = cb.event_cb;
-                            c(&mut e, cb.user_data);
-                        }
-                    });
-                }
-            });
+        for (display, mut e) in events {
+            if is_uncaptured_motion(&e, self.mouse_captured) {
+                continue;
+            }
+            if e.event_type() == event::EventType::WindowResized {
+                // The window already has its new size; only the engine side
+                // needs to follow.
+                let size = unsafe { e.motion2d() };
+                self.apply_display_size(display, [size.x as u32, size.y as u32]);
+            }
+
+            triggered = true;
+            self.debug_gui.handle_event(&e);
+            self.gui_input.handle_event(&e);
+            if let Some(cb) = self.event_cb.as_mut() {
+                let c = cb.event_cb;
+                c(&mut e, cb.user_data);
+            }
+        }
 
-            if !triggered {
+        if !triggered {
+            if let Some(cb) = self.event_cb.as_mut() {
                 let mut synthetic: event::Event = unsafe { std::mem::zeroed() };
                 let c = cb.event_cb;
                 c(&mut synthetic, cb.user_data);
             }
-        } else {
-            self.displays.for_each_occupied_mut(|dis| {
-                if let DisplayImpl::Window(Some(display)) = &mut dis.raw {
-                    let event_loop = display.winit_event_loop();
-                    event_loop.run_return(|event, _target, control_flow| {
-                        *control_flow = ControlFlow::Exit;
-                        if let Some(e) = event::from_winit_event(&event) {
-                            unsafe {
-                                (*debug_gui_ptr).handle_event(&e);
-                            }
-                            unsafe {
-                                (*gui_input_ptr).handle_event(&e);
-                            }
-                        }
-                    });
-                }
-            });
         }
     }
 
+    /// Queue `event` as if `display` had produced it. Queued events are
+    /// handled and forwarded to the event callback on the next update.
+    pub fn queue_event(&mut self, display: Handle<Display>, event: event::Event) {
+        self.queued_events.push((display, event));
+    }
+
     pub fn update(&mut self, delta_time: f32) {
         self.gui_input.begin_frame();
         self.publish_events();
@@ -718,12 +1269,21 @@ impl RenderEngine {
             self.renderer.set_cloud_settings(self.cloud_settings);
         }
 
-        let mut gui_frame = self.pending_gui_frame.take().unwrap_or_default();
+        let mut sprites = Vec::new();
+        self.sprites
+            .for_each_occupied(|sprite| sprites.push(sprite.info.clone()));
+        let mut gui_frame = sprite_frame(&sprites, viewport_size);
+        if let Some(mut pending) = self.pending_gui_frame.take() {
+            gui_frame.batches.append(&mut pending.batches);
+            gui_frame.text_draws.append(&mut pending.text_draws);
+        }
         if let Some(mut debug_frame) = debug_output.frame {
             gui_frame.batches.append(&mut debug_frame.batches);
             gui_frame.text_draws.append(&mut debug_frame.text_draws);
         }
         self.renderer.upload_gui_frame(gui_frame);
+        self.renderer.set_debug_lines(self.debug_lines.vertices());
+        self.debug_lines.clear();
 
         let mut views = Vec::new();
         let mut seen = HashSet::new();
@@ -735,6 +1295,7 @@ impl RenderEngine {
         });
 
         let view_outputs = self.renderer.update(&[], &views, delta_time);
+        self.dispatch_animation_events();
         let mut outputs_by_camera = HashMap::new();
         for output in view_outputs {
             outputs_by_camera.insert(output.camera, output);
@@ -822,69 +1383,176 @@ impl RenderEngine {
     }
 
     pub fn register_window_display(&mut self, info: dashi::DisplayInfo) -> Handle<Display> {
-        let raw = Some(Box::new(
+        let raw = Box::new(
             self.context()
                 .make_display(&info)
                 .expect("Failed to make display!"),
-        ));
+        );
+        if self.mouse_captured {
+            apply_cursor_capture(raw.winit_window(), true);
+        }
 
         info!("Registered window {}", info.window.title);
-        return self
+        let handle = self
             .displays
             .insert(Display {
-                raw: DisplayImpl::Window(raw),
+                raw: DisplayImpl::Window(Some(raw)),
                 scene: Default::default(),
+                size: info.window.size,
+                fullscreen: false,
+                windowed_size: info.window.size,
             })
             .unwrap();
+        self.primary_display.get_or_insert(handle);
+        handle
     }
 
     pub fn register_cpu_display(&mut self, info: dashi::DisplayInfo) -> Handle<Display> {
-        let size = info.window.size;
-        let format = Format::BGRA8;
-        let img = self
-            .context()
-            .make_image(&ImageInfo {
-                debug_name: "[MESHI CPU] Display Image",
-                dim: [size[0], size[1], 1],
-                layers: 1,
-                format,
-                mip_levels: 1,
-                samples: SampleCount::S1,
-                initial_data: None,
-                ..Default::default()
-            })
-            .expect("Failed to make CPU display image");
-        let img = ImageView {
-            img,
-            range: SubresourceRange::default(),
-            aspect: AspectMask::Color,
-            view_type: ImageViewType::Type2D,
-        };
-        let byte_size = size[0] as usize * size[1] as usize * 4;
-        let staging = self
-            .context()
-            .make_buffer(&BufferInfo {
-                debug_name: "[MESHI CPU] Display Staging",
-                byte_size: byte_size as u32,
-                visibility: MemoryVisibility::CpuAndGpu,
-                usage: BufferUsage::ALL,
-                initial_data: None,
-            })
-            .expect("Failed to make CPU display staging buffer");
-
-        self.displays
+        let output = make_cpu_image_output(
+            self.context(),
+            "[MESHI CPU] Display",
+            info.window.size,
+            Format::BGRA8,
+        )
+        .expect("Failed to make CPU display image");
+
+        let handle = self
+            .displays
             .insert(Display {
-                raw: DisplayImpl::CPUImage(CPUImageOutput {
-                    img,
-                    staging,
-                    width: size[0],
-                    height: size[1],
-                    format,
-                    pixels: vec![0; byte_size],
-                }),
+                raw: DisplayImpl::CPUImage(output),
                 scene: Default::default(),
+                size: info.window.size,
+                fullscreen: false,
+                windowed_size: info.window.size,
             })
-            .unwrap()
+            .unwrap();
+        self.primary_display.get_or_insert(handle);
+        handle
+    }
+
+    /// Size of `display` in pixels, or `[0, 0]` for an unknown handle.
+    pub fn display_size(&self, display: Handle<Display>) -> [u32; 2] {
+        if !display.valid() {
+            return [0, 0];
+        }
+        self.displays
+            .get_ref(display)
+            .map_or([0, 0], |dis| dis.size)
+    }
+
+    pub fn display_fullscreen(&self, display: Handle<Display>) -> bool {
+        display.valid()
+            && self
+                .displays
+                .get_ref(display)
+                .is_some_and(|dis| dis.fullscreen)
+    }
+
+    /// Size of the renderer's scene output in pixels.
+    pub fn viewport_size(&self) -> [u32; 2] {
+        let viewport = self.renderer.viewport();
+        [viewport.area.w as u32, viewport.area.h as u32]
+    }
+
+    /// Resize `display`. Resizing the primary display also resizes the
+    /// renderer's viewport and refits the aspect of its attached camera.
+    pub fn set_display_size(&mut self, display: Handle<Display>, size: [u32; 2]) {
+        if !display.valid() || size.contains(&0) || self.display_size(display) == size {
+            return;
+        }
+        if let Some(DisplayImpl::Window(Some(window))) =
+            self.displays.get_mut_ref(display).map(|dis| &mut dis.raw)
+        {
+            let size = winit::dpi::PhysicalSize::new(size[0], size[1]);
+            window.winit_window().set_inner_size(size);
+        }
+        self.apply_display_size(display, size);
+    }
+
+    /// Bring the engine's view of `display` to `size`: CPU displays get new
+    /// images, and the primary display resizes the renderer.
+    fn apply_display_size(&mut self, display: Handle<Display>, size: [u32; 2]) {
+        if !display.valid() || size.contains(&0) || self.display_size(display) == size {
+            return;
+        }
+        if let Err(err) = self.blit_queue.wait_all() {
+            warn!("Failed waiting on blit queue: {err:?}");
+            return;
+        }
+
+        let ctx = unsafe { &mut *(self.context() as *mut Context) };
+        let Some(dis) = self.displays.get_mut_ref(display) else {
+            return;
+        };
+        if let DisplayImpl::CPUImage(output) = &mut dis.raw {
+            let resized =
+                match make_cpu_image_output(ctx, "[MESHI CPU] Display", size, output.format) {
+                    Ok(resized) => resized,
+                    Err(err) => {
+                        warn!("Failed to resize CPU display: {err:?}");
+                        return;
+                    }
+                };
+            let old = std::mem::replace(output, resized);
+            ctx.destroy_image(old.img.img);
+            ctx.destroy_buffer(old.staging);
+        }
+        dis.size = size;
+        if !dis.fullscreen {
+            dis.windowed_size = size;
+        }
+
+        let camera = dis.scene;
+        if self.primary_display == Some(display) {
+            self.renderer.resize(size);
+            self.refit_camera_aspect(camera, size);
+        }
+    }
+
+    /// Toggle borderless fullscreen on a window display. Offscreen displays
+    /// only record the flag.
+    pub fn set_display_fullscreen(&mut self, display: Handle<Display>, fullscreen: bool) {
+        if !display.valid() {
+            return;
+        }
+        let Some(dis) = self.displays.get_mut_ref(display) else {
+            return;
+        };
+        if dis.fullscreen == fullscreen {
+            return;
+        }
+        dis.fullscreen = fullscreen;
+
+        let size = match &mut dis.raw {
+            DisplayImpl::Window(Some(window)) => {
+                let window = window.winit_window();
+                if fullscreen {
+                    window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)));
+                    window.current_monitor().map_or(dis.size, |monitor| {
+                        [monitor.size().width, monitor.size().height]
+                    })
+                } else {
+                    window.set_fullscreen(None);
+                    dis.windowed_size
+                }
+            }
+            _ => return,
+        };
+        self.set_display_size(display, size);
+    }
+
+    fn refit_camera_aspect(&mut self, camera: Handle<Camera>, size: [u32; 2]) {
+        let Some(perspective) = self.camera_perspectives.get(&camera).copied() else {
+            return;
+        };
+        self.set_camera_perspective(
+            camera,
+            perspective.fov_y_radians,
+            size[0] as f32,
+            size[1] as f32,
+            perspective.near,
+            perspective.far,
+        );
     }
 
     pub fn frame_dump(&mut self, display: Handle<Display>) -> Option<FFIImage> {
@@ -903,29 +1571,140 @@ impl RenderEngine {
             return None;
         }
 
-        let mapped = match ctx.map_buffer::<u8>(BufferView::new(output.staging)) {
-            Ok(mapped) => mapped,
-            Err(err) => {
-                warn!("Failed to map CPU display buffer: {err:?}");
-                return None;
-            }
-        };
+        read_back_cpu_output(ctx, output)
+    }
 
-        if output.pixels.len() != mapped.len() {
-            output.pixels.resize(mapped.len(), 0);
+    /// Render the current scene from `camera` into an offscreen image of
+    /// `extent` pixels and return its RGBA8 pixels. Registered displays are
+    /// neither drawn nor presented. The pixels stay valid until the next call.
+    pub fn render_to_image(
+        &mut self,
+        camera: Handle<Camera>,
+        extent: [u32; 2],
+    ) -> Result<FFIImage, MeshiError> {
+        if !camera.valid() {
+            return Err(MeshiError::Other("invalid camera handle".to_string()));
+        }
+        if extent[0] == 0 || extent[1] == 0 {
+            return Err(MeshiError::Other(format!(
+                "invalid image extent {extent:?}"
+            )));
         }
-        output.pixels.copy_from_slice(mapped);
 
-        if let Err(err) = ctx.unmap_buffer(output.staging) {
-            warn!("Failed to unmap CPU display buffer: {err:?}");
+        // Render at the capture's own size so its aspect, not the display's,
+        // shapes the projection. The display's size and camera come back after.
+        let viewport = self.renderer.viewport();
+        let scene_size = [viewport.area.w as u32, viewport.area.h as u32];
+        let saved_camera = self.camera_data(camera);
+        if scene_size != extent {
+            self.renderer.resize(extent);
         }
+        self.refit_camera_aspect(camera, extent);
 
-        Some(FFIImage {
-            width: output.width,
-            height: output.height,
-            format: output.format as u32,
-            pixels: output.pixels.as_ptr(),
-        })
+        let image = self.render_offscreen(camera, extent);
+
+        if scene_size != extent {
+            self.renderer.resize(scene_size);
+        }
+        if let Some(saved) = saved_camera {
+            self.set_camera_data(camera, saved);
+        }
+        image
+    }
+
+    fn render_offscreen(
+        &mut self,
+        camera: Handle<Camera>,
+        extent: [u32; 2],
+    ) -> Result<FFIImage, MeshiError> {
+        self.prepare_offscreen_target(extent)?;
+
+        let output = self
+            .renderer
+            .update(&[], &[camera], 0.0)
+            .into_iter()
+            .find(|output| output.camera == camera)
+            .ok_or_else(|| MeshiError::Other("renderer produced no view".to_string()))?;
+
+        let target = self.offscreen.as_mut().unwrap();
+        self.blit_queue
+            .record(|c| {
+                CommandStream::new()
+                    .begin()
+                    .resolve_images(&MSImageResolve {
+                        src: output.image.img,
+                        dst: target.resolve.img,
+                        ..Default::default()
+                    })
+                    .blit_images(&BlitImage {
+                        src: target.resolve.img,
+                        dst: target.output.img.img,
+                        src_range: SubresourceRange::new(0, 1, 0, 1),
+                        dst_range: SubresourceRange::new(0, 1, 0, 1),
+                        filter: Filter::Linear,
+                        src_region: Rect2D {
+                            x: 0,
+                            y: 0,
+                            w: extent[0],
+                            h: extent[1],
+                        },
+                        dst_region: Rect2D {
+                            x: 0,
+                            y: 0,
+                            w: extent[0],
+                            h: extent[1],
+                        },
+                    })
+                    .copy_image_to_buffer(&CopyImageBuffer {
+                        src: target.output.img.img,
+                        dst: target.output.staging,
+                        range: target.output.img.range,
+                        dst_offset: 0,
+                    })
+                    .end()
+                    .append(c)
+                    .unwrap();
+            })
+            .map_err(|err| MeshiError::Other(format!("failed to record readback: {err:?}")))?;
+        self.blit_queue
+            .submit(&SubmitInfo {
+                wait_sems: &[output.semaphore],
+                signal_sems: &[],
+            })
+            .map_err(|err| MeshiError::Other(format!("failed to submit readback: {err:?}")))?;
+        self.blit_queue
+            .wait_all()
+            .map_err(|err| MeshiError::Other(format!("failed waiting on readback: {err:?}")))?;
+
+        let ctx = self.renderer.context();
+        let target = self.offscreen.as_mut().unwrap();
+        read_back_cpu_output(ctx, &mut target.output)
+            .ok_or_else(|| MeshiError::Other("failed to read back offscreen image".to_string()))
+    }
+
+    /// Create or resize the images used by [`RenderEngine::render_to_image`].
+    fn prepare_offscreen_target(&mut self, extent: [u32; 2]) -> Result<(), MeshiError> {
+        if let Some(target) = &self.offscreen {
+            if [target.output.width, target.output.height] == extent {
+                return Ok(());
+            }
+        }
+
+        let ctx = self.renderer.context();
+        if let Some(old) = self.offscreen.take() {
+            ctx.destroy_image(old.resolve.img);
+            ctx.destroy_image(old.output.img.img);
+            ctx.destroy_buffer(old.output.staging);
+        }
+        let resolve = make_color_view(
+            ctx,
+            "[MESHI] Offscreen Resolve Image",
+            extent,
+            Format::BGRA8,
+        )?;
+        let output = make_cpu_image_output(ctx, "[MESHI] Offscreen", extent, Format::RGBA8)?;
+        self.offscreen = Some(OffscreenTarget { resolve, output });
+        Ok(())
     }
 
     pub fn attach_camera_to_display(&mut self, display: Handle<Display>, camera: Handle<Camera>) {
@@ -935,8 +1714,20 @@ impl RenderEngine {
         }
     }
 
+    /// Grab and hide the cursor on every window display and report relative
+    /// [`event::EventSource::MouseMotion`] events instead. Headless engines
+    /// only record the request.
     pub fn set_capture_mouse(&mut self, capture: bool) {
-        let _ = capture; // window management handled by renderer
+        self.mouse_captured = capture;
+        self.displays.for_each_occupied_mut(|dis| {
+            if let DisplayImpl::Window(Some(display)) = &mut dis.raw {
+                apply_cursor_capture(display.winit_window(), capture);
+            }
+        });
+    }
+
+    pub fn mouse_captured(&self) -> bool {
+        self.mouse_captured
     }
 
     pub fn register_camera(&mut self, initial_transform: &Mat4) -> Handle<Camera> {
@@ -953,6 +1744,7 @@ impl RenderEngine {
             )
             .unwrap();
 
+        self.cameras.insert(h);
         h
     }
 
@@ -970,6 +1762,23 @@ impl RenderEngine {
                 },
             )
             .unwrap();
+        self.cameras.remove(&camera);
+        self.camera_perspectives.remove(&camera);
+    }
+
+    /// Number of live render objects.
+    pub fn object_count(&self) -> usize {
+        self.renderer.object_handles().len()
+    }
+
+    /// Number of live lights, including the environment's sun and moon.
+    pub fn light_count(&self) -> usize {
+        self.light_cache.len()
+    }
+
+    /// Number of live cameras.
+    pub fn camera_count(&self) -> usize {
+        self.cameras.len()
     }
 
     pub fn set_camera_perspective(
@@ -991,6 +1800,40 @@ impl RenderEngine {
                 },
             )
             .unwrap();
+        self.camera_perspectives.insert(
+            camera,
+            CameraPerspective {
+                fov_y_radians,
+                near,
+                far,
+            },
+        );
+    }
+
+    fn camera_data(&mut self, camera: Handle<Camera>) -> Option<Camera> {
+        let mut data = None;
+        self.renderer
+            .state()
+            .reserved_mut(
+                "meshi_bindless_cameras",
+                |a: &mut furikake::reservations::bindless_camera::ReservedBindlessCamera| {
+                    data = Some(*a.camera_mut(camera));
+                },
+            )
+            .ok()?;
+        data
+    }
+
+    fn set_camera_data(&mut self, camera: Handle<Camera>, data: Camera) {
+        self.renderer
+            .state()
+            .reserved_mut(
+                "meshi_bindless_cameras",
+                |a: &mut furikake::reservations::bindless_camera::ReservedBindlessCamera| {
+                    *a.camera_mut(camera) = data;
+                },
+            )
+            .unwrap();
     }
 
     pub fn set_camera_transform(&mut self, camera: Handle<Camera>, transform: &Mat4) {
@@ -1010,6 +1853,17 @@ impl RenderEngine {
             .unwrap();
     }
 
+    /// Place `camera` at `eye`, facing `target` with `up` as the vertical.
+    pub fn set_camera_look_at(
+        &mut self,
+        camera: Handle<Camera>,
+        eye: Vec3,
+        target: Vec3,
+        up: Vec3,
+    ) {
+        self.set_camera_transform(camera, &look_at_transform(eye, target, up));
+    }
+
     pub fn set_camera_projection(&mut self, camera: Handle<Camera>, projection: &Mat4) {
         if !camera.valid() {
             return;
@@ -1025,6 +1879,7 @@ impl RenderEngine {
                 },
             )
             .unwrap();
+        self.camera_perspectives.remove(&camera);
     }
 
     pub fn set_camera_position(&mut self, camera: Handle<Camera>, position: Vec3) {
@@ -1098,6 +1953,24 @@ impl RenderEngine {
         view
     }
 
+    pub fn camera_projection(&mut self, camera: Handle<Camera>) -> Mat4 {
+        if !camera.valid() {
+            return Mat4::IDENTITY;
+        }
+
+        let mut projection = Mat4::IDENTITY;
+        self.renderer
+            .state()
+            .reserved_mut(
+                "meshi_bindless_cameras",
+                |a: &mut furikake::reservations::bindless_camera::ReservedBindlessCamera| {
+                    projection = a.camera(camera).projection;
+                },
+            )
+            .unwrap();
+        projection
+    }
+
     pub fn set_event_cb(
         &mut self,
         event_cb: extern "C" fn(*mut event::Event, *mut c_void),
@@ -1110,27 +1983,67 @@ impl RenderEngine {
     }
 }
 
-fn directional_light_info(direction: Vec3, color: Vec3, intensity: f32) -> LightInfo {
-    let direction = direction.normalize_or_zero();
-    LightInfo {
-        ty: LightType::Directional,
-        flags: LightFlags::CASTS_SHADOWS.bits(),
-        intensity,
-        range: 0.0,
-        color_r: color.x,
-        color_g: color.y,
-        color_b: color.z,
-        pos_x: 0.0,
-        pos_y: 0.0,
-        pos_z: 0.0,
-        dir_x: direction.x,
-        dir_y: direction.y,
-        dir_z: direction.z,
-        spot_inner_angle_rad: 0.0,
-        spot_outer_angle_rad: 0.0,
-        rect_half_width: 0.0,
-        rect_half_height: 0.0,
+/// Camera transform placed at `eye` and facing `target`, suitable for
+/// [`RenderEngine::set_camera_transform`]. Cameras look down their local -Z.
+pub fn look_at_transform(eye: Vec3, target: Vec3, up: Vec3) -> Mat4 {
+    Mat4::look_at_rh(eye, target, up).inverse()
+}
+
+/// Build the overlay batches for `sprites`, back-to-front by `z`. Sprites
+/// sharing a `z` keep their registration order.
+fn sprite_frame(sprites: &[SpriteInfo], viewport: Vec2) -> gui::GuiFrame {
+    let mut ordered = sprites.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|sprite| sprite.z);
+
+    let mut context = gui::GuiContext::new();
+    for sprite in ordered {
+        let min = sprite.position / viewport * 2.0 - Vec2::ONE;
+        let max = (sprite.position + sprite.size) / viewport * 2.0 - Vec2::ONE;
+        let (left, right) = (min.x, max.x);
+        let (top, bottom) = (-min.y, -max.y);
+        context.submit_draw(gui::GuiDraw::new(
+            gui::GuiLayer::Background,
+            sprite.texture_id,
+            gui::GuiQuad {
+                positions: [[left, top], [right, top], [right, bottom], [left, bottom]],
+                uvs: [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+                color: sprite.color.to_array(),
+            },
+        ));
     }
+    context.build_frame()
+}
+
+/// World-space ray through a pixel, found by unprojecting it onto the near and
+/// far planes.
+fn screen_ray(view_projection: &Mat4, screen_pos: Vec2, viewport: Vec2) -> Option<(Vec3, Vec3)> {
+    if viewport.x <= 0.0 || viewport.y <= 0.0 {
+        return None;
+    }
+
+    let ndc_x = (screen_pos.x / viewport.x) * 2.0 - 1.0;
+    let ndc_y = 1.0 - (screen_pos.y / viewport.y) * 2.0;
+    let inverse = view_projection.inverse();
+    let near = inverse.project_point3(Vec3::new(ndc_x, ndc_y, 0.0));
+    let far = inverse.project_point3(Vec3::new(ndc_x, ndc_y, 1.0));
+    let dir = (far - near).try_normalize()?;
+    Some((near, dir))
+}
+
+fn nearest_hit(
+    origin: Vec3,
+    dir: Vec3,
+    candidates: impl IntoIterator<Item = (Handle<RenderObject>, Aabb)>,
+) -> Option<Handle<RenderObject>> {
+    candidates
+        .into_iter()
+        .filter_map(|(handle, bounds)| Some((handle, bounds.ray_distance(origin, dir)?)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(handle, _)| handle)
+}
+
+fn directional_light_info(direction: Vec3, color: Vec3, intensity: f32) -> LightInfo {
+    LightInfo::directional(direction.normalize_or_zero(), color, intensity)
 }
 
 fn resolve_sun_moon_direction(settings: &SkyFrameSettings) -> (Vec3, Vec3) {
@@ -1182,6 +2095,23 @@ fn compute_celestial_lighting(settings: &SkyFrameSettings) -> CelestialLighting
     }
 }
 
+/// Sun and moon light direction, color, and intensity for `settings`.
+fn resolved_celestial_lights(settings: &EnvironmentLightingSettings) -> [(Vec3, Vec3, f32); 2] {
+    let lighting = compute_celestial_lighting(&settings.sky);
+    [
+        (
+            -lighting.sun_dir.normalize_or_zero(),
+            settings.sky.sun_color,
+            settings.sun_light_intensity * lighting.sun_intensity,
+        ),
+        (
+            -lighting.moon_dir.normalize_or_zero(),
+            settings.sky.moon_color,
+            settings.moon_light_intensity * lighting.moon_intensity,
+        ),
+    ]
+}
+
 fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
     if (edge1 - edge0).abs() <= f32::EPSILON {
         return if x < edge0 { 0.0 } else { 1.0 };
@@ -1213,6 +2143,7 @@ fn empty_light_info() -> LightInfo {
         spot_outer_angle_rad: 0.0,
         rect_half_width: 0.0,
         rect_half_height: 0.0,
+        falloff: LightFalloff::InverseSquare,
     }
 }
 
@@ -1248,3 +2179,87 @@ fn resolve_celestial_direction(
 
     if is_moon { -Vec3::Y } else { Vec3::Y }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn center_screen_ray_picks_object_in_front() {
+        let view = Mat4::look_at_rh(Vec3::ZERO, Vec3::NEG_Z, Vec3::Y);
+        let projection = Mat4::perspective_rh(60f32.to_radians(), 16.0 / 9.0, 0.1, 100.0);
+        let viewport = Vec2::new(1280.0, 720.0);
+        let (origin, dir) = screen_ray(&(projection * view), viewport * 0.5, viewport).unwrap();
+
+        let front = Handle::<RenderObject>::new(1, 0);
+        let behind = Handle::<RenderObject>::new(2, 0);
+        let off_axis = Handle::<RenderObject>::new(3, 0);
+        let at =
+            |x: f32, z: f32| Aabb::UNIT.transformed(&Mat4::from_translation(Vec3::new(x, 0.0, z)));
+        let candidates = [
+            (behind, at(0.0, -10.0)),
+            (front, at(0.0, -5.0)),
+            (off_axis, at(4.0, -3.0)),
+        ];
+
+        let picked = nearest_hit(origin, dir, candidates).unwrap();
+        assert_eq!(picked.slot, front.slot);
+        assert!(nearest_hit(origin, -dir, candidates).is_none());
+    }
+
+    #[test]
+    fn look_at_transform_faces_the_target() {
+        let transform = look_at_transform(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO, Vec3::Y);
+        let forward = transform.transform_vector3(Vec3::NEG_Z);
+        assert!(forward.abs_diff_eq(Vec3::NEG_Z, 1.0e-5));
+        assert!(
+            transform
+                .transform_point3(Vec3::ZERO)
+                .abs_diff_eq(Vec3::new(0.0, 0.0, 5.0), 1.0e-5)
+        );
+    }
+
+    #[test]
+    fn sprite_frame_draws_higher_z_last() {
+        let front = SpriteInfo {
+            size: Vec2::splat(10.0),
+            color: Vec4::new(1.0, 0.0, 0.0, 1.0),
+            z: 2,
+            ..Default::default()
+        };
+        let back = SpriteInfo {
+            color: Vec4::new(0.0, 0.0, 1.0, 1.0),
+            z: 1,
+            ..front.clone()
+        };
+        let frame = sprite_frame(&[front, back], Vec2::new(20.0, 20.0));
+        let vertices = &frame.batches[0].mesh.vertices;
+        assert_eq!(vertices.len(), 8);
+        assert_eq!(vertices[0].color, [0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(vertices[4].color, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(vertices[0].position, [-1.0, 1.0]);
+        assert_eq!(vertices[2].position, [0.0, 0.0]);
+    }
+
+    #[test]
+    fn screen_ray_rejects_empty_viewport() {
+        assert!(screen_ray(&Mat4::IDENTITY, Vec2::ZERO, Vec2::ZERO).is_none());
+    }
+
+    #[test]
+    fn noon_sun_shines_down_and_outshines_midnight() {
+        let at_hour = |hour: f32| {
+            let mut settings = EnvironmentLightingSettings::default();
+            settings.sky.current_time_of_day = hour;
+            resolved_celestial_lights(&settings)
+        };
+        let [(noon_dir, noon_color, noon_intensity), _] = at_hour(12.0);
+        let [(_, _, midnight_intensity), (moon_dir, _, moon_intensity)] = at_hour(0.0);
+
+        assert!(noon_dir.y < -0.5, "noon sun direction {noon_dir}");
+        assert_eq!(noon_color, SkyFrameSettings::default().sun_color);
+        assert!(noon_intensity > midnight_intensity);
+        assert!(moon_dir.y < -0.5, "midnight moon direction {moon_dir}");
+        assert!(moon_intensity > 0.0);
+    }
+}
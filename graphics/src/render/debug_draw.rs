@@ -0,0 +1,474 @@
+use bento::builder::{AttachmentDesc, PSO, PSOBuilder};
+use bento::{Compiler, OptimizationLevel, Request, ShaderLang};
+use dashi::cmd::{Executable, PendingGraphics};
+use dashi::driver::command::Draw;
+use dashi::{
+    BufferInfo, BufferUsage, CommandStream, Context, DepthInfo, DynamicAllocator, Format,
+    GraphicsPipelineDetails, IndexedResource, MemoryVisibility, SampleCount, ShaderResource,
+    ShaderType, Viewport,
+};
+use furikake::BindlessState;
+use glam::{Mat4, Quat, Vec3, Vec4};
+use meshi_physics::{CollisionShape, CollisionShapeType};
+use tare::utils::StagedBuffer;
+
+/// Maximum number of debug line vertices drawn in a single frame.
+pub const MAX_DEBUG_LINE_VERTICES: usize = 65536;
+/// Segments used for each of the three circles of a debug sphere.
+pub const DEBUG_SPHERE_SEGMENTS: usize = 24;
+/// Color of the physics collision shape overlay.
+pub const PHYSICS_DEBUG_COLOR: Vec4 = Vec4::new(0.2, 1.0, 0.2, 1.0);
+/// Half-size of the square patch drawn for an unbounded plane.
+pub const DEBUG_PLANE_HALF_EXTENT: f32 = 5.0;
+const DEBUG_LINE_WIDTH_PX: f32 = 1.5;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DebugLineVertex {
+    pub position: [f32; 4],
+    pub color: [f32; 4],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+struct DebugLineParams {
+    view_projection: Mat4,
+    viewport: [f32; 2],
+    line_width: f32,
+    _padding: f32,
+}
+
+/// CPU-side list of line segments, two vertices per segment.
+#[derive(Default)]
+pub struct DebugLines {
+    vertices: Vec<DebugLineVertex>,
+}
+
+impl DebugLines {
+    pub fn line(&mut self, a: Vec3, b: Vec3, color: Vec4) {
+        let color = color.to_array();
+        self.vertices.push(DebugLineVertex {
+            position: a.extend(1.0).to_array(),
+            color,
+        });
+        self.vertices.push(DebugLineVertex {
+            position: b.extend(1.0).to_array(),
+            color,
+        });
+    }
+
+    /// Twelve edges of the axis-aligned box spanning `min` to `max`.
+    pub fn aabb(&mut self, min: Vec3, max: Vec3, color: Vec4) {
+        let corner = |i: usize| {
+            Vec3::new(
+                if i & 1 == 0 { min.x } else { max.x },
+                if i & 2 == 0 { min.y } else { max.y },
+                if i & 4 == 0 { min.z } else { max.z },
+            )
+        };
+        for i in 0..8 {
+            for axis in [1, 2, 4] {
+                if i & axis == 0 {
+                    self.line(corner(i), corner(i | axis), color);
+                }
+            }
+        }
+    }
+
+    /// Three axis-aligned circles approximating a sphere.
+    pub fn sphere(&mut self, center: Vec3, radius: f32, color: Vec4) {
+        let point = |plane: usize, step: usize| {
+            let angle = step as f32 / DEBUG_SPHERE_SEGMENTS as f32 * std::f32::consts::TAU;
+            let (sin, cos) = angle.sin_cos();
+            let offset = match plane {
+                0 => Vec3::new(cos, sin, 0.0),
+                1 => Vec3::new(cos, 0.0, sin),
+                _ => Vec3::new(0.0, cos, sin),
+            };
+            center + offset * radius
+        };
+        for plane in 0..3 {
+            for step in 0..DEBUG_SPHERE_SEGMENTS {
+                self.line(point(plane, step), point(plane, step + 1), color);
+            }
+        }
+    }
+
+    /// Twelve edges of a box with `half_extents` rotated by `rotation`.
+    pub fn oriented_box(&mut self, center: Vec3, rotation: Quat, half_extents: Vec3, color: Vec4) {
+        let corner = |i: usize| {
+            let local = Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            ) * half_extents;
+            center + rotation * local
+        };
+        for i in 0..8 {
+            for axis in [1, 2, 4] {
+                if i & axis == 0 {
+                    self.line(corner(i), corner(i | axis), color);
+                }
+            }
+        }
+    }
+
+    /// Capsule running along its local Y axis: a ring and two half-circle
+    /// arcs per cap, joined by four side lines.
+    pub fn capsule(
+        &mut self,
+        center: Vec3,
+        rotation: Quat,
+        radius: f32,
+        half_height: f32,
+        color: Vec4,
+    ) {
+        let half = DEBUG_SPHERE_SEGMENTS / 2;
+        for cap in [1.0f32, -1.0] {
+            let cap_center = Vec3::new(0.0, half_height * cap, 0.0);
+            let point = |plane: usize, step: usize, steps: usize| {
+                let angle = step as f32 / steps as f32 * std::f32::consts::TAU;
+                let (sin, cos) = angle.sin_cos();
+                let offset = match plane {
+                    0 => Vec3::new(cos, 0.0, sin),
+                    1 => Vec3::new(cos, sin * cap, 0.0),
+                    _ => Vec3::new(0.0, sin * cap, cos),
+                };
+                center + rotation * (cap_center + offset * radius)
+            };
+            for step in 0..DEBUG_SPHERE_SEGMENTS {
+                self.line(
+                    point(0, step, DEBUG_SPHERE_SEGMENTS),
+                    point(0, step + 1, DEBUG_SPHERE_SEGMENTS),
+                    color,
+                );
+            }
+            for plane in 1..3 {
+                for step in 0..half {
+                    self.line(
+                        point(plane, step, DEBUG_SPHERE_SEGMENTS),
+                        point(plane, step + 1, DEBUG_SPHERE_SEGMENTS),
+                        color,
+                    );
+                }
+            }
+        }
+        for side in [Vec3::X, Vec3::NEG_X, Vec3::Z, Vec3::NEG_Z] {
+            let offset = side * radius;
+            self.line(
+                center + rotation * (offset + Vec3::Y * half_height),
+                center + rotation * (offset - Vec3::Y * half_height),
+                color,
+            );
+        }
+    }
+
+    /// Square patch of the plane through `center` facing `normal`, with the
+    /// normal drawn from its center.
+    pub fn plane(&mut self, center: Vec3, normal: Vec3, half_extent: f32, color: Vec4) {
+        let normal = normal.try_normalize().unwrap_or(Vec3::Y);
+        let rotation = Quat::from_rotation_arc(Vec3::Y, normal);
+        let corner = |x: f32, z: f32| center + rotation * Vec3::new(x, 0.0, z) * half_extent;
+        let corners = [
+            corner(-1.0, -1.0),
+            corner(1.0, -1.0),
+            corner(1.0, 1.0),
+            corner(-1.0, 1.0),
+        ];
+        for i in 0..4 {
+            self.line(corners[i], corners[(i + 1) % 4], color);
+        }
+        self.line(center, center + normal, color);
+    }
+
+    /// Wireframe of a physics collision shape at the given pose. Boxes are
+    /// drawn axis-aligned because that is how the physics collides them.
+    /// Planes are drawn as a finite patch. Heightfields are skipped because
+    /// their heights are stored in the simulation, not on the shape.
+    pub fn collision_shape(
+        &mut self,
+        shape: &CollisionShape,
+        position: Vec3,
+        rotation: Quat,
+        color: Vec4,
+    ) {
+        match shape.shape_type {
+            CollisionShapeType::Sphere => self.sphere(position, shape.radius, color),
+            CollisionShapeType::Box => {
+                self.oriented_box(position, Quat::IDENTITY, shape.dimensions * 0.5, color)
+            }
+            CollisionShapeType::Capsule => {
+                self.capsule(position, rotation, shape.radius, shape.half_height, color)
+            }
+            CollisionShapeType::Plane => {
+                self.plane(position, shape.dimensions, DEBUG_PLANE_HALF_EXTENT, color)
+            }
+            CollisionShapeType::Heightfield => {}
+        }
+    }
+
+    pub fn vertices(&self) -> &[DebugLineVertex] {
+        &self.vertices
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+}
+
+/// Draws debug lines as screen-space quads expanded in the vertex shader.
+pub struct DebugLineRenderer {
+    vertices: StagedBuffer,
+    pso: PSO,
+    vertex_count: usize,
+}
+
+impl DebugLineRenderer {
+    pub fn new(
+        ctx: &mut Context,
+        state: &mut BindlessState,
+        dynamic: &DynamicAllocator,
+        color_format: Format,
+        sample_count: SampleCount,
+    ) -> Self {
+        let vertices = StagedBuffer::new(
+            ctx,
+            BufferInfo {
+                debug_name: "[MESHI] Debug Line Vertices",
+                byte_size: (std::mem::size_of::<DebugLineVertex>() * MAX_DEBUG_LINE_VERTICES)
+                    as u32,
+                visibility: MemoryVisibility::CpuAndGpu,
+                usage: BufferUsage::STORAGE,
+                initial_data: None,
+            },
+        );
+
+        let pso = Self::build_pipeline(ctx, state, dynamic, sample_count, &vertices);
+        Self {
+            vertices,
+            pso,
+            vertex_count: 0,
+        }
+    }
+
+    fn build_pipeline(
+        ctx: &mut Context,
+        state: &mut BindlessState,
+        dynamic: &DynamicAllocator,
+        sample_count: SampleCount,
+        vertices: &StagedBuffer,
+    ) -> PSO {
+        let compiler = Compiler::new().expect("Failed to create shader compiler");
+        let base_request = Request {
+            name: Some("meshi_debug_lines".to_string()),
+            lang: ShaderLang::Slang,
+            stage: ShaderType::Vertex,
+            optimization: OptimizationLevel::Performance,
+            debug_symbols: true,
+            defines: Default::default(),
+        };
+
+        let vertex = compiler
+            .compile(
+                include_str!("shaders/debug_lines_vert.slang").as_bytes(),
+                &Request {
+                    stage: ShaderType::Vertex,
+                    ..base_request.clone()
+                },
+            )
+            .expect("Failed to compile debug line vertex shader");
+        let fragment = compiler
+            .compile(
+                include_str!("shaders/debug_lines_frag.slang").as_bytes(),
+                &Request {
+                    stage: ShaderType::Fragment,
+                    ..base_request
+                },
+            )
+            .expect("Failed to compile debug line fragment shader");
+
+        let pso = PSOBuilder::new()
+            .set_debug_name("[MESHI] Debug Lines")
+            .vertex_compiled(Some(vertex))
+            .fragment_compiled(Some(fragment))
+            .add_table_variable_with_resources(
+                "debug_vertices",
+                vec![IndexedResource {
+                    resource: ShaderResource::StorageBuffer(vertices.device().into()),
+                    slot: 0,
+                }],
+            )
+            .add_table_variable_with_resources(
+                "debug_line_ssbo",
+                vec![IndexedResource {
+                    resource: ShaderResource::DynamicStorage(dynamic.state()),
+                    slot: 0,
+                }],
+            )
+            .add_depth_target(AttachmentDesc {
+                format: Format::D24S8,
+                samples: sample_count,
+            })
+            .set_attachment_format(0, color_format)
+            .set_details(GraphicsPipelineDetails {
+                color_blend_states: vec![Default::default(); 1],
+                sample_count,
+                depth_test: Some(DepthInfo {
+                    should_test: true,
+                    should_write: false,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            })
+            .build(ctx)
+            .expect("Failed to build debug line pipeline");
+
+        state.register_pso_tables(&pso);
+        pso
+    }
+
+    /// Copies this frame's lines into the staging buffer. Lines past
+    /// [`MAX_DEBUG_LINE_VERTICES`] are dropped.
+    pub fn upload(&mut self, lines: &[DebugLineVertex]) {
+        let count = lines.len().min(MAX_DEBUG_LINE_VERTICES) & !1;
+        self.vertices.as_slice_mut::<DebugLineVertex>()[..count].copy_from_slice(&lines[..count]);
+        self.vertex_count = count;
+    }
+
+    pub fn pre_compute(&mut self) -> CommandStream<Executable> {
+        let mut stream = CommandStream::new().begin();
+        let bytes = (self.vertex_count * std::mem::size_of::<DebugLineVertex>()) as u32;
+        if bytes > 0 {
+            stream = stream.combine(self.vertices.sync_up_range(0, bytes));
+        }
+        stream.end()
+    }
+
+    pub fn render(
+        &self,
+        viewport: &Viewport,
+        view_projection: Mat4,
+        dynamic: &mut DynamicAllocator,
+    ) -> CommandStream<PendingGraphics> {
+        let cmd = CommandStream::<PendingGraphics>::subdraw();
+        if self.vertex_count == 0 {
+            return cmd;
+        }
+
+        let mut alloc = dynamic
+            .bump()
+            .expect("Failed to allocate debug line params");
+        alloc.slice::<DebugLineParams>()[0] = DebugLineParams {
+            view_projection,
+            viewport: [viewport.area.w, viewport.area.h],
+            line_width: DEBUG_LINE_WIDTH_PX,
+            _padding: 0.0,
+        };
+
+        // Each segment is expanded into two triangles.
+        cmd.bind_graphics_pipeline(self.pso.handle)
+            .update_viewport(viewport)
+            .draw(&Draw {
+                bind_tables: self.pso.tables(),
+                dynamic_buffers: [None, Some(alloc), None, None],
+                count: (self.vertex_count / 2 * 6) as u32,
+                instance_count: 1,
+                ..Default::default()
+            })
+            .unbind_graphics_pipeline()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_emit_two_vertices_per_segment() {
+        let mut lines = DebugLines::default();
+        lines.line(Vec3::ZERO, Vec3::X, Vec4::ONE);
+        assert_eq!(lines.vertex_count(), 2);
+
+        lines.aabb(Vec3::splat(-1.0), Vec3::ONE, Vec4::ONE);
+        assert_eq!(lines.vertex_count(), 2 + 12 * 2);
+
+        lines.sphere(Vec3::ZERO, 2.0, Vec4::ONE);
+        assert_eq!(
+            lines.vertex_count(),
+            2 + 12 * 2 + 3 * DEBUG_SPHERE_SEGMENTS * 2
+        );
+
+        lines.clear();
+        assert_eq!(lines.vertex_count(), 0);
+    }
+
+    #[test]
+    fn sphere_points_lie_on_radius() {
+        let mut lines = DebugLines::default();
+        let center = Vec3::new(1.0, 2.0, 3.0);
+        lines.sphere(center, 2.0, Vec4::ONE);
+        for vertex in lines.vertices() {
+            let position = Vec4::from_array(vertex.position).truncate();
+            assert!((position.distance(center) - 2.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn capsule_points_stay_within_radius_of_its_axis() {
+        let mut lines = DebugLines::default();
+        lines.capsule(Vec3::ZERO, Quat::IDENTITY, 0.5, 1.0, Vec4::ONE);
+        assert_eq!(
+            lines.vertex_count(),
+            (2 * DEBUG_SPHERE_SEGMENTS * 2 + 4) * 2
+        );
+        for vertex in lines.vertices() {
+            let position = Vec4::from_array(vertex.position).truncate();
+            let axis = Vec3::new(0.0, position.y.clamp(-1.0, 1.0), 0.0);
+            assert!((position.distance(axis) - 0.5).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn collision_boxes_ignore_body_rotation() {
+        let shape = CollisionShape {
+            shape_type: CollisionShapeType::Box,
+            dimensions: Vec3::new(1.0, 2.0, 3.0),
+            ..Default::default()
+        };
+        let mut rotated = DebugLines::default();
+        rotated.collision_shape(&shape, Vec3::ONE, Quat::from_rotation_y(0.7), Vec4::ONE);
+        let mut aligned = DebugLines::default();
+        aligned.collision_shape(&shape, Vec3::ONE, Quat::IDENTITY, Vec4::ONE);
+        assert_eq!(rotated.vertices(), aligned.vertices());
+    }
+
+    #[test]
+    fn plane_patch_lies_in_the_plane() {
+        let mut lines = DebugLines::default();
+        let center = Vec3::new(0.0, 2.0, 0.0);
+        let normal = Vec3::new(1.0, 1.0, 0.0).normalize();
+        lines.plane(center, normal, 3.0, Vec4::ONE);
+        assert_eq!(lines.vertex_count(), 5 * 2);
+        // The last segment is the normal; the outline is flat.
+        for vertex in &lines.vertices()[..8] {
+            let position = Vec4::from_array(vertex.position).truncate();
+            assert!((position - center).dot(normal).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn aabb_edges_are_axis_aligned() {
+        let mut lines = DebugLines::default();
+        lines.aabb(Vec3::ZERO, Vec3::new(1.0, 2.0, 3.0), Vec4::ONE);
+        for segment in lines.vertices().chunks(2) {
+            let a = Vec4::from_array(segment[0].position).truncate();
+            let b = Vec4::from_array(segment[1].position).truncate();
+            let changed = (b - a).to_array().iter().filter(|d| **d != 0.0).count();
+            assert_eq!(changed, 1);
+        }
+    }
+}
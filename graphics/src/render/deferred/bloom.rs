@@ -0,0 +1,163 @@
+use bento::builder::{PSOBuilder, PSO};
+use bento::{Compiler, OptimizationLevel, Request, ShaderLang};
+use dashi::cmd::PendingGraphics;
+use dashi::driver::command::Draw;
+use dashi::{
+    BlendFactor, BlendOp, ColorBlendState, CommandStream, Context, Format, GraphicsPipelineDetails,
+    IndexedResource, SampleCount, ShaderResource, ShaderType, Viewport,
+};
+use furikake::PSOBuilderFurikakeExt;
+
+use crate::BloomSettings;
+
+/// Which step of the bloom chain a draw performs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BloomStage {
+    /// Extract pixels above the threshold and blur them horizontally.
+    BrightHorizontal = 0,
+    /// Blur the horizontal result vertically.
+    Vertical = 1,
+    /// Add the blurred result on top of the lit scene.
+    Composite = 2,
+}
+
+#[repr(C)]
+struct BloomParams {
+    source_texture_id: u32,
+    mode: u32,
+    width: u32,
+    height: u32,
+    threshold: f32,
+    intensity: f32,
+    radius: f32,
+    padding: f32,
+}
+
+/// Threshold, separable blur and additive composite applied to the lit scene.
+pub struct BloomPass {
+    blur: PSO,
+    composite: PSO,
+}
+
+impl BloomPass {
+    pub fn new(
+        ctx: &mut Context,
+        state: &mut furikake::BindlessState,
+        dynamic: &dashi::DynamicAllocator,
+        color_format: Format,
+        sample_count: SampleCount,
+    ) -> Self {
+        let compiler = Compiler::new().expect("Failed to create shader compiler");
+        let base_request = Request {
+            name: Some("meshi_deferred_bloom".to_string()),
+            lang: ShaderLang::Slang,
+            stage: ShaderType::Vertex,
+            optimization: OptimizationLevel::Performance,
+            debug_symbols: true,
+            defines: Default::default(),
+        };
+
+        let vertex = compiler
+            .compile(
+                include_str!("shaders/deferred_combine_vert.slang").as_bytes(),
+                &Request {
+                    stage: ShaderType::Vertex,
+                    ..base_request.clone()
+                },
+            )
+            .expect("Failed to compile bloom vertex shader");
+        let fragment = compiler
+            .compile(
+                include_str!("shaders/bloom_frag.slang").as_bytes(),
+                &Request {
+                    stage: ShaderType::Fragment,
+                    ..base_request
+                },
+            )
+            .expect("Failed to compile bloom fragment shader");
+
+        let additive = ColorBlendState {
+            enable: true,
+            src_blend: BlendFactor::One,
+            dst_blend: BlendFactor::One,
+            blend_op: BlendOp::Add,
+            src_alpha_blend: BlendFactor::One,
+            dst_alpha_blend: BlendFactor::One,
+            alpha_blend_op: BlendOp::Add,
+            write_mask: Default::default(),
+        };
+
+        let mut build = |name: &str, blend: ColorBlendState| {
+            let pso = PSOBuilder::new()
+                .set_debug_name(name)
+                .vertex_compiled(Some(vertex.clone()))
+                .fragment_compiled(Some(fragment.clone()))
+                .set_attachment_format(0, color_format)
+                .set_details(GraphicsPipelineDetails {
+                    color_blend_states: vec![blend],
+                    sample_count,
+                    ..Default::default()
+                })
+                .add_table_variable_with_resources(
+                    "bloom_params_ssbo",
+                    vec![IndexedResource {
+                        resource: ShaderResource::DynamicStorage(dynamic.state()),
+                        slot: 0,
+                    }],
+                )
+                .add_reserved_table_variables(state)
+                .unwrap()
+                .build(ctx)
+                .expect("Failed to build bloom pipeline");
+
+            state.register_pso_tables(&pso);
+            pso
+        };
+
+        let blur = build("[MESHI] Deferred Bloom Blur", Default::default());
+        let composite = build("[MESHI] Deferred Bloom Composite", additive);
+
+        Self { blur, composite }
+    }
+
+    /// Records one fullscreen bloom draw reading `source_texture_id`.
+    pub fn record(
+        &self,
+        viewport: &Viewport,
+        dynamic: &mut dashi::DynamicAllocator,
+        stage: BloomStage,
+        source_texture_id: u32,
+        settings: &BloomSettings,
+    ) -> CommandStream<PendingGraphics> {
+        let mut alloc = dynamic
+            .bump()
+            .expect("Failed to allocate bloom dynamic buffer");
+        alloc.slice::<BloomParams>()[0] = BloomParams {
+            source_texture_id,
+            mode: stage as u32,
+            width: viewport.area.w as u32,
+            height: viewport.area.h as u32,
+            threshold: settings.threshold,
+            intensity: settings.intensity,
+            radius: settings.radius,
+            padding: 0.0,
+        };
+
+        let pipeline = match stage {
+            BloomStage::Composite => &self.composite,
+            _ => &self.blur,
+        };
+
+        CommandStream::<PendingGraphics>::subdraw()
+            .bind_graphics_pipeline(pipeline.handle)
+            .update_viewport(viewport)
+            .draw(&Draw {
+                bind_tables: pipeline.tables(),
+                dynamic_buffers: [None, Some(alloc), None, None],
+                instance_count: 1,
+                count: 3,
+                ..Default::default()
+            })
+            .unbind_graphics_pipeline()
+    }
+}
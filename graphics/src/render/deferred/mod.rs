@@ -1,3 +1,4 @@
+use super::debug_draw::{DebugLineRenderer, DebugLineVertex};
 use super::debug_layer::DebugLayer;
 use super::environment::{
     terrain::{TerrainFrameSettings, TERRAIN_DRAW_BIN},
@@ -6,7 +7,7 @@ use super::environment::{
 use super::gpu_draw_builder::GPUDrawBuilder;
 use super::gui::GuiRenderer;
 use super::scene::GPUScene;
-use super::skinning::{SkinningDispatcher, SkinningHandle, SkinningInfo};
+use super::skinning::{PendingAnimationEvent, SkinningDispatcher, SkinningHandle, SkinningInfo};
 use super::text::{TextDraw, TextDrawMode, TextRenderer};
 use super::{Renderer, RendererInfo, ViewOutput};
 use crate::gui::debug::{
@@ -19,7 +20,10 @@ use crate::render::gpu_draw_builder::GPUDrawBuilderInfo;
 use crate::{
     render::scene::*, BillboardInfo, BillboardType, RenderObject, RenderObjectInfo, TextObject,
 };
-use crate::{AnimationState, CloudDebugView, GuiInfo, GuiObject, TextInfo, TextRenderMode};
+use crate::{
+    Aabb, AnimationState, BloomSettings, CloudDebugView, FogSettings, GuiInfo, GuiObject,
+    ShadowFilter, TextInfo, TextRenderMode, TonemapSettings,
+};
 use bento::builder::{AttachmentDesc, PSOBuilder, PSO};
 use bento::{Compiler, OptimizationLevel, Request, ShaderLang};
 use bumpalo::collections::Vec as BumpVec;
@@ -34,6 +38,7 @@ use execution::{CommandDispatch, CommandRing};
 use furikake::reservations::bindless_camera::ReservedBindlessCamera;
 use furikake::reservations::bindless_indices::ReservedBindlessIndices;
 use furikake::reservations::bindless_materials::ReservedBindlessMaterials;
+use furikake::reservations::bindless_samplers::ReservedBindlessSamplers;
 use furikake::reservations::bindless_vertices::ReservedBindlessVertices;
 use furikake::reservations::ReservedBinding;
 use furikake::types::AnimationState as FurikakeAnimationState;
@@ -53,11 +58,15 @@ use tare::transient::TransientAllocator;
 use tare::utils::StagedBuffer;
 use tracing::{info, warn};
 
+mod bloom;
 mod shadow;
 mod shadows;
+mod tonemap;
 
+use bloom::{BloomPass, BloomStage};
 use shadow::ShadowPassInfo;
 use shadows::{ShadowCascadeInfo, ShadowPipelineMode, ShadowSystem};
+use tonemap::TonemapPass;
 
 //////////////////////////////////////////////////////////////////////////////
 //////////////////////////////////////////////////////////////////////////////
@@ -76,6 +85,10 @@ const BIN_GBUFFER_OPAQUE: u32 = 1;
 const BIN_SHADOW: u32 = 2;
 const BIN_TRANSPARENT: u32 = 3;
 
+/// Scene color is lit and composited in HDR, then tonemapped into the BGRA8
+/// framebuffer that the overlay and displays consume.
+const HDR_COLOR_FORMAT: Format = Format::RGBA32F;
+
 #[repr(C)]
 #[derive(Default, Clone, Copy)]
 pub struct PerDrawData {
@@ -141,6 +154,7 @@ struct RendererData {
     viewport: Viewport,
     objects: ResourceList<RenderObjectData>,
     lookup: HashMap<u16, Handle<RenderObjectData>>,
+    mesh_bounds: HashMap<u32, Aabb>,
     renderables: GPUPool<PerDrawData>,
     dynamic: DynamicAllocator,
 }
@@ -154,6 +168,7 @@ struct DataProcessors {
 struct Renderers {
     environment: EnvironmentRenderer,
     debug: DebugLayer,
+    lines: DebugLineRenderer,
 }
 
 struct DeferredPSO {
@@ -231,6 +246,8 @@ pub struct DeferredRenderer {
     subrender: Renderers,
     psos: DeferredPSO,
     sample_count: SampleCount,
+    sampler_info: SamplerInfo,
+    anisotropy_limit: Option<u32>,
     exec: DeferredExecution,
     state: Box<BindlessState>,
     alloc: Box<TransientAllocator>,
@@ -243,12 +260,20 @@ pub struct DeferredRenderer {
     frame_count: usize,
     frame_bump: Bump,
     debug_views: DeferredDebugViews,
+    shadow_filter: ShadowFilter,
+    fog: FogSettings,
+    bloom: BloomSettings,
+    bloom_pass: BloomPass,
+    tonemap: TonemapSettings,
+    tonemap_pass: TonemapPass,
 }
 
 struct RenderObjectData {
     kind: RenderObjectKind,
     scene_handle: Handle<SceneObject>,
+    transform_handle: Handle<Transformation>,
     draws: Vec<Handle<PerDrawData>>,
+    local_bounds: Aabb,
 }
 
 enum RenderObjectKind {
@@ -321,6 +346,15 @@ impl DeferredRenderer {
         ctx.init_gpu_timers(64).unwrap();
         CommandDispatch::init(ctx.as_mut()).expect("Failed to init command dispatcher!");
         let mut state = Box::new(BindlessState::new(&mut ctx));
+        let anisotropy_limit = super::device_anisotropy_limit(&ctx);
+        let anisotropy = crate::structs::supported_anisotropy(info.anisotropy, anisotropy_limit);
+        let sampler_info = super::anisotropic_sampler_info(anisotropy);
+        // Material, terrain and database textures all sample through the
+        // bindless sampler table.
+        let _ = state
+            .reserved_mut::<ReservedBindlessSamplers, _>("meshi_bindless_samplers", |samplers| {
+                samplers.set_sampler_info(&sampler_info)
+            });
         let scene = GPUScene::new(
             &GPUSceneInfo {
                 name: "[MESHI] Deferred Renderer Scene",
@@ -369,37 +403,23 @@ impl DeferredRenderer {
             },
         );
 
-        let depth_image = ctx
-            .make_image(&ImageInfo {
-                debug_name: "[MESHI DEFERRED] Persistent Depth",
-                dim: [
-                    info.initial_viewport.area.w as u32,
-                    info.initial_viewport.area.h as u32,
-                    1,
-                ],
-                layers: 1,
-                format: Format::D24S8,
-                mip_levels: 1,
-                samples: info.sample_count,
-                initial_data: None,
-                ..Default::default()
-            })
-            .expect("create persistent depth image");
-
-        let depth = ImageView {
-            img: depth_image,
-            aspect: AspectMask::Depth,
-            view_type: ImageViewType::Type2D,
-            range: SubresourceRange::new(0, 1, 0, 1),
-        };
+        let depth = make_persistent_depth(
+            ctx.as_mut(),
+            [
+                info.initial_viewport.area.w as u32,
+                info.initial_viewport.area.h as u32,
+            ],
+            info.sample_count,
+        );
 
         let environment = EnvironmentRenderer::new(
             ctx.as_mut(),
             state.as_mut(),
             EnvironmentRendererInfo {
                 initial_viewport: info.initial_viewport,
-                color_format: Format::BGRA8,
+                color_format: HDR_COLOR_FORMAT,
                 sample_count: info.sample_count,
+                anisotropy,
                 use_depth: true,
                 skybox: super::environment::sky::SkyboxInfo::default(),
                 ocean: super::environment::ocean::OceanInfo::default(),
@@ -452,7 +472,7 @@ impl DeferredRenderer {
             .set_debug_name("[MESHI] Deferred Combine")
             .vertex_compiled(Some(vertex))
             .fragment_compiled(Some(fragment))
-            .set_attachment_format(0, Format::BGRA8)
+            .set_attachment_format(0, HDR_COLOR_FORMAT)
             .set_details(GraphicsPipelineDetails {
                 color_blend_states: vec![Default::default(); 1],
                 sample_count: info.sample_count,
@@ -487,6 +507,7 @@ impl DeferredRenderer {
             viewport: info.initial_viewport,
             objects: ResourceList::default(),
             lookup: Default::default(),
+            mesh_bounds: Default::default(),
             renderables: GPUPool::new(
                 ctx.as_mut(),
                 &BufferInfo {
@@ -520,7 +541,18 @@ impl DeferredRenderer {
             ),
         };
 
-        let mut subrender = Renderers { environment, debug };
+        let lines = DebugLineRenderer::new(
+            ctx.as_mut(),
+            state.as_mut(),
+            &data.dynamic,
+            HDR_COLOR_FORMAT,
+            info.sample_count,
+        );
+        let mut subrender = Renderers {
+            environment,
+            debug,
+            lines,
+        };
 
         subrender.environment.initialize_terrain_deferred(
             ctx.as_mut(),
@@ -567,6 +599,19 @@ impl DeferredRenderer {
             },
             ShadowPipelineMode::Deferred,
         );
+        let bloom_pass = BloomPass::new(
+            ctx.as_mut(),
+            state.as_mut(),
+            &data.dynamic,
+            HDR_COLOR_FORMAT,
+            info.sample_count,
+        );
+        let tonemap_pass = TonemapPass::new(
+            ctx.as_mut(),
+            state.as_mut(),
+            &data.dynamic,
+            info.sample_count,
+        );
 
         let exec = DeferredExecution { cull_queue };
         let mut text = TextRenderer::new();
@@ -585,6 +630,8 @@ impl DeferredRenderer {
             graph,
             exec,
             sample_count: info.sample_count,
+            sampler_info,
+            anisotropy_limit,
             alloc,
             data,
             proc,
@@ -598,6 +645,12 @@ impl DeferredRenderer {
             frame_count: 0,
             frame_bump: Bump::new(),
             debug_views: DeferredDebugViews::default(),
+            shadow_filter: ShadowFilter::default(),
+            fog: FogSettings::default(),
+            bloom: BloomSettings::default(),
+            bloom_pass,
+            tonemap: TonemapSettings::default(),
+            tonemap_pass,
         }
     }
 
@@ -675,7 +728,7 @@ impl DeferredRenderer {
             .set_debug_name("[MESHI] Deferred Billboard")
             .vertex_compiled(Some(shaders[0].clone()))
             .fragment_compiled(Some(shaders[1].clone()))
-            .set_attachment_format(0, Format::BGRA8)
+            .set_attachment_format(0, HDR_COLOR_FORMAT)
             .add_table_variable_with_resources(
                 "per_obj_ssbo",
                 vec![IndexedResource {
@@ -972,6 +1025,18 @@ impl DeferredRenderer {
                 ];
                 Self::billboard_vertices_world(corners, Vec4::ONE)
             }
+            BillboardType::TargetFacing => {
+                let (right, up) = billboard.info.target_facing_axes(center);
+                let half_right = right * (size.x * 0.5);
+                let half_up = up * (size.y * 0.5);
+                let corners = [
+                    center - half_right - half_up,
+                    center + half_right - half_up,
+                    center + half_right + half_up,
+                    center - half_right + half_up,
+                ];
+                Self::billboard_vertices_world(corners, Vec4::ONE)
+            }
         };
         let mapped = self
             .ctx
@@ -1108,6 +1173,29 @@ impl DeferredRenderer {
                 "Opaque Spot Shadow Resolution",
                 Some("Controls the resolution of the opaque spot light shadow map."),
             );
+            debug_register_radial_with_description(
+                PageType::Shadow,
+                "Opaque Point Shadow Enabled",
+                DebugRegistryValue::Bool(shadows.point_enabled_mut()),
+                &[
+                    DebugRadialOption {
+                        label: "Off",
+                        value: 0.0,
+                    },
+                    DebugRadialOption {
+                        label: "On",
+                        value: 1.0,
+                    },
+                ],
+                Some("Toggle rendering the active opaque point light cube shadow map."),
+            );
+            debug_register_int_with_description(
+                PageType::Shadow,
+                Slider::new_int(0, "Opaque Point Shadow Resolution", 128.0, 4096.0, 0.0),
+                shadows.point_resolution_mut() as *mut u32,
+                "Opaque Point Shadow Resolution",
+                Some("Controls the per-face resolution of the opaque point light shadow map."),
+            );
         }
     }
 
@@ -1318,34 +1406,20 @@ impl DeferredRenderer {
         });
 
         match info {
-            RenderObjectInfo::Model(m) => {
-                let draws: Vec<Handle<PerDrawData>> = m
-                    .meshes
-                    .iter()
-                    .enumerate()
-                    .map(|(idx, mesh)| {
-                        self.proc.draw_builder.register_draw(&PerDrawData {
-                            scene_id: scene_handle,
-                            transform_id: transform_handle,
-                            material_id: mesh
-                                .material
-                                .as_ref()
-                                .and_then(|material| material.furikake_material_handle)
-                                .unwrap_or_default(),
-
-                            vertex_id: mesh.geometry.base.furikake_vertex_id.unwrap(),
-                            vertex_count: mesh.geometry.base.vertex_count,
-                            index_id: mesh.geometry.base.furikake_index_id.unwrap(),
-                            index_count: mesh.geometry.base.index_count.unwrap(),
-                            ..Default::default()
-                        })
-                    })
-                    .collect();
-
+            RenderObjectInfo::Model(info) => {
+                let m = &info.model;
+                let draws = self.register_model_draws(m, scene_handle, transform_handle);
+
+                let local_bounds = info
+                    .bounds
+                    .map(|(min, max)| Aabb { min, max })
+                    .unwrap_or_else(|| self.model_bounds(m));
                 let h = self.data.objects.push(RenderObjectData {
                     kind: RenderObjectKind::Model(m.clone()),
                     scene_handle,
+                    transform_handle,
                     draws,
+                    local_bounds,
                 });
                 Ok(to_handle(h))
             }
@@ -1386,10 +1460,16 @@ impl DeferredRenderer {
                     })
                     .collect();
 
+                let local_bounds = skinned
+                    .bounds
+                    .map(|(min, max)| Aabb { min, max })
+                    .unwrap_or_else(|| self.model_bounds(&skinned_data.model));
                 let h = self.data.objects.push(RenderObjectData {
                     kind: RenderObjectKind::SkinnedModel(skinned_data),
                     scene_handle,
+                    transform_handle,
                     draws,
+                    local_bounds,
                 });
                 Ok(to_handle(h))
             }
@@ -1398,14 +1478,53 @@ impl DeferredRenderer {
                 let h = self.data.objects.push(RenderObjectData {
                     kind: RenderObjectKind::Billboard(billboard_data),
                     scene_handle,
+                    transform_handle,
                     draws: Vec::new(),
+                    local_bounds: Aabb::UNIT,
                 });
                 Ok(to_handle(h))
             }
-            RenderObjectInfo::Empty => todo!(), //Err(MeshiError::ResourceUnavailable),
+            RenderObjectInfo::Empty => Err(MeshiError::Other(
+                "cannot register an empty render object".to_string(),
+            )),
         }
     }
 
+    fn register_model_draws(
+        &mut self,
+        model: &DeviceModel,
+        scene_handle: Handle<SceneObject>,
+        transform_handle: Handle<Transformation>,
+    ) -> Vec<Handle<PerDrawData>> {
+        model
+            .meshes
+            .iter()
+            .map(|mesh| {
+                self.proc.draw_builder.register_draw(&PerDrawData {
+                    scene_id: scene_handle,
+                    transform_id: transform_handle,
+                    material_id: mesh
+                        .material
+                        .as_ref()
+                        .and_then(|material| material.furikake_material_handle)
+                        .unwrap_or_default(),
+
+                    vertex_id: mesh.geometry.base.furikake_vertex_id.unwrap(),
+                    vertex_count: mesh.geometry.base.vertex_count,
+                    index_id: mesh.geometry.base.furikake_index_id.unwrap(),
+                    index_count: mesh.geometry.base.index_count.unwrap(),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
+    /// Local bounds of a model, derived from its mesh vertices and cached per
+    /// mesh.
+    fn model_bounds(&mut self, model: &DeviceModel) -> Aabb {
+        super::model_local_bounds(self.ctx.as_mut(), &mut self.data.mesh_bounds, model)
+    }
+
     pub fn set_skinned_animation_state(
         &mut self,
         handle: Handle<RenderObject>,
@@ -1441,6 +1560,89 @@ impl DeferredRenderer {
         }
     }
 
+    pub fn blend_skinned_animation(
+        &mut self,
+        handle: Handle<RenderObject>,
+        to_clip: u32,
+        blend_time: f32,
+    ) {
+        if !self.is_live_object(handle) {
+            warn!("Attempted to blend animation on invalid handle.");
+            return;
+        }
+
+        let obj = self.data.objects.get_ref(from_handle(handle));
+        match &obj.kind {
+            RenderObjectKind::SkinnedModel(skinned) => {
+                self.proc
+                    .skinning
+                    .blend_animation(skinned.skinning_handle, to_clip, blend_time);
+            }
+            _ => {
+                warn!("Attempted to blend animation on non-skinned object.");
+            }
+        }
+    }
+
+    pub fn add_animation_marker(
+        &mut self,
+        handle: Handle<RenderObject>,
+        clip_index: u32,
+        name: &str,
+        time_seconds: f32,
+    ) {
+        if !self.is_live_object(handle) {
+            warn!("Attempted to add an animation marker on invalid handle.");
+            return;
+        }
+
+        let obj = self.data.objects.get_ref(from_handle(handle));
+        match &obj.kind {
+            RenderObjectKind::SkinnedModel(skinned) => {
+                self.proc.skinning.add_marker(
+                    skinned.skinning_handle,
+                    clip_index,
+                    name,
+                    time_seconds,
+                );
+            }
+            _ => {
+                warn!("Attempted to add an animation marker on non-skinned object.");
+            }
+        }
+    }
+
+    /// Drains skinned animation events, resolved to the owning render object.
+    pub fn take_animation_events(&mut self) -> Vec<(Handle<RenderObject>, PendingAnimationEvent)> {
+        let events = self.proc.skinning.take_events();
+        if events.is_empty() {
+            return Vec::new();
+        }
+
+        let owners: Vec<_> = self
+            .data
+            .objects
+            .entries
+            .iter()
+            .filter_map(|h| match &self.data.objects.get_ref(*h).kind {
+                RenderObjectKind::SkinnedModel(skinned) => {
+                    Some((skinned.skinning_handle, to_handle(*h)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        events
+            .into_iter()
+            .filter_map(|event| {
+                owners
+                    .iter()
+                    .find(|(skinning, _)| skinning.slot == event.skinning.slot)
+                    .map(|(_, object)| (*object, event))
+            })
+            .collect()
+    }
+
     pub fn set_billboard_texture(&mut self, handle: Handle<RenderObject>, texture_id: u32) {
         if !handle.valid() {
             warn!("Attempted to update billboard texture on invalid handle.");
@@ -1548,6 +1750,110 @@ impl DeferredRenderer {
         }
     }
 
+    pub fn set_billboard_target(&mut self, handle: Handle<RenderObject>, target: Vec3) {
+        if !self.is_live_object(handle) {
+            warn!("Attempted to set billboard target on invalid handle.");
+            return;
+        }
+
+        let obj = self.data.objects.get_ref_mut(from_handle(handle));
+        match &mut obj.kind {
+            RenderObjectKind::Billboard(billboard) => billboard.info.target = target,
+            _ => warn!("Attempted to set billboard target on non-billboard object."),
+        }
+    }
+
+    /// Rebinds every draw of a model to `material`, keeping its geometry.
+    /// Billboards switch to the shared material instead of their own.
+    pub fn set_object_material(
+        &mut self,
+        handle: Handle<RenderObject>,
+        material: Handle<Material>,
+    ) -> Result<(), MeshiError> {
+        if !self.is_live_object(handle) {
+            return Err(MeshiError::Other(format!(
+                "cannot set material on invalid render object {}",
+                handle.slot
+            )));
+        }
+
+        let obj = self.data.objects.get_ref(from_handle(handle));
+        if matches!(obj.kind, RenderObjectKind::Billboard(_)) {
+            self.set_billboard_material(handle, Some(material));
+            return Ok(());
+        }
+
+        for draw in obj.draws.clone() {
+            if let Some(data) = self.proc.draw_builder.draw_data_mut(draw) {
+                data.material_id = material;
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the geometry of a model object, keeping its handle and
+    /// transform. Materials and bounds come from the new model.
+    pub fn set_object_model(
+        &mut self,
+        handle: Handle<RenderObject>,
+        model: &DeviceModel,
+    ) -> Result<(), MeshiError> {
+        if !self.is_live_object(handle) {
+            return Err(MeshiError::Other(format!(
+                "cannot set model on invalid render object {}",
+                handle.slot
+            )));
+        }
+
+        let obj = self.data.objects.get_ref(from_handle(handle));
+        if !matches!(obj.kind, RenderObjectKind::Model(_)) {
+            return Err(MeshiError::Other(format!(
+                "render object {} is not a model",
+                handle.slot
+            )));
+        }
+        let (scene_handle, transform_handle) = (obj.scene_handle, obj.transform_handle);
+        let old_draws = obj.draws.clone();
+
+        let draws = self.register_model_draws(model, scene_handle, transform_handle);
+        for draw in old_draws {
+            self.proc.draw_builder.release_draw(draw);
+        }
+        let local_bounds = self.model_bounds(model);
+
+        let obj = self.data.objects.get_ref_mut(from_handle(handle));
+        obj.kind = RenderObjectKind::Model(model.clone());
+        obj.draws = draws;
+        obj.local_bounds = local_bounds;
+        Ok(())
+    }
+
+    pub fn object_material(&self, handle: Handle<RenderObject>) -> Option<Handle<Material>> {
+        if !self.is_live_object(handle) {
+            return None;
+        }
+
+        let obj = self.data.objects.get_ref(from_handle(handle));
+        match &obj.kind {
+            RenderObjectKind::Billboard(billboard) => billboard.info.material,
+            _ => obj
+                .draws
+                .first()
+                .and_then(|draw| self.proc.draw_builder.draw_data(*draw))
+                .map(|data| data.material_id),
+        }
+    }
+
+    fn is_live_object(&self, handle: Handle<RenderObject>) -> bool {
+        handle.valid()
+            && self
+                .data
+                .objects
+                .entries
+                .iter()
+                .any(|h| h.slot == handle.slot && h.generation == handle.generation)
+    }
+
     pub fn release_object(&mut self, handle: Handle<RenderObject>) {
         if !handle.valid() {
             return;
@@ -1629,6 +1935,31 @@ impl DeferredRenderer {
         self.proc.scene.get_object_transform(obj.scene_handle)
     }
 
+    pub fn object_handles(&self) -> Vec<Handle<RenderObject>> {
+        self.data
+            .objects
+            .entries
+            .iter()
+            .map(|h| to_handle(*h))
+            .collect()
+    }
+
+    pub fn object_local_bounds(&self, handle: Handle<RenderObject>) -> Option<Aabb> {
+        if !self.is_live_object(handle) {
+            return None;
+        }
+
+        Some(self.data.objects.get_ref(from_handle(handle)).local_bounds)
+    }
+
+    pub fn object_draw_count(&self, handle: Handle<RenderObject>) -> usize {
+        if !self.is_live_object(handle) {
+            return 0;
+        }
+
+        self.data.objects.get_ref(from_handle(handle)).draws.len()
+    }
+
     pub fn set_object_transform(&mut self, handle: Handle<RenderObject>, transform: &glam::Mat4) {
         if !handle.valid() {
             warn!("Attempted to update transformation of invalid handle.");
@@ -1701,6 +2032,7 @@ impl DeferredRenderer {
                 color: Vec4::from_array(draw.color),
                 scale: draw.scale,
                 mode: TextDrawMode::Plain,
+                clip_rect: draw.clip_rect,
             })
             .collect();
         self.text.set_frame_draws(frame_draws);
@@ -1772,6 +2104,7 @@ impl DeferredRenderer {
                 .combine(self.proc.scene.cull());
 
             self.shadows.update_spot_light_state(self.state.as_mut());
+            self.shadows.update_point_light_state(self.state.as_mut());
 
             cmd = cmd
                 .combine(self.proc.scene.pre_compute())
@@ -1779,6 +2112,7 @@ impl DeferredRenderer {
                 .combine(self.subrender.environment.pre_compute(self.ctx.as_mut()))
                 .combine(self.shadows.pre_compute())
                 .combine(self.gui.pre_compute())
+                .combine(self.subrender.lines.pre_compute())
                 .combine(self.text.pre_compute())
                 .combine(DeferredFrameBlitter::pre_compute())
                 .sync(SyncPoint::TransferToCompute, Scope::AllCommonReads)
@@ -1860,6 +2194,12 @@ impl DeferredRenderer {
                 ..default_framebuffer_info
             });
 
+            let hdr_color = self.graph.make_image(&ImageInfo {
+                debug_name: &format!("[MESHI DEFERRED] HDR Color View {view_idx}"),
+                format: HDR_COLOR_FORMAT,
+                samples: self.sample_count,
+                ..default_framebuffer_info
+            });
             let final_combine = self.graph.make_image(&ImageInfo {
                 debug_name: &format!("[MESHI DEFERRED] Combined Framebuffer View {view_idx}"),
                 format: Format::BGRA8,
@@ -1868,7 +2208,7 @@ impl DeferredRenderer {
             });
             let scene_color = self.graph.make_image(&ImageInfo {
                 debug_name: &format!("[MESHI DEFERRED] Scene Color View {view_idx}"),
-                format: Format::BGRA8,
+                format: HDR_COLOR_FORMAT,
                 samples: self.sample_count,
                 ..default_framebuffer_info
             });
@@ -1899,7 +2239,7 @@ impl DeferredRenderer {
             deferred_pass_clear[..4].fill(Some(ClearValue::Color([0.0, 0.0, 0.0, 0.0])));
 
             let mut deferred_combine_attachments: [Option<ImageView>; 8] = [None; 8];
-            deferred_combine_attachments[0] = Some(final_combine.view);
+            deferred_combine_attachments[0] = Some(hdr_color.view);
             let mut deferred_combine_clear: [Option<ClearValue>; 8] = [None; 8];
             deferred_combine_clear[0] = Some(ClearValue::Color([0.0, 0.0, 0.0, 0.0]));
 
@@ -1923,6 +2263,11 @@ impl DeferredRenderer {
             let spot_shadow_resolution = shadow_result.spot.shadow_resolution;
             let spot_shadow_matrix = shadow_result.spot.shadow_matrix;
             let spot_shadow_map = shadow_result.spot.shadow_map;
+            let point_shadow = shadow_result.point;
+            let shadow_filter_radius = self.shadow_filter.radius();
+            let fog = self.fog;
+            let bloom = self.bloom;
+            let tonemap = self.tonemap;
 
             self.graph.add_compute_pass(|cmd| {
                 let cmd = cmd
@@ -2047,9 +2392,15 @@ impl DeferredRenderer {
                         debug_view: u32,
                         spot_shadow_texture: u32,
                         spot_shadow_resolution: u32,
-                        spot_shadow_padding0: u32,
+                        shadow_filter_radius: u32,
                         spot_shadow_padding1: u32,
                         spot_shadow_matrix: Mat4,
+                        fog_color_density: Vec4,
+                        fog_params: Vec4,
+                        point_shadow_texture: u32,
+                        point_shadow_resolution: u32,
+                        point_shadow_near: f32,
+                        point_shadow_far: f32,
                     }
 
                     let per_obj = &mut alloc.slice::<PerObj>()[0];
@@ -2064,9 +2415,16 @@ impl DeferredRenderer {
                         self.subrender.environment.cloud_settings().debug_view as u32;
                     per_obj.spot_shadow_texture = spot_shadow_bindless_id;
                     per_obj.spot_shadow_resolution = spot_shadow_resolution;
-                    per_obj.spot_shadow_padding0 = 0;
+                    per_obj.shadow_filter_radius = shadow_filter_radius;
                     per_obj.spot_shadow_padding1 = 0;
                     per_obj.spot_shadow_matrix = spot_shadow_matrix;
+                    per_obj.fog_color_density = fog.color.extend(fog.density);
+                    let fog_enabled = if fog.enabled { 1.0 } else { 0.0 };
+                    per_obj.fog_params = Vec4::new(fog_enabled, fog.start, fog.end, 0.0);
+                    per_obj.point_shadow_texture = point_shadow.shadow_bindless_id;
+                    per_obj.point_shadow_resolution = point_shadow.shadow_resolution;
+                    per_obj.point_shadow_near = point_shadow.near;
+                    per_obj.point_shadow_far = point_shadow.far;
 
                     cmd = cmd
                         .bind_graphics_pipeline(self.psos.combine_pso.handle)
@@ -2116,6 +2474,7 @@ impl DeferredRenderer {
             let debug_output_active = debug_output.is_some();
 
             let scene_color_view = scene_color.view;
+            let hdr_color_view = hdr_color.view;
             let final_combine_view = final_combine.view;
             let scene_width = self.data.viewport.area.w as u32;
             let scene_height = self.data.viewport.area.h as u32;
@@ -2146,7 +2505,7 @@ impl DeferredRenderer {
                 });
             }
             let scene_blits = vec![BlitImage {
-                src: final_combine_view.img,
+                src: hdr_color_view.img,
                 dst: scene_color_view.img,
                 src_range: SubresourceRange::new(0, 1, 0, 1),
                 dst_range: SubresourceRange::new(0, 1, 0, 1),
@@ -2219,8 +2578,9 @@ impl DeferredRenderer {
                 }
             }
 
+            let view_projection = camera_data.projection * camera_data.view_matrix();
             let mut transparent_attachments: [Option<ImageView>; 8] = [None; 8];
-            transparent_attachments[0] = Some(final_combine.view);
+            transparent_attachments[0] = Some(hdr_color.view);
             let transparent_clear: [Option<ClearValue>; 8] = [None; 8];
 
             self.graph.add_subpass(
@@ -2284,13 +2644,131 @@ impl DeferredRenderer {
                                 .environment
                                 .render_fog(&self.data.viewport, camera_handle),
                         );
+                        cmd = cmd.combine(self.subrender.lines.render(
+                            &self.data.viewport,
+                            view_projection,
+                            &mut self.data.dynamic,
+                        ));
                     }
 
-                    cmd = cmd.combine(
-                        self.gui
-                            .render_gui(&self.data.viewport, &mut self.data.dynamic),
+                    cmd
+                },
+            );
+
+            ///////////////////////////////////////////////////////////////////
+            // Deferred BLOOM pass. Thresholds and blurs the lit scene, then //
+            // adds it back underneath the GUI and text overlay.             //
+            ///////////////////////////////////////////////////////////////////
+            if bloom.enabled && !debug_output_active {
+                let bloom_horizontal = self.graph.make_image(&ImageInfo {
+                    debug_name: &format!("[MESHI DEFERRED] Bloom Horizontal View {view_idx}"),
+                    format: HDR_COLOR_FORMAT,
+                    ..default_framebuffer_info
+                });
+                let bloom_vertical = self.graph.make_image(&ImageInfo {
+                    debug_name: &format!("[MESHI DEFERRED] Bloom Vertical View {view_idx}"),
+                    format: HDR_COLOR_FORMAT,
+                    ..default_framebuffer_info
+                });
+
+                let bloom_steps = [
+                    (
+                        "[MESHI] DEFERRED BLOOM HORIZONTAL",
+                        BloomStage::BrightHorizontal,
+                        hdr_color.bindless_id,
+                        bloom_horizontal.view,
+                    ),
+                    (
+                        "[MESHI] DEFERRED BLOOM VERTICAL",
+                        BloomStage::Vertical,
+                        bloom_horizontal.bindless_id,
+                        bloom_vertical.view,
+                    ),
+                    (
+                        "[MESHI] DEFERRED BLOOM COMPOSITE",
+                        BloomStage::Composite,
+                        bloom_vertical.bindless_id,
+                        hdr_color.view,
+                    ),
+                ];
+
+                for (name, stage, source, target) in bloom_steps {
+                    let mut attachments: [Option<ImageView>; 8] = [None; 8];
+                    attachments[0] = Some(target);
+                    let mut clear: [Option<ClearValue>; 8] = [None; 8];
+                    if stage != BloomStage::Composite {
+                        clear[0] = Some(ClearValue::Color([0.0, 0.0, 0.0, 0.0]));
+                    }
+
+                    self.graph.add_subpass(
+                        &SubpassInfo {
+                            name: Some(name.to_string()),
+                            viewport: self.data.viewport,
+                            color_attachments: attachments,
+                            depth_attachment: None,
+                            clear_values: clear,
+                            depth_clear: None,
+                        },
+                        |cmd| {
+                            cmd.combine(self.bloom_pass.record(
+                                &self.data.viewport,
+                                &mut self.data.dynamic,
+                                stage,
+                                source.unwrap_or(u16::MAX) as u32,
+                                &bloom,
+                            ))
+                        },
                     );
+                }
+            }
+
+            ///////////////////////////////////////////////////////////////////
+            // Deferred TONEMAP pass. Maps the HDR scene into the display    //
+            // framebuffer. Debug views were already blitted there as-is.    //
+            ///////////////////////////////////////////////////////////////////
+            if !debug_output_active {
+                let mut tonemap_attachments: [Option<ImageView>; 8] = [None; 8];
+                tonemap_attachments[0] = Some(final_combine.view);
+                let mut tonemap_clear: [Option<ClearValue>; 8] = [None; 8];
+                tonemap_clear[0] = Some(ClearValue::Color([0.0, 0.0, 0.0, 0.0]));
+
+                self.graph.add_subpass(
+                    &SubpassInfo {
+                        name: Some("[MESHI] DEFERRED TONEMAP".to_string()),
+                        viewport: self.data.viewport,
+                        color_attachments: tonemap_attachments,
+                        depth_attachment: None,
+                        clear_values: tonemap_clear,
+                        depth_clear: None,
+                    },
+                    |cmd| {
+                        cmd.combine(self.tonemap_pass.record(
+                            &self.data.viewport,
+                            &mut self.data.dynamic,
+                            hdr_color.bindless_id.unwrap_or(u16::MAX) as u32,
+                            &tonemap,
+                        ))
+                    },
+                );
+            }
+
+            let mut overlay_attachments: [Option<ImageView>; 8] = [None; 8];
+            overlay_attachments[0] = Some(final_combine.view);
+            self.graph.add_subpass(
+                &SubpassInfo {
+                    name: Some("[MESHI] OVERLAY".to_string()),
+                    viewport: self.data.viewport,
+                    color_attachments: overlay_attachments,
+                    depth_attachment: Some(depth),
+                    clear_values: [None; 8],
+                    depth_clear: None,
+                },
+                |cmd| {
                     cmd.combine(
+                        self.gui
+                            .render_gui(&self.data.viewport, &mut self.data.dynamic),
+                    )
+                    .combine(
                         self.text
                             .render_transparent(self.ctx.as_mut(), &self.data.viewport),
                     )
@@ -2342,6 +2820,55 @@ impl DeferredRenderer {
             .environment
             .set_terrain_project_key(project_key);
     }
+
+    /// Resize the scene output, recreating the persistent depth target and
+    /// the cloud passes that sample it.
+    pub fn resize(&mut self, extent: [u32; 2]) {
+        let current = [
+            self.data.viewport.area.w as u32,
+            self.data.viewport.area.h as u32,
+        ];
+        if extent.contains(&0) || extent == current {
+            return;
+        }
+
+        let old_depth = self.depth;
+        self.data.viewport = super::resized_viewport(self.data.viewport, extent);
+        self.depth = make_persistent_depth(self.ctx.as_mut(), extent, self.sample_count);
+        self.subrender.environment.resize(
+            self.ctx.as_mut(),
+            self.state.as_mut(),
+            &self.data.viewport,
+            self.depth,
+        );
+        self.ctx.destroy_image(old_depth.img);
+    }
+}
+
+fn make_persistent_depth(
+    ctx: &mut Context,
+    extent: [u32; 2],
+    sample_count: SampleCount,
+) -> ImageView {
+    let img = ctx
+        .make_image(&ImageInfo {
+            debug_name: "[MESHI DEFERRED] Persistent Depth",
+            dim: [extent[0], extent[1], 1],
+            layers: 1,
+            format: Format::D24S8,
+            mip_levels: 1,
+            samples: sample_count,
+            initial_data: None,
+            ..Default::default()
+        })
+        .expect("create persistent depth image");
+
+    ImageView {
+        img,
+        aspect: AspectMask::Depth,
+        view_type: ImageViewType::Type2D,
+        range: SubresourceRange::new(0, 1, 0, 1),
+    }
 }
 
 impl Renderer for DeferredRenderer {
@@ -2349,6 +2876,10 @@ impl Renderer for DeferredRenderer {
         self.data.viewport
     }
 
+    fn resize(&mut self, extent: [u32; 2]) {
+        DeferredRenderer::resize(self, extent);
+    }
+
     fn context(&mut self) -> &'static mut Context {
         unsafe { &mut (*(self.ctx.as_mut() as *mut Context)) }
     }
@@ -2383,6 +2914,26 @@ impl Renderer for DeferredRenderer {
         self.subrender.environment.update_ocean(settings);
     }
 
+    fn ocean_patch_sizes(&self) -> [f32; 3] {
+        self.subrender.environment.ocean_patch_sizes()
+    }
+
+    fn set_fog(&mut self, settings: FogSettings) {
+        self.fog = settings;
+    }
+
+    fn set_bloom(&mut self, settings: BloomSettings) {
+        self.bloom = settings;
+    }
+
+    fn set_tonemap(&mut self, settings: TonemapSettings) {
+        self.tonemap = settings;
+    }
+
+    fn set_debug_lines(&mut self, vertices: &[DebugLineVertex]) {
+        self.subrender.lines.upload(vertices);
+    }
+
     fn set_spot_shadow_light(&mut self, light: Option<super::SpotShadowLight>) {
         let previous_handle = self.shadows.spot_light_handle();
         let next_handle = light.map(|entry| entry.handle);
@@ -2408,6 +2959,51 @@ impl Renderer for DeferredRenderer {
         self.shadows.set_spot_light(light);
     }
 
+    fn set_point_shadow_light(&mut self, light: Option<super::PointShadowLight>) {
+        let previous_handle = self.shadows.point_light_handle();
+        let next_handle = light.map(|entry| entry.handle);
+        if previous_handle != next_handle {
+            self.state
+                .reserved_mut(
+                    "meshi_bindless_lights",
+                    |lights: &mut furikake::reservations::bindless_lights::ReservedBindlessLights| {
+                        if let Some(handle) = previous_handle {
+                            if handle.valid() {
+                                lights.light_mut(handle).extra.z = 0.0;
+                            }
+                        }
+                        if let Some(handle) = next_handle {
+                            if handle.valid() {
+                                lights.light_mut(handle).extra.z = 1.0;
+                            }
+                        }
+                    },
+                )
+                .ok();
+        }
+        self.shadows.set_point_light(light);
+    }
+
+    fn set_shadow_resolution(&mut self, resolution: u32) {
+        self.shadows.set_resolution(resolution);
+    }
+
+    fn set_shadow_filter(&mut self, filter: ShadowFilter) {
+        self.shadow_filter = filter;
+    }
+
+    fn local_shadow_map_count(&self) -> usize {
+        self.shadows.local_shadow_map_count()
+    }
+
+    fn sampler_anisotropy(&self) -> u32 {
+        super::sampler_info_anisotropy(&self.sampler_info)
+    }
+
+    fn sampler_anisotropy_limit(&self) -> u32 {
+        self.anisotropy_limit.unwrap_or(1)
+    }
+
     fn register_object(
         &mut self,
         info: &RenderObjectInfo,
@@ -2419,6 +3015,29 @@ impl Renderer for DeferredRenderer {
         DeferredRenderer::set_skinned_animation_state(self, handle, state);
     }
 
+    fn blend_skinned_animation(
+        &mut self,
+        handle: Handle<RenderObject>,
+        to_clip: u32,
+        blend_time: f32,
+    ) {
+        DeferredRenderer::blend_skinned_animation(self, handle, to_clip, blend_time);
+    }
+
+    fn add_animation_marker(
+        &mut self,
+        handle: Handle<RenderObject>,
+        clip_index: u32,
+        name: &str,
+        time_seconds: f32,
+    ) {
+        DeferredRenderer::add_animation_marker(self, handle, clip_index, name, time_seconds);
+    }
+
+    fn take_animation_events(&mut self) -> Vec<(Handle<RenderObject>, PendingAnimationEvent)> {
+        DeferredRenderer::take_animation_events(self)
+    }
+
     fn set_billboard_texture(&mut self, handle: Handle<RenderObject>, texture_id: u32) {
         DeferredRenderer::set_billboard_texture(self, handle, texture_id);
     }
@@ -2431,6 +3050,30 @@ impl Renderer for DeferredRenderer {
         DeferredRenderer::set_billboard_material(self, handle, material);
     }
 
+    fn set_billboard_target(&mut self, handle: Handle<RenderObject>, target: Vec3) {
+        DeferredRenderer::set_billboard_target(self, handle, target);
+    }
+
+    fn set_object_material(
+        &mut self,
+        handle: Handle<RenderObject>,
+        material: Handle<Material>,
+    ) -> Result<(), MeshiError> {
+        DeferredRenderer::set_object_material(self, handle, material)
+    }
+
+    fn object_material(&self, handle: Handle<RenderObject>) -> Option<Handle<Material>> {
+        DeferredRenderer::object_material(self, handle)
+    }
+
+    fn set_object_model(
+        &mut self,
+        handle: Handle<RenderObject>,
+        model: &DeviceModel,
+    ) -> Result<(), MeshiError> {
+        DeferredRenderer::set_object_model(self, handle, model)
+    }
+
     fn set_object_transform(&mut self, handle: Handle<RenderObject>, transform: &glam::Mat4) {
         DeferredRenderer::set_object_transform(self, handle, transform);
     }
@@ -2439,6 +3082,18 @@ impl Renderer for DeferredRenderer {
         DeferredRenderer::object_transform(self, handle)
     }
 
+    fn object_local_bounds(&self, handle: Handle<RenderObject>) -> Option<Aabb> {
+        DeferredRenderer::object_local_bounds(self, handle)
+    }
+
+    fn object_draw_count(&self, handle: Handle<RenderObject>) -> usize {
+        DeferredRenderer::object_draw_count(self, handle)
+    }
+
+    fn object_handles(&self) -> Vec<Handle<RenderObject>> {
+        DeferredRenderer::object_handles(self)
+    }
+
     fn release_object(&mut self, handle: Handle<RenderObject>) {
         DeferredRenderer::release_object(self, handle);
     }
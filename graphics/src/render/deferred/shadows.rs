@@ -1,8 +1,8 @@
 use crate::ShadowCascadeSettings;
-use crate::render::SpotShadowLight;
 use crate::render::deferred::shadow::{ShadowPass, ShadowPassInfo};
 use crate::render::environment::{EnvironmentRenderer, terrain::TERRAIN_DRAW_BIN};
 use crate::render::gpu_draw_builder::GPUDrawBuilder;
+use crate::render::{PointShadowLight, SpotShadowLight};
 use dashi::cmd::Executable;
 use dashi::gpu::cmd::{Scope, SyncPoint};
 use dashi::{
@@ -59,9 +59,18 @@ pub struct SpotShadowResult {
     pub shadow_matrix: Mat4,
 }
 
+pub struct PointShadowResult {
+    pub shadow_map: Option<TransientImage>,
+    pub shadow_bindless_id: u32,
+    pub shadow_resolution: u32,
+    pub near: f32,
+    pub far: f32,
+}
+
 pub struct ShadowResult {
     pub cascaded: CascadedShadowResult,
     pub spot: SpotShadowResult,
+    pub point: PointShadowResult,
 }
 
 pub struct CascadedShadows {
@@ -78,11 +87,55 @@ pub struct SpotShadows {
     enabled: bool,
     resolution: u32,
     spot_light: Option<SpotShadowLight>,
+    allocated: bool,
+}
+
+/// Cube shadows for a single point light. The six faces are rendered into a
+/// 3x2 depth atlas, face `i` at tile `(i % 3, i / 3)`.
+pub struct PointShadows {
+    mode: ShadowPipelineMode,
+    main_pass: ShadowPass,
+    terrain_pass: ShadowPass,
+    enabled: bool,
+    resolution: u32,
+    point_light: Option<PointShadowLight>,
+    allocated: bool,
 }
 
 pub struct ShadowSystem {
     cascaded: CascadedShadows,
     spot: SpotShadows,
+    point: PointShadows,
+}
+
+/// Largest per-map resolution accepted by [`ShadowSystem::set_resolution`].
+pub const MAX_SHADOW_RESOLUTION: u32 = 4096;
+const DEFAULT_POINT_SHADOW_RESOLUTION: u32 = 1024;
+const LOCAL_SHADOW_NEAR: f32 = 0.1;
+
+/// Forward and up axes of each cube face. Mirrored by `point_shadow_face` in
+/// the deferred combine shader.
+const CUBE_FACES: [(Vec3, Vec3); 6] = [
+    (Vec3::X, Vec3::NEG_Y),
+    (Vec3::NEG_X, Vec3::NEG_Y),
+    (Vec3::Y, Vec3::Z),
+    (Vec3::NEG_Y, Vec3::NEG_Z),
+    (Vec3::Z, Vec3::NEG_Y),
+    (Vec3::NEG_Z, Vec3::NEG_Y),
+];
+
+fn clip_space_fixup() -> Mat4 {
+    Mat4::from_cols_array(&[
+        1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0, 0.5, 1.0,
+    ])
+}
+
+fn local_shadow_far(light: &LightInfo) -> f32 {
+    if light.range > LOCAL_SHADOW_NEAR {
+        light.range
+    } else {
+        1000.0
+    }
 }
 
 impl CascadedShadows {
@@ -183,12 +236,6 @@ impl CascadedShadows {
         splits
     }
 
-    fn clip_space_fixup() -> Mat4 {
-        Mat4::from_cols_array(&[
-            1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0, 0.5, 1.0,
-        ])
-    }
-
     fn compute_shadow_cascade_data(
         &self,
         camera: &furikake::types::Camera,
@@ -265,7 +312,7 @@ impl CascadedShadows {
             }
 
             let light_proj = Mat4::orthographic_rh(min.x, max.x, min.y, max.y, near, far);
-            matrices[cascade_index] = Self::clip_space_fixup() * light_proj * light_view;
+            matrices[cascade_index] = clip_space_fixup() * light_proj * light_view;
         }
 
         let bump = crate::render::global_bump().get();
@@ -450,6 +497,7 @@ impl SpotShadows {
             enabled: true,
             resolution,
             spot_light: None,
+            allocated: false,
         }
     }
 
@@ -484,11 +532,6 @@ impl SpotShadows {
             },
         );
     }
-    fn clip_space_fixup() -> Mat4 {
-        Mat4::from_cols_array(&[
-            1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.5, 0.0, 0.0, 0.0, 0.5, 1.0,
-        ])
-    }
 
     fn spot_shadow_matrix(light: &LightInfo) -> Mat4 {
         let position = Vec3::new(light.pos_x, light.pos_y, light.pos_z);
@@ -506,14 +549,8 @@ impl SpotShadows {
         let view = Mat4::look_at_rh(position, position + direction, up);
         let outer = light.spot_outer_angle_rad.max(0.01);
         let fov = (outer * 2.0).clamp(0.01, std::f32::consts::PI - 0.01);
-        let near = 0.1;
-        let far = if light.range > near {
-            light.range
-        } else {
-            1000.0
-        };
-        let proj = Mat4::perspective_rh(fov, 1.0, near, far);
-        Self::clip_space_fixup() * proj * view
+        let proj = Mat4::perspective_rh(fov, 1.0, LOCAL_SHADOW_NEAR, local_shadow_far(light));
+        clip_space_fixup() * proj * view
     }
 
     pub fn process(
@@ -555,6 +592,7 @@ impl SpotShadows {
                 shadow_map = Some(spot_shadow_image);
             }
         }
+        self.allocated = shadow_map.is_some();
 
         if let Some(spot_shadow_map) = shadow_map.as_ref() {
             let spot_shadow_viewport = bump.alloc(Viewport {
@@ -642,6 +680,219 @@ impl SpotShadows {
     }
 }
 
+impl PointShadows {
+    pub fn new(
+        ctx: &mut Context,
+        state: &mut BindlessState,
+        draw_builder: &GPUDrawBuilder,
+        terrain_draw_builder: &GPUDrawBuilder,
+        dynamic: &DynamicAllocator,
+        info: ShadowPassInfo,
+        mode: ShadowPipelineMode,
+    ) -> Self {
+        let resolution = info.resolution.min(DEFAULT_POINT_SHADOW_RESOLUTION);
+        let main_pass = ShadowPass::new(ctx, state, draw_builder, dynamic, info);
+        let terrain_pass = ShadowPass::new(ctx, state, terrain_draw_builder, dynamic, info);
+        Self {
+            mode,
+            main_pass,
+            terrain_pass,
+            enabled: true,
+            resolution,
+            point_light: None,
+            allocated: false,
+        }
+    }
+
+    pub fn enabled_mut(&mut self) -> &mut bool {
+        &mut self.enabled
+    }
+
+    pub fn resolution_mut(&mut self) -> &mut u32 {
+        &mut self.resolution
+    }
+
+    pub fn set_light(&mut self, light: Option<PointShadowLight>) {
+        self.point_light = light;
+    }
+
+    pub fn light_handle(&self) -> Option<Handle<furikake::types::Light>> {
+        self.point_light.map(|light| light.handle)
+    }
+
+    pub fn update_light_state(&self, state: &mut BindlessState) {
+        let Some(point_light) = self.point_light else {
+            return;
+        };
+        let enabled_value = if self.enabled { 1.0 } else { 0.0 };
+        let handle = point_light.handle;
+        let _ = state.reserved_mut(
+            "meshi_bindless_lights",
+            |lights: &mut furikake::reservations::bindless_lights::ReservedBindlessLights| {
+                if handle.valid() {
+                    lights.light_mut(handle).extra.z = enabled_value;
+                }
+            },
+        );
+    }
+
+    /// View-projection for each cube face, in [`CUBE_FACES`] order.
+    pub fn cube_face_matrices(light: &LightInfo) -> [Mat4; 6] {
+        let position = Vec3::new(light.pos_x, light.pos_y, light.pos_z);
+        let proj = Mat4::perspective_rh(
+            std::f32::consts::FRAC_PI_2,
+            1.0,
+            LOCAL_SHADOW_NEAR,
+            local_shadow_far(light),
+        );
+        CUBE_FACES.map(|(forward, up)| {
+            clip_space_fixup() * proj * Mat4::look_at_rh(position, position + forward, up)
+        })
+    }
+
+    pub fn process(
+        &mut self,
+        graph: &mut RenderGraph,
+        state: &BindlessState,
+        dynamic: &mut DynamicAllocator,
+        draw_builder: &GPUDrawBuilder,
+        environment: &mut EnvironmentRenderer,
+        view_idx: u32,
+    ) -> PointShadowResult {
+        let bump = crate::render::global_bump().get();
+
+        let mut result = PointShadowResult {
+            shadow_map: None,
+            shadow_bindless_id: u16::MAX as u32,
+            shadow_resolution: 0,
+            near: LOCAL_SHADOW_NEAR,
+            far: 0.0,
+        };
+        let point_light = match self.point_light {
+            Some(light) if self.enabled => light,
+            _ => {
+                self.allocated = false;
+                return result;
+            }
+        };
+
+        let face_resolution = self.resolution.max(1);
+        let face_matrices = bump.alloc(Self::cube_face_matrices(&point_light.info));
+        let mut point_shadow_image = graph.make_image(&ImageInfo {
+            debug_name: &format!("[MESHI {}] Point Shadow Map {view_idx}", self.mode.label()),
+            dim: [face_resolution * 3, face_resolution * 2, 1],
+            layers: 1,
+            format: Format::D24S8,
+            mip_levels: 1,
+            samples: self.main_pass.sample_count(),
+            initial_data: None,
+            ..Default::default()
+        });
+        point_shadow_image.view.aspect = AspectMask::Depth;
+
+        let atlas_viewport = bump.alloc(Viewport {
+            area: dashi::FRect2D {
+                x: 0.0,
+                y: 0.0,
+                w: (face_resolution * 3) as f32,
+                h: (face_resolution * 2) as f32,
+            },
+            scissor: Rect2D {
+                x: 0,
+                y: 0,
+                w: face_resolution * 3,
+                h: face_resolution * 2,
+            },
+            ..Default::default()
+        });
+
+        let shadow_clear: [Option<ClearValue>; 8] = [None; 8];
+        graph.add_subpass(
+            &SubpassInfo {
+                name: Some("[MESHI] POINT SHADOW PASS".to_string()),
+                viewport: *atlas_viewport,
+                color_attachments: [None; 8],
+                depth_attachment: Some(point_shadow_image.view),
+                clear_values: shadow_clear,
+                depth_clear: Some(self.main_pass.depth_clear_value()),
+            },
+            |mut cmd| {
+                let indices = state
+                    .binding("meshi_bindless_indices")
+                    .expect("Bindless indices not available")
+                    .binding();
+
+                let indices_handle = match indices {
+                    ReservedBinding::TableBinding {
+                        binding: _,
+                        resources,
+                    } => match resources[0].resource {
+                        ShaderResource::StorageBuffer(view) => Some(view.handle),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+
+                let Some(indices_handle) = indices_handle else {
+                    return cmd;
+                };
+
+                let (terrain_draw_list, terrain_draw_count) = environment
+                    .terrain_draw_builder()
+                    .map(|builder| (builder.draw_list(), builder.draw_count()))
+                    .unwrap_or((Handle::default(), 0));
+
+                for (face, face_matrix) in face_matrices.iter().enumerate() {
+                    let tile_x = face as u32 % 3;
+                    let tile_y = face as u32 / 3;
+                    let face_viewport = Viewport {
+                        area: dashi::FRect2D {
+                            x: (tile_x * face_resolution) as f32,
+                            y: (tile_y * face_resolution) as f32,
+                            w: face_resolution as f32,
+                            h: face_resolution as f32,
+                        },
+                        scissor: Rect2D {
+                            x: tile_x * face_resolution,
+                            y: tile_y * face_resolution,
+                            w: face_resolution,
+                            h: face_resolution,
+                        },
+                        ..Default::default()
+                    };
+                    cmd = cmd.combine(self.main_pass.record(
+                        &face_viewport,
+                        dynamic,
+                        *face_matrix,
+                        indices_handle,
+                        draw_builder.draw_list(),
+                        draw_builder.draw_count(),
+                    ));
+                    if terrain_draw_count > 0 {
+                        cmd = cmd.combine(self.terrain_pass.record(
+                            &face_viewport,
+                            dynamic,
+                            *face_matrix,
+                            indices_handle,
+                            terrain_draw_list,
+                            terrain_draw_count,
+                        ));
+                    }
+                }
+
+                cmd
+            },
+        );
+
+        self.allocated = true;
+        result.shadow_bindless_id = point_shadow_image.bindless_id.unwrap_or(u16::MAX) as u32;
+        result.shadow_resolution = face_resolution;
+        result.far = local_shadow_far(&point_light.info);
+        result.shadow_map = Some(point_shadow_image);
+        result
+    }
+}
+
 impl ShadowSystem {
     pub fn new(
         ctx: &mut Context,
@@ -672,7 +923,20 @@ impl ShadowSystem {
             info,
             mode,
         );
-        Self { cascaded, spot }
+        let point = PointShadows::new(
+            ctx,
+            state,
+            draw_builder,
+            terrain_draw_builder,
+            dynamic,
+            info,
+            mode,
+        );
+        Self {
+            cascaded,
+            spot,
+            point,
+        }
     }
 
     pub fn pre_compute(&mut self) -> CommandStream<Executable> {
@@ -722,6 +986,39 @@ impl ShadowSystem {
         self.spot.update_light_state(state);
     }
 
+    pub fn point_enabled_mut(&mut self) -> &mut bool {
+        self.point.enabled_mut()
+    }
+
+    pub fn point_resolution_mut(&mut self) -> &mut u32 {
+        self.point.resolution_mut()
+    }
+
+    pub fn set_point_light(&mut self, light: Option<PointShadowLight>) {
+        self.point.set_light(light);
+    }
+
+    pub fn point_light_handle(&self) -> Option<Handle<furikake::types::Light>> {
+        self.point.light_handle()
+    }
+
+    pub fn update_point_light_state(&self, state: &mut BindlessState) {
+        self.point.update_light_state(state);
+    }
+
+    /// Set the cascade, spot and point-face resolution together.
+    pub fn set_resolution(&mut self, resolution: u32) {
+        let resolution = resolution.clamp(1, MAX_SHADOW_RESOLUTION);
+        *self.cascaded.resolution_mut() = resolution;
+        self.spot.resolution = resolution;
+        self.point.resolution = resolution;
+    }
+
+    /// Spot and point shadow maps allocated by the last processed view.
+    pub fn local_shadow_map_count(&self) -> usize {
+        self.spot.allocated as usize + self.point.allocated as usize
+    }
+
     pub fn cascade_buffer(&self) -> &StagedBuffer {
         self.cascaded.cascade_buffer()
     }
@@ -754,7 +1051,69 @@ impl ShadowSystem {
         let spot = self
             .spot
             .process(graph, state, dynamic, draw_builder, environment, view_idx);
+        let point = self
+            .point
+            .process(graph, state, dynamic, draw_builder, environment, view_idx);
+
+        ShadowResult {
+            cascaded,
+            spot,
+            point,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point_light(position: Vec3, range: f32) -> LightInfo {
+        LightInfo::point(position, Vec3::ONE, 1.0, range)
+    }
 
-        ShadowResult { cascaded, spot }
+    fn dominant_face(direction: Vec3) -> usize {
+        let abs = direction.abs();
+        if abs.x >= abs.y && abs.x >= abs.z {
+            if direction.x >= 0.0 { 0 } else { 1 }
+        } else if abs.y >= abs.z {
+            if direction.y >= 0.0 { 2 } else { 3 }
+        } else if direction.z >= 0.0 {
+            4
+        } else {
+            5
+        }
+    }
+
+    #[test]
+    fn cube_faces_cover_their_dominant_axis() {
+        let position = Vec3::new(2.0, 1.0, -3.0);
+        let matrices = PointShadows::cube_face_matrices(&point_light(position, 20.0));
+        let directions = [
+            Vec3::new(1.0, 0.3, -0.6),
+            Vec3::new(-1.0, -0.9, 0.2),
+            Vec3::new(0.1, 1.0, 0.8),
+            Vec3::new(-0.4, -1.0, 0.4),
+            Vec3::new(0.9, -0.2, 1.0),
+            Vec3::new(0.0, 0.5, -1.0),
+        ];
+        for direction in directions {
+            let face = dominant_face(direction);
+            let clip = matrices[face] * (position + direction * 5.0).extend(1.0);
+            let ndc = clip.truncate() / clip.w;
+            assert!(clip.w > 0.0, "face {face} sees {direction} behind it");
+            assert!(ndc.x.abs() <= 1.0 + 1e-5 && ndc.y.abs() <= 1.0 + 1e-5);
+            assert!((0.0..=1.0).contains(&ndc.z));
+        }
+    }
+
+    #[test]
+    fn cube_face_depth_grows_with_distance() {
+        let matrices = PointShadows::cube_face_matrices(&point_light(Vec3::ZERO, 50.0));
+        let depth = |distance: f32| {
+            let clip = matrices[0] * Vec3::new(distance, 0.0, 0.0).extend(1.0);
+            clip.z / clip.w
+        };
+        assert!(depth(1.0) < depth(10.0));
+        assert!(depth(10.0) < depth(49.0));
     }
 }
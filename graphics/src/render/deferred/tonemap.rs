@@ -0,0 +1,119 @@
+use bento::builder::{PSOBuilder, PSO};
+use bento::{Compiler, OptimizationLevel, Request, ShaderLang};
+use dashi::cmd::PendingGraphics;
+use dashi::driver::command::Draw;
+use dashi::{
+    CommandStream, Context, Format, GraphicsPipelineDetails, IndexedResource, SampleCount,
+    ShaderResource, ShaderType, Viewport,
+};
+use furikake::PSOBuilderFurikakeExt;
+
+use crate::TonemapSettings;
+
+#[repr(C)]
+struct TonemapParams {
+    source_texture_id: u32,
+    operator: u32,
+    exposure: f32,
+    padding: f32,
+}
+
+/// Fullscreen pass that maps the HDR scene into the display-range framebuffer.
+pub struct TonemapPass {
+    pipeline: PSO,
+}
+
+impl TonemapPass {
+    pub fn new(
+        ctx: &mut Context,
+        state: &mut furikake::BindlessState,
+        dynamic: &dashi::DynamicAllocator,
+        sample_count: SampleCount,
+    ) -> Self {
+        let compiler = Compiler::new().expect("Failed to create shader compiler");
+        let base_request = Request {
+            name: Some("meshi_deferred_tonemap".to_string()),
+            lang: ShaderLang::Slang,
+            stage: ShaderType::Vertex,
+            optimization: OptimizationLevel::Performance,
+            debug_symbols: true,
+            defines: Default::default(),
+        };
+
+        let vertex = compiler
+            .compile(
+                include_str!("shaders/deferred_combine_vert.slang").as_bytes(),
+                &Request {
+                    stage: ShaderType::Vertex,
+                    ..base_request.clone()
+                },
+            )
+            .expect("Failed to compile tonemap vertex shader");
+        let fragment = compiler
+            .compile(
+                include_str!("shaders/tonemap_frag.slang").as_bytes(),
+                &Request {
+                    stage: ShaderType::Fragment,
+                    ..base_request
+                },
+            )
+            .expect("Failed to compile tonemap fragment shader");
+
+        let pso = PSOBuilder::new()
+            .set_debug_name("[MESHI] Deferred Tonemap")
+            .vertex_compiled(Some(vertex))
+            .fragment_compiled(Some(fragment))
+            .set_attachment_format(0, Format::BGRA8)
+            .set_details(GraphicsPipelineDetails {
+                color_blend_states: vec![Default::default(); 1],
+                sample_count,
+                ..Default::default()
+            })
+            .add_table_variable_with_resources(
+                "tonemap_params_ssbo",
+                vec![IndexedResource {
+                    resource: ShaderResource::DynamicStorage(dynamic.state()),
+                    slot: 0,
+                }],
+            )
+            .add_reserved_table_variables(state)
+            .unwrap()
+            .build(ctx)
+            .expect("Failed to build tonemap pipeline");
+
+        state.register_pso_tables(&pso);
+
+        Self { pipeline: pso }
+    }
+
+    /// Records the fullscreen draw tonemapping `source_texture_id`.
+    pub fn record(
+        &self,
+        viewport: &Viewport,
+        dynamic: &mut dashi::DynamicAllocator,
+        source_texture_id: u32,
+        settings: &TonemapSettings,
+    ) -> CommandStream<PendingGraphics> {
+        let mut alloc = dynamic
+            .bump()
+            .expect("Failed to allocate tonemap dynamic buffer");
+        alloc.slice::<TonemapParams>()[0] = TonemapParams {
+            source_texture_id,
+            operator: settings.operator as u32,
+            exposure: settings.exposure,
+            padding: 0.0,
+        };
+
+        CommandStream::<PendingGraphics>::subdraw()
+            .bind_graphics_pipeline(self.pipeline.handle)
+            .update_viewport(viewport)
+            .draw(&Draw {
+                bind_tables: self.pipeline.tables(),
+                dynamic_buffers: [None, Some(alloc), None, None],
+                instance_count: 1,
+                count: 3,
+                ..Default::default()
+            })
+            .unbind_graphics_pipeline()
+    }
+}
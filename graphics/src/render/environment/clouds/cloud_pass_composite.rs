@@ -44,6 +44,7 @@ impl CloudCompositePass {
         history_weight: [Handle<dashi::Buffer>; 2],
         shadow_buffer: Handle<dashi::Buffer>,
         depth_view: dashi::ImageView,
+        color_format: Format,
         sample_count: dashi::SampleCount,
     ) -> Self {
         let params = StagedBuffer::new(
@@ -193,7 +194,7 @@ impl CloudCompositePass {
                     slot: 0, // binding 2
                 }],
             )
-            .set_attachment_format(0, Format::BGRA8)
+            .set_attachment_format(0, color_format)
             .add_depth_target(AttachmentDesc {
                 format: Format::D24S8,
                 samples: sample_count,
@@ -53,6 +53,7 @@ pub struct CloudRenderer {
     time: f32,
     timings: CloudTimingResult,
     depth_view: dashi::ImageView,
+    color_format: dashi::Format,
     sample_count: dashi::SampleCount,
     pending_weather_map: Option<dashi::ImageView>,
     weather_map_configured: bool,
@@ -631,6 +632,7 @@ impl CloudRenderer {
         state: &mut BindlessState,
         viewport: &Viewport,
         depth_view: dashi::ImageView,
+        color_format: dashi::Format,
         sample_count: dashi::SampleCount,
         environment_map: ImageView,
     ) -> Self {
@@ -679,6 +681,7 @@ impl CloudRenderer {
             temporal_pass.history_weight,
             shadow_pass.shadow_buffer,
             depth_view,
+            color_format,
             sample_count,
         );
 
@@ -698,6 +701,7 @@ impl CloudRenderer {
             time: 0.0,
             timings: CloudTimingResult::default(),
             depth_view,
+            color_format,
             sample_count,
             pending_weather_map: None,
             weather_map_configured: true,
@@ -708,6 +712,55 @@ impl CloudRenderer {
         self.settings
     }
 
+    /// Recreate the low-resolution passes for a resized scene. Assets, the
+    /// shadow pass and settings carry over; temporal history restarts.
+    pub fn resize(
+        &mut self,
+        ctx: &mut Context,
+        state: &mut BindlessState,
+        viewport: &Viewport,
+        depth_view: dashi::ImageView,
+        environment_map: ImageView,
+    ) {
+        let low_resolution = calc_low_res(viewport, self.settings.low_res_scale);
+        self.raymarch_pass = CloudRaymarchPass::new(
+            ctx,
+            state,
+            &self.assets,
+            &self.shadow_pass,
+            environment_map,
+            low_resolution,
+            TIMER_RAYMARCH,
+        );
+        self.temporal_pass = CloudTemporalPass::new(
+            ctx,
+            state,
+            low_resolution,
+            self.raymarch_pass.color_buffer,
+            self.raymarch_pass.transmittance_buffer,
+            self.raymarch_pass.depth_buffer,
+            TIMER_TEMPORAL,
+        );
+        self.composite_pass = CloudCompositePass::new(
+            ctx,
+            &self.assets,
+            self.temporal_pass.history_color,
+            self.temporal_pass.history_transmittance,
+            self.temporal_pass.history_depth,
+            self.raymarch_pass.steps_buffer,
+            self.temporal_pass.history_weight,
+            self.shadow_pass.shadow_buffer,
+            depth_view,
+            self.color_format,
+            self.sample_count,
+        );
+        state.register_pso_tables(self.composite_pass.pipeline());
+
+        self.low_resolution = low_resolution;
+        self.depth_view = depth_view;
+        self.frame_index = 0;
+    }
+
     pub fn set_settings(&mut self, settings: CloudSettings) {
         self.settings = settings;
     }
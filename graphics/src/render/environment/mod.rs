@@ -24,6 +24,7 @@ pub struct EnvironmentRendererInfo {
     pub initial_viewport: Viewport,
     pub color_format: Format,
     pub sample_count: SampleCount,
+    pub anisotropy: u32,
     pub use_depth: bool,
     pub skybox: sky::SkyboxInfo,
     pub ocean: ocean::OceanInfo,
@@ -87,6 +88,7 @@ impl EnvironmentRenderer {
                 state,
                 &info.initial_viewport,
                 depth_view,
+                info.color_format,
                 info.sample_count,
                 sky.environment_cubemap_view(),
             )
@@ -167,6 +169,10 @@ impl EnvironmentRenderer {
         self.ocean.update(settings);
     }
 
+    pub fn ocean_patch_sizes(&self) -> [f32; 3] {
+        self.ocean.cascade_patch_sizes()
+    }
+
     pub fn update_skybox(&mut self, settings: sky::SkyboxFrameSettings) {
         self.sky.update_skybox(settings);
     }
@@ -423,6 +429,20 @@ impl EnvironmentRenderer {
         }
     }
 
+    /// Rebuild viewport-sized cloud targets against a new scene depth view.
+    pub fn resize(
+        &mut self,
+        ctx: &mut Context,
+        state: &mut BindlessState,
+        viewport: &Viewport,
+        depth_view: ImageView,
+    ) {
+        let environment_map = self.sky.environment_cubemap_view();
+        if let Some(clouds) = self.clouds.as_mut() {
+            clouds.resize(ctx, state, viewport, depth_view, environment_map);
+        }
+    }
+
     pub fn set_cloud_weather_map(&mut self, view: Option<ImageView>) {
         if let Some(clouds) = self.clouds.as_mut() {
             clouds.set_authored_weather_map(view);
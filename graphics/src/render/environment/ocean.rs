@@ -19,6 +19,7 @@ use crate::gui::debug::{
     DebugRadialOption, DebugRegistryValue, PageType, debug_register_int_with_description,
     debug_register_radial_with_description, debug_register_with_description,
 };
+use crate::render::anisotropic_sampler_info;
 
 #[derive(Clone, Copy)]
 pub struct OceanInfo {
@@ -55,6 +56,23 @@ impl Default for OceanInfo {
     }
 }
 
+impl OceanInfo {
+    /// World-space half-size of each cascade patch.
+    pub fn cascade_patch_sizes(&self) -> [f32; 3] {
+        std::array::from_fn(|index| self.patch_size * self.cascade_patch_scales[index])
+    }
+}
+
+/// Rotation of each Gerstner swell away from the wind, one wave per cascade.
+const GERSTNER_ANGLES: [f32; 3] = [0.0, 0.45, -0.6];
+/// Amplitude weight of the near, mid, and far cascade swells.
+const GERSTNER_WEIGHTS: [f32; 3] = [0.25, 0.5, 1.0];
+/// Horizontal crest sharpening shared by all swells.
+const GERSTNER_STEEPNESS: f32 = 0.6;
+const GERSTNER_GRAVITY: f32 = 9.81;
+/// Fixed-point steps used to undo horizontal displacement in height queries.
+const GERSTNER_INVERSE_STEPS: usize = 4;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OceanDebugView {
     None = 0,
@@ -88,6 +106,7 @@ pub struct OceanFrameSettings {
     /// Scales overall wave height, slope, and velocity (1.0 = default).
     pub wave_amplitude: f32,
     /// Multiplier for Gerstner wave amplitude relative to `wave_amplitude`.
+    /// `0.0` (the default) leaves the swell off.
     pub gerstner_amplitude: f32,
     /// Per-cascade spectrum amplitude multipliers for near, mid, and far cascades.
     pub cascade_spectrum_scales: [f32; 3],
@@ -152,7 +171,7 @@ impl Default for OceanFrameSettings {
             swell_dir: Vec2::new(0.8, 0.1),
             current: Vec2::ZERO,
             wave_amplitude: 2.0,
-            gerstner_amplitude: 0.12,
+            gerstner_amplitude: 0.0,
             cascade_spectrum_scales: [400.0, 40.85, 4.65],
             cascade_swell_strengths: [0.35, 0.55, 0.75],
             depth_meters: 200.0,
@@ -183,6 +202,48 @@ impl Default for OceanFrameSettings {
 }
 
 impl OceanFrameSettings {
+    /// Gerstner swell displacement of the undisplaced surface point
+    /// `position` (world XZ). Mirrors `gerstner_displacement` in
+    /// `environment_ocean.vert.glsl`.
+    pub fn gerstner_displacement(&self, patch_sizes: [f32; 3], position: Vec2, time: f32) -> Vec3 {
+        let amplitude = self.wave_amplitude.max(0.0) * self.gerstner_amplitude;
+        let mut offset = Vec3::ZERO;
+        if amplitude <= 0.0 {
+            return offset;
+        }
+
+        let wind_dir = self.wind_dir / self.wind_dir.length().max(0.001);
+        let sample = position + self.current * time;
+        let phase_time = time * self.time_scale;
+        for index in 0..3 {
+            let dir = Vec2::from_angle(GERSTNER_ANGLES[index]).rotate(wind_dir);
+            let k = std::f32::consts::TAU / (patch_sizes[index] * 2.0).max(0.01);
+            let omega = (GERSTNER_GRAVITY * k).sqrt();
+            let wave_amplitude = amplitude * GERSTNER_WEIGHTS[index];
+            let (sin, cos) = (k * dir.dot(sample) - omega * phase_time).sin_cos();
+            offset.x += dir.x * GERSTNER_STEEPNESS * wave_amplitude * cos;
+            offset.y += wave_amplitude * sin;
+            offset.z += dir.y * GERSTNER_STEEPNESS * wave_amplitude * cos;
+        }
+        offset
+    }
+
+    /// Height of the Gerstner swell at world `(x, z)`, or `0.0` while the
+    /// ocean is disabled. The FFT detail layered on top is not included.
+    pub fn gerstner_height_at(&self, patch_sizes: [f32; 3], x: f32, z: f32, time: f32) -> f32 {
+        if !self.enabled {
+            return 0.0;
+        }
+
+        let target = Vec2::new(x, z);
+        let mut position = target;
+        for _ in 0..GERSTNER_INVERSE_STEPS {
+            let offset = self.gerstner_displacement(patch_sizes, position, time);
+            position = target - Vec2::new(offset.x, offset.z);
+        }
+        self.gerstner_displacement(patch_sizes, position, time).y
+    }
+
     pub fn register_debug(&mut self) {
         unsafe {
             debug_register_radial_with_description(
@@ -484,7 +545,8 @@ struct OceanDrawParams {
     foam_decay_rate: f32,
     foam_noise_scale: f32,
     current: [f32; 2],
-    _padding1: [f32; 3],
+    gerstner_time: f32,
+    _padding1: [f32; 2],
     absorption_coeff: [f32; 4],
     shallow_color: [f32; 4],
     deep_color: [f32; 4],
@@ -923,7 +985,7 @@ impl OceanRenderer {
 
         let shaders = compile_ocean_shaders();
         let environment_sampler = ctx
-            .make_sampler(&SamplerInfo::default())
+            .make_sampler(&anisotropic_sampler_info(info.anisotropy))
             .expect("Failed to create ocean environment sampler");
         let scene_sampler = ctx
             .make_sampler(&SamplerInfo::default())
@@ -1157,6 +1219,12 @@ impl OceanRenderer {
         CommandStream::new().begin().end()
     }
 
+    /// World-space half-size of each cascade patch, as configured by
+    /// [`OceanInfo`] when the renderer was created.
+    pub fn cascade_patch_sizes(&self) -> [f32; 3] {
+        std::array::from_fn(|index| self.cascades[index].patch_size)
+    }
+
     pub fn update(&mut self, settings: OceanFrameSettings) {
         let bump = crate::render::global_bump().get();
         let _frame_marker = bump.alloc(0u8);
@@ -1910,7 +1978,7 @@ impl OceanRenderer {
             time,
             wind_dir: [self.wind_dir.x, self.wind_dir.y],
             wind_speed: self.wind_speed,
-            gerstner_amplitude: self.gerstner_amplitude,
+            gerstner_amplitude: self.gerstner_amplitude * self.wave_amplitude.max(0.0),
             fresnel_bias: self.fresnel_bias,
             fresnel_strength: self.fresnel_strength,
             foam_strength: self.foam_strength,
@@ -1919,7 +1987,8 @@ impl OceanRenderer {
             foam_decay_rate: self.foam_decay_rate,
             foam_noise_scale: self.foam_noise_scale,
             current: [self.current.x, self.current.y],
-            _padding1: [0.0; 3],
+            gerstner_time: time * self.time_scale,
+            _padding1: [0.0; 2],
             absorption_coeff: [
                 self.absorption_coeff.x,
                 self.absorption_coeff.y,
@@ -2012,3 +2081,75 @@ impl OceanRenderer {
             .unbind_graphics_pipeline()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn swell_settings() -> OceanFrameSettings {
+        OceanFrameSettings {
+            enabled: true,
+            wind_dir: Vec2::X,
+            wave_amplitude: 2.0,
+            gerstner_amplitude: 0.5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn swell_is_off_by_default() {
+        let settings = OceanFrameSettings {
+            enabled: true,
+            ..Default::default()
+        };
+        let patch_sizes = OceanInfo::default().cascade_patch_sizes();
+        for (x, z, time) in [(0.0, 0.0, 0.0), (12.0, -3.5, 1.25), (-40.0, 71.0, 9.0)] {
+            assert_eq!(settings.gerstner_height_at(patch_sizes, x, z, time), 0.0);
+        }
+
+        let flat = OceanFrameSettings {
+            gerstner_amplitude: 0.0,
+            ..swell_settings()
+        };
+        assert_eq!(flat.gerstner_height_at(patch_sizes, 12.0, -3.5, 1.25), 0.0);
+    }
+
+    #[test]
+    fn swell_repeats_after_the_longest_wave_period() {
+        // Deep-water waves with patches a factor of four apart have angular
+        // frequencies 4:2:1, so the whole swell repeats with the far period.
+        let settings = swell_settings();
+        let patch_sizes = [4.0, 16.0, 64.0];
+        let far_k = std::f32::consts::TAU / (patch_sizes[2] * 2.0);
+        let period = std::f32::consts::TAU / (9.81 * far_k).sqrt();
+        let amplitude_bound = 2.0 * 0.5 * (0.25 + 0.5 + 1.0);
+
+        let mut saw_swell = false;
+        for (x, z, time) in [(3.0, 4.0, 0.5), (-20.0, 8.0, 1.75), (41.0, -17.0, 2.5)] {
+            let height = settings.gerstner_height_at(patch_sizes, x, z, time);
+            let repeated = settings.gerstner_height_at(patch_sizes, x, z, time + period);
+            assert!((height - repeated).abs() < 1e-3, "{height} != {repeated}");
+            assert!(height.abs() <= amplitude_bound, "{height}");
+            saw_swell |= height.abs() > 1e-3;
+        }
+        assert!(saw_swell);
+    }
+
+    #[test]
+    fn height_query_undoes_horizontal_displacement() {
+        let settings = swell_settings();
+        let patch_sizes = OceanInfo::default().cascade_patch_sizes();
+        for (position, time) in [(Vec2::new(3.0, 4.0), 0.5), (Vec2::new(-20.0, 8.0), 4.0)] {
+            let offset = settings.gerstner_displacement(patch_sizes, position, time);
+            let surface = position + Vec2::new(offset.x, offset.z);
+            let height = settings.gerstner_height_at(patch_sizes, surface.x, surface.y, time);
+            assert!((height - offset.y).abs() < 1e-3, "{height} != {}", offset.y);
+        }
+
+        let disabled = OceanFrameSettings {
+            enabled: false,
+            ..settings
+        };
+        assert_eq!(disabled.gerstner_height_at(patch_sizes, 3.0, 4.0, 0.5), 0.0);
+    }
+}
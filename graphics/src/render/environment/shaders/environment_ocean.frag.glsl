@@ -34,7 +34,8 @@ layout(scalar, std430, set = 0, binding = 0) readonly buffer OceanParams {
     float foam_decay_rate;
     float foam_noise_scale;
     vec2 current;
-    vec3 _padding1;
+    float gerstner_time;
+    vec2 _padding1;
     vec4 absorption_coeff;
     vec4 shallow_color;
     vec4 deep_color;
@@ -112,7 +112,8 @@ layout(scalar, std430, set = 0, binding = 0) readonly buffer OceanParams {
     float foam_decay_rate;
     float foam_noise_scale;
     vec2 current;
-    vec3 _padding1;
+    float gerstner_time;
+    vec2 _padding1;
     vec4 absorption_coeff;
     vec4 shallow_color;
     vec4 deep_color;
@@ -129,6 +130,35 @@ layout(scalar, std430, set = 0, binding = 0) readonly buffer OceanParams {
 } params;
 
 
+// Keep in sync with OceanFrameSettings::gerstner_displacement.
+const float GERSTNER_ANGLES[3] = float[3](0.0, 0.45, -0.6);
+const float GERSTNER_WEIGHTS[3] = float[3](0.25, 0.5, 1.0);
+const float GERSTNER_STEEPNESS = 0.6;
+
+vec3 gerstner_displacement(vec2 position, out vec2 slope) {
+    vec3 offset = vec3(0.0);
+    slope = vec2(0.0);
+    if (params.gerstner_amplitude <= 0.0) {
+        return offset;
+    }
+
+    vec2 wind_dir = safe_normalize(params.wind_dir);
+    for (int i = 0; i < 3; ++i) {
+        float c = cos(GERSTNER_ANGLES[i]);
+        float s = sin(GERSTNER_ANGLES[i]);
+        vec2 dir = vec2(c * wind_dir.x - s * wind_dir.y, s * wind_dir.x + c * wind_dir.y);
+        float k = 6.28318530718 / max(params.cascade_patch_sizes[i] * 2.0, 0.01);
+        float omega = sqrt(9.81 * k);
+        float amplitude = params.gerstner_amplitude * GERSTNER_WEIGHTS[i];
+        float theta = k * dot(dir, position) - omega * params.gerstner_time;
+        float cos_theta = cos(theta);
+        offset.xz += dir * (GERSTNER_STEEPNESS * amplitude * cos_theta);
+        offset.y += amplitude * sin(theta);
+        slope += dir * (amplitude * k * cos_theta);
+    }
+    return offset;
+}
+
 layout(std430, set = 1, binding = 10) readonly buffer OceanShadowParams {
     uint shadow_cascade_count;
     uint shadow_resolution;
@@ -346,11 +376,19 @@ void main() {
     float velocity = waves_near.w * w_near + waves_mid.w * w_mid + waves_far.w * w_far;
     vec2 wind_dir = safe_normalize(params.wind_dir);
     vec2 choppy_offset = -gradient_world * (base_patch_size * 0.15);
-    vec4 position = vec4(world.x + choppy_offset.x, height * 100.0, world.y + choppy_offset.y, 1.0);
+    vec2 swell_slope;
+    vec3 swell = gerstner_displacement(wave_world, swell_slope);
+    vec4 position = vec4(
+        world.x + choppy_offset.x + swell.x,
+        height * 100.0 + swell.y,
+        world.y + choppy_offset.y + swell.z,
+        1.0
+    );
     // --- Surface shading ---
     // Use gradients to compute normals and choppy displacement.
     float normal_strength = 500.0;
-    vec3 normal = normalize(vec3(-gradient_world.x * normal_strength, 1.0, -gradient_world.y * normal_strength));
+    vec2 slope = gradient_world * normal_strength + swell_slope;
+    vec3 normal = normalize(vec3(-slope.x, 1.0, -slope.y));
     mat4 view = inverse(camera_view());
     mat4 proj = camera_proj();
     gl_Position = proj * view * position;
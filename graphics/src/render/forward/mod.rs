@@ -1,12 +1,12 @@
 use super::environment::{EnvironmentFrameSettings, EnvironmentRenderer, EnvironmentRendererInfo};
 use super::gui::GuiRenderer;
 use super::scene::GPUScene;
-use super::skinning::{SkinningDispatcher, SkinningHandle, SkinningInfo};
+use super::skinning::{PendingAnimationEvent, SkinningDispatcher, SkinningHandle, SkinningInfo};
 use super::text::{TextDraw, TextDrawMode, TextRenderer};
 use super::{Renderer, RendererInfo, ViewOutput};
 use crate::gui::GuiFrame;
 use crate::{
-    render::scene::*, AnimationState, BillboardInfo, CloudSettings, GuiInfo, GuiObject,
+    render::scene::*, Aabb, AnimationState, BillboardInfo, CloudSettings, GuiInfo, GuiObject,
     RenderObject, RenderObjectInfo, TextInfo, TextObject,
 };
 use bento::builder::{AttachmentDesc, PSOBuilder, PSO};
@@ -55,6 +55,7 @@ pub struct ForwardRenderer {
     billboard_pso: PSO,
     objects: ResourceList<RenderObjectData>,
     scene_lookup: HashMap<u16, Handle<RenderObjectData>>,
+    mesh_bounds: HashMap<u32, Aabb>,
     dynamic: DynamicAllocator,
     cull_queue: CommandRing,
     skinning: SkinningDispatcher,
@@ -70,6 +71,7 @@ struct RenderObjectData {
     kind: RenderObjectKind,
     scene_handle: Handle<SceneObject>,
     draw_range: RenderObjectKind,
+    local_bounds: Aabb,
 }
 
 enum RenderObjectKind {
@@ -274,7 +276,26 @@ impl ForwardRenderer {
         &mut self,
         info: &RenderObjectInfo,
     ) -> Result<Handle<RenderObject>, MeshiError> {
-        todo!()
+        let local_bounds = self.local_bounds(info);
+        warn!("Forward renderer cannot register render objects yet; skipping object with bounds {local_bounds:?}.");
+        Err(MeshiError::Other(
+            "the forward renderer does not support render objects yet".to_string(),
+        ))
+    }
+
+    /// Local bounds recorded for a new object. An explicit `bounds` override
+    /// wins; otherwise they are derived from the mesh vertices and cached per
+    /// mesh like the deferred renderer.
+    fn local_bounds(&mut self, info: &RenderObjectInfo) -> Aabb {
+        let (model, bounds) = match info {
+            RenderObjectInfo::Model(info) => (&info.model, info.bounds),
+            RenderObjectInfo::SkinnedModel(skinned) => (&skinned.model, skinned.bounds),
+            RenderObjectInfo::Billboard(_) | RenderObjectInfo::Empty => return Aabb::UNIT,
+        };
+        match bounds {
+            Some((min, max)) => Aabb { min, max },
+            None => super::model_local_bounds(self.ctx.as_mut(), &mut self.mesh_bounds, model),
+        }
     }
 
     pub fn set_skinned_animation_state(
@@ -304,6 +325,83 @@ impl ForwardRenderer {
         }
     }
 
+    pub fn blend_skinned_animation(
+        &mut self,
+        handle: Handle<RenderObject>,
+        to_clip: u32,
+        blend_time: f32,
+    ) {
+        if !handle.valid() || !self.objects.entries.iter().any(|h| h.slot == handle.slot) {
+            warn!("Attempted to blend animation on invalid handle.");
+            return;
+        }
+
+        let obj = self.objects.get_ref(from_handle(handle));
+        match &obj.kind {
+            RenderObjectKind::SkinnedModel(skinned) => {
+                self.skinning
+                    .blend_animation(skinned.skinning_handle, to_clip, blend_time);
+            }
+            _ => {
+                warn!("Attempted to blend animation on non-skinned object.");
+            }
+        }
+    }
+
+    pub fn add_animation_marker(
+        &mut self,
+        handle: Handle<RenderObject>,
+        clip_index: u32,
+        name: &str,
+        time_seconds: f32,
+    ) {
+        if !handle.valid() || !self.objects.entries.iter().any(|h| h.slot == handle.slot) {
+            warn!("Attempted to add an animation marker on invalid handle.");
+            return;
+        }
+
+        let obj = self.objects.get_ref(from_handle(handle));
+        match &obj.kind {
+            RenderObjectKind::SkinnedModel(skinned) => {
+                self.skinning
+                    .add_marker(skinned.skinning_handle, clip_index, name, time_seconds);
+            }
+            _ => {
+                warn!("Attempted to add an animation marker on non-skinned object.");
+            }
+        }
+    }
+
+    /// Drains skinned animation events, resolved to the owning render object.
+    pub fn take_animation_events(&mut self) -> Vec<(Handle<RenderObject>, PendingAnimationEvent)> {
+        let events = self.skinning.take_events();
+        if events.is_empty() {
+            return Vec::new();
+        }
+
+        let owners: Vec<_> = self
+            .objects
+            .entries
+            .iter()
+            .filter_map(|h| match &self.objects.get_ref(*h).kind {
+                RenderObjectKind::SkinnedModel(skinned) => {
+                    Some((skinned.skinning_handle, to_handle(*h)))
+                }
+                _ => None,
+            })
+            .collect();
+
+        events
+            .into_iter()
+            .filter_map(|event| {
+                owners
+                    .iter()
+                    .find(|(skinning, _)| skinning.slot == event.skinning.slot)
+                    .map(|(_, object)| (*object, event))
+            })
+            .collect()
+    }
+
     pub fn set_billboard_texture(&mut self, handle: Handle<RenderObject>, texture_id: u32) {
         if !handle.valid() {
             warn!("Attempted to update billboard texture on invalid handle.");
@@ -347,6 +445,19 @@ impl ForwardRenderer {
         todo!()
     }
 
+    pub fn set_billboard_target(&mut self, handle: Handle<RenderObject>, target: Vec3) {
+        if !handle.valid() || !self.objects.entries.iter().any(|h| h.slot == handle.slot) {
+            warn!("Attempted to set billboard target on invalid handle.");
+            return;
+        }
+
+        let obj = self.objects.get_ref_mut(from_handle(handle));
+        match &mut obj.kind {
+            RenderObjectKind::Billboard(billboard) => billboard.info.target = target,
+            _ => warn!("Attempted to set billboard target on non-billboard object."),
+        }
+    }
+
     pub fn release_object(&mut self, handle: Handle<RenderObject>) {
         if !handle.valid() {
             return;
@@ -406,6 +517,27 @@ impl ForwardRenderer {
         self.scene.get_object_transform(obj.scene_handle)
     }
 
+    pub fn object_handles(&self) -> Vec<Handle<RenderObject>> {
+        self.objects.entries.iter().map(|h| to_handle(*h)).collect()
+    }
+
+    pub fn object_local_bounds(&self, handle: Handle<RenderObject>) -> Option<Aabb> {
+        if !handle.valid() {
+            return None;
+        }
+
+        if !self
+            .objects
+            .entries
+            .iter()
+            .any(|h| h.slot == handle.slot && h.generation == handle.generation)
+        {
+            return None;
+        }
+
+        Some(self.objects.get_ref(from_handle(handle)).local_bounds)
+    }
+
     pub fn set_object_transform(&mut self, handle: Handle<RenderObject>, transform: &glam::Mat4) {
         if !handle.valid() {
             warn!("Attempted to update transformation of invalid handle.");
@@ -470,6 +602,7 @@ impl ForwardRenderer {
                 color: Vec4::from_array(draw.color),
                 scale: draw.scale,
                 mode: TextDrawMode::Plain,
+                clip_rect: draw.clip_rect,
             })
             .collect();
         self.text.set_frame_draws(frame_draws);
@@ -717,6 +850,11 @@ impl Renderer for ForwardRenderer {
         self.viewport
     }
 
+    fn resize(&mut self, extent: [u32; 2]) {
+        // Frame targets come from the graph each frame at the viewport size.
+        self.viewport = super::resized_viewport(self.viewport, extent);
+    }
+
     fn context(&mut self) -> &'static mut Context {
         unsafe { &mut (*(self.ctx.as_mut() as *mut Context)) }
     }
@@ -750,8 +888,38 @@ impl Renderer for ForwardRenderer {
         self.environment.update_ocean(settings);
     }
 
+    fn ocean_patch_sizes(&self) -> [f32; 3] {
+        self.environment.ocean_patch_sizes()
+    }
+
     fn set_spot_shadow_light(&mut self, _light: Option<super::SpotShadowLight>) {}
 
+    fn set_point_shadow_light(&mut self, _light: Option<super::PointShadowLight>) {}
+
+    fn set_shadow_resolution(&mut self, _resolution: u32) {}
+
+    fn set_shadow_filter(&mut self, _filter: crate::ShadowFilter) {}
+
+    fn local_shadow_map_count(&self) -> usize {
+        0
+    }
+
+    fn sampler_anisotropy(&self) -> u32 {
+        1
+    }
+
+    fn sampler_anisotropy_limit(&self) -> u32 {
+        1
+    }
+
+    fn set_fog(&mut self, _settings: crate::FogSettings) {}
+
+    fn set_bloom(&mut self, _settings: crate::BloomSettings) {}
+
+    fn set_tonemap(&mut self, _settings: crate::TonemapSettings) {}
+
+    fn set_debug_lines(&mut self, _vertices: &[super::debug_draw::DebugLineVertex]) {}
+
     fn register_object(
         &mut self,
         info: &RenderObjectInfo,
@@ -763,6 +931,29 @@ impl Renderer for ForwardRenderer {
         ForwardRenderer::set_skinned_animation_state(self, handle, state);
     }
 
+    fn blend_skinned_animation(
+        &mut self,
+        handle: Handle<RenderObject>,
+        to_clip: u32,
+        blend_time: f32,
+    ) {
+        ForwardRenderer::blend_skinned_animation(self, handle, to_clip, blend_time);
+    }
+
+    fn add_animation_marker(
+        &mut self,
+        handle: Handle<RenderObject>,
+        clip_index: u32,
+        name: &str,
+        time_seconds: f32,
+    ) {
+        ForwardRenderer::add_animation_marker(self, handle, clip_index, name, time_seconds);
+    }
+
+    fn take_animation_events(&mut self) -> Vec<(Handle<RenderObject>, PendingAnimationEvent)> {
+        ForwardRenderer::take_animation_events(self)
+    }
+
     fn set_billboard_texture(&mut self, handle: Handle<RenderObject>, texture_id: u32) {
         ForwardRenderer::set_billboard_texture(self, handle, texture_id);
     }
@@ -775,6 +966,34 @@ impl Renderer for ForwardRenderer {
         ForwardRenderer::set_billboard_material(self, handle, material);
     }
 
+    fn set_billboard_target(&mut self, handle: Handle<RenderObject>, target: Vec3) {
+        ForwardRenderer::set_billboard_target(self, handle, target);
+    }
+
+    fn set_object_material(
+        &mut self,
+        _handle: Handle<RenderObject>,
+        _material: Handle<Material>,
+    ) -> Result<(), MeshiError> {
+        Err(MeshiError::Other(
+            "material overrides are not supported by the forward renderer".to_string(),
+        ))
+    }
+
+    fn object_material(&self, _handle: Handle<RenderObject>) -> Option<Handle<Material>> {
+        None
+    }
+
+    fn set_object_model(
+        &mut self,
+        _handle: Handle<RenderObject>,
+        _model: &DeviceModel,
+    ) -> Result<(), MeshiError> {
+        Err(MeshiError::Other(
+            "model swaps are not supported by the forward renderer".to_string(),
+        ))
+    }
+
     fn set_object_transform(&mut self, handle: Handle<RenderObject>, transform: &glam::Mat4) {
         ForwardRenderer::set_object_transform(self, handle, transform);
     }
@@ -783,6 +1002,18 @@ impl Renderer for ForwardRenderer {
         ForwardRenderer::object_transform(self, handle)
     }
 
+    fn object_local_bounds(&self, handle: Handle<RenderObject>) -> Option<Aabb> {
+        ForwardRenderer::object_local_bounds(self, handle)
+    }
+
+    fn object_draw_count(&self, _handle: Handle<RenderObject>) -> usize {
+        0
+    }
+
+    fn object_handles(&self) -> Vec<Handle<RenderObject>> {
+        ForwardRenderer::object_handles(self)
+    }
+
     fn release_object(&mut self, handle: Handle<RenderObject>) {
         ForwardRenderer::release_object(self, handle);
     }
@@ -161,6 +161,14 @@ impl GPUDrawBuilder {
     pub fn release_draw(&mut self, handle: Handle<PerDrawData>) {
         self.data.draw_objects.release(handle);
     }
+
+    pub fn draw_data(&self, handle: Handle<PerDrawData>) -> Option<&PerDrawData> {
+        self.data.draw_objects.get_ref(handle)
+    }
+
+    pub fn draw_data_mut(&mut self, handle: Handle<PerDrawData>) -> Option<&mut PerDrawData> {
+        self.data.draw_objects.get_mut_ref(handle)
+    }
     
     pub fn per_draw_data(&self) -> Handle<Buffer> {
         self.data.draw_objects.get_gpu_handle()
@@ -101,7 +101,7 @@ impl<'a> ImagePagerDefaultLoader<'a> {
 
 impl ImagePagerLoader for ImagePagerDefaultLoader<'_> {
     fn load_from_disk(&mut self, path: &Path) -> Result<ImageView, MeshiError> {
-        let image = image::open(path).map_err(|_| MeshiError {})?;
+        let image = image::open(path).map_err(|err| MeshiError::Other(err.to_string()))?;
         let rgba = image.to_rgba8();
         let (width, height) = image.dimensions();
         let debug_name = path.to_string_lossy();
@@ -116,19 +116,20 @@ impl ImagePagerLoader for ImagePagerDefaultLoader<'_> {
             ..Default::default()
         };
 
-        let image = self.ctx.make_image(&info).map_err(|_| MeshiError {})?;
+        let image = self.ctx.make_image(&info)?;
         Ok(Self::view_from_image_handle(image))
     }
 
     fn load_from_database(&mut self, key: &DatabaseImageKey) -> Result<ImageView, MeshiError> {
         let entry = Self::database_entry(key);
         let Some(mut db) = self.db else {
-            return Err(MeshiError {});
+            return Err(MeshiError::Other(
+                "image pager has no database to load from".to_string(),
+            ));
         };
         let image = unsafe { db.as_mut() }
             .imagery_mut()
-            .fetch_gpu_image(&entry)
-            .map_err(|_| MeshiError {})?;
+            .fetch_gpu_image(&entry)?;
         Ok(Self::view_from_device_image(image))
     }
 }
@@ -1,3 +1,4 @@
+pub(crate) mod debug_draw;
 mod debug_layer;
 pub mod deferred;
 pub mod environment;
@@ -7,23 +8,30 @@ pub mod gui;
 pub mod image_pager;
 mod particle_system;
 mod scene;
-mod skinning;
+pub(crate) mod skinning;
 pub mod text;
 
 use crate::gui::GuiFrame;
 use crate::{
-    AnimationState, CloudSettings, GuiInfo, GuiObject, RenderObject, RenderObjectInfo,
-    ShadowCascadeSettings, TextInfo, TextObject,
+    Aabb, AnimationState, BloomSettings, CloudSettings, FogSettings, GuiInfo, GuiObject,
+    RenderObject, RenderObjectInfo, ShadowCascadeSettings, ShadowFilter, TextInfo, TextObject,
+    TonemapSettings,
 };
 use bumpalo_herd::Herd;
-use dashi::{Context, Handle, ImageView, SampleCount, Semaphore, Viewport};
+use dashi::{
+    BufferView, Context, Handle, ImageView, SampleCount, SamplerInfo, Semaphore, Viewport,
+};
 use furikake::{types::Camera, types::Light, types::Material, BindlessState};
-use glam::Mat4;
+use glam::{Mat4, Vec3};
 use meshi_ffi_structs::LightInfo;
 use meshi_utils::MeshiError;
+use noren::meta::DeviceModel;
+use noren::rdb::primitives::Vertex;
+use noren::rdb::DeviceGeometryLayer;
 use noren::RDBFile;
 use noren::DB;
-use std::collections::VecDeque;
+use skinning::PendingAnimationEvent;
+use std::collections::{HashMap, VecDeque};
 use std::sync::OnceLock;
 use std::time::{Duration, Instant};
 
@@ -32,6 +40,7 @@ pub struct RendererInfo {
     pub initial_viewport: Viewport,
     pub sample_count: SampleCount,
     pub shadow_cascades: ShadowCascadeSettings,
+    pub anisotropy: u32,
 }
 
 pub struct ViewOutput {
@@ -46,6 +55,12 @@ pub struct SpotShadowLight {
     pub info: LightInfo,
 }
 
+#[derive(Clone, Copy, Debug)]
+pub struct PointShadowLight {
+    pub handle: Handle<Light>,
+    pub info: LightInfo,
+}
+
 pub struct FrameTimer {
     rolling_total: Duration,
     window: VecDeque<Duration>,
@@ -112,8 +127,82 @@ impl FrameTimer {
     }
 }
 
+/// Local bounds of `model`, the union of its meshes' vertex bounds. Each
+/// mesh is read back once and cached by its bindless vertex id; meshes whose
+/// vertices cannot be read fall back to a unit cube.
+pub(crate) fn model_local_bounds(
+    ctx: &mut Context,
+    cache: &mut HashMap<u32, Aabb>,
+    model: &DeviceModel,
+) -> Aabb {
+    model
+        .meshes
+        .iter()
+        .map(|mesh| {
+            let layer = &mesh.geometry.base;
+            let Some(vertex_id) = layer.furikake_vertex_id else {
+                return Aabb::UNIT;
+            };
+            *cache
+                .entry(vertex_id)
+                .or_insert_with(|| mesh_vertex_bounds(ctx, layer).unwrap_or(Aabb::UNIT))
+        })
+        .reduce(|a, b| a.union(&b))
+        .unwrap_or(Aabb::UNIT)
+}
+
+fn mesh_vertex_bounds(ctx: &mut Context, layer: &DeviceGeometryLayer) -> Option<Aabb> {
+    let vertices = ctx
+        .map_buffer_mut::<Vertex>(BufferView::new(layer.vertices))
+        .ok()?;
+    let count = (layer.vertex_count as usize).min(vertices.len());
+    let bounds = Aabb::from_vertices(&vertices[..count]);
+    ctx.unmap_buffer(layer.vertices).ok()?;
+    bounds
+}
+
+/// `viewport` with its area and scissor resized to `extent`.
+pub(crate) fn resized_viewport(viewport: Viewport, extent: [u32; 2]) -> Viewport {
+    let mut viewport = viewport;
+    viewport.area.w = extent[0] as f32;
+    viewport.area.h = extent[1] as f32;
+    viewport.scissor.w = extent[0];
+    viewport.scissor.h = extent[1];
+    viewport
+}
+
+/// Default sampler with anisotropic filtering enabled above level `1`.
+pub(crate) fn anisotropic_sampler_info(anisotropy: u32) -> SamplerInfo {
+    SamplerInfo {
+        anisotropy_enable: anisotropy > 1,
+        max_anisotropy: anisotropy as f32,
+        ..Default::default()
+    }
+}
+
+/// Anisotropy level `info` filters with.
+pub(crate) fn sampler_info_anisotropy(info: &SamplerInfo) -> u32 {
+    if info.anisotropy_enable {
+        info.max_anisotropy as u32
+    } else {
+        1
+    }
+}
+
+/// The device's `maxSamplerAnisotropy`, or `None` when it lacks the
+/// `samplerAnisotropy` feature.
+pub(crate) fn device_anisotropy_limit(ctx: &Context) -> Option<u32> {
+    if !ctx.device_features().sampler_anisotropy {
+        return None;
+    }
+    Some(ctx.device_limits().max_sampler_anisotropy as u32)
+}
+
 pub trait Renderer {
     fn viewport(&self) -> Viewport;
+    /// Resize the scene output to `extent` pixels, rebuilding any
+    /// size-dependent targets.
+    fn resize(&mut self, extent: [u32; 2]);
     fn context(&mut self) -> &'static mut Context;
     fn state(&mut self) -> &mut BindlessState;
     fn initialize_database(&mut self, db: &mut DB);
@@ -127,20 +216,63 @@ pub trait Renderer {
         &mut self,
         settings: crate::render::environment::ocean::OceanFrameSettings,
     );
+    /// Half-size of each ocean cascade patch the environment was built with.
+    fn ocean_patch_sizes(&self) -> [f32; 3];
     fn set_spot_shadow_light(&mut self, light: Option<SpotShadowLight>);
+    fn set_point_shadow_light(&mut self, light: Option<PointShadowLight>);
+    fn set_shadow_resolution(&mut self, resolution: u32);
+    fn set_shadow_filter(&mut self, filter: ShadowFilter);
+    fn local_shadow_map_count(&self) -> usize;
+    /// Anisotropy level applied to the samplers this renderer created.
+    fn sampler_anisotropy(&self) -> u32;
+    /// Highest anisotropy level the device accepts.
+    fn sampler_anisotropy_limit(&self) -> u32;
+    fn set_fog(&mut self, settings: FogSettings);
+    fn set_bloom(&mut self, settings: BloomSettings);
+    fn set_tonemap(&mut self, settings: TonemapSettings);
     fn register_object(
         &mut self,
         info: &RenderObjectInfo,
     ) -> Result<Handle<RenderObject>, MeshiError>;
     fn set_skinned_animation_state(&mut self, handle: Handle<RenderObject>, state: AnimationState);
+    fn blend_skinned_animation(
+        &mut self,
+        handle: Handle<RenderObject>,
+        to_clip: u32,
+        blend_time: f32,
+    );
+    fn add_animation_marker(
+        &mut self,
+        handle: Handle<RenderObject>,
+        clip_index: u32,
+        name: &str,
+        time_seconds: f32,
+    );
+    fn take_animation_events(&mut self) -> Vec<(Handle<RenderObject>, PendingAnimationEvent)>;
     fn set_billboard_texture(&mut self, handle: Handle<RenderObject>, texture_id: u32);
     fn set_billboard_material(
         &mut self,
         handle: Handle<RenderObject>,
         material: Option<Handle<Material>>,
     );
+    fn set_billboard_target(&mut self, handle: Handle<RenderObject>, target: Vec3);
+    fn set_object_material(
+        &mut self,
+        handle: Handle<RenderObject>,
+        material: Handle<Material>,
+    ) -> Result<(), MeshiError>;
+    fn object_material(&self, handle: Handle<RenderObject>) -> Option<Handle<Material>>;
+    fn set_object_model(
+        &mut self,
+        handle: Handle<RenderObject>,
+        model: &DeviceModel,
+    ) -> Result<(), MeshiError>;
     fn set_object_transform(&mut self, handle: Handle<RenderObject>, transform: &Mat4);
     fn object_transform(&self, handle: Handle<RenderObject>) -> Mat4;
+    fn object_local_bounds(&self, handle: Handle<RenderObject>) -> Option<Aabb>;
+    /// Per-draw entries registered for an object, `0` for dead handles.
+    fn object_draw_count(&self, handle: Handle<RenderObject>) -> usize;
+    fn object_handles(&self) -> Vec<Handle<RenderObject>>;
     fn release_object(&mut self, handle: Handle<RenderObject>);
     fn register_text(&mut self, info: &TextInfo) -> Handle<TextObject>;
     fn release_text(&mut self, handle: Handle<TextObject>);
@@ -151,6 +283,7 @@ pub trait Renderer {
     fn set_gui_info(&mut self, handle: Handle<GuiObject>, info: &GuiInfo);
     fn set_gui_visibility(&mut self, handle: Handle<GuiObject>, visible: bool);
     fn upload_gui_frame(&mut self, frame: GuiFrame);
+    fn set_debug_lines(&mut self, vertices: &[debug_draw::DebugLineVertex]);
     fn update(
         &mut self,
         sems: &[Handle<Semaphore>],
@@ -13,6 +13,9 @@ struct SkinningDispatch {
     float playback_rate;
     float delta_time;
     uint looping;
+    uint blend_clip_handle;
+    float blend_time_seconds;
+    float blend_weight;
 };
 
 struct AnimationClip {
@@ -122,6 +125,25 @@ mat3 quat_to_mat3(vec4 q) {
     );
 }
 
+vec4 mat3_to_quat(mat3 m) {
+    float trace = m[0][0] + m[1][1] + m[2][2];
+    vec4 q;
+    if (trace > 0.0) {
+        float s = sqrt(trace + 1.0) * 2.0;
+        q = vec4((m[1][2] - m[2][1]) / s, (m[2][0] - m[0][2]) / s, (m[0][1] - m[1][0]) / s, 0.25 * s);
+    } else if (m[0][0] > m[1][1] && m[0][0] > m[2][2]) {
+        float s = sqrt(1.0 + m[0][0] - m[1][1] - m[2][2]) * 2.0;
+        q = vec4(0.25 * s, (m[1][0] + m[0][1]) / s, (m[2][0] + m[0][2]) / s, (m[1][2] - m[2][1]) / s);
+    } else if (m[1][1] > m[2][2]) {
+        float s = sqrt(1.0 + m[1][1] - m[0][0] - m[2][2]) * 2.0;
+        q = vec4((m[1][0] + m[0][1]) / s, 0.25 * s, (m[2][1] + m[1][2]) / s, (m[2][0] - m[0][2]) / s);
+    } else {
+        float s = sqrt(1.0 + m[2][2] - m[0][0] - m[1][1]) * 2.0;
+        q = vec4((m[2][0] + m[0][2]) / s, (m[2][1] + m[1][2]) / s, 0.25 * s, (m[0][1] - m[1][0]) / s);
+    }
+    return normalize(q);
+}
+
 AnimationKeyframe read_keyframe(uint index) {
     return meshi_bindless_animation_keyframes.keyframes[index];
 }
@@ -180,11 +202,14 @@ mat3 bind_rotation(mat4 bind_pose, vec3 bind_scale) {
     );
 }
 
-mat4 sample_local_transform(
+void sample_local_pose(
     uint joint_index,
     AnimationClip clip,
     float time_seconds,
-    uint bind_pose_offset
+    uint bind_pose_offset,
+    out vec3 out_translation,
+    out vec4 out_rotation,
+    out vec3 out_scale
 ) {
     JointTransform bind_joint = meshi_bindless_joints.joints[bind_pose_offset + joint_index];
     vec3 bind_translation = bind_joint.bind_pose[3].xyz;
@@ -225,23 +250,79 @@ mat4 sample_local_transform(
         }
     }
 
-    vec3 final_translation = has_translation ? translation : bind_translation;
-    vec3 final_scale = has_scale ? scale : bind_scale;
-    mat3 final_rotation = has_rotation ? quat_to_mat3(rotation) : bind_rot;
+    out_translation = has_translation ? translation : bind_translation;
+    out_scale = has_scale ? scale : bind_scale;
+    out_rotation = has_rotation ? normalize(rotation) : mat3_to_quat(bind_rot);
+}
+
+// Crossfades toward the blend clip; mirrors JointPose::blend.
+mat4 sample_local_transform(
+    uint joint_index,
+    SkinningDispatch dispatch,
+    AnimationClip clip,
+    float time_seconds,
+    float blend_time_seconds,
+    uint bind_pose_offset
+) {
+    vec3 translation;
+    vec4 rotation;
+    vec3 scale;
+    sample_local_pose(joint_index, clip, time_seconds, bind_pose_offset, translation, rotation, scale);
+
+    if (dispatch.blend_clip_handle != 0xFFFFu && dispatch.blend_weight > 0.0) {
+        AnimationClip blend_clip = meshi_bindless_animations.clips[dispatch.blend_clip_handle];
+        vec3 blend_translation;
+        vec4 blend_rotation;
+        vec3 blend_scale;
+        sample_local_pose(
+            joint_index,
+            blend_clip,
+            blend_time_seconds,
+            bind_pose_offset,
+            blend_translation,
+            blend_rotation,
+            blend_scale
+        );
+        if (dot(rotation, blend_rotation) < 0.0) {
+            blend_rotation = -blend_rotation;
+        }
+        float weight = clamp(dispatch.blend_weight, 0.0, 1.0);
+        translation = mix(translation, blend_translation, weight);
+        rotation = normalize(mix(rotation, blend_rotation, weight));
+        scale = mix(scale, blend_scale, weight);
+    }
+
+    return compose_transform(translation, quat_to_mat3(rotation), scale);
+}
 
-    return compose_transform(final_translation, final_rotation, final_scale);
+float wrap_clip_time(float time, float duration, uint looping) {
+    if (duration <= 0.0) {
+        return time;
+    }
+    if (looping != 0u) {
+        time = mod(time, duration);
+        if (time < 0.0) {
+            time += duration;
+        }
+        return time;
+    }
+    return min(time, duration);
 }
 
 mat4 compute_global_transform(
     uint joint_index,
+    SkinningDispatch dispatch,
     AnimationClip clip,
     float time_seconds,
+    float blend_time_seconds,
     SkeletonHeader skeleton
 ) {
     mat4 world = sample_local_transform(
         joint_index,
+        dispatch,
         clip,
         time_seconds,
+        blend_time_seconds,
         skeleton.bind_pose_offset
     );
 
@@ -251,8 +332,10 @@ mat4 compute_global_transform(
         uint parent_index = uint(parent);
         mat4 parent_local = sample_local_transform(
             parent_index,
+            dispatch,
             clip,
             time_seconds,
+            blend_time_seconds,
             skeleton.bind_pose_offset
         );
         world = parent_local * world;
@@ -280,15 +363,15 @@ void main() {
         duration = meshi_bindless_animations.clips[dispatch.clip_handle].duration;
     }
 
-    if (duration > 0.0) {
-        if (dispatch.looping != 0u) {
-            time = mod(time, duration);
-            if (time < 0.0) {
-                time += duration;
-            }
-        } else {
-            time = min(time, duration);
-        }
+    time = wrap_clip_time(time, duration, dispatch.looping);
+
+    float blend_time = dispatch.blend_time_seconds;
+    if (dispatch.blend_clip_handle != 0xFFFFu) {
+        blend_time = wrap_clip_time(
+            blend_time,
+            meshi_bindless_animations.clips[dispatch.blend_clip_handle].duration,
+            dispatch.looping
+        );
     }
 
     if (dispatch.skeleton_handle == 0xFFFFu) {
@@ -317,7 +400,7 @@ void main() {
 
     AnimationClip clip = meshi_bindless_animations.clips[dispatch.clip_handle];
     for (uint joint_idx = 0u; joint_idx < skeleton.joint_count; joint_idx++) {
-        mat4 world = compute_global_transform(joint_idx, clip, time, skeleton);
+        mat4 world = compute_global_transform(joint_idx, dispatch, clip, time, blend_time, skeleton);
         JointTransform bind_joint =
             meshi_bindless_joints.joints[skeleton.bind_pose_offset + joint_idx];
         meshi_per_obj_joints
@@ -1,4 +1,4 @@
-use crate::{AnimationState, SkinnedModelInfo};
+use crate::{AnimationBlend, AnimationEventKind, AnimationState, SkinnedModelInfo};
 use bento::builder::CSOBuilder;
 use dashi::{
     BufferInfo, BufferUsage, CommandQueueInfo2, CommandStream, Context, MemoryVisibility,
@@ -10,13 +10,15 @@ use furikake::PSOBuilderFurikakeExt;
 use furikake::{
     BindlessState,
     reservations::{
-        bindless_joints::ReservedBindlessJoints, bindless_skeletons::ReservedBindlessSkeletons,
+        bindless_animations::ReservedBindlessAnimations, bindless_joints::ReservedBindlessJoints,
+        bindless_skeletons::ReservedBindlessSkeletons,
         per_obj_joints::{PerObjectJointAllocation, ReservedPerObjJoints},
     },
     types::{AnimationState as FurikakeAnimationState, JointTransform, SkeletonHeader},
 };
 use noren::meta::DeviceModel;
 use resource_pool::{Handle, resource_list::ResourceList};
+use std::ffi::CString;
 use tare::utils::StagedBuffer;
 use tracing::error;
 
@@ -27,6 +29,25 @@ pub struct SkinningInfo {
     pub joints: Handle<JointTransform>,
 }
 
+/// Named point in a clip's timeline that raises a
+/// [`AnimationEventKind::Marker`] event when playback of that clip crosses it.
+#[derive(Clone, Debug)]
+pub struct AnimationMarker {
+    pub clip_index: u32,
+    pub name: CString,
+    pub time_seconds: f32,
+}
+
+/// Playback event collected during [`SkinningDispatcher::update`] and
+/// drained with [`SkinningDispatcher::take_events`].
+#[derive(Clone, Debug)]
+pub struct PendingAnimationEvent {
+    pub skinning: SkinningHandle,
+    pub kind: AnimationEventKind,
+    pub clip_index: u32,
+    pub marker: Option<CString>,
+}
+
 #[derive(Clone)]
 pub struct SkinnedModelData {
     pub info: SkinnedModelInfo,
@@ -36,8 +57,12 @@ pub struct SkinnedModelData {
     pub instance_joint_count: u32,
     accumulated_time: f32,
     animation_clips: Vec<Handle<furikake::types::AnimationClip>>,
+    clip_durations: Vec<f32>,
     animation_dirty: bool,
     per_obj_joints: Option<PerObjectJointAllocation>,
+    blend: Option<AnimationBlend>,
+    markers: Vec<AnimationMarker>,
+    completed: bool,
 }
 
 impl SkinnedModelData {
@@ -54,6 +79,7 @@ impl SkinnedModelData {
                 entries.into_iter().map(|(_, handle)| *handle).collect()
             })
             .unwrap_or_default();
+        let clip_durations = clip_durations(bindless, &animation_clips);
 
         let animation_state = if rig.is_some() {
             bindless.register_animation_state()
@@ -81,8 +107,12 @@ impl SkinnedModelData {
             instance_joint_count,
             accumulated_time,
             animation_clips,
+            clip_durations,
             animation_dirty: true,
             per_obj_joints,
+            blend: None,
+            markers: Vec::new(),
+            completed: false,
         }
     }
 
@@ -131,13 +161,121 @@ impl SkinnedModelData {
             .unwrap_or_default()
     }
 
-    fn advance_time(&mut self, delta_time: f32) -> f32 {
+    fn advance_time(
+        &mut self,
+        handle: SkinningHandle,
+        delta_time: f32,
+        events: &mut Vec<PendingAnimationEvent>,
+    ) -> f32 {
         if self.animation_dirty {
             self.accumulated_time = self.info.animation.time_seconds;
+            self.completed = false;
         }
+        let previous = self.accumulated_time;
         self.accumulated_time += delta_time * self.info.animation.speed;
+
+        let fired = playback_events(
+            &self.info.animation,
+            self.clip_duration(self.info.animation.clip_index),
+            &self.markers,
+            previous,
+            self.accumulated_time,
+            &mut self.completed,
+        );
+        let clip_index = self.info.animation.clip_index;
+        for (kind, marker) in fired {
+            events.push(PendingAnimationEvent {
+                skinning: handle,
+                kind,
+                clip_index,
+                marker: marker.map(|index| self.markers[index].name.clone()),
+            });
+        }
+
+        if let Some(blend) = self.blend.as_mut() {
+            if blend.advance(delta_time, self.info.animation.speed) {
+                self.info.animation.clip_index = blend.to_clip;
+                self.info.animation.time_seconds = blend.to_time_seconds;
+                self.accumulated_time = blend.to_time_seconds;
+                self.blend = None;
+                self.completed = false;
+            }
+        }
         self.accumulated_time
     }
+
+    /// Length of `clip_index` as stored in the rig's clip, or zero when the
+    /// clip is missing.
+    fn clip_duration(&self, clip_index: u32) -> f32 {
+        self.clip_durations
+            .get(clip_index as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn clip_slot(&self, clip_index: u32) -> u32 {
+        self.animation_clips
+            .get(clip_index as usize)
+            .filter(|handle| handle.valid())
+            .map(|handle| handle.slot as u32)
+            .unwrap_or(u16::MAX as u32)
+    }
+}
+
+/// Events raised as playback of a `duration` second clip moves from
+/// `previous` to `current` seconds, paired with the index of the marker that
+/// fired. Only markers on the active clip fire. Looping clips report every
+/// wrap; non-looping clips report completion once, latched through
+/// `completed`. A zero `duration` only reports markers.
+fn playback_events(
+    state: &AnimationState,
+    duration: f32,
+    markers: &[AnimationMarker],
+    previous: f32,
+    current: f32,
+    completed: &mut bool,
+) -> Vec<(AnimationEventKind, Option<usize>)> {
+    let mut events = Vec::new();
+    if current <= previous {
+        return events;
+    }
+
+    let markers = markers
+        .iter()
+        .enumerate()
+        .filter(|(_, marker)| marker.clip_index == state.clip_index);
+    if state.looping && duration > 0.0 {
+        let crossings = |time: f32| {
+            (((current - time) / duration).floor() - ((previous - time) / duration).floor())
+                .max(0.0) as usize
+        };
+        for (index, marker) in markers {
+            for _ in 0..crossings(marker.time_seconds) {
+                events.push((AnimationEventKind::Marker, Some(index)));
+            }
+        }
+        for _ in 0..crossings(0.0) {
+            events.push((AnimationEventKind::Looped, None));
+        }
+        return events;
+    }
+
+    let end = if duration > 0.0 {
+        duration
+    } else {
+        f32::INFINITY
+    };
+    let (from, to) = (previous.min(end), current.min(end));
+    for (index, marker) in markers {
+        if from < marker.time_seconds && marker.time_seconds <= to {
+            events.push((AnimationEventKind::Marker, Some(index)));
+        }
+    }
+    if duration > 0.0 && current >= duration && !*completed {
+        *completed = true;
+        events.push((AnimationEventKind::Completed, None));
+    }
+    events
 }
 
 pub struct SkinningDispatcher {
@@ -146,6 +284,7 @@ pub struct SkinningDispatcher {
     dispatches: StagedBuffer,
     skinned: ResourceList<SkinnedModelData>,
     completion_semaphore: Handle<Semaphore>,
+    events: Vec<PendingAnimationEvent>,
 }
 
 pub const MAX_SKINNING_DISPATCHES: usize = 1024;
@@ -193,6 +332,7 @@ impl SkinningDispatcher {
             dispatches,
             skinned: Default::default(),
             completion_semaphore,
+            events: Vec::new(),
         }
     }
 
@@ -232,9 +372,73 @@ impl SkinningDispatcher {
 
         let skinned = self.skinned.get_ref_mut(handle);
         skinned.info.animation = state;
+        skinned.blend = None;
         skinned.mark_animation_dirty();
     }
 
+    /// Adds a marker that fires whenever playback of `clip_index` crosses
+    /// `time_seconds`. Markers past the end of the clip are rejected.
+    pub fn add_marker(
+        &mut self,
+        handle: SkinningHandle,
+        clip_index: u32,
+        name: &str,
+        time_seconds: f32,
+    ) {
+        if !handle.valid() {
+            return;
+        }
+
+        if !self.skinned.entries.iter().any(|h| h.slot == handle.slot) {
+            return;
+        }
+
+        let Ok(name) = CString::new(name) else {
+            error!("Animation marker name '{name}' contains an interior nul byte.");
+            return;
+        };
+        let skinned = self.skinned.get_ref_mut(handle);
+        let duration = skinned.clip_duration(clip_index);
+        if !(0.0..=duration).contains(&time_seconds) {
+            error!(
+                "Animation marker {name:?} at {time_seconds}s lies outside clip {clip_index} ({duration}s)."
+            );
+            return;
+        }
+        skinned.markers.push(AnimationMarker {
+            clip_index,
+            name,
+            time_seconds,
+        });
+    }
+
+    /// Drains the playback events raised since the last call.
+    pub fn take_events(&mut self) -> Vec<PendingAnimationEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Crossfades from the active clip to `to_clip` over `blend_time`
+    /// seconds. A non-positive `blend_time` switches immediately.
+    pub fn blend_animation(&mut self, handle: SkinningHandle, to_clip: u32, blend_time: f32) {
+        if !handle.valid() {
+            return;
+        }
+
+        if !self.skinned.entries.iter().any(|h| h.slot == handle.slot) {
+            return;
+        }
+
+        let skinned = self.skinned.get_ref_mut(handle);
+        if blend_time <= 0.0 {
+            skinned.info.animation.clip_index = to_clip;
+            skinned.info.animation.time_seconds = 0.0;
+            skinned.blend = None;
+            skinned.mark_animation_dirty();
+            return;
+        }
+        skinned.blend = Some(AnimationBlend::new(to_clip, blend_time));
+    }
+
     pub fn skinning_info(&self, handle: SkinningHandle) -> SkinningInfo {
         if !handle.valid() {
             return SkinningInfo::default();
@@ -267,7 +471,7 @@ impl SkinningDispatcher {
             if skinned.info.model.rig.is_some() && !skinned.per_obj_joints_handle().valid() {
                 continue;
             }
-            let time_seconds = skinned.advance_time(delta_time);
+            let time_seconds = skinned.advance_time(entry, delta_time, &mut self.events);
             buffer[dispatch_count] = SkinningDispatch::from_model(skinned, delta_time, time_seconds);
             skinned.clear_animation_dirty();
             dispatch_count += 1;
@@ -355,14 +559,21 @@ pub struct SkinningDispatch {
     playback_rate: f32,
     delta_time: f32,
     looping: u32,
+    blend_clip_handle: u32,
+    blend_time_seconds: f32,
+    blend_weight: f32,
 }
 
 impl SkinningDispatch {
     pub fn from_model(model: &SkinnedModelData, delta_time: f32, time_seconds: f32) -> Self {
-        let clip_handle = model
-            .animation_clips
-            .get(model.info.animation.clip_index as usize)
-            .copied();
+        let (blend_clip_handle, blend_time_seconds, blend_weight) = match model.blend {
+            Some(blend) => (
+                model.clip_slot(blend.to_clip),
+                blend.to_time_seconds,
+                blend.weight(),
+            ),
+            None => (u16::MAX as u32, 0.0, 0.0),
+        };
         let skeleton_handle = model.dispatch_skeleton();
         let per_obj_joints_handle = model.per_obj_joints_handle();
 
@@ -372,10 +583,7 @@ impl SkinningDispatch {
             } else {
                 u16::MAX as u32
             },
-            clip_handle: clip_handle
-                .filter(|handle| handle.valid())
-                .map(|handle| handle.slot as u32)
-                .unwrap_or(u16::MAX as u32),
+            clip_handle: model.clip_slot(model.info.animation.clip_index),
             skeleton_handle: if skeleton_handle.valid() {
                 skeleton_handle.slot as u32
             } else {
@@ -391,6 +599,9 @@ impl SkinningDispatch {
             playback_rate: model.info.animation.speed,
             delta_time,
             looping: model.info.animation.looping as u32,
+            blend_clip_handle,
+            blend_time_seconds,
+            blend_weight,
         }
     }
 }
@@ -468,6 +679,27 @@ fn clone_instance_skeleton(
     }
 }
 
+fn clip_durations(
+    bindless: &BindlessState,
+    clips: &[Handle<furikake::types::AnimationClip>],
+) -> Vec<f32> {
+    let Ok(animations) = bindless.reserved::<ReservedBindlessAnimations>("meshi_bindless_animations")
+    else {
+        return vec![0.0; clips.len()];
+    };
+
+    clips
+        .iter()
+        .map(|clip| {
+            if clip.valid() {
+                animations.animation(*clip).duration
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
 fn reserve_per_obj_joints(
     bindless: &mut BindlessState,
     skeleton: Handle<SkeletonHeader>,
@@ -491,3 +723,77 @@ fn reserve_per_obj_joints(
 
     h
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advance(
+        state: &AnimationState,
+        duration: f32,
+        markers: &[AnimationMarker],
+        steps: &[f32],
+    ) -> Vec<(AnimationEventKind, Option<usize>)> {
+        let mut completed = false;
+        let mut time = state.time_seconds;
+        let mut events = Vec::new();
+        for step in steps {
+            let previous = time;
+            time += step * state.speed;
+            events.extend(playback_events(
+                state,
+                duration,
+                markers,
+                previous,
+                time,
+                &mut completed,
+            ));
+        }
+        events
+    }
+
+    #[test]
+    fn short_clip_completes_once() {
+        let state = AnimationState {
+            looping: false,
+            ..Default::default()
+        };
+        let markers = [
+            AnimationMarker {
+                clip_index: 0,
+                name: CString::new("footstep").unwrap(),
+                time_seconds: 0.5,
+            },
+            AnimationMarker {
+                clip_index: 1,
+                name: CString::new("other_clip").unwrap(),
+                time_seconds: 0.5,
+            },
+        ];
+
+        let events = advance(&state, 1.0, &markers, &[0.4, 0.4, 0.4, 0.4, 0.4]);
+
+        assert_eq!(
+            events,
+            vec![
+                (AnimationEventKind::Marker, Some(0)),
+                (AnimationEventKind::Completed, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn looping_clip_reports_each_wrap() {
+        let state = AnimationState::default();
+
+        let events = advance(&state, 1.0, &[], &[0.75, 0.75, 0.75]);
+
+        assert_eq!(
+            events,
+            vec![
+                (AnimationEventKind::Looped, None),
+                (AnimationEventKind::Looped, None),
+            ]
+        );
+    }
+}
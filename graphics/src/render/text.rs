@@ -15,6 +15,7 @@ use noren::{DB, meta::DeviceSDFFont};
 use resource_pool::{Handle, resource_list::ResourceList};
 use tracing::{error, warn};
 
+use crate::gui::GuiClipRect;
 use crate::{TextInfo, TextObject, TextRenderMode};
 
 #[derive(Clone, Debug)]
@@ -33,6 +34,8 @@ pub struct TextDraw {
     pub color: glam::Vec4,
     pub scale: f32,
     pub mode: TextDrawMode,
+    /// Pixel-space rect glyph quads are clipped to.
+    pub clip_rect: Option<GuiClipRect>,
 }
 
 #[derive(Clone, Debug)]
@@ -48,6 +51,7 @@ struct TextGlyphCacheKey {
     color: [u32; 4],
     scale: u32,
     font: String,
+    clip: Option<[u32; 4]>,
 }
 
 #[repr(C)]
@@ -80,6 +84,40 @@ pub struct TextRenderer {
     glyph_capacity: usize,
 }
 
+type GlyphRect = ([f32; 4], [f32; 2], [f32; 2]);
+
+/// Trims a glyph's `[x0, y0, x1, y1]` pixel rect to `clip`, shrinking its UVs
+/// by the same fraction. Returns `None` once nothing of the glyph is visible.
+fn clip_glyph(
+    rect: [f32; 4],
+    uv_min: [f32; 2],
+    uv_max: [f32; 2],
+    clip: &GuiClipRect,
+) -> Option<GlyphRect> {
+    let [x0, y0, x1, y1] = rect;
+    let cx0 = x0.max(clip.min[0]);
+    let cy0 = y0.max(clip.min[1]);
+    let cx1 = x1.min(clip.max[0]);
+    let cy1 = y1.min(clip.max[1]);
+    if cx0 >= cx1 || cy0 >= cy1 {
+        return None;
+    }
+
+    let uv_at = |value: f32, start: f32, end: f32, axis: usize| {
+        let t = if end > start {
+            (value - start) / (end - start)
+        } else {
+            0.0
+        };
+        uv_min[axis] + (uv_max[axis] - uv_min[axis]) * t
+    };
+    Some((
+        [cx0, cy0, cx1, cy1],
+        [uv_at(cx0, x0, x1, 0), uv_at(cy0, y0, y1, 1)],
+        [uv_at(cx1, x0, x1, 0), uv_at(cy1, y0, y1, 1)],
+    ))
+}
+
 fn to_handle(h: Handle<TextObjectData>) -> Handle<TextObject> {
     Handle::new(h.slot, h.generation)
 }
@@ -344,14 +382,24 @@ impl TextRenderer {
             let y0 = baseline_y - plane_bounds.top * scale;
             let y1 = baseline_y - plane_bounds.bottom * scale;
 
+            let uv_min = [atlas_bounds.left / atlas_w, atlas_bounds.bottom / atlas_h];
+            let uv_max = [atlas_bounds.right / atlas_w, atlas_bounds.top / atlas_h];
+            let ([x0, y0, x1, y1], uv_min, uv_max) = match draw.clip_rect {
+                Some(clip) => {
+                    let Some(clipped) = clip_glyph([x0, y0, x1, y1], uv_min, uv_max, &clip) else {
+                        pen_x += glyph.advance * scale;
+                        continue;
+                    };
+                    clipped
+                }
+                None => ([x0, y0, x1, y1], uv_min, uv_max),
+            };
+
             let ndc_x0 = (x0 / screen_w) * 2.0 - 1.0;
             let ndc_x1 = (x1 / screen_w) * 2.0 - 1.0;
             let ndc_y0 = 1.0 - (y0 / screen_h) * 2.0;
             let ndc_y1 = 1.0 - (y1 / screen_h) * 2.0;
 
-            let uv_min = [atlas_bounds.left / atlas_w, atlas_bounds.bottom / atlas_h];
-            let uv_max = [atlas_bounds.right / atlas_w, atlas_bounds.top / atlas_h];
-
             glyphs.push(TextGlyph {
                 origin: [ndc_x0, ndc_y0],
                 size: [ndc_x1 - ndc_x0, ndc_y1 - ndc_y0],
@@ -400,6 +448,14 @@ impl TextRenderer {
                 ],
                 scale: draw.scale.to_bits(),
                 font: font_key,
+                clip: draw.clip_rect.map(|clip| {
+                    [
+                        clip.min[0].to_bits(),
+                        clip.min[1].to_bits(),
+                        clip.max[0].to_bits(),
+                        clip.max[1].to_bits(),
+                    ]
+                }),
             };
 
             if let Some(cached) = self.glyph_cache.remove(&cache_key) {
@@ -557,6 +613,7 @@ impl TextRenderer {
             color: info.color,
             scale: info.scale,
             mode,
+            clip_rect: None,
         }
     }
 
@@ -621,3 +678,21 @@ impl TextRenderer {
         &self.draws
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clipped_glyphs_trim_their_uvs() {
+        let clip = GuiClipRect::from_min_max([5.0, 0.0], [100.0, 100.0]);
+        let (rect, uv_min, uv_max) =
+            clip_glyph([0.0, 0.0, 10.0, 10.0], [0.0, 0.0], [1.0, 1.0], &clip).unwrap();
+        assert_eq!(rect, [5.0, 0.0, 10.0, 10.0]);
+        assert_eq!(uv_min, [0.5, 0.0]);
+        assert_eq!(uv_max, [1.0, 1.0]);
+
+        let outside = GuiClipRect::from_min_max([20.0, 0.0], [30.0, 10.0]);
+        assert!(clip_glyph([0.0, 0.0, 10.0, 10.0], [0.0, 0.0], [1.0, 1.0], &outside).is_none());
+    }
+}
@@ -5,6 +5,7 @@ use glam::*;
 use glam::{Mat4, Vec2, Vec4};
 use meshi_ffi_structs::{LightInfo, LightType};
 use noren::meta::DeviceModel;
+use noren::rdb::primitives::Vertex;
 use resource_pool::Handle;
 
 #[derive(Default)]
@@ -19,10 +20,27 @@ pub type MeshObject = RenderObjectInstance;
 
 pub enum RenderObjectInfo {
     Empty,
-    Model(DeviceModel),
+    Model(ModelInfo),
     SkinnedModel(SkinnedModelInfo),
     Billboard(BillboardInfo),
 }
+
+#[derive(Clone, Debug)]
+pub struct ModelInfo {
+    pub model: DeviceModel,
+    /// Local-space `(min, max)` bounds used for culling and picking. `None`
+    /// derives them from the model's meshes.
+    pub bounds: Option<(Vec3, Vec3)>,
+}
+
+impl From<DeviceModel> for ModelInfo {
+    fn from(model: DeviceModel) -> Self {
+        Self {
+            model,
+            bounds: None,
+        }
+    }
+}
 pub struct RenderObject;
 
 pub struct TextObject;
@@ -118,10 +136,40 @@ impl Default for GuiInfo {
     }
 }
 
+/// A screen-space quad drawn in the 2D overlay after the 3D scene.
+#[derive(Clone, Debug)]
+pub struct SpriteInfo {
+    /// Bindless texture sampled across the sprite. `None` draws a solid
+    /// `color` quad.
+    pub texture_id: Option<u32>,
+    /// Top-left corner in viewport pixels.
+    pub position: Vec2,
+    /// Extent in viewport pixels.
+    pub size: Vec2,
+    pub color: Vec4,
+    /// Draw order; sprites with a higher `z` are drawn over lower ones.
+    pub z: i32,
+}
+
+impl Default for SpriteInfo {
+    fn default() -> Self {
+        Self {
+            texture_id: None,
+            position: Vec2::ZERO,
+            size: Vec2::ZERO,
+            color: Vec4::ONE,
+            z: 0,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SkinnedModelInfo {
     pub model: DeviceModel,
     pub animation: AnimationState,
+    /// Local-space `(min, max)` bounds used for culling and picking. `None`
+    /// derives them from the model's meshes.
+    pub bounds: Option<(Vec3, Vec3)>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -143,11 +191,115 @@ impl Default for AnimationState {
     }
 }
 
+/// Why an [`AnimationEvent`] was raised.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimationEventKind {
+    /// A looping clip wrapped back to its start.
+    Looped = 0,
+    /// A non-looping clip reached its end.
+    Completed = 1,
+    /// Playback crossed a marker.
+    Marker = 2,
+}
+
+/// Playback event passed to animation callbacks.
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct AnimationEvent {
+    pub object: Handle<RenderObject>,
+    pub kind: AnimationEventKind,
+    pub clip_index: u32,
+    /// Marker name for [`AnimationEventKind::Marker`], null otherwise. Only
+    /// valid for the duration of the callback.
+    pub marker: *const std::ffi::c_char,
+}
+
+pub type AnimationCallback = extern "C" fn(*const AnimationEvent, *mut std::ffi::c_void);
+
+/// Crossfade from a skinned object's active clip to `to_clip`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimationBlend {
+    pub to_clip: u32,
+    pub duration: f32,
+    pub elapsed: f32,
+    /// Playback time of `to_clip`, which starts from zero.
+    pub to_time_seconds: f32,
+}
+
+impl AnimationBlend {
+    pub fn new(to_clip: u32, duration: f32) -> Self {
+        Self {
+            to_clip,
+            duration,
+            elapsed: 0.0,
+            to_time_seconds: 0.0,
+        }
+    }
+
+    /// Weight of `to_clip`, from 0 at the start of the blend to 1 at the end.
+    pub fn weight(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed / self.duration).clamp(0.0, 1.0)
+    }
+
+    /// Advances the blend and returns `true` once it has finished.
+    pub fn advance(&mut self, delta_time: f32, speed: f32) -> bool {
+        self.elapsed += delta_time;
+        self.to_time_seconds += delta_time * speed;
+        self.weight() >= 1.0
+    }
+}
+
+/// Local joint transform as sampled from an animation clip.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JointPose {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub scale: Vec3,
+}
+
+impl JointPose {
+    /// Blends toward `other` the same way the skinning shader crossfades clips.
+    pub fn blend(&self, other: &JointPose, weight: f32) -> JointPose {
+        let mut rotation = other.rotation;
+        if self.rotation.dot(rotation) < 0.0 {
+            rotation = -rotation;
+        }
+        JointPose {
+            translation: self.translation.lerp(other.translation, weight),
+            rotation: self.rotation.lerp(rotation, weight).normalize(),
+            scale: self.scale.lerp(other.scale, weight),
+        }
+    }
+
+    pub fn to_mat4(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(self.scale, self.rotation, self.translation)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct BillboardInfo {
     pub texture_id: u32,
     pub material: Option<Handle<Material>>,
     pub billboard_type: BillboardType,
+    /// World-space point faced by [`BillboardType::TargetFacing`] billboards.
+    pub target: Vec3,
+}
+
+impl BillboardInfo {
+    /// Right and up axes of a quad at `center` facing [`Self::target`].
+    pub fn target_facing_axes(&self, center: Vec3) -> (Vec3, Vec3) {
+        let forward = (self.target - center).normalize_or(Vec3::Z);
+        let mut right = forward.cross(Vec3::Y);
+        if right.length_squared() <= 1e-6 {
+            right = forward.cross(Vec3::Z);
+        }
+        let right = right.normalize();
+        (right, right.cross(forward))
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -156,6 +308,219 @@ pub enum BillboardType {
     ScreenAligned,
     AxisAligned,
     Fixed,
+    /// Fully rotates to face [`BillboardInfo::target`].
+    TargetFacing,
+}
+
+/// Axis-aligned bounding box.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// A unit cube centred on the origin.
+    pub const UNIT: Self = Self {
+        min: Vec3::splat(-0.5),
+        max: Vec3::splat(0.5),
+    };
+
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(
+            Self {
+                min: first,
+                max: first,
+            },
+            |bounds, point| Self {
+                min: bounds.min.min(point),
+                max: bounds.max.max(point),
+            },
+        ))
+    }
+
+    /// Bounds of a mesh's vertex positions.
+    pub fn from_vertices(vertices: &[Vertex]) -> Option<Self> {
+        Self::from_points(vertices.iter().map(|vertex| Vec3::from(vertex.position)))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Bounds of the eight corners after transforming them by `transform`.
+    pub fn transformed(&self, transform: &Mat4) -> Self {
+        let corners = (0..8).map(|i| {
+            let corner = Vec3::new(
+                if i & 1 == 0 { self.min.x } else { self.max.x },
+                if i & 2 == 0 { self.min.y } else { self.max.y },
+                if i & 4 == 0 { self.min.z } else { self.max.z },
+            );
+            transform.transform_point3(corner)
+        });
+        Self::from_points(corners).unwrap_or(*self)
+    }
+
+    /// Distance along `dir` at which a ray from `origin` enters the box, or
+    /// `None` if it misses. Rays starting inside the box return zero.
+    pub fn ray_distance(&self, origin: Vec3, dir: Vec3) -> Option<f32> {
+        let inv = dir.recip();
+        let t0 = (self.min - origin) * inv;
+        let t1 = (self.max - origin) * inv;
+        let near = t0.min(t1).max_element().max(0.0);
+        let far = t0.max(t1).min_element();
+        (near <= far).then_some(near)
+    }
+}
+
+/// Distance fog applied in the lighting pass. A positive `density` selects
+/// exponential fog beginning at `start`; otherwise fog ramps linearly from
+/// `start` to `end`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FogSettings {
+    pub enabled: bool,
+    pub color: Vec3,
+    pub density: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Default for FogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            color: Vec3::new(0.7, 0.75, 0.8),
+            density: 0.02,
+            start: 0.0,
+            end: 500.0,
+        }
+    }
+}
+
+impl FogSettings {
+    /// Fraction of fog color blended in at `distance` from the camera.
+    pub fn factor(&self, distance: f32) -> f32 {
+        if !self.enabled {
+            return 0.0;
+        }
+
+        let distance = (distance - self.start).max(0.0);
+        if self.density > 0.0 {
+            1.0 - (-self.density * distance).exp()
+        } else {
+            (distance / (self.end - self.start).max(1e-4)).clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// Bloom applied to the lit scene before it is presented. Pixels brighter than
+/// `threshold` are blurred over roughly `radius` pixels and added back scaled
+/// by `intensity`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BloomSettings {
+    pub enabled: bool,
+    pub threshold: f32,
+    pub intensity: f32,
+    pub radius: f32,
+}
+
+impl Default for BloomSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.8,
+            intensity: 1.0,
+            radius: 8.0,
+        }
+    }
+}
+
+/// Curve used to map HDR scene color into display range.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TonemapOperator {
+    /// Clamp to `[0, 1]` without any curve.
+    None = 0,
+    Reinhard = 1,
+    Aces = 2,
+}
+
+/// Final conversion of the HDR scene to display range. `exposure` scales the
+/// scene before the operator is applied.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TonemapSettings {
+    pub operator: TonemapOperator,
+    pub exposure: f32,
+}
+
+impl Default for TonemapSettings {
+    fn default() -> Self {
+        Self {
+            operator: TonemapOperator::Aces,
+            exposure: 1.0,
+        }
+    }
+}
+
+impl TonemapSettings {
+    /// Display-range color for an HDR `color`, matching the tonemap shader.
+    pub fn apply(&self, color: Vec3) -> Vec3 {
+        let x = (color * self.exposure).max(Vec3::ZERO);
+        let mapped = match self.operator {
+            TonemapOperator::None => x,
+            TonemapOperator::Reinhard => x / (Vec3::ONE + x),
+            TonemapOperator::Aces => (x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14),
+        };
+        mapped.clamp(Vec3::ZERO, Vec3::ONE)
+    }
+}
+
+/// How shadow map lookups are filtered in the lighting pass.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShadowFilterMode {
+    /// A single depth comparison per pixel.
+    Hard = 0,
+    /// Percentage-closer filtering over a `kernel_size` square of texels.
+    Pcf = 1,
+}
+
+/// Shadow edge filtering shared by the cascaded, spot and point shadow maps.
+/// Even kernel sizes round up to the next odd size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShadowFilter {
+    pub mode: ShadowFilterMode,
+    pub kernel_size: u32,
+}
+
+impl Default for ShadowFilter {
+    fn default() -> Self {
+        Self {
+            mode: ShadowFilterMode::Hard,
+            kernel_size: 3,
+        }
+    }
+}
+
+impl ShadowFilter {
+    pub const MAX_KERNEL_SIZE: u32 = 7;
+
+    /// Texels sampled on each side of the center tap, as used by the shader.
+    pub fn radius(&self) -> u32 {
+        match self.mode {
+            ShadowFilterMode::Hard => 0,
+            ShadowFilterMode::Pcf => self.kernel_size.min(Self::MAX_KERNEL_SIZE) / 2,
+        }
+    }
 }
 
 #[repr(C)]
@@ -499,6 +864,7 @@ pub fn pack_gpu_light(s: LightInfo) -> Light {
 
     // flags (bitwise packed into f32)
     out.extra.x = f32::from_bits(s.flags);
+    out.extra.y = s.falloff as u32 as f32;
 
     // Enforce your documented semantics
     match s.ty {
@@ -530,7 +896,6 @@ pub enum RendererSelect {
     Forward,
 }
 
-#[derive(Default)]
 pub struct RenderEngineInfo {
     pub headless: bool,
     pub canvas_extent: Option<[u32; 2]>,
@@ -539,4 +904,235 @@ pub struct RenderEngineInfo {
     pub skybox_cubemap_entry: Option<String>,
     pub debug_mode: bool,
     pub shadow_cascades: ShadowCascadeSettings,
+    /// Anisotropic filtering level for material, terrain and environment
+    /// samplers. `1` disables it; values above the device's
+    /// `maxSamplerAnisotropy` are clamped.
+    pub anisotropy: u32,
+}
+
+impl Default for RenderEngineInfo {
+    fn default() -> Self {
+        Self {
+            headless: false,
+            canvas_extent: None,
+            renderer: RendererSelect::default(),
+            sample_count: None,
+            skybox_cubemap_entry: None,
+            debug_mode: false,
+            shadow_cascades: ShadowCascadeSettings::default(),
+            anisotropy: 1,
+        }
+    }
+}
+
+/// `requested` clamped to the device's `maxSamplerAnisotropy`. A device
+/// without the `samplerAnisotropy` feature reports no limit and gets `1`.
+pub(crate) fn supported_anisotropy(requested: u32, device_limit: Option<u32>) -> u32 {
+    match device_limit {
+        Some(limit) => requested.clamp(1, limit.max(1)),
+        None => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translated_unit_cube_is_centred_on_translation() {
+        let bounds = Aabb::UNIT.transformed(&Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0)));
+        assert_eq!(bounds.center(), Vec3::new(5.0, 0.0, 0.0));
+        assert_eq!(bounds.max - bounds.min, Vec3::ONE);
+    }
+
+    #[test]
+    fn rotated_bounds_enclose_all_corners() {
+        let rotation = Mat4::from_rotation_y(std::f32::consts::FRAC_PI_4);
+        let bounds = Aabb::UNIT.transformed(&rotation);
+        let half_diagonal = 0.5 * std::f32::consts::SQRT_2;
+        assert!((bounds.max.x - half_diagonal).abs() < 1e-5);
+        assert!((bounds.min.z + half_diagonal).abs() < 1e-5);
+        assert!((bounds.max.y - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn ray_distance_hits_front_face() {
+        let bounds = Aabb::UNIT.transformed(&Mat4::from_translation(Vec3::new(0.0, 0.0, -5.0)));
+        let distance = bounds.ray_distance(Vec3::ZERO, Vec3::NEG_Z).unwrap();
+        assert!((distance - 4.5).abs() < 1e-5);
+        assert!(bounds.ray_distance(Vec3::ZERO, Vec3::Z).is_none());
+        let offset = Vec3::new(2.0, 0.0, 0.0);
+        assert!(bounds.ray_distance(offset, Vec3::NEG_Z).is_none());
+    }
+
+    #[test]
+    fn fog_factor_is_zero_when_disabled() {
+        let fog = FogSettings::default();
+        assert_eq!(fog.factor(1000.0), 0.0);
+    }
+
+    #[test]
+    fn exponential_fog_thickens_with_distance() {
+        let fog = FogSettings {
+            enabled: true,
+            density: 0.1,
+            start: 10.0,
+            ..Default::default()
+        };
+        assert_eq!(fog.factor(5.0), 0.0);
+        assert!(fog.factor(20.0) < fog.factor(40.0));
+        assert!(fog.factor(1000.0) > 0.99);
+    }
+
+    #[test]
+    fn linear_fog_ramps_between_start_and_end() {
+        let fog = FogSettings {
+            enabled: true,
+            density: 0.0,
+            start: 10.0,
+            end: 30.0,
+            ..Default::default()
+        };
+        assert_eq!(fog.factor(20.0), 0.5);
+        assert_eq!(fog.factor(50.0), 1.0);
+    }
+
+    #[test]
+    fn aces_keeps_bright_colors_below_white() {
+        let bright = Vec3::splat(4.0);
+        let aces = TonemapSettings::default().apply(bright);
+        assert!(aces.max_element() < 254.5 / 255.0);
+
+        let none = TonemapSettings {
+            operator: TonemapOperator::None,
+            ..Default::default()
+        };
+        assert_eq!(none.apply(bright), Vec3::ONE);
+    }
+
+    #[test]
+    fn exposure_scales_before_the_curve() {
+        let reinhard = TonemapSettings {
+            operator: TonemapOperator::Reinhard,
+            exposure: 2.0,
+        };
+        assert!((reinhard.apply(Vec3::splat(0.5)).x - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn shadow_filter_radius_follows_mode_and_kernel() {
+        let hard = ShadowFilter {
+            mode: ShadowFilterMode::Hard,
+            kernel_size: 5,
+        };
+        assert_eq!(hard.radius(), 0);
+        assert_eq!(ShadowFilter::default().radius(), 0);
+        let pcf = ShadowFilter {
+            mode: ShadowFilterMode::Pcf,
+            ..Default::default()
+        };
+        assert_eq!(pcf.radius(), 1);
+
+        let wide = ShadowFilter {
+            mode: ShadowFilterMode::Pcf,
+            kernel_size: 64,
+        };
+        assert_eq!(wide.radius(), ShadowFilter::MAX_KERNEL_SIZE / 2);
+    }
+
+    #[test]
+    fn anisotropy_defaults_off_and_clamps_to_device_limit() {
+        let default = RenderEngineInfo::default().anisotropy;
+        assert_eq!(supported_anisotropy(default, Some(16)), 1);
+        assert_eq!(supported_anisotropy(0, Some(16)), 1);
+        assert_eq!(supported_anisotropy(8, Some(16)), 8);
+        assert_eq!(supported_anisotropy(64, Some(16)), 16);
+        assert_eq!(supported_anisotropy(64, Some(2)), 2);
+        assert_eq!(supported_anisotropy(8, None), 1);
+    }
+
+    #[test]
+    fn vertex_bounds_follow_mesh_positions() {
+        let vertex = |position: [f32; 3]| Vertex {
+            position,
+            normal: [0.0, 1.0, 0.0],
+            tangent: [1.0, 0.0, 0.0, 1.0],
+            uv: [0.0; 2],
+            color: [1.0; 4],
+            joint_indices: [0; 4],
+            joint_weights: [0.0; 4],
+        };
+        let triangle = [
+            vertex([2.0, 0.0, -1.0]),
+            vertex([4.0, 3.0, -1.0]),
+            vertex([3.0, 1.0, 5.0]),
+        ];
+        let bounds = Aabb::from_vertices(&triangle).unwrap();
+        assert_eq!(bounds.min, Vec3::new(2.0, 0.0, -1.0));
+        assert_eq!(bounds.max, Vec3::new(4.0, 3.0, 5.0));
+        assert!(Aabb::from_vertices(&[]).is_none());
+    }
+
+    #[test]
+    fn from_points_returns_none_when_empty() {
+        assert!(Aabb::from_points(std::iter::empty()).is_none());
+        let bounds = Aabb::from_points([Vec3::X, -Vec3::Y]).unwrap();
+        assert_eq!(bounds.min, Vec3::new(0.0, -1.0, 0.0));
+        assert_eq!(bounds.max, Vec3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn target_facing_billboard_turns_toward_target() {
+        let center = Vec3::new(1.0, 2.0, 3.0);
+        let mut info = BillboardInfo {
+            texture_id: 0,
+            material: None,
+            billboard_type: BillboardType::TargetFacing,
+            target: Vec3::new(1.0, 2.0, 10.0),
+        };
+        let facing = |info: &BillboardInfo| {
+            let (right, up) = info.target_facing_axes(center);
+            assert!(right.dot(up).abs() < 1e-5);
+            up.cross(right)
+        };
+
+        let normal = facing(&info);
+        assert!(normal.distance(Vec3::Z) < 1e-5, "{normal}");
+
+        info.target = Vec3::new(-4.0, 7.0, 3.0);
+        let normal = facing(&info);
+        let expected = (info.target - center).normalize();
+        assert!(normal.distance(expected) < 1e-5, "{normal} != {expected}");
+    }
+
+    #[test]
+    fn blend_midpoint_pose_lies_between_clips() {
+        let from = JointPose {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            scale: Vec3::ONE,
+        };
+        let to = JointPose {
+            translation: Vec3::new(2.0, 4.0, 0.0),
+            rotation: Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+            scale: Vec3::splat(3.0),
+        };
+
+        let mut blend = AnimationBlend::new(1, 0.5);
+        assert!(!blend.advance(0.25, 1.0));
+        assert!((blend.weight() - 0.5).abs() < 1e-6);
+
+        let mid = from.blend(&to, blend.weight());
+        assert_eq!(mid.translation, Vec3::new(1.0, 2.0, 0.0));
+        assert_eq!(mid.scale, Vec3::splat(2.0));
+        let angle = mid.rotation.angle_between(Quat::IDENTITY);
+        assert!(
+            (angle - std::f32::consts::FRAC_PI_4).abs() < 1e-4,
+            "{angle}"
+        );
+
+        assert!(blend.advance(0.25, 1.0));
+        assert_eq!(from.blend(&to, blend.weight()).translation, to.translation);
+        assert!((blend.to_time_seconds - 0.5).abs() < 1e-6);
+    }
 }
@@ -129,6 +129,7 @@ fn cloud_transmittance_deterministic_and_jittered() {
         &mut state,
         &viewport,
         depth_view,
+        Format::BGRA8,
         SampleCount::S1,
         environment_view,
     );
@@ -142,6 +143,7 @@ fn cloud_transmittance_deterministic_and_jittered() {
         &mut state,
         &viewport,
         depth_view,
+        Format::BGRA8,
         SampleCount::S1,
         environment_view_b,
     );
@@ -24,6 +24,8 @@ enum class EventSource {
   Mouse = 2,
   MouseButton = 3,
   Gamepad = 4,
+  Window = 5,
+  MouseMotion = 6,
 };
 
 enum class KeyCode {
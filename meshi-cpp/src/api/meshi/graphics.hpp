@@ -58,6 +58,21 @@ public:
     api_->gfx_attach_camera_to_display(m_gfx, display, camera);
   }
 
+  void set_display_size(Handle<gfx::Display> &display, uint32_t width,
+                        uint32_t height) {
+    api_->gfx_set_display_size(m_gfx, display, width, height);
+  }
+
+  auto display_size(Handle<gfx::Display> &display) -> glm::uvec2 {
+    glm::uvec2 size{0, 0};
+    api_->gfx_get_display_size(m_gfx, display, &size.x, &size.y);
+    return size;
+  }
+
+  void set_display_fullscreen(Handle<gfx::Display> &display, bool value) {
+    api_->gfx_set_display_fullscreen(m_gfx, display, static_cast<int>(value));
+  }
+
   auto register_camera(const glm::mat4 &initial_transform)
       -> Handle<gfx::Camera> {
     MeshiMat4 t = to_meshi_mat4(initial_transform);
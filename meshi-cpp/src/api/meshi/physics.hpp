@@ -29,6 +29,7 @@ public:
                            info.initial_rotation.z, info.initial_rotation.w};
     ffi.has_gravity = info.has_gravity;
     ffi.collision_shape = info.collision_shape;
+    ffi.mass = info.mass;
     return api_->physx_create_rigid_body(m_phys, &ffi);
   }
 
@@ -49,6 +49,7 @@ struct RigidBodyCreateInfo {
   glm::quat initial_rotation{1.0f, 0.0f, 0.0f, 0.0f};
   std::uint32_t has_gravity{0};
   MeshiCollisionShape collision_shape{};
+  float mass{0.0f};
 };
 using ForceApplyInfo = MeshiForceApplyInfo;
 struct PhysicsActorStatus {
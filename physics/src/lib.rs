@@ -1,13 +1,14 @@
 use glam::*;
 use resource_pool::{Handle, Pool};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashSet};
+use std::ffi::c_void;
 
 #[repr(C)]
 #[derive(Clone, Copy)]
 /// Environment parameters for the physics simulation.
 ///
-/// Gravity defaults to Earth's gravity (`-9.8`). It can be customized by
-/// constructing an [`EnvironmentInfo`] with a different value:
+/// Gravity defaults to Earth's gravity (`-9.8` along Y). It can be customized
+/// by constructing an [`EnvironmentInfo`] with a different value:
 ///
 /// ```
 /// use meshi_physics::{EnvironmentInfo, PhysicsSimulation, SimulationInfo};
@@ -18,27 +19,100 @@ use std::collections::{HashMap, HashSet};
 /// ```
 pub struct EnvironmentInfo {
     /// Gravitational acceleration in meters per second squared.
-    pub gravity_mps: f32,
+    pub gravity: Vec3,
 }
 
 impl EnvironmentInfo {
-    /// Create a new [`EnvironmentInfo`] with the provided gravity value.
+    /// Create a new [`EnvironmentInfo`] with `gravity_mps` along the Y axis.
     pub fn new(gravity_mps: f32) -> Self {
-        Self { gravity_mps }
+        Self::from_vector(vec3(0.0, gravity_mps, 0.0))
+    }
+
+    /// Create a new [`EnvironmentInfo`] pulling in an arbitrary direction.
+    pub fn from_vector(gravity: Vec3) -> Self {
+        Self { gravity }
     }
 }
 
 impl Default for EnvironmentInfo {
     fn default() -> Self {
-        Self { gravity_mps: -9.8 }
+        Self::new(-9.8)
     }
 }
 
 #[repr(C)]
-#[derive(Default, Clone, Copy)]
+#[derive(Clone, Copy)]
 pub struct SimulationInfo {
     pub environment: EnvironmentInfo,
     pub debug_mode: bool,
+    /// Approach speed in meters per second below which contacts don't
+    /// bounce, so resting bodies settle instead of jittering.
+    pub restitution_threshold: f32,
+    /// Number of detect-and-correct passes per step. More passes let deep
+    /// overlaps, such as in stacks, settle; `1` is a single pass.
+    pub solver_iterations: u32,
+    /// Seconds per simulation step. `update` accumulates frame time and runs
+    /// as many whole steps as fit; `0.0` runs one step of the frame time.
+    pub fixed_timestep: f32,
+    /// Most fixed steps a single `update` may run. Time beyond that is
+    /// dropped so a slow frame cannot snowball.
+    pub max_substeps: u32,
+    /// How two touching materials' friction coefficients combine.
+    pub friction_combine: FrictionCombine,
+}
+
+impl Default for SimulationInfo {
+    fn default() -> Self {
+        Self {
+            environment: EnvironmentInfo::default(),
+            debug_mode: false,
+            restitution_threshold: 1.0,
+            solver_iterations: 1,
+            fixed_timestep: 0.0,
+            max_substeps: 8,
+            friction_combine: FrictionCombine::default(),
+        }
+    }
+}
+
+/// Rule for combining the friction coefficients of two bodies in contact.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FrictionCombine {
+    #[default]
+    Average,
+    Min,
+    Max,
+    Multiply,
+}
+
+impl FrictionCombine {
+    pub fn combine(self, a: f32, b: f32) -> f32 {
+        match self {
+            FrictionCombine::Average => (a + b) * 0.5,
+            FrictionCombine::Min => a.min(b),
+            FrictionCombine::Max => a.max(b),
+            FrictionCombine::Multiply => a * b,
+        }
+    }
+}
+
+fn pair_key(a: Handle<RigidBody>, b: Handle<RigidBody>) -> (u16, u16) {
+    if a.slot < b.slot {
+        (a.slot, b.slot)
+    } else {
+        (b.slot, a.slot)
+    }
+}
+
+/// Combined restitution of a contact, or zero when the bodies approach
+/// slower than `threshold`.
+fn contact_restitution(a: f32, b: f32, vel_along_normal: f32, threshold: f32) -> f32 {
+    if -vel_along_normal < threshold {
+        0.0
+    } else {
+        (a + b) * 0.5
+    }
 }
 
 #[repr(C)]
@@ -47,6 +121,9 @@ pub struct MaterialInfo {
     pub dynamic_friction_m: f32,
     pub static_friction_m: f32,
     pub restitution: f32,
+    /// Mass per unit volume, used for bodies created without a mass. Zero
+    /// leaves such bodies at unit mass.
+    pub density: f32,
 }
 
 impl Default for MaterialInfo {
@@ -55,6 +132,7 @@ impl Default for MaterialInfo {
             dynamic_friction_m: 5.0,
             static_friction_m: 5.0,
             restitution: 0.0,
+            density: 0.0,
         }
     }
 }
@@ -72,20 +150,83 @@ pub enum CollisionShapeType {
     Sphere = 0,
     Box = 1,
     Capsule = 2,
+    /// Infinite plane, only supported on static bodies.
+    Plane = 3,
+    /// Grid of heights, only supported on static bodies created with
+    /// [`PhysicsSimulation::create_static_heightfield`].
+    Heightfield = 4,
 }
 
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub struct CollisionShape {
-    /// Full extents of the box shape. For spheres this value is ignored.
+    /// Full extents of the box shape, or the unit normal of a plane. For
+    /// spheres this value is ignored.
     pub dimensions: Vec3,
     /// Radius for sphere shapes. For boxes this value is ignored.
     pub radius: f32,
-    /// Half height for capsule shapes. Ignored for other shapes.
+    /// Half height for capsule shapes, measured along the body's local Y
+    /// axis. Ignored for other shapes.
     pub half_height: f32,
     pub shape_type: CollisionShapeType,
 }
 
+impl CollisionShape {
+    /// Volume enclosed by the shape. Planes and heightfields are unbounded
+    /// and report zero.
+    pub fn volume(&self) -> f32 {
+        use std::f32::consts::PI;
+        let sphere = 4.0 / 3.0 * PI * self.radius.powi(3);
+        match self.shape_type {
+            CollisionShapeType::Sphere => sphere,
+            CollisionShapeType::Box => self.dimensions.x * self.dimensions.y * self.dimensions.z,
+            CollisionShapeType::Capsule => {
+                sphere + PI * self.radius * self.radius * self.half_height * 2.0
+            }
+            CollisionShapeType::Plane | CollisionShapeType::Heightfield => 0.0,
+        }
+    }
+
+    /// Bounds of the shape around its body's origin, before rotation.
+    /// Capsules run along Y. Planes and heightfields are unbounded.
+    pub fn local_aabb(&self) -> (Vec3, Vec3) {
+        let half = match self.shape_type {
+            CollisionShapeType::Sphere => Vec3::splat(self.radius),
+            CollisionShapeType::Box => self.dimensions * 0.5,
+            CollisionShapeType::Capsule => {
+                Vec3::splat(self.radius) + vec3(0.0, self.half_height, 0.0)
+            }
+            CollisionShapeType::Plane | CollisionShapeType::Heightfield => {
+                Vec3::splat(f32::INFINITY)
+            }
+        };
+        (-half, half)
+    }
+
+    /// World-space bounds of the shape placed at `position` with `rotation`.
+    pub fn world_aabb(&self, position: Vec3, rotation: Quat) -> (Vec3, Vec3) {
+        let (min, max) = self.local_aabb();
+        if !min.is_finite() || !max.is_finite() {
+            return (min, max);
+        }
+        let basis = Mat3::from_quat(rotation);
+        let center = position + basis * ((min + max) * 0.5);
+        let half = (max - min) * 0.5;
+        let extent = Vec3::new(
+            basis.row(0).abs().dot(half),
+            basis.row(1).abs().dot(half),
+            basis.row(2).abs().dot(half),
+        );
+        (center - extent, center + extent)
+    }
+
+    /// Offset from a capsule's centre to the centre of its top cap when the
+    /// body is turned by `rotation`.
+    fn capsule_half_axis(&self, rotation: Quat) -> Vec3 {
+        rotation * vec3(0.0, self.half_height, 0.0)
+    }
+}
+
 impl Default for CollisionShape {
     fn default() -> Self {
         Self {
@@ -97,6 +238,95 @@ impl Default for CollisionShape {
     }
 }
 
+/// Static grid of height samples used for terrain collision. Samples are
+/// stored row by row, with columns along X and rows along Z starting at the
+/// owning body's position. Each cell is split into two triangles along its
+/// diagonal.
+#[derive(Clone, Debug, Default)]
+pub struct Heightfield {
+    heights: Vec<f32>,
+    rows: u32,
+    cols: u32,
+    cell_size: f32,
+}
+
+impl Heightfield {
+    fn height(&self, col: u32, row: u32) -> f32 {
+        let idx = (row * self.cols + col) as usize;
+        self.heights.get(idx).copied().unwrap_or(0.0)
+    }
+
+    fn vertex(&self, col: u32, row: u32) -> Vec3 {
+        vec3(
+            col as f32 * self.cell_size,
+            self.height(col, row),
+            row as f32 * self.cell_size,
+        )
+    }
+
+    fn has_cells(&self) -> bool {
+        self.rows >= 2 && self.cols >= 2 && self.cell_size > 0.0
+    }
+
+    /// Height and surface normal of the triangle under `(x, z)`, relative to
+    /// the first sample, or `None` outside the grid.
+    fn surface(&self, x: f32, z: f32) -> Option<(f32, Vec3)> {
+        if !self.has_cells() {
+            return None;
+        }
+        let fx = x / self.cell_size;
+        let fz = z / self.cell_size;
+        if !(0.0..=(self.cols - 1) as f32).contains(&fx)
+            || !(0.0..=(self.rows - 1) as f32).contains(&fz)
+        {
+            return None;
+        }
+        let col = (fx.floor() as u32).min(self.cols - 2);
+        let row = (fz.floor() as u32).min(self.rows - 2);
+        let tx = fx - col as f32;
+        let tz = fz - row as f32;
+        let h00 = self.height(col, row);
+        let h10 = self.height(col + 1, row);
+        let h01 = self.height(col, row + 1);
+        let h11 = self.height(col + 1, row + 1);
+        let (rise_x, rise_z) = if tx >= tz {
+            (h10 - h00, h11 - h10)
+        } else {
+            (h11 - h01, h01 - h00)
+        };
+        let height = h00 + rise_x * tx + rise_z * tz;
+        let normal = vec3(-rise_x, self.cell_size, -rise_z).normalize();
+        Some((height, normal))
+    }
+
+    /// Triangles of every cell overlapping the X/Z rectangle from `min` to
+    /// `max`, relative to the first sample.
+    fn triangles(&self, min: Vec2, max: Vec2) -> Vec<[Vec3; 3]> {
+        if !self.has_cells() {
+            return Vec::new();
+        }
+        let last_col = (self.cols - 2) as f32;
+        let last_row = (self.rows - 2) as f32;
+        let first = (min / self.cell_size).floor();
+        let last = (max / self.cell_size).floor();
+        if last.x < 0.0 || last.y < 0.0 || first.x > last_col || first.y > last_row {
+            return Vec::new();
+        }
+        let mut triangles = Vec::new();
+        for row in first.y.max(0.0) as u32..=last.y.min(last_row) as u32 {
+            for col in first.x.max(0.0) as u32..=last.x.min(last_col) as u32 {
+                let v00 = self.vertex(col, row);
+                let v10 = self.vertex(col + 1, row);
+                let v01 = self.vertex(col, row + 1);
+                let v11 = self.vertex(col + 1, row + 1);
+                triangles.push([v00, v10, v11]);
+                triangles.push([v00, v11, v01]);
+            }
+        }
+        triangles
+    }
+}
+
 #[repr(C)]
 #[derive(Default, Clone, Copy)]
 pub struct RigidBodyInfo {
@@ -106,6 +336,9 @@ pub struct RigidBodyInfo {
     pub initial_rotation: glam::Quat,
     pub has_gravity: u32,
     pub collision_shape: CollisionShape,
+    /// Mass in kilograms. Zero derives it from the material's density and
+    /// the shape's volume, falling back to unit mass.
+    pub mass: f32,
 }
 
 #[repr(C)]
@@ -134,14 +367,42 @@ impl From<&MaterialInfo> for Material {
 pub struct RigidBody {
     position: Vec3,
     velocity: Vec3,
+    /// Rotation rate in radians per second about each world axis.
+    angular_velocity: Vec3,
     rotation: Quat,
     shape: CollisionShape,
     material: Handle<Material>,
+    /// Samples of a heightfield shape. Invalid for other shapes.
+    heightfield: Handle<Heightfield>,
     has_gravity: u32,
+    mass: f32,
+    /// Static bodies never move and behave as if they had infinite mass.
+    is_static: bool,
+    /// Bitmask of the layers this body belongs to, used by queries.
+    collision_layer: u32,
+    /// Sweep fast motion against other bodies to avoid tunnelling.
+    continuous: bool,
+    /// Leave this body out of collision resolution for the next step, so a
+    /// teleport is not undone by the resolver.
+    skip_resolution: bool,
+    /// Position at the start of the current step, used by the sweep and
+    /// for interpolation.
+    previous_position: Vec3,
+    /// Rotation at the start of the current step, used for interpolation.
+    previous_rotation: Quat,
     forces: Vec<Vec3>,
 }
 
 impl RigidBody {
+    /// Zero for static bodies, which no impulse can move.
+    fn inverse_mass(&self) -> f32 {
+        if self.is_static || self.mass <= 0.0 {
+            0.0
+        } else {
+            1.0 / self.mass
+        }
+    }
+
     pub fn dampen_velocity(&mut self, mat: &Material, dt: &Vec3) {
         let dfc = mat.info.dynamic_friction_m;
         self.velocity -= vec3(dfc, dfc, dfc) * *dt;
@@ -159,6 +420,17 @@ pub struct ActorStatus {
     pub rotation: Quat,
 }
 
+/// Full kinematic snapshot of a rigid body, e.g. for replicating it over
+/// the network and restoring it on the other side.
+#[repr(C)]
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub struct RigidBodyState {
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub linear_velocity: Vec3,
+    pub angular_velocity: Vec3,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Default)]
 pub struct ContactInfo {
@@ -166,8 +438,60 @@ pub struct ContactInfo {
     pub b: Handle<RigidBody>,
     pub normal: Vec3,
     pub penetration: f32,
+    /// Magnitude of the normal impulse applied to separate the bodies. Near
+    /// zero for resting contacts, larger for harder hits.
+    pub impulse: f32,
+}
+
+/// Upper bound on how many broadphase cells a body spans along one axis.
+const MAX_CELLS_PER_AXIS: f32 = 8.0;
+
+/// Cell size for the broadphase grid. Cells are sized from the average body
+/// so one large body does not collapse the grid, but never so small that a
+/// body spans more than [`MAX_CELLS_PER_AXIS`] cells.
+fn broadphase_cell_size(bounds: impl Iterator<Item = (Vec3, Vec3)> + Clone) -> f32 {
+    let extents = bounds.map(|(min, max)| (max - min).max_element());
+    let largest = extents.clone().fold(0.0f32, f32::max);
+    let count = extents.clone().count().max(1);
+    let mean = extents.sum::<f32>() / count as f32;
+    let cell_size = mean.max(largest / MAX_CELLS_PER_AXIS);
+    if cell_size > 0.0 {
+        cell_size
+    } else {
+        1.0
+    }
+}
+
+/// Layer assigned to newly created rigid bodies.
+pub const DEFAULT_COLLISION_LAYER: u32 = 1;
+
+#[repr(C)]
+#[derive(Clone, Copy, Default)]
+pub struct RaycastHit {
+    pub body: Handle<RigidBody>,
+    pub point: Vec3,
+    pub normal: Vec3,
+    pub distance: f32,
 }
 
+/// Callback invoked for each contact produced by [`PhysicsSimulation::update`].
+pub type ContactCallback = extern "C" fn(*const ContactInfo, *mut c_void);
+
+/// Opaque pointer handed back to a [`ContactCallback`].
+///
+/// The simulation never dereferences it; it is only passed back to the
+/// callback on the thread running [`PhysicsSimulation::update`]. Whoever
+/// registers the callback is responsible for the pointee being usable from
+/// that thread.
+#[derive(Clone, Copy)]
+struct ContactUserData(*mut c_void);
+
+// SAFETY: the pointer is never read by the simulation, see above.
+unsafe impl Send for ContactUserData {}
+
+/// Callback run around each simulation step with the step's `dt`.
+pub type StepCallback = Box<dyn FnMut(&mut PhysicsSimulation, f32)>;
+
 fn collide_sphere_box(
     sphere_pos: Vec3,
     radius: f32,
@@ -209,19 +533,64 @@ fn collide_sphere_box(
 
 fn closest_point_on_segment(p: Vec3, a: Vec3, b: Vec3) -> Vec3 {
     let ab = b - a;
-    let t = (p - a).dot(ab) / ab.length_squared();
+    let length_sq = ab.length_squared();
+    if length_sq <= f32::EPSILON {
+        return a;
+    }
+    let t = (p - a).dot(ab) / length_sq;
     a + ab * t.clamp(0.0, 1.0)
 }
 
+/// Closest points between segments `p1`-`q1` and `p2`-`q2`.
+fn closest_points_on_segments(p1: Vec3, q1: Vec3, p2: Vec3, q2: Vec3) -> (Vec3, Vec3) {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.length_squared();
+    let e = d2.length_squared();
+    let f = d2.dot(r);
+    if a <= f32::EPSILON && e <= f32::EPSILON {
+        return (p1, p2);
+    }
+    let (s, t) = if a <= f32::EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(r);
+        if e <= f32::EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(d2);
+            let denom = a * e - b * b;
+            // Parallel segments pick the middle of their shared span.
+            let s = if denom > f32::EPSILON {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                let lo = (-c / a).clamp(0.0, 1.0);
+                let hi = ((b - c) / a).clamp(0.0, 1.0);
+                (lo + hi) * 0.5
+            };
+            let t = (b * s + f) / e;
+            if t < 0.0 {
+                ((-c / a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((b - c) / a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+    (p1 + d1 * s, p2 + d2 * t)
+}
+
 fn collide_capsule_sphere(
     cap_pos: Vec3,
-    half_height: f32,
+    half_axis: Vec3,
     radius: f32,
     sphere_pos: Vec3,
     sphere_radius: f32,
 ) -> Option<(Vec3, f32)> {
-    let a = cap_pos + vec3(0.0, -half_height, 0.0);
-    let b = cap_pos + vec3(0.0, half_height, 0.0);
+    let a = cap_pos - half_axis;
+    let b = cap_pos + half_axis;
     let closest = closest_point_on_segment(sphere_pos, a, b);
     let delta = sphere_pos - closest;
     let dist = delta.length();
@@ -236,28 +605,18 @@ fn collide_capsule_sphere(
 
 fn collide_capsule_capsule(
     a_pos: Vec3,
-    a_half: f32,
+    a_axis: Vec3,
     a_radius: f32,
     b_pos: Vec3,
-    b_half: f32,
+    b_axis: Vec3,
     b_radius: f32,
 ) -> Option<(Vec3, f32)> {
-    let a_min = a_pos.y - a_half;
-    let a_max = a_pos.y + a_half;
-    let b_min = b_pos.y - b_half;
-    let b_max = b_pos.y + b_half;
-
-    let (ya, yb) = if a_max < b_min {
-        (a_max, b_min)
-    } else if b_max < a_min {
-        (a_min, b_max)
-    } else {
-        let y = (a_min.max(b_min) + a_max.min(b_max)) * 0.5;
-        (y, y)
-    };
-
-    let pa = vec3(a_pos.x, ya, a_pos.z);
-    let pb = vec3(b_pos.x, yb, b_pos.z);
+    let (pa, pb) = closest_points_on_segments(
+        a_pos - a_axis,
+        a_pos + a_axis,
+        b_pos - b_axis,
+        b_pos + b_axis,
+    );
     let delta = pb - pa;
     let dist = delta.length();
     let penetration = a_radius + b_radius - dist;
@@ -271,29 +630,24 @@ fn collide_capsule_capsule(
 
 fn collide_capsule_box(
     cap_pos: Vec3,
-    half_height: f32,
+    half_axis: Vec3,
     radius: f32,
     box_pos: Vec3,
     box_half: Vec3,
 ) -> Option<(Vec3, f32)> {
-    let seg_min = cap_pos.y - half_height;
-    let seg_max = cap_pos.y + half_height;
+    let bottom = cap_pos - half_axis;
+    let top = cap_pos + half_axis;
     let box_min = box_pos - box_half;
     let box_max = box_pos + box_half;
 
-    let closest_x = cap_pos.x.clamp(box_min.x, box_max.x);
-    let closest_z = cap_pos.z.clamp(box_min.z, box_max.z);
-    let closest_y = if seg_max < box_min.y {
-        box_min.y
-    } else if seg_min > box_max.y {
-        box_max.y
-    } else {
-        cap_pos.y.clamp(box_min.y, box_max.y)
-    };
-
-    let axis_y = closest_y.clamp(seg_min, seg_max);
-    let capsule_point = vec3(cap_pos.x, axis_y, cap_pos.z);
-    let box_point = vec3(closest_x, closest_y, closest_z);
+    // Alternate closest points between the axis and the box to find the
+    // axis point nearest the box.
+    let mut capsule_point = closest_point_on_segment(box_pos, bottom, top);
+    let mut box_point = capsule_point.clamp(box_min, box_max);
+    for _ in 0..4 {
+        capsule_point = closest_point_on_segment(box_point, bottom, top);
+        box_point = capsule_point.clamp(box_min, box_max);
+    }
     let delta = capsule_point - box_point;
     let dist_sq = delta.length_squared();
     if dist_sq < radius * radius {
@@ -305,15 +659,522 @@ fn collide_capsule_box(
     }
 }
 
+/// Collide a plane with a non-plane shape. The returned normal points from the
+/// plane towards the body.
+///
+/// Boxes are axis-aligned, so their deepest corner is their half extents
+/// projected onto the plane normal. A capsule's nearest end cap is its axis,
+/// turned by `body_rotation`, projected the same way.
+fn collide_plane(
+    plane_pos: Vec3,
+    plane_normal: Vec3,
+    body_pos: Vec3,
+    body_rotation: Quat,
+    body_shape: &CollisionShape,
+) -> Option<(Vec3, f32)> {
+    let extent = match body_shape.shape_type {
+        CollisionShapeType::Sphere => body_shape.radius,
+        CollisionShapeType::Box => (body_shape.dimensions * 0.5).dot(plane_normal.abs()),
+        CollisionShapeType::Capsule => {
+            body_shape.radius
+                + body_shape
+                    .capsule_half_axis(body_rotation)
+                    .dot(plane_normal)
+                    .abs()
+        }
+        CollisionShapeType::Plane | CollisionShapeType::Heightfield => return None,
+    };
+    let distance = (body_pos - plane_pos).dot(plane_normal);
+    let penetration = extent - distance;
+    if penetration > 0.0 {
+        Some((plane_normal, penetration))
+    } else {
+        None
+    }
+}
+
+fn closest_point_on_triangle(p: Vec3, [a, b, c]: [Vec3; 3]) -> Vec3 {
+    let ab = b - a;
+    let ac = c - a;
+    let d1 = ab.dot(p - a);
+    let d2 = ac.dot(p - a);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+    let d3 = ab.dot(p - b);
+    let d4 = ac.dot(p - b);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+    let d5 = ab.dot(p - c);
+    let d6 = ac.dot(p - c);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+/// Overlap test between a sphere and an upward facing terrain triangle. A
+/// centre below the triangle is pushed back out along the triangle normal.
+/// The normal points from the triangle towards the sphere.
+fn collide_sphere_triangle(center: Vec3, radius: f32, triangle: [Vec3; 3]) -> Option<(Vec3, f32)> {
+    let [a, b, c] = triangle;
+    let face = (c - a).cross(b - a).try_normalize()?;
+    let face = if face.y < 0.0 { -face } else { face };
+    let delta = center - closest_point_on_triangle(center, triangle);
+    let above = delta.dot(face);
+    let (normal, penetration) = if above < 0.0 {
+        (face, radius - above)
+    } else {
+        let dist = delta.length();
+        let normal = if dist > 1e-6 { delta / dist } else { face };
+        (normal, radius - dist)
+    };
+    (penetration > 0.0).then_some((normal, penetration))
+}
+
+/// Overlap test between a heightfield and a body. Spheres and capsules are
+/// tested against the triangles under them; boxes against the plane of the
+/// triangle under their centre. The normal points from the heightfield
+/// towards the body.
+fn collide_heightfield(
+    field: &Heightfield,
+    field_pos: Vec3,
+    body_pos: Vec3,
+    body_rotation: Quat,
+    body_shape: &CollisionShape,
+) -> Option<(Vec3, f32)> {
+    let local = body_pos - field_pos;
+    let footprint = |reach: f32| {
+        field.triangles(
+            vec2(local.x - reach, local.z - reach),
+            vec2(local.x + reach, local.z + reach),
+        )
+    };
+    let deepest = |best: Option<(Vec3, f32)>, hit: Option<(Vec3, f32)>| match (best, hit) {
+        (Some(b), Some(h)) if b.1 >= h.1 => Some(b),
+        (b, None) => b,
+        (_, h) => h,
+    };
+    match body_shape.shape_type {
+        CollisionShapeType::Sphere => footprint(body_shape.radius)
+            .into_iter()
+            .map(|triangle| collide_sphere_triangle(local, body_shape.radius, triangle))
+            .fold(None, deepest),
+        CollisionShapeType::Capsule => {
+            let half_axis = body_shape.capsule_half_axis(body_rotation);
+            let (bottom, top) = if half_axis.y >= 0.0 {
+                (local - half_axis, local + half_axis)
+            } else {
+                (local + half_axis, local - half_axis)
+            };
+            let reach = body_shape.radius + half_axis.x.abs().max(half_axis.z.abs());
+            footprint(reach)
+                .into_iter()
+                .map(|triangle| {
+                    // Alternate closest points between the axis and the
+                    // triangle to find the axis point nearest the terrain,
+                    // starting from the lower end.
+                    let mut point = bottom;
+                    if body_shape.half_height > 0.0 {
+                        for _ in 0..2 {
+                            let on_triangle = closest_point_on_triangle(point, triangle);
+                            point = closest_point_on_segment(on_triangle, bottom, top);
+                        }
+                    }
+                    collide_sphere_triangle(point, body_shape.radius, triangle)
+                })
+                .fold(None, deepest)
+        }
+        CollisionShapeType::Box => {
+            let (height, normal) = field.surface(local.x, local.z)?;
+            collide_plane(
+                field_pos + vec3(local.x, height, local.z),
+                normal,
+                body_pos,
+                body_rotation,
+                body_shape,
+            )
+        }
+        CollisionShapeType::Plane | CollisionShapeType::Heightfield => None,
+    }
+}
+
+/// Overlap test between two shapes. The normal points from `a` towards `b`.
+/// Capsules follow their body's rotation; boxes stay axis-aligned.
+/// Heightfields need their samples and are handled by
+/// [`collide_heightfield`] instead.
+fn collide_shapes(
+    a_pos: Vec3,
+    a_rot: Quat,
+    a_shape: &CollisionShape,
+    b_pos: Vec3,
+    b_rot: Quat,
+    b_shape: &CollisionShape,
+) -> Option<(Vec3, f32)> {
+    let mut result: Option<(Vec3, f32)> = None;
+
+    match (a_shape.shape_type, b_shape.shape_type) {
+        (CollisionShapeType::Sphere, CollisionShapeType::Sphere) => {
+            let delta = b_pos - a_pos;
+            let dist = delta.length();
+            let penetration = a_shape.radius + b_shape.radius - dist;
+            if penetration > 0.0 {
+                let normal = if dist > 0.0 { delta / dist } else { Vec3::Z };
+                result = Some((normal, penetration));
+            }
+        }
+        (CollisionShapeType::Box, CollisionShapeType::Box) => {
+            let a_half = a_shape.dimensions * 0.5;
+            let b_half = b_shape.dimensions * 0.5;
+            let delta = b_pos - a_pos;
+            let overlap_x = a_half.x + b_half.x - delta.x.abs();
+            let overlap_y = a_half.y + b_half.y - delta.y.abs();
+            let overlap_z = a_half.z + b_half.z - delta.z.abs();
+            if overlap_x > 0.0 && overlap_y > 0.0 && overlap_z > 0.0 {
+                if overlap_x < overlap_y && overlap_x < overlap_z {
+                    let normal = vec3(delta.x.signum(), 0.0, 0.0);
+                    result = Some((normal, overlap_x));
+                } else if overlap_y < overlap_z {
+                    let normal = vec3(0.0, delta.y.signum(), 0.0);
+                    result = Some((normal, overlap_y));
+                } else {
+                    let normal = vec3(0.0, 0.0, delta.z.signum());
+                    result = Some((normal, overlap_z));
+                }
+            }
+        }
+        (CollisionShapeType::Sphere, CollisionShapeType::Box) => {
+            if let Some((normal, penetration)) =
+                collide_sphere_box(a_pos, a_shape.radius, b_pos, b_shape.dimensions * 0.5)
+            {
+                result = Some((normal, penetration));
+            }
+        }
+        (CollisionShapeType::Box, CollisionShapeType::Sphere) => {
+            if let Some((normal, penetration)) =
+                collide_sphere_box(b_pos, b_shape.radius, a_pos, a_shape.dimensions * 0.5)
+            {
+                result = Some((-normal, penetration));
+            }
+        }
+        (CollisionShapeType::Capsule, CollisionShapeType::Capsule) => {
+            if let Some((normal, penetration)) = collide_capsule_capsule(
+                a_pos,
+                a_shape.capsule_half_axis(a_rot),
+                a_shape.radius,
+                b_pos,
+                b_shape.capsule_half_axis(b_rot),
+                b_shape.radius,
+            ) {
+                result = Some((normal, penetration));
+            }
+        }
+        (CollisionShapeType::Capsule, CollisionShapeType::Sphere) => {
+            if let Some((normal, penetration)) = collide_capsule_sphere(
+                a_pos,
+                a_shape.capsule_half_axis(a_rot),
+                a_shape.radius,
+                b_pos,
+                b_shape.radius,
+            ) {
+                result = Some((normal, penetration));
+            }
+        }
+        (CollisionShapeType::Sphere, CollisionShapeType::Capsule) => {
+            if let Some((normal, penetration)) = collide_capsule_sphere(
+                b_pos,
+                b_shape.capsule_half_axis(b_rot),
+                b_shape.radius,
+                a_pos,
+                a_shape.radius,
+            ) {
+                result = Some((-normal, penetration));
+            }
+        }
+        (CollisionShapeType::Capsule, CollisionShapeType::Box) => {
+            if let Some((normal, penetration)) = collide_capsule_box(
+                a_pos,
+                a_shape.capsule_half_axis(a_rot),
+                a_shape.radius,
+                b_pos,
+                b_shape.dimensions * 0.5,
+            ) {
+                result = Some((normal, penetration));
+            }
+        }
+        (CollisionShapeType::Box, CollisionShapeType::Capsule) => {
+            if let Some((normal, penetration)) = collide_capsule_box(
+                b_pos,
+                b_shape.capsule_half_axis(b_rot),
+                b_shape.radius,
+                a_pos,
+                a_shape.dimensions * 0.5,
+            ) {
+                result = Some((-normal, penetration));
+            }
+        }
+        (CollisionShapeType::Heightfield, _) | (_, CollisionShapeType::Heightfield) => {}
+        (CollisionShapeType::Plane, CollisionShapeType::Plane) => {}
+        (CollisionShapeType::Plane, _) => {
+            result = collide_plane(a_pos, a_shape.dimensions, b_pos, b_rot, b_shape);
+        }
+        (_, CollisionShapeType::Plane) => {
+            if let Some((normal, penetration)) =
+                collide_plane(b_pos, b_shape.dimensions, a_pos, a_rot, a_shape)
+            {
+                result = Some((-normal, penetration));
+            }
+        }
+    }
+
+    result
+}
+
+/// Smallest distance from a shape's centre to its surface, used as the CCD
+/// threshold and the conservative advancement step.
+fn sweep_radius(shape: &CollisionShape) -> f32 {
+    match shape.shape_type {
+        CollisionShapeType::Sphere | CollisionShapeType::Capsule => shape.radius,
+        CollisionShapeType::Box => shape.dimensions.min_element() * 0.5,
+        CollisionShapeType::Plane | CollisionShapeType::Heightfield => f32::INFINITY,
+    }
+}
+
+/// Cast a sphere along `motion` against `other` by ray casting against the
+/// other shape grown by the sphere radius. Returns the fraction of `motion`
+/// travelled and the surface normal facing the sphere.
+fn sweep_sphere(start: Vec3, motion: Vec3, radius: f32, other: &RigidBody) -> Option<(f32, Vec3)> {
+    let distance = motion.length();
+    let dir = motion / distance;
+    let mut grown = RigidBody {
+        position: other.position,
+        rotation: other.rotation,
+        shape: other.shape,
+        ..Default::default()
+    };
+    match other.shape.shape_type {
+        CollisionShapeType::Sphere | CollisionShapeType::Capsule => grown.shape.radius += radius,
+        CollisionShapeType::Box => grown.shape.dimensions += Vec3::splat(radius * 2.0),
+        CollisionShapeType::Plane => grown.position += other.shape.dimensions * radius,
+        CollisionShapeType::Heightfield => return None,
+    }
+    let (t, normal) = ray_body(start, dir, &grown)?;
+    (t <= distance).then_some((t / distance, normal))
+}
+
+/// Step along `motion` in increments no larger than the shape's radius and
+/// return the last fraction before it first overlaps `other`.
+fn advance_conservatively(
+    start: Vec3,
+    motion: Vec3,
+    rotation: Quat,
+    shape: &CollisionShape,
+    other: &RigidBody,
+) -> Option<(f32, Vec3)> {
+    let step = sweep_radius(shape).max(1e-3);
+    let steps = (motion.length() / step).ceil().max(1.0) as u32;
+    (1..=steps).find_map(|i| {
+        let position = start + motion * (i as f32 / steps as f32);
+        collide_shapes(
+            position,
+            rotation,
+            shape,
+            other.position,
+            other.rotation,
+            &other.shape,
+        )
+        .map(|(normal, _)| ((i - 1) as f32 / steps as f32, -normal))
+    })
+}
+
+/// Sweep `shape` along `motion` against `other`, exactly for spheres and by
+/// conservative advancement otherwise. Returns the fraction of `motion`
+/// travelled and the normal pointing from `other` towards the swept shape.
+fn sweep_shape(
+    start: Vec3,
+    motion: Vec3,
+    rotation: Quat,
+    shape: &CollisionShape,
+    other: &RigidBody,
+) -> Option<(f32, Vec3)> {
+    match shape.shape_type {
+        CollisionShapeType::Sphere => sweep_sphere(start, motion, shape.radius, other),
+        _ => advance_conservatively(start, motion, rotation, shape, other),
+    }
+}
+
+fn ray_sphere(origin: Vec3, dir: Vec3, center: Vec3, radius: f32) -> Option<f32> {
+    let oc = origin - center;
+    let b = oc.dot(dir);
+    let c = oc.length_squared() - radius * radius;
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    let disc = b * b - c;
+    if disc < 0.0 || b > 0.0 {
+        return None;
+    }
+    Some(-b - disc.sqrt())
+}
+
+fn ray_box(origin: Vec3, dir: Vec3, center: Vec3, half: Vec3) -> Option<(f32, Vec3)> {
+    let min = center - half;
+    let max = center + half;
+    let mut t_near = f32::NEG_INFINITY;
+    let mut t_far = f32::INFINITY;
+    let mut normal = -dir;
+    for axis in 0..3 {
+        if dir[axis].abs() < f32::EPSILON {
+            if origin[axis] < min[axis] || origin[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let inv = 1.0 / dir[axis];
+        let (t0, t1) = {
+            let a = (min[axis] - origin[axis]) * inv;
+            let b = (max[axis] - origin[axis]) * inv;
+            if a < b {
+                (a, b)
+            } else {
+                (b, a)
+            }
+        };
+        if t0 > t_near {
+            t_near = t0;
+            normal = Vec3::ZERO;
+            normal[axis] = -dir[axis].signum();
+        }
+        t_far = t_far.min(t1);
+        if t_near > t_far || t_far < 0.0 {
+            return None;
+        }
+    }
+    if t_near < 0.0 {
+        // The ray starts inside the box.
+        Some((0.0, -dir))
+    } else {
+        Some((t_near, normal))
+    }
+}
+
+fn ray_capsule(
+    origin: Vec3,
+    dir: Vec3,
+    center: Vec3,
+    half_axis: Vec3,
+    radius: f32,
+) -> Option<(f32, Vec3)> {
+    let a = center - half_axis;
+    let b = center + half_axis;
+    let mut best: Option<f32> = None;
+    let mut consider = |t: f32| {
+        if t >= 0.0 && !matches!(best, Some(b) if b <= t) {
+            best = Some(t);
+        }
+    };
+
+    if let Some(t) = ray_sphere(origin, dir, a, radius) {
+        consider(t);
+    }
+    if let Some(t) = ray_sphere(origin, dir, b, radius) {
+        consider(t);
+    }
+
+    // Cylinder wall around the segment, solved in the plane across its axis.
+    let half_length = half_axis.length();
+    if half_length > f32::EPSILON {
+        let axis = half_axis / half_length;
+        let offset = origin - center;
+        let o = offset - axis * offset.dot(axis);
+        let d = dir - axis * dir.dot(axis);
+        let qa = d.length_squared();
+        if qa > f32::EPSILON {
+            let qb = o.dot(d);
+            let qc = o.length_squared() - radius * radius;
+            let disc = qb * qb - qa * qc;
+            if disc >= 0.0 {
+                let t = (-qb - disc.sqrt()) / qa;
+                let along = (offset + dir * t).dot(axis);
+                if along.abs() <= half_length {
+                    consider(t.max(0.0));
+                }
+            }
+        }
+    }
+
+    let t = best?;
+    let point = origin + dir * t;
+    let normal = (point - closest_point_on_segment(point, a, b)).normalize_or_zero();
+    Some((t, normal))
+}
+
+fn ray_plane(origin: Vec3, dir: Vec3, plane_pos: Vec3, normal: Vec3) -> Option<(f32, Vec3)> {
+    let denom = dir.dot(normal);
+    if denom.abs() < f32::EPSILON {
+        return None;
+    }
+    let t = (plane_pos - origin).dot(normal) / denom;
+    if t < 0.0 {
+        return None;
+    }
+    Some((t, if denom < 0.0 { normal } else { -normal }))
+}
+
+fn ray_body(origin: Vec3, dir: Vec3, rb: &RigidBody) -> Option<(f32, Vec3)> {
+    let shape = &rb.shape;
+    match shape.shape_type {
+        CollisionShapeType::Sphere => ray_sphere(origin, dir, rb.position, shape.radius).map(|t| {
+            let normal = (origin + dir * t - rb.position)
+                .try_normalize()
+                .unwrap_or(-dir);
+            (t, normal)
+        }),
+        CollisionShapeType::Box => ray_box(origin, dir, rb.position, shape.dimensions * 0.5),
+        CollisionShapeType::Capsule => ray_capsule(
+            origin,
+            dir,
+            rb.position,
+            shape.capsule_half_axis(rb.rotation),
+            shape.radius,
+        ),
+        CollisionShapeType::Plane => ray_plane(origin, dir, rb.position, shape.dimensions),
+        CollisionShapeType::Heightfield => None,
+    }
+}
+
 impl From<&RigidBodyInfo> for RigidBody {
     fn from(value: &RigidBodyInfo) -> Self {
         RigidBody {
             position: value.initial_position,
             velocity: Default::default(),
+            angular_velocity: Vec3::ZERO,
             rotation: value.initial_rotation,
             shape: value.collision_shape,
             material: value.material,
+            heightfield: Handle::default(),
             has_gravity: value.has_gravity,
+            mass: if value.mass > 0.0 { value.mass } else { 1.0 },
+            is_static: false,
+            collision_layer: DEFAULT_COLLISION_LAYER,
+            continuous: false,
+            skip_resolution: false,
+            previous_position: value.initial_position,
+            previous_rotation: value.initial_rotation,
             forces: Vec::new(),
         }
     }
@@ -336,7 +1197,14 @@ pub struct PhysicsSimulation {
     materials: Pool<Material>,
     rigid_bodies: Pool<RigidBody>,
     contacts: Vec<ContactInfo>,
+    contact_callbacks: Vec<(ContactCallback, ContactUserData)>,
+    heightfields: Pool<Heightfield>,
+    pre_step_callback: Option<StepCallback>,
+    post_step_callback: Option<StepCallback>,
+    /// Frame time not yet simulated when using a fixed timestep.
+    accumulator: f32,
     default_material: Handle<Material>,
+    paused: bool,
 }
 
 impl PhysicsSimulation {
@@ -346,7 +1214,13 @@ impl PhysicsSimulation {
             materials: Default::default(),
             rigid_bodies: Default::default(),
             contacts: Vec::new(),
+            contact_callbacks: Vec::new(),
+            heightfields: Default::default(),
+            pre_step_callback: None,
+            post_step_callback: None,
+            accumulator: 0.0,
             default_material: Default::default(),
+            paused: false,
         };
 
         let default = s.materials.insert(Default::default()).unwrap();
@@ -364,279 +1238,442 @@ impl PhysicsSimulation {
 
     /// Set the global gravitational acceleration in meters per second squared.
     pub fn set_gravity(&mut self, gravity_mps: f32) {
-        self.info.environment.gravity_mps = gravity_mps;
+        self.set_gravity_vector(vec3(0.0, gravity_mps, 0.0));
     }
 
-    pub fn update(&mut self, dt: f32) -> Result<(), PhysicsError> {
-        let dt_vec = vec3(dt, dt, dt);
-        let mut had_invalid = false;
-
-        self.rigid_bodies.for_each_occupied_mut(|r| {
-            if let Some(mat) = self.materials.get_ref(r.material) {
-                if r.has_gravity == 1 {
-                    r.forces
-                        .push(vec3(0.0, self.info.environment.gravity_mps, 0.0) * dt_vec);
-                }
+    /// Set gravity as a full acceleration vector in meters per second squared.
+    pub fn set_gravity_vector(&mut self, gravity: Vec3) {
+        self.info.environment.gravity = gravity;
+    }
 
-                let total_force = r.forces.iter().fold(Vec3::ZERO, |acc, f| acc + *f);
-                r.velocity += total_force;
-                r.forces.clear();
+    /// Freeze the simulation. While paused [`Self::update`] does nothing and
+    /// the last frame's contacts stay available.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
 
-                let adj_velocity = r.velocity * dt_vec;
-                let pos = r.position;
-                r.position = pos + adj_velocity;
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
 
-                r.dampen_velocity(mat, &dt_vec);
-            } else {
-                had_invalid = true;
+    /// Advance the simulation by `dt` seconds, as one step or as whole fixed
+    /// steps depending on [`SimulationInfo::fixed_timestep`]. Contacts from
+    /// every step are reported once all steps have run.
+    pub fn update(&mut self, dt: f32) -> Result<(), PhysicsError> {
+        if self.paused {
+            return Ok(());
+        }
+        self.contacts.clear();
+        let (steps, step_dt) = self.plan_steps(dt);
+        let mut had_invalid = false;
+        for _ in 0..steps {
+            if let Some(mut cb) = self.pre_step_callback.take() {
+                cb(self, step_dt);
+                self.pre_step_callback.get_or_insert(cb);
             }
-        });
+            had_invalid |= self.step(step_dt);
+            if let Some(mut cb) = self.post_step_callback.take() {
+                cb(self, step_dt);
+                self.post_step_callback.get_or_insert(cb);
+            }
+        }
 
-        // Collision detection and resolution using a simple spatial grid
-        self.contacts.clear();
-        let mut handles = Vec::new();
-        self.rigid_bodies
-            .for_each_occupied_handle_mut(|h| handles.push(h));
+        self.notify_contacts();
 
-        // Determine a cell size based on the largest radius
-        let mut max_radius = 0.0f32;
-        for &h in &handles {
-            if let Some(rb) = self.rigid_bodies.get_ref(h) {
-                let r = match rb.shape.shape_type {
-                    CollisionShapeType::Sphere => rb.shape.radius,
-                    CollisionShapeType::Box => rb.shape.dimensions.max_element() * 0.5,
-                    CollisionShapeType::Capsule => rb.shape.radius + rb.shape.half_height,
-                };
-                max_radius = max_radius.max(r);
+        if had_invalid {
+            Err(PhysicsError::InvalidHandle)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Number of steps to run for a frame of `dt` seconds and their length.
+    fn plan_steps(&mut self, dt: f32) -> (u32, f32) {
+        let fixed = self.info.fixed_timestep;
+        if fixed <= 0.0 {
+            return (1, dt);
+        }
+        self.accumulator += dt.max(0.0);
+        let due = (self.accumulator / fixed).floor() as u32;
+        let steps = due.min(self.info.max_substeps.max(1));
+        self.accumulator -= steps as f32 * fixed;
+        if due > steps {
+            self.accumulator %= fixed;
+        }
+        (steps, fixed)
+    }
+
+    /// Set a callback run before each simulation step, e.g. to apply forces
+    /// once per fixed step. Replaces any previous pre-step callback.
+    pub fn set_pre_step_callback(&mut self, cb: impl FnMut(&mut PhysicsSimulation, f32) + 'static) {
+        self.pre_step_callback = Some(Box::new(cb));
+    }
+
+    /// Set a callback run after each simulation step has resolved
+    /// collisions. Replaces any previous post-step callback.
+    pub fn set_post_step_callback(
+        &mut self,
+        cb: impl FnMut(&mut PhysicsSimulation, f32) + 'static,
+    ) {
+        self.post_step_callback = Some(Box::new(cb));
+    }
+
+    /// Integrate and resolve one step. Returns `true` if an invalid handle
+    /// was encountered.
+    fn step(&mut self, dt: f32) -> bool {
+        let dt_vec = vec3(dt, dt, dt);
+        let mut had_invalid = false;
+
+        self.rigid_bodies.for_each_occupied_mut(|r| {
+            r.previous_position = r.position;
+            r.previous_rotation = r.rotation;
+            if r.is_static {
+                r.forces.clear();
+                r.velocity = Vec3::ZERO;
+                r.angular_velocity = Vec3::ZERO;
+                return;
+            }
+            if let Some(mat) = self.materials.get_ref(r.material) {
+                if r.has_gravity == 1 {
+                    r.velocity += self.info.environment.gravity * dt_vec;
+                }
+
+                let total_force = r.forces.iter().fold(Vec3::ZERO, |acc, f| acc + *f);
+                r.velocity += total_force * r.inverse_mass();
+                r.forces.clear();
+
+                let adj_velocity = r.velocity * dt_vec;
+                r.position += adj_velocity;
+                if r.angular_velocity != Vec3::ZERO {
+                    let spin = Quat::from_scaled_axis(r.angular_velocity * dt);
+                    r.rotation = (spin * r.rotation).normalize();
+                }
+
+                r.dampen_velocity(mat, &dt_vec);
             } else {
                 had_invalid = true;
             }
+        });
+
+        // Collision detection and resolution using a simple spatial grid
+        let mut reported = HashSet::new();
+        self.sweep_continuous_bodies(&mut reported);
+        for _ in 0..self.info.solver_iterations.max(1) {
+            had_invalid |= self.resolve_collisions(&mut reported);
         }
-        let cell_size = if max_radius > 0.0 {
-            max_radius * 2.0
-        } else {
-            1.0
-        };
+        self.rigid_bodies
+            .for_each_occupied_mut(|r| r.skip_resolution = false);
+        had_invalid
+    }
+
+    /// Detect and correct overlapping pairs once. A pair is only added to
+    /// the step's contacts the first time it is found in `reported`.
+    /// Returns `true` if an invalid handle was encountered.
+    fn resolve_collisions(&mut self, reported: &mut HashSet<(u16, u16)>) -> bool {
+        let mut had_invalid = false;
+        let mut handles = Vec::new();
+        self.rigid_bodies
+            .for_each_occupied_handle_mut(|h| handles.push(h));
 
-        // Populate the grid
-        let mut grid: HashMap<(i32, i32, i32), Vec<Handle<RigidBody>>> = HashMap::new();
+        // Planes and heightfields span far more than a grid cell, so they are
+        // tested against every body instead of going through the grid.
+        let mut planes = Vec::new();
+        handles.retain(|&h| match self.rigid_bodies.get_ref(h) {
+            Some(rb)
+                if matches!(
+                    rb.shape.shape_type,
+                    CollisionShapeType::Plane | CollisionShapeType::Heightfield
+                ) =>
+            {
+                planes.push(h);
+                false
+            }
+            _ => true,
+        });
+
+        // The narrow phase only turns capsules, so the grid does too.
+        let mut bounds = Vec::with_capacity(handles.len());
         for &h in &handles {
             if let Some(rb) = self.rigid_bodies.get_ref(h) {
-                let cell = (
-                    (rb.position.x / cell_size).floor() as i32,
-                    (rb.position.y / cell_size).floor() as i32,
-                    (rb.position.z / cell_size).floor() as i32,
-                );
-                grid.entry(cell).or_default().push(h);
+                let rotation = match rb.shape.shape_type {
+                    CollisionShapeType::Capsule => rb.rotation,
+                    _ => Quat::IDENTITY,
+                };
+                bounds.push((h, rb.shape.world_aabb(rb.position, rotation)));
             } else {
                 had_invalid = true;
             }
         }
+        let cell_size = broadphase_cell_size(bounds.iter().map(|(_, aabb)| *aabb));
+
+        // Insert each body into every cell its bounds touch. Cells are
+        // visited in key order so identical inputs resolve their contacts in
+        // the same order every run.
+        let cell_of = |p: Vec3| {
+            (
+                (p.x / cell_size).floor() as i32,
+                (p.y / cell_size).floor() as i32,
+                (p.z / cell_size).floor() as i32,
+            )
+        };
+        let mut grid: BTreeMap<(i32, i32, i32), Vec<Handle<RigidBody>>> = BTreeMap::new();
+        for (h, (min, max)) in &bounds {
+            let (lo, hi) = (cell_of(*min), cell_of(*max));
+            for x in lo.0..=hi.0 {
+                for y in lo.1..=hi.1 {
+                    for z in lo.2..=hi.2 {
+                        grid.entry((x, y, z)).or_default().push(*h);
+                    }
+                }
+            }
+        }
+
+        let restitution_threshold = self.info.restitution_threshold;
+        let friction_combine = self.info.friction_combine;
 
         // Helper closure to process a potential pair
         let mut process_pair = |ha: Handle<RigidBody>, hb: Handle<RigidBody>| {
             let a_ref = self.rigid_bodies.get_ref(ha).unwrap();
             let b_ref = self.rigid_bodies.get_ref(hb).unwrap();
+            if (a_ref.is_static && b_ref.is_static)
+                || a_ref.skip_resolution
+                || b_ref.skip_resolution
+            {
+                return;
+            }
+            let a_inv_mass = a_ref.inverse_mass();
+            let b_inv_mass = b_ref.inverse_mass();
             let a_pos = a_ref.position;
             let b_pos = b_ref.position;
+            let a_rot = a_ref.rotation;
+            let b_rot = b_ref.rotation;
             let a_vel = a_ref.velocity;
             let b_vel = b_ref.velocity;
             let a_shape = a_ref.shape;
             let b_shape = b_ref.shape;
+            let a_field = a_ref.heightfield;
+            let b_field = b_ref.heightfield;
             let a_mat = self.materials.get_ref(a_ref.material).unwrap();
             let b_mat = self.materials.get_ref(b_ref.material).unwrap();
 
-            let mut result: Option<(Vec3, f32)> = None;
-
-            match (a_shape.shape_type, b_shape.shape_type) {
-                (CollisionShapeType::Sphere, CollisionShapeType::Sphere) => {
-                    let delta = b_pos - a_pos;
-                    let dist = delta.length();
-                    let penetration = a_shape.radius + b_shape.radius - dist;
-                    if penetration > 0.0 {
-                        let normal = if dist > 0.0 { delta / dist } else { Vec3::Z };
-                        result = Some((normal, penetration));
-                    }
-                }
-                (CollisionShapeType::Box, CollisionShapeType::Box) => {
-                    let a_half = a_shape.dimensions * 0.5;
-                    let b_half = b_shape.dimensions * 0.5;
-                    let delta = b_pos - a_pos;
-                    let overlap_x = a_half.x + b_half.x - delta.x.abs();
-                    let overlap_y = a_half.y + b_half.y - delta.y.abs();
-                    let overlap_z = a_half.z + b_half.z - delta.z.abs();
-                    if overlap_x > 0.0 && overlap_y > 0.0 && overlap_z > 0.0 {
-                        if overlap_x < overlap_y && overlap_x < overlap_z {
-                            let normal = vec3(delta.x.signum(), 0.0, 0.0);
-                            result = Some((normal, overlap_x));
-                        } else if overlap_y < overlap_z {
-                            let normal = vec3(0.0, delta.y.signum(), 0.0);
-                            result = Some((normal, overlap_y));
-                        } else {
-                            let normal = vec3(0.0, 0.0, delta.z.signum());
-                            result = Some((normal, overlap_z));
-                        }
-                    }
-                }
-                (CollisionShapeType::Sphere, CollisionShapeType::Box) => {
-                    if let Some((normal, penetration)) =
-                        collide_sphere_box(a_pos, a_shape.radius, b_pos, b_shape.dimensions * 0.5)
-                    {
-                        result = Some((normal, penetration));
-                    }
-                }
-                (CollisionShapeType::Box, CollisionShapeType::Sphere) => {
-                    if let Some((normal, penetration)) =
-                        collide_sphere_box(b_pos, b_shape.radius, a_pos, a_shape.dimensions * 0.5)
-                    {
-                        result = Some((-normal, penetration));
-                    }
-                }
-                (CollisionShapeType::Capsule, CollisionShapeType::Capsule) => {
-                    if let Some((normal, penetration)) = collide_capsule_capsule(
-                        a_pos,
-                        a_shape.half_height,
-                        a_shape.radius,
-                        b_pos,
-                        b_shape.half_height,
-                        b_shape.radius,
-                    ) {
-                        result = Some((normal, penetration));
-                    }
-                }
-                (CollisionShapeType::Capsule, CollisionShapeType::Sphere) => {
-                    if let Some((normal, penetration)) = collide_capsule_sphere(
-                        a_pos,
-                        a_shape.half_height,
-                        a_shape.radius,
-                        b_pos,
-                        b_shape.radius,
-                    ) {
-                        result = Some((normal, penetration));
-                    }
-                }
-                (CollisionShapeType::Sphere, CollisionShapeType::Capsule) => {
-                    if let Some((normal, penetration)) = collide_capsule_sphere(
-                        b_pos,
-                        b_shape.half_height,
-                        b_shape.radius,
-                        a_pos,
-                        a_shape.radius,
-                    ) {
-                        result = Some((-normal, penetration));
-                    }
-                }
-                (CollisionShapeType::Capsule, CollisionShapeType::Box) => {
-                    if let Some((normal, penetration)) = collide_capsule_box(
-                        a_pos,
-                        a_shape.half_height,
-                        a_shape.radius,
-                        b_pos,
-                        b_shape.dimensions * 0.5,
-                    ) {
-                        result = Some((normal, penetration));
-                    }
-                }
-                (CollisionShapeType::Box, CollisionShapeType::Capsule) => {
-                    if let Some((normal, penetration)) = collide_capsule_box(
-                        b_pos,
-                        b_shape.half_height,
-                        b_shape.radius,
-                        a_pos,
-                        a_shape.dimensions * 0.5,
-                    ) {
-                        result = Some((-normal, penetration));
-                    }
-                }
-            }
+            let result = match (a_shape.shape_type, b_shape.shape_type) {
+                (CollisionShapeType::Heightfield, _) => self
+                    .heightfields
+                    .get_ref(a_field)
+                    .and_then(|field| collide_heightfield(field, a_pos, b_pos, b_rot, &b_shape)),
+                (_, CollisionShapeType::Heightfield) => self
+                    .heightfields
+                    .get_ref(b_field)
+                    .and_then(|field| collide_heightfield(field, b_pos, a_pos, a_rot, &a_shape))
+                    .map(|(normal, penetration)| (-normal, penetration)),
+                _ => collide_shapes(a_pos, a_rot, &a_shape, b_pos, b_rot, &b_shape),
+            };
 
             if let Some((normal, penetration)) = result {
-                let correction = normal * (penetration / 2.0);
+                let inv_mass_sum = a_inv_mass + b_inv_mass;
+                let correction = normal * (penetration / inv_mass_sum);
                 let rel_vel = b_vel - a_vel;
                 let vel_along_normal = rel_vel.dot(normal);
                 let mut a_vel_new = a_vel;
                 let mut b_vel_new = b_vel;
+                let mut normal_impulse = 0.0;
                 if vel_along_normal < 0.0 {
-                    let restitution = (a_mat.info.restitution + b_mat.info.restitution) * 0.5;
-                    let j = -vel_along_normal * (1.0 + restitution) * 0.5;
+                    let restitution = contact_restitution(
+                        a_mat.info.restitution,
+                        b_mat.info.restitution,
+                        vel_along_normal,
+                        restitution_threshold,
+                    );
+                    let j = -vel_along_normal * (1.0 + restitution) / inv_mass_sum;
+                    normal_impulse = j;
                     let impulse = normal * j;
-                    a_vel_new -= impulse;
-                    b_vel_new += impulse;
+                    a_vel_new -= impulse * a_inv_mass;
+                    b_vel_new += impulse * b_inv_mass;
+
+                    // Coulomb friction against the sliding velocity, bounded
+                    // by the normal impulse. Below the static limit the
+                    // bodies stick.
+                    let rel_vel = b_vel_new - a_vel_new;
+                    let tangent = rel_vel - normal * rel_vel.dot(normal);
+                    let slide_speed = tangent.length();
+                    if slide_speed > f32::EPSILON {
+                        let stop = slide_speed / inv_mass_sum;
+                        let static_limit = friction_combine
+                            .combine(a_mat.info.static_friction_m, b_mat.info.static_friction_m)
+                            * j;
+                        let jt = if stop <= static_limit {
+                            stop
+                        } else {
+                            let dynamic = friction_combine.combine(
+                                a_mat.info.dynamic_friction_m,
+                                b_mat.info.dynamic_friction_m,
+                            );
+                            (dynamic * j).min(stop)
+                        };
+                        let friction = tangent / slide_speed * jt;
+                        a_vel_new += friction * a_inv_mass;
+                        b_vel_new -= friction * b_inv_mass;
+                    }
                 }
 
                 if let Some(a_mut) = self.rigid_bodies.get_mut_ref(ha) {
-                    a_mut.position = a_pos - correction;
+                    a_mut.position = a_pos - correction * a_inv_mass;
                     a_mut.velocity = a_vel_new;
                 } else {
                     had_invalid = true;
                     return;
                 }
                 if let Some(b_mut) = self.rigid_bodies.get_mut_ref(hb) {
-                    b_mut.position = b_pos + correction;
+                    b_mut.position = b_pos + correction * b_inv_mass;
                     b_mut.velocity = b_vel_new;
                 } else {
                     had_invalid = true;
                     return;
                 }
 
-                self.contacts.push(ContactInfo {
-                    a: ha,
-                    b: hb,
-                    normal,
-                    penetration,
-                });
+                if reported.insert(pair_key(ha, hb)) {
+                    self.contacts.push(ContactInfo {
+                        a: ha,
+                        b: hb,
+                        normal,
+                        penetration,
+                        impulse: normal_impulse,
+                    });
+                }
             }
         };
 
         let mut checked: HashSet<(u16, u16)> = HashSet::new();
-        let offsets = [-1, 0, 1];
-        for (cell, bodies) in grid.iter() {
-            for i in 0..bodies.len() {
-                let ha = bodies[i];
-
-                // Check other bodies in the same cell
-                for j in (i + 1)..bodies.len() {
-                    let hb = bodies[j];
-                    let key = if ha.slot < hb.slot {
-                        (ha.slot, hb.slot)
-                    } else {
-                        (hb.slot, ha.slot)
-                    };
-                    if checked.insert(key) {
+        for bodies in grid.values() {
+            for (i, &ha) in bodies.iter().enumerate() {
+                for &hb in &bodies[i + 1..] {
+                    if checked.insert(pair_key(ha, hb)) {
                         process_pair(ha, hb);
                     }
                 }
+            }
+        }
 
-                // Check neighboring cells
-                for dx in offsets.iter() {
-                    for dy in offsets.iter() {
-                        for dz in offsets.iter() {
-                            if *dx == 0 && *dy == 0 && *dz == 0 {
-                                continue;
-                            }
-                            let neighbor = (cell.0 + *dx, cell.1 + *dy, cell.2 + *dz);
-                            if let Some(neighbors) = grid.get(&neighbor) {
-                                for &hb in neighbors {
-                                    let key = if ha.slot < hb.slot {
-                                        (ha.slot, hb.slot)
-                                    } else {
-                                        (hb.slot, ha.slot)
-                                    };
-                                    if checked.insert(key) {
-                                        process_pair(ha, hb);
-                                    }
-                                }
-                            }
-                        }
+        for &hp in &planes {
+            for &h in &handles {
+                process_pair(hp, h);
+            }
+        }
+
+        had_invalid
+    }
+
+    /// Move continuous bodies that travelled further than their radius this
+    /// step back to their first time of impact and resolve the hit there.
+    /// Hits are reported through `reported` like discrete contacts.
+    fn sweep_continuous_bodies(&mut self, reported: &mut HashSet<(u16, u16)>) {
+        let mut handles = Vec::new();
+        self.rigid_bodies
+            .for_each_occupied_handle_mut(|h| handles.push(h));
+
+        for &h in &handles {
+            let Some(rb) = self.rigid_bodies.get_ref(h) else {
+                continue;
+            };
+            if !rb.continuous || rb.is_static {
+                continue;
+            }
+            let start = rb.previous_position;
+            let motion = rb.position - start;
+            if motion.length() <= sweep_radius(&rb.shape) {
+                continue;
+            }
+
+            let mut first: Option<(f32, Vec3, Handle<RigidBody>)> = None;
+            for &other in &handles {
+                if other.slot == h.slot {
+                    continue;
+                }
+                let Some(ob) = self.rigid_bodies.get_ref(other) else {
+                    continue;
+                };
+                if let Some((fraction, normal)) =
+                    sweep_shape(start, motion, rb.rotation, &rb.shape, ob)
+                {
+                    if !matches!(first, Some((best, _, _)) if best <= fraction) {
+                        first = Some((fraction, normal, other));
                     }
                 }
             }
+            let Some((fraction, normal, other)) = first else {
+                continue;
+            };
+
+            // Same impulse as the discrete solver, with the normal pointing
+            // from the swept body towards the body it hit.
+            let normal = -normal;
+            let (b_vel, b_inv_mass, b_restitution) = match self.rigid_bodies.get_ref(other) {
+                Some(ob) => (
+                    ob.velocity,
+                    ob.inverse_mass(),
+                    self.restitution(ob.material),
+                ),
+                None => continue,
+            };
+            let a_vel = rb.velocity;
+            let a_inv_mass = rb.inverse_mass();
+            let a_restitution = self.restitution(rb.material);
+            let inv_mass_sum = a_inv_mass + b_inv_mass;
+            let vel_along_normal = (b_vel - a_vel).dot(normal);
+            let mut a_vel_new = a_vel;
+            let mut b_vel_new = b_vel;
+            let mut normal_impulse = 0.0;
+            if vel_along_normal < 0.0 {
+                let restitution = contact_restitution(
+                    a_restitution,
+                    b_restitution,
+                    vel_along_normal,
+                    self.info.restitution_threshold,
+                );
+                let j = -vel_along_normal * (1.0 + restitution) / inv_mass_sum;
+                a_vel_new -= normal * j * a_inv_mass;
+                b_vel_new += normal * j * b_inv_mass;
+                normal_impulse = j;
+            }
+
+            if let Some(a_mut) = self.rigid_bodies.get_mut_ref(h) {
+                a_mut.position = start + motion * fraction;
+                a_mut.velocity = a_vel_new;
+            }
+            if let Some(b_mut) = self.rigid_bodies.get_mut_ref(other) {
+                b_mut.velocity = b_vel_new;
+            }
+            if reported.insert(pair_key(h, other)) {
+                self.contacts.push(ContactInfo {
+                    a: h,
+                    b: other,
+                    normal,
+                    penetration: 0.0,
+                    impulse: normal_impulse,
+                });
+            }
         }
+    }
 
-        if had_invalid {
-            Err(PhysicsError::InvalidHandle)
-        } else {
-            Ok(())
+    fn restitution(&self, material: Handle<Material>) -> f32 {
+        self.materials
+            .get_ref(material)
+            .map(|m| m.info.restitution)
+            .unwrap_or(0.0)
+    }
+
+    /// Register a callback that receives every contact found during
+    /// [`PhysicsSimulation::update`], after collisions have been resolved.
+    pub fn register_contact_callback(&mut self, cb: ContactCallback, user_data: *mut c_void) {
+        self.contact_callbacks
+            .push((cb, ContactUserData(user_data)));
+    }
+
+    fn notify_contacts(&self) {
+        for contact in &self.contacts {
+            for (cb, data) in &self.contact_callbacks {
+                cb(contact, data.0);
+            }
         }
     }
 
@@ -649,10 +1686,115 @@ impl PhysicsSimulation {
         if !info.material.valid() {
             info.material = self.default_material;
         }
+        if info.mass <= 0.0 {
+            let density = self
+                .materials
+                .get_ref(info.material)
+                .map_or(0.0, |m| m.info.density);
+            info.mass = density * info.collision_shape.volume();
+        }
 
         self.rigid_bodies.insert((&info).into()).unwrap()
     }
 
+    /// Create an immovable infinite plane at `offset` along `normal`.
+    pub fn create_static_plane(
+        &mut self,
+        normal: Vec3,
+        offset: f32,
+        material: Handle<Material>,
+    ) -> Handle<RigidBody> {
+        let normal = normal.try_normalize().unwrap_or(Vec3::Y);
+        let shape = CollisionShape {
+            dimensions: normal,
+            radius: 0.0,
+            half_height: 0.0,
+            shape_type: CollisionShapeType::Plane,
+        };
+        self.create_static_body(normal * offset, shape, material)
+    }
+
+    /// Create an immovable axis-aligned box.
+    pub fn create_static_box(
+        &mut self,
+        center: Vec3,
+        half_extents: Vec3,
+        material: Handle<Material>,
+    ) -> Handle<RigidBody> {
+        let shape = CollisionShape {
+            dimensions: half_extents * 2.0,
+            radius: 0.0,
+            half_height: 0.0,
+            shape_type: CollisionShapeType::Box,
+        };
+        self.create_static_body(center, shape, material)
+    }
+
+    /// Store a grid of `rows` by `cols` height samples, `cell_size` apart, for
+    /// use by [`PhysicsSimulation::create_static_heightfield`]. Samples are
+    /// row-major with columns along X and rows along Z; missing samples read
+    /// as zero.
+    pub fn create_heightfield(
+        &mut self,
+        heights: &[f32],
+        rows: u32,
+        cols: u32,
+        cell_size: f32,
+    ) -> Handle<Heightfield> {
+        self.heightfields
+            .insert(Heightfield {
+                heights: heights.to_vec(),
+                rows,
+                cols,
+                cell_size,
+            })
+            .unwrap()
+    }
+
+    pub fn release_heightfield(&mut self, h: Handle<Heightfield>) {
+        self.heightfields.release(h);
+    }
+
+    /// Create an immovable body colliding with `heightfield`, whose first
+    /// sample sits at `origin`.
+    pub fn create_static_heightfield(
+        &mut self,
+        origin: Vec3,
+        heightfield: Handle<Heightfield>,
+        material: Handle<Material>,
+    ) -> Handle<RigidBody> {
+        let shape = CollisionShape {
+            dimensions: Vec3::ZERO,
+            radius: 0.0,
+            half_height: 0.0,
+            shape_type: CollisionShapeType::Heightfield,
+        };
+        let h = self.create_static_body(origin, shape, material);
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.heightfield = heightfield;
+        }
+        h
+    }
+
+    fn create_static_body(
+        &mut self,
+        position: Vec3,
+        shape: CollisionShape,
+        material: Handle<Material>,
+    ) -> Handle<RigidBody> {
+        let h = self.create_rigid_body(&RigidBodyInfo {
+            material,
+            initial_position: position,
+            has_gravity: 0,
+            collision_shape: shape,
+            ..Default::default()
+        });
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.is_static = true;
+        }
+        h
+    }
+
     pub fn release_material(&mut self, h: Handle<Material>) {
         self.materials.release(h);
     }
@@ -661,6 +1803,36 @@ impl PhysicsSimulation {
         self.rigid_bodies.release(h);
     }
 
+    /// Handles of every live rigid body, static bodies included.
+    pub fn rigid_body_handles(&mut self) -> Vec<Handle<RigidBody>> {
+        let mut handles = Vec::new();
+        self.rigid_bodies
+            .for_each_occupied_handle_mut(|h| handles.push(h));
+        handles
+    }
+
+    /// Visit every live rigid body with its handle.
+    pub fn for_each_rigid_body(&mut self, mut f: impl FnMut(Handle<RigidBody>, &RigidBody)) {
+        for h in self.rigid_body_handles() {
+            if let Some(rb) = self.rigid_bodies.get_ref(h) {
+                f(h, rb);
+            }
+        }
+    }
+
+    /// Collision shape and pose of every live rigid body, e.g. for drawing
+    /// them as a debug overlay.
+    pub fn collision_shapes(&self) -> Vec<(CollisionShape, ActorStatus)> {
+        let mut shapes = Vec::new();
+        self.rigid_bodies
+            .for_each_occupied(|rb| shapes.push((rb.shape, rb.into())));
+        shapes
+    }
+
+    pub fn rigid_body_count(&mut self) -> usize {
+        self.rigid_body_handles().len()
+    }
+
     pub fn apply_rigid_body_force(
         &mut self,
         h: Handle<RigidBody>,
@@ -682,8 +1854,33 @@ impl PhysicsSimulation {
             return false;
         }
         if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            // Teleports snap rather than interpolate from the old pose.
             rb.position = info.position;
             rb.rotation = info.rotation;
+            rb.previous_position = info.position;
+            rb.previous_rotation = info.rotation;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Place a body at `position` and `rotation`, clearing its velocity and
+    /// pending forces. The body is left out of the next step's collision
+    /// resolution so it stays where placed even if it overlaps another body.
+    pub fn teleport(&mut self, h: Handle<RigidBody>, position: Vec3, rotation: Quat) -> bool {
+        if !h.valid() {
+            return false;
+        }
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.position = position;
+            rb.rotation = rotation;
+            rb.previous_position = position;
+            rb.previous_rotation = rotation;
+            rb.velocity = Vec3::ZERO;
+            rb.angular_velocity = Vec3::ZERO;
+            rb.forces.clear();
+            rb.skip_resolution = true;
             true
         } else {
             false
@@ -706,6 +1903,49 @@ impl PhysicsSimulation {
         }
     }
 
+    /// Enable or disable gravity on an existing body. Takes effect on the
+    /// next step; velocity already gained is kept.
+    pub fn set_rigid_body_gravity(&mut self, h: Handle<RigidBody>, enabled: bool) -> bool {
+        if !h.valid() {
+            return false;
+        }
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.has_gravity = enabled as u32;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Enable continuous collision for a body. Continuous bodies that move
+    /// further than their radius in one step are swept along their path and
+    /// stopped at the first impact instead of passing through thin geometry.
+    pub fn set_rigid_body_continuous(&mut self, h: Handle<RigidBody>, enabled: bool) -> bool {
+        if !h.valid() {
+            return false;
+        }
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.continuous = enabled;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn rigid_body_has_gravity(&self, h: Handle<RigidBody>) -> Option<bool> {
+        if !h.valid() {
+            return None;
+        }
+        self.rigid_bodies.get_ref(h).map(|rb| rb.has_gravity == 1)
+    }
+
+    pub fn rigid_body_mass(&self, h: Handle<RigidBody>) -> Option<f32> {
+        if !h.valid() {
+            return None;
+        }
+        self.rigid_bodies.get_ref(h).map(|rb| rb.mass)
+    }
+
     pub fn get_rigid_body_status(&self, h: Handle<RigidBody>) -> Option<ActorStatus> {
         if !h.valid() {
             return None;
@@ -713,6 +1953,32 @@ impl PhysicsSimulation {
         self.rigid_bodies.get_ref(h).map(|rb| rb.into())
     }
 
+    /// Pose `alpha` of the way from the start of the last step to its end.
+    /// Pass [`PhysicsSimulation::step_alpha`] to render between fixed steps.
+    pub fn interpolated_status(&self, h: Handle<RigidBody>, alpha: f32) -> Option<ActorStatus> {
+        if !h.valid() {
+            return None;
+        }
+        let rb = self.rigid_bodies.get_ref(h)?;
+        let alpha = alpha.clamp(0.0, 1.0);
+        Some(ActorStatus {
+            position: rb.previous_position.lerp(rb.position, alpha),
+            rotation: rb.previous_rotation.slerp(rb.rotation, alpha),
+        })
+    }
+
+    /// Fraction of a fixed step left unsimulated after the last `update`.
+    /// Always `1.0` without a fixed timestep, since the last step ended at
+    /// the current time.
+    pub fn step_alpha(&self) -> f32 {
+        let fixed = self.info.fixed_timestep;
+        if fixed > 0.0 {
+            (self.accumulator / fixed).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
     pub fn get_rigid_body_velocity(&self, h: Handle<RigidBody>) -> Option<Vec3> {
         if !h.valid() {
             return None;
@@ -720,7 +1986,1246 @@ impl PhysicsSimulation {
         self.rigid_bodies.get_ref(h).map(|rb| rb.velocity)
     }
 
+    /// Set the linear velocity of a body directly, e.g. for precise launches.
+    /// Pending forces still apply on the next step.
+    pub fn set_rigid_body_velocity(&mut self, h: Handle<RigidBody>, linear: Vec3) -> bool {
+        if !h.valid() {
+            return false;
+        }
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.velocity = linear;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn get_rigid_body_state(&self, h: Handle<RigidBody>) -> Option<RigidBodyState> {
+        if !h.valid() {
+            return None;
+        }
+        self.rigid_bodies.get_ref(h).map(|rb| RigidBodyState {
+            position: rb.position,
+            rotation: rb.rotation,
+            linear_velocity: rb.velocity,
+            angular_velocity: rb.angular_velocity,
+        })
+    }
+
+    /// Restore a snapshot from [`PhysicsSimulation::get_rigid_body_state`].
+    /// Like [`PhysicsSimulation::set_rigid_body_transform`], the pose snaps
+    /// rather than interpolating from the old one. Pending forces are kept.
+    pub fn set_rigid_body_state(&mut self, h: Handle<RigidBody>, state: &RigidBodyState) -> bool {
+        if !h.valid() {
+            return false;
+        }
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.position = state.position;
+            rb.rotation = state.rotation;
+            rb.velocity = state.linear_velocity;
+            rb.angular_velocity = state.angular_velocity;
+            rb.previous_position = state.position;
+            rb.previous_rotation = state.rotation;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn get_contacts(&self) -> &[ContactInfo] {
         &self.contacts
     }
+
+    pub fn set_rigid_body_collision_layer(&mut self, h: Handle<RigidBody>, layer: u32) -> bool {
+        if !h.valid() {
+            return false;
+        }
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.collision_layer = layer;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Move `h` by `delta` without pushing anything, sliding along whatever
+    /// it runs into. Each hit removes the motion into the surface and the
+    /// rest is retried, up to `max_slides` times. Returns the displacement
+    /// actually achieved.
+    pub fn move_and_slide(&mut self, h: Handle<RigidBody>, delta: Vec3, max_slides: u32) -> Vec3 {
+        // Gap kept from surfaces so the next sweep does not start in contact.
+        const SKIN: f32 = 1e-3;
+
+        let Some(rb) = self.rigid_bodies.get_ref(h) else {
+            return Vec3::ZERO;
+        };
+        let shape = rb.shape;
+        let rotation = rb.rotation;
+        let start = rb.position;
+
+        let mut obstacles = Vec::new();
+        self.rigid_bodies.for_each_occupied_handle_mut(|other| {
+            if other.slot != h.slot {
+                obstacles.push(other);
+            }
+        });
+        let obstacles: Vec<RigidBody> = obstacles
+            .into_iter()
+            .filter_map(|other| self.rigid_bodies.get_ref(other))
+            .map(|ob| RigidBody {
+                position: ob.position,
+                rotation: ob.rotation,
+                shape: ob.shape,
+                ..Default::default()
+            })
+            .collect();
+
+        let mut position = start;
+        let mut remaining = delta;
+        for _ in 0..=max_slides {
+            if remaining.length_squared() <= f32::EPSILON {
+                break;
+            }
+            let hit = obstacles
+                .iter()
+                .filter_map(|ob| sweep_shape(position, remaining, rotation, &shape, ob))
+                .min_by(|a, b| a.0.total_cmp(&b.0));
+            let Some((fraction, normal)) = hit else {
+                position += remaining;
+                break;
+            };
+
+            position += remaining * fraction + normal * SKIN;
+            let rest = remaining * (1.0 - fraction);
+            remaining = rest - normal * rest.dot(normal).min(0.0);
+        }
+
+        if let Some(rb) = self.rigid_bodies.get_mut_ref(h) {
+            rb.position = position;
+            rb.previous_position = position;
+        }
+        position - start
+    }
+
+    /// Return the nearest body hit within `max_distance` whose collision layer
+    /// intersects `layer_mask`.
+    pub fn raycast(
+        &mut self,
+        origin: Vec3,
+        dir: Vec3,
+        max_distance: f32,
+        layer_mask: u32,
+    ) -> Option<RaycastHit> {
+        self.raycast_all(origin, dir, max_distance, layer_mask)
+            .into_iter()
+            .next()
+    }
+
+    /// Return every body hit within `max_distance`, sorted nearest first.
+    pub fn raycast_all(
+        &mut self,
+        origin: Vec3,
+        dir: Vec3,
+        max_distance: f32,
+        layer_mask: u32,
+    ) -> Vec<RaycastHit> {
+        let mut hits = Vec::new();
+        let Some(dir) = dir.try_normalize() else {
+            return hits;
+        };
+        let mut handles = Vec::new();
+        self.rigid_bodies
+            .for_each_occupied_handle_mut(|h| handles.push(h));
+        for h in handles {
+            let Some(rb) = self.rigid_bodies.get_ref(h) else {
+                continue;
+            };
+            if rb.collision_layer & layer_mask == 0 {
+                continue;
+            }
+            if let Some((distance, normal)) = ray_body(origin, dir, rb) {
+                if distance <= max_distance {
+                    hits.push(RaycastHit {
+                        body: h,
+                        point: origin + dir * distance,
+                        normal,
+                        distance,
+                    });
+                }
+            }
+        }
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern "C" fn record_contact(contact: *const ContactInfo, user_data: *mut c_void) {
+        let contacts = unsafe { &mut *(user_data as *mut Vec<ContactInfo>) };
+        contacts.push(unsafe { *contact });
+    }
+
+    #[test]
+    fn contact_callback_receives_colliding_pair() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let a = sim.create_rigid_body(&RigidBodyInfo {
+            initial_position: Vec3::ZERO,
+            ..Default::default()
+        });
+        let b = sim.create_rigid_body(&RigidBodyInfo {
+            initial_position: vec3(1.5, 0.0, 0.0),
+            ..Default::default()
+        });
+
+        let mut received: Vec<ContactInfo> = Vec::new();
+        sim.register_contact_callback(
+            record_contact,
+            &mut received as *mut Vec<ContactInfo> as *mut c_void,
+        );
+        sim.update(0.016).unwrap();
+
+        assert_eq!(received.len(), 1);
+        let contact = received[0];
+        let pair = [contact.a, contact.b];
+        assert!(pair.contains(&a) && pair.contains(&b));
+        assert!(contact.penetration > 0.0);
+        assert!(contact.normal.length() > 0.0);
+    }
+
+    fn drop_sphere_onto(sim: &mut PhysicsSimulation, height: f32) -> Handle<RigidBody> {
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            restitution: 0.0,
+            ..Default::default()
+        });
+        let sphere = sim.create_rigid_body(&RigidBodyInfo {
+            material: frictionless,
+            initial_position: vec3(0.0, height, 0.0),
+            has_gravity: 1,
+            collision_shape: CollisionShape {
+                radius: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        for _ in 0..240 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+        sphere
+    }
+
+    #[test]
+    fn sphere_comes_to_rest_on_static_box() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let floor =
+            sim.create_static_box(vec3(0.0, -0.5, 0.0), vec3(5.0, 0.5, 5.0), Handle::default());
+        let sphere = drop_sphere_onto(&mut sim, 2.0);
+
+        let status = sim.get_rigid_body_status(sphere).unwrap();
+        assert!((status.position.y - 0.5).abs() < 0.01);
+        assert!(sim.get_rigid_body_velocity(sphere).unwrap().length() < 1e-3);
+        assert_eq!(
+            sim.get_rigid_body_status(floor).unwrap().position,
+            vec3(0.0, -0.5, 0.0)
+        );
+    }
+
+    #[test]
+    fn sphere_comes_to_rest_on_static_plane() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        sim.create_static_plane(Vec3::Y, 1.0, Handle::default());
+        let sphere = drop_sphere_onto(&mut sim, 4.0);
+
+        let status = sim.get_rigid_body_status(sphere).unwrap();
+        assert!((status.position.y - 1.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn sphere_comes_to_rest_on_heightfield() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let field = sim.create_heightfield(&[1.0; 25], 5, 5, 1.0);
+        sim.create_static_heightfield(vec3(-2.0, 0.0, -2.0), field, Handle::default());
+        let sphere = drop_sphere_onto(&mut sim, 4.0);
+
+        let status = sim.get_rigid_body_status(sphere).unwrap();
+        assert!((status.position.y - 1.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn heightfield_surface_interpolates_slopes() {
+        // Ramp rising by 0.5 per sample along X.
+        let field = Heightfield {
+            heights: vec![0.0, 0.5, 1.0, 0.0, 0.5, 1.0],
+            rows: 2,
+            cols: 3,
+            cell_size: 2.0,
+        };
+        let (height, normal) = field.surface(1.0, 1.0).unwrap();
+        assert!((height - 0.25).abs() < 1e-5);
+        assert!((normal - vec3(-0.25, 1.0, 0.0).normalize()).length() < 1e-5);
+        assert!(field.surface(4.5, 1.0).is_none());
+    }
+
+    #[test]
+    fn sloped_heightfield_contact_uses_the_slope_normal() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        // Ramp rising 0.25 per cell along X, 0.5 high at the origin.
+        let heights: Vec<f32> = (0..5)
+            .flat_map(|_| (0..5).map(|col| col as f32 * 0.25))
+            .collect();
+        let field = sim.create_heightfield(&heights, 5, 5, 1.0);
+        let terrain =
+            sim.create_static_heightfield(vec3(-2.0, 0.0, -2.0), field, Handle::default());
+
+        let slope_normal = vec3(-0.25, 1.0, 0.0).normalize();
+        let sphere = sim.create_rigid_body(&RigidBodyInfo {
+            initial_position: vec3(0.0, 0.5, 0.0) + slope_normal * 0.45,
+            collision_shape: CollisionShape {
+                radius: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        sim.update(0.0).unwrap();
+
+        let contact = sim.get_contacts()[0];
+        assert_eq!(contact.a, terrain);
+        assert_eq!(contact.b, sphere);
+        assert!((contact.normal - slope_normal).length() < 1e-4);
+        assert!((contact.penetration - 0.05).abs() < 1e-4);
+    }
+
+    /// Drops `shape` onto the ground plane and returns its resting height
+    /// and the normal of its last contact.
+    fn rest_on_ground_plane(shape: CollisionShape) -> (f32, Vec3) {
+        rest_on_ground(shape, Quat::IDENTITY, |sim| {
+            sim.create_static_plane(Vec3::Y, 0.0, Handle::default())
+        })
+    }
+
+    /// Drops `shape`, turned by `rotation`, onto the static body built by
+    /// `ground` and returns its resting height and the normal of its last
+    /// contact, pointing from the ground towards the body.
+    fn rest_on_ground(
+        shape: CollisionShape,
+        rotation: Quat,
+        ground: impl FnOnce(&mut PhysicsSimulation) -> Handle<RigidBody>,
+    ) -> (f32, Vec3) {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let ground = ground(&mut sim);
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            restitution: 0.0,
+            ..Default::default()
+        });
+        let body = sim.create_rigid_body(&RigidBodyInfo {
+            material: frictionless,
+            initial_position: vec3(0.0, 2.0, 0.0),
+            initial_rotation: rotation,
+            has_gravity: 1,
+            collision_shape: shape,
+            ..Default::default()
+        });
+        for _ in 0..120 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+
+        let normal = sim
+            .get_contacts()
+            .iter()
+            .find_map(|c| {
+                if c.a == ground && c.b == body {
+                    Some(c.normal)
+                } else if c.a == body && c.b == ground {
+                    Some(-c.normal)
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+        let height = sim.get_rigid_body_status(body).unwrap().position.y;
+        (height, normal)
+    }
+
+    #[test]
+    fn box_rests_flat_on_plane() {
+        let (height, normal) = rest_on_ground_plane(CollisionShape {
+            dimensions: vec3(2.0, 1.0, 2.0),
+            shape_type: CollisionShapeType::Box,
+            ..Default::default()
+        });
+        assert!((height - 0.5).abs() < 0.01, "box at {height}");
+        assert_eq!(normal, Vec3::Y);
+    }
+
+    #[test]
+    fn capsule_rests_on_its_end_cap() {
+        let (height, normal) = rest_on_ground_plane(CollisionShape {
+            radius: 0.25,
+            half_height: 0.5,
+            shape_type: CollisionShapeType::Capsule,
+            ..Default::default()
+        });
+        assert!((height - 0.75).abs() < 0.01, "capsule at {height}");
+        assert_eq!(normal, Vec3::Y);
+    }
+
+    fn lying_capsule() -> (CollisionShape, Quat) {
+        let shape = CollisionShape {
+            radius: 0.25,
+            half_height: 1.0,
+            shape_type: CollisionShapeType::Capsule,
+            ..Default::default()
+        };
+        (shape, Quat::from_rotation_z(std::f32::consts::FRAC_PI_2))
+    }
+
+    #[test]
+    fn lying_capsule_rests_on_its_side_on_plane() {
+        let (shape, rotation) = lying_capsule();
+        let (height, normal) = rest_on_ground(shape, rotation, |sim| {
+            sim.create_static_plane(Vec3::Y, 0.0, Handle::default())
+        });
+        assert!((height - 0.25).abs() < 0.01, "capsule at {height}");
+        assert!(normal.abs_diff_eq(Vec3::Y, 1e-5), "normal {normal}");
+    }
+
+    #[test]
+    fn lying_capsule_rests_across_a_narrow_box() {
+        let (shape, rotation) = lying_capsule();
+        let (height, normal) = rest_on_ground(shape, rotation, |sim| {
+            sim.create_static_box(
+                vec3(0.0, -0.5, 0.0),
+                vec3(0.25, 0.5, 2.0),
+                Handle::default(),
+            )
+        });
+        assert!((height - 0.25).abs() < 0.01, "capsule at {height}");
+        assert!(normal.abs_diff_eq(Vec3::Y, 1e-5), "normal {normal}");
+    }
+
+    fn sphere_at(sim: &mut PhysicsSimulation, position: Vec3) -> Handle<RigidBody> {
+        sim.create_rigid_body(&RigidBodyInfo {
+            initial_position: position,
+            collision_shape: CollisionShape {
+                radius: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn teleport_holds_an_overlapping_body_for_one_step() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let anchor = sphere_at(&mut sim, Vec3::ZERO);
+        let body = sphere_at(&mut sim, vec3(10.0, 0.0, 0.0));
+        sim.apply_rigid_body_force(
+            body,
+            &ForceApplyInfo {
+                amt: vec3(0.0, 50.0, 0.0),
+            },
+        )
+        .unwrap();
+
+        let target = vec3(0.5, 0.0, 0.0);
+        assert!(sim.teleport(body, target, Quat::IDENTITY));
+        sim.update(1.0 / 60.0).unwrap();
+        assert_eq!(sim.get_rigid_body_status(body).unwrap().position, target);
+        assert_eq!(
+            sim.get_rigid_body_status(anchor).unwrap().position,
+            Vec3::ZERO
+        );
+
+        sim.update(1.0 / 60.0).unwrap();
+        let separation = sim.get_rigid_body_status(body).unwrap().position
+            - sim.get_rigid_body_status(anchor).unwrap().position;
+        assert!(
+            separation.length() > 0.99,
+            "still overlapping at {separation}"
+        );
+    }
+
+    #[test]
+    fn set_velocity_moves_the_body_by_velocity_times_dt() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            ..Default::default()
+        });
+        let body = sim.create_rigid_body(&RigidBodyInfo {
+            material: frictionless,
+            ..Default::default()
+        });
+
+        let velocity = vec3(3.0, 0.0, -1.5);
+        assert!(sim.set_rigid_body_velocity(body, velocity));
+        assert_eq!(sim.get_rigid_body_velocity(body), Some(velocity));
+        sim.update(0.5).unwrap();
+        let position = sim.get_rigid_body_status(body).unwrap().position;
+        assert!(
+            position.abs_diff_eq(velocity * 0.5, 1e-5),
+            "moved to {position}"
+        );
+    }
+
+    #[test]
+    fn paused_simulation_holds_bodies_until_resumed() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            ..Default::default()
+        });
+        let body = sim.create_rigid_body(&RigidBodyInfo {
+            material: frictionless,
+            ..Default::default()
+        });
+        let velocity = vec3(3.0, 0.0, -1.5);
+        assert!(sim.set_rigid_body_velocity(body, velocity));
+
+        sim.set_paused(true);
+        for _ in 0..3 {
+            sim.update(0.5).unwrap();
+        }
+        assert_eq!(
+            sim.get_rigid_body_status(body).unwrap().position,
+            Vec3::ZERO
+        );
+        assert_eq!(sim.get_rigid_body_velocity(body), Some(velocity));
+
+        sim.set_paused(false);
+        sim.update(0.5).unwrap();
+        let position = sim.get_rigid_body_status(body).unwrap().position;
+        assert!(
+            position.abs_diff_eq(velocity * 0.5, 1e-5),
+            "moved to {position}"
+        );
+    }
+
+    #[test]
+    fn raycast_skips_masked_out_layers() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let player = sphere_at(&mut sim, vec3(0.0, 0.0, -2.0));
+        let wall = sphere_at(&mut sim, vec3(0.0, 0.0, -6.0));
+        assert!(sim.set_rigid_body_collision_layer(player, 0b10));
+
+        let hit = sim
+            .raycast(Vec3::ZERO, Vec3::NEG_Z, 100.0, u32::MAX)
+            .unwrap();
+        assert!(hit.body == player);
+        assert!((hit.distance - 1.5).abs() < 1e-4);
+
+        let hit = sim.raycast(Vec3::ZERO, Vec3::NEG_Z, 100.0, 0b01).unwrap();
+        assert!(hit.body == wall);
+        assert!((hit.distance - 5.5).abs() < 1e-4);
+        assert!((hit.normal - Vec3::Z).length() < 1e-4);
+
+        assert!(sim.raycast(Vec3::ZERO, Vec3::NEG_Z, 5.0, 0b01).is_none());
+    }
+
+    #[test]
+    fn raycast_all_sorts_hits_by_distance() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let far = sim.create_static_box(vec3(10.0, 0.0, 0.0), Vec3::ONE, Handle::default());
+        let near = sphere_at(&mut sim, vec3(3.0, 0.0, 0.0));
+        let middle = sphere_at(&mut sim, vec3(6.0, 0.0, 0.0));
+        sphere_at(&mut sim, vec3(0.0, 5.0, 0.0));
+
+        let hits = sim.raycast_all(Vec3::ZERO, Vec3::X, 100.0, u32::MAX);
+        let bodies: Vec<_> = hits.iter().map(|h| h.body).collect();
+        assert!(bodies == vec![near, middle, far]);
+        assert!(hits.windows(2).all(|w| w[0].distance <= w[1].distance));
+        assert!((hits[2].distance - 9.0).abs() < 1e-4);
+        assert_eq!(hits[2].normal, Vec3::NEG_X);
+    }
+
+    fn fire_at_thin_wall(shape: CollisionShape, continuous: bool) -> Vec3 {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            restitution: 0.0,
+            ..Default::default()
+        });
+        sim.create_static_box(Vec3::ZERO, vec3(0.05, 2.0, 2.0), frictionless);
+        let bullet = sim.create_rigid_body(&RigidBodyInfo {
+            material: frictionless,
+            initial_position: vec3(-5.0, 0.0, 0.0),
+            collision_shape: shape,
+            ..Default::default()
+        });
+        assert!(sim.set_rigid_body_continuous(bullet, continuous));
+        sim.apply_rigid_body_force(
+            bullet,
+            &ForceApplyInfo {
+                amt: vec3(600.0, 0.0, 0.0),
+            },
+        )
+        .unwrap();
+        for _ in 0..3 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+        sim.get_rigid_body_status(bullet).unwrap().position
+    }
+
+    #[test]
+    fn continuous_sphere_stops_at_thin_wall() {
+        let sphere = CollisionShape {
+            radius: 0.05,
+            ..Default::default()
+        };
+        assert!(fire_at_thin_wall(sphere, false).x > 0.0);
+
+        let stopped = fire_at_thin_wall(sphere, true);
+        assert!((stopped.x + 0.1).abs() < 1e-3, "sphere at {stopped}");
+    }
+
+    #[test]
+    fn continuous_box_stops_before_thin_wall() {
+        let cube = CollisionShape {
+            dimensions: Vec3::splat(0.1),
+            shape_type: CollisionShapeType::Box,
+            ..Default::default()
+        };
+        assert!(fire_at_thin_wall(cube, false).x > 0.0);
+        assert!(fire_at_thin_wall(cube, true).x < -0.05);
+    }
+
+    #[test]
+    fn continuous_hit_is_reported_once_per_update() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let wall = sim.create_static_box(Vec3::ZERO, vec3(0.05, 2.0, 2.0), Handle::default());
+        let bullet = sim.create_rigid_body(&RigidBodyInfo {
+            initial_position: vec3(-5.0, 0.0, 0.0),
+            collision_shape: CollisionShape {
+                radius: 0.05,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        assert!(sim.set_rigid_body_continuous(bullet, true));
+        sim.apply_rigid_body_force(
+            bullet,
+            &ForceApplyInfo {
+                amt: vec3(600.0, 0.0, 0.0),
+            },
+        )
+        .unwrap();
+
+        let mut hits = Vec::new();
+        for _ in 0..3 {
+            sim.update(1.0 / 60.0).unwrap();
+            hits.push(
+                sim.get_contacts()
+                    .iter()
+                    .filter(|c| pair_key(c.a, c.b) == pair_key(bullet, wall))
+                    .count(),
+            );
+        }
+        assert!(
+            hits.iter().all(|&count| count <= 1),
+            "hits per update: {hits:?}"
+        );
+        assert!(hits.contains(&1), "hits per update: {hits:?}");
+    }
+
+    #[test]
+    fn move_and_slide_glides_along_a_wall() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let wall = sim.create_static_box(
+            Vec3::new(3.5, 0.0, 0.0),
+            Vec3::new(0.5, 5.0, 10.0),
+            Handle::default(),
+        );
+        let capsule = sim.create_rigid_body(&RigidBodyInfo {
+            has_gravity: 0,
+            collision_shape: CollisionShape {
+                shape_type: CollisionShapeType::Capsule,
+                radius: 0.5,
+                half_height: 1.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let moved = sim.move_and_slide(capsule, Vec3::new(5.0, 0.0, 5.0), 4);
+        // Stopped by the wall face at x = 3, but kept all of its z motion.
+        assert!(moved.x > 1.5 && moved.x < 2.5 + 1e-3, "{moved:?}");
+        assert!((moved.z - 5.0).abs() < 1e-3, "{moved:?}");
+        let status = sim.get_rigid_body_status(capsule).unwrap();
+        assert_eq!(status.position, moved);
+        assert_eq!(sim.get_rigid_body_velocity(wall), Some(Vec3::ZERO));
+    }
+
+    #[test]
+    fn step_callbacks_run_once_per_fixed_step() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let mut sim = PhysicsSimulation::new(&SimulationInfo {
+            fixed_timestep: 0.015,
+            ..Default::default()
+        });
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let pre_log = Rc::clone(&log);
+        sim.set_pre_step_callback(move |_, dt| pre_log.borrow_mut().push(("pre", dt)));
+        let post_log = Rc::clone(&log);
+        sim.set_post_step_callback(move |_, dt| post_log.borrow_mut().push(("post", dt)));
+
+        sim.update(0.033).unwrap();
+        assert_eq!(
+            *log.borrow(),
+            [
+                ("pre", 0.015),
+                ("post", 0.015),
+                ("pre", 0.015),
+                ("post", 0.015)
+            ]
+        );
+
+        // The 3 ms left over carries into the next frame.
+        log.borrow_mut().clear();
+        sim.update(0.013).unwrap();
+        assert_eq!(log.borrow().len(), 2);
+    }
+
+    #[test]
+    fn pre_step_forces_apply_every_step() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo {
+            fixed_timestep: 0.01,
+            ..Default::default()
+        });
+        let body = sim.create_rigid_body(&RigidBodyInfo::default());
+        sim.set_rigid_body_gravity(body, false);
+        sim.set_pre_step_callback(move |sim, _| {
+            sim.apply_rigid_body_force(
+                body,
+                &ForceApplyInfo {
+                    amt: Vec3::new(1.0, 0.0, 0.0),
+                },
+            )
+            .unwrap();
+        });
+
+        sim.update(0.035).unwrap();
+        let velocity = sim.get_rigid_body_velocity(body).unwrap();
+        // Three steps of +1 m/s each, less the material's friction.
+        assert!((velocity.x - 3.0).abs() < 0.2, "{velocity:?}");
+    }
+
+    #[test]
+    fn interpolated_status_blends_the_last_step() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo {
+            fixed_timestep: 0.1,
+            ..Default::default()
+        });
+        let body = sim.create_rigid_body(&RigidBodyInfo::default());
+        sim.set_rigid_body_gravity(body, false);
+        sim.apply_rigid_body_force(
+            body,
+            &ForceApplyInfo {
+                amt: Vec3::new(2.0, 0.0, 0.0),
+            },
+        )
+        .unwrap();
+
+        sim.update(0.1).unwrap();
+        let current = sim.get_rigid_body_status(body).unwrap().position;
+        assert!(current.x > 0.0);
+        let midpoint = sim.interpolated_status(body, 0.5).unwrap().position;
+        assert!((midpoint - current * 0.5).length() < 1e-6, "{midpoint:?}");
+
+        // Half a step of time leaves the pose alone and reports the leftover.
+        sim.update(0.05).unwrap();
+        assert_eq!(sim.get_rigid_body_status(body).unwrap().position, current);
+        assert!((sim.step_alpha() - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn gravity_vector_accelerates_along_its_direction() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        sim.set_gravity_vector(Vec3::new(9.8, 0.0, 0.0));
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            restitution: 0.0,
+            ..Default::default()
+        });
+        let body = sim.create_rigid_body(&RigidBodyInfo {
+            material: frictionless,
+            has_gravity: 1,
+            ..Default::default()
+        });
+
+        let mut last_x = 0.0;
+        for _ in 0..10 {
+            sim.update(0.1).unwrap();
+            let position = sim.get_rigid_body_status(body).unwrap().position;
+            assert!(position.x > last_x, "{position:?}");
+            assert_eq!((position.y, position.z), (0.0, 0.0));
+            last_x = position.x;
+        }
+        let velocity = sim.get_rigid_body_velocity(body).unwrap();
+        assert!((velocity.x - 9.8).abs() < 1e-3, "{velocity:?}");
+    }
+
+    #[test]
+    fn friction_combine_rules() {
+        let (high, low) = (0.9, 0.1);
+        let combined = |mode: FrictionCombine| mode.combine(high, low);
+        assert!((combined(FrictionCombine::Average) - 0.5).abs() < 1e-6);
+        assert_eq!(combined(FrictionCombine::Min), 0.1);
+        assert_eq!(combined(FrictionCombine::Max), 0.9);
+        assert!((combined(FrictionCombine::Multiply) - 0.09).abs() < 1e-6);
+    }
+
+    /// Slide a low-friction box across a high-friction floor and return its
+    /// remaining speed.
+    fn slide_across_floor(friction_combine: FrictionCombine) -> f32 {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo {
+            friction_combine,
+            ..Default::default()
+        });
+        let material = |sim: &mut PhysicsSimulation, friction: f32| {
+            sim.create_material(&MaterialInfo {
+                dynamic_friction_m: friction,
+                static_friction_m: friction,
+                restitution: 0.0,
+                ..Default::default()
+            })
+        };
+        let rough = material(&mut sim, 0.9);
+        let slick = material(&mut sim, 0.0);
+        sim.create_static_box(Vec3::new(0.0, -0.5, 0.0), Vec3::new(50.0, 0.5, 50.0), rough);
+        let body = sim.create_rigid_body(&RigidBodyInfo {
+            material: slick,
+            has_gravity: 1,
+            initial_position: Vec3::new(0.0, 0.49, 0.0),
+            collision_shape: CollisionShape {
+                shape_type: CollisionShapeType::Box,
+                dimensions: Vec3::ONE,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        sim.apply_rigid_body_force(
+            body,
+            &ForceApplyInfo {
+                amt: Vec3::new(3.0, 0.0, 0.0),
+            },
+        )
+        .unwrap();
+        for _ in 0..30 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+        sim.get_rigid_body_velocity(body).unwrap().x
+    }
+
+    #[test]
+    fn contact_friction_uses_the_combined_coefficient() {
+        let min = slide_across_floor(FrictionCombine::Min);
+        let multiply = slide_across_floor(FrictionCombine::Multiply);
+        let average = slide_across_floor(FrictionCombine::Average);
+        let max = slide_across_floor(FrictionCombine::Max);
+        // Min and Multiply both give a frictionless contact.
+        assert!((min - 3.0).abs() < 1e-4, "{min}");
+        assert!((multiply - 3.0).abs() < 1e-4, "{multiply}");
+        assert!(average < min - 0.5, "{average}");
+        assert!(max < average - 0.5, "{max}");
+    }
+
+    #[test]
+    fn enumeration_skips_released_bodies() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let a = sphere_at(&mut sim, vec3(0.0, 0.0, 0.0));
+        let b = sphere_at(&mut sim, vec3(5.0, 0.0, 0.0));
+        let c = sphere_at(&mut sim, vec3(10.0, 0.0, 0.0));
+        sim.release_rigid_body(b);
+
+        let mut slots: Vec<u16> = sim.rigid_body_handles().iter().map(|h| h.slot).collect();
+        slots.sort();
+        assert_eq!(slots, vec![a.slot, c.slot]);
+        assert_eq!(sim.rigid_body_count(), 2);
+
+        let mut visited = Vec::new();
+        sim.for_each_rigid_body(|h, rb| visited.push((h.slot, rb.position.x)));
+        visited.sort_by_key(|v| v.0);
+        assert_eq!(visited, vec![(a.slot, 0.0), (c.slot, 10.0)]);
+    }
+
+    /// Position bits per sphere and `(a, b, normal bits, penetration bits)`
+    /// per contact.
+    type PileSnapshot = (Vec<[u32; 3]>, Vec<(u16, u16, [u32; 3], u32)>);
+
+    fn run_sphere_pile() -> PileSnapshot {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        sim.create_static_box(
+            vec3(0.0, -0.5, 0.0),
+            vec3(10.0, 0.5, 10.0),
+            Handle::default(),
+        );
+        let mut spheres = Vec::new();
+        for i in 0..12 {
+            let x = (i % 4) as f32 * 0.9 - 1.3;
+            let z = (i / 4) as f32 * 0.9 - 0.9;
+            let sphere = sphere_at(&mut sim, vec3(x, 1.0 + (i % 3) as f32 * 0.7, z));
+            assert!(sim.set_rigid_body_gravity(sphere, true));
+            spheres.push(sphere);
+        }
+        for _ in 0..90 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+
+        let positions = spheres
+            .iter()
+            .map(|&h| {
+                let p = sim.get_rigid_body_status(h).unwrap().position;
+                p.to_array().map(f32::to_bits)
+            })
+            .collect();
+        let contacts = sim
+            .get_contacts()
+            .iter()
+            .map(|c| {
+                (
+                    c.a.slot,
+                    c.b.slot,
+                    c.normal.to_array().map(f32::to_bits),
+                    c.penetration.to_bits(),
+                )
+            })
+            .collect();
+        (positions, contacts)
+    }
+
+    #[test]
+    fn identical_scenarios_resolve_identically() {
+        let (positions, contacts) = run_sphere_pile();
+        assert!(!contacts.is_empty());
+        for _ in 0..3 {
+            assert_eq!(run_sphere_pile(), (positions.clone(), contacts.clone()));
+        }
+    }
+
+    /// Drops a perfectly elastic sphere from `gap` above a floor and returns
+    /// its final height and the fastest upward speed it reached.
+    fn drop_bouncy_sphere(gap: f32) -> (f32, f32) {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let bouncy = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            restitution: 1.0,
+            ..Default::default()
+        });
+        sim.create_static_box(vec3(0.0, -0.5, 0.0), vec3(5.0, 0.5, 5.0), bouncy);
+        let sphere = sim.create_rigid_body(&RigidBodyInfo {
+            material: bouncy,
+            initial_position: vec3(0.0, 0.5 + gap, 0.0),
+            has_gravity: 1,
+            collision_shape: CollisionShape {
+                radius: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let mut max_rise = 0.0f32;
+        for _ in 0..120 {
+            sim.update(1.0 / 60.0).unwrap();
+            max_rise = max_rise.max(sim.get_rigid_body_velocity(sphere).unwrap().y);
+        }
+        let height = sim.get_rigid_body_status(sphere).unwrap().position.y;
+        (height, max_rise)
+    }
+
+    /// Largest contact impulse reported while dropping a sphere from `gap`
+    /// above a floor, and the impulse once it has come to rest.
+    fn landing_impulses(gap: f32) -> (f32, f32) {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let material = sim.create_material(&MaterialInfo {
+            restitution: 0.0,
+            ..Default::default()
+        });
+        sim.create_static_box(vec3(0.0, -0.5, 0.0), vec3(5.0, 0.5, 5.0), material);
+        sim.create_rigid_body(&RigidBodyInfo {
+            material,
+            initial_position: vec3(0.0, 0.5 + gap, 0.0),
+            has_gravity: 1,
+            collision_shape: CollisionShape {
+                radius: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        let mut peak = 0.0f32;
+        let mut resting = 0.0f32;
+        for _ in 0..180 {
+            sim.update(1.0 / 60.0).unwrap();
+            resting = sim
+                .get_contacts()
+                .iter()
+                .map(|contact| contact.impulse)
+                .fold(0.0, f32::max);
+            peak = peak.max(resting);
+        }
+        (peak, resting)
+    }
+
+    #[test]
+    fn harder_hits_report_larger_impulses() {
+        let (slow, _) = landing_impulses(0.1);
+        let (fast, resting) = landing_impulses(5.0);
+        assert!(fast > slow * 2.0, "fast {fast} vs slow {slow}");
+        assert!(resting < fast * 0.1, "resting {resting} vs fast {fast}");
+    }
+
+    #[test]
+    fn slow_contacts_settle_while_fast_impacts_bounce() {
+        let (height, max_rise) = drop_bouncy_sphere(0.02);
+        assert!((height - 0.5).abs() < 0.01, "settled at {height}");
+        assert!(max_rise < 1e-4, "rose at {max_rise} m/s");
+
+        let (_, max_rise) = drop_bouncy_sphere(5.0);
+        assert!(max_rise > 5.0, "rose at {max_rise} m/s");
+    }
+
+    /// Heights of three unit boxes stacked on a floor after two seconds.
+    fn settle_box_stack(solver_iterations: u32) -> [f32; 3] {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo {
+            solver_iterations,
+            ..Default::default()
+        });
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            restitution: 0.0,
+            ..Default::default()
+        });
+        sim.create_static_box(vec3(0.0, -0.5, 0.0), vec3(5.0, 0.5, 5.0), frictionless);
+        let boxes = [0.5, 1.5, 2.5].map(|y| {
+            sim.create_rigid_body(&RigidBodyInfo {
+                material: frictionless,
+                initial_position: vec3(0.0, y, 0.0),
+                has_gravity: 1,
+                collision_shape: CollisionShape {
+                    dimensions: Vec3::ONE,
+                    shape_type: CollisionShapeType::Box,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+        });
+        for _ in 0..120 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+        boxes.map(|h| sim.get_rigid_body_status(h).unwrap().position.y)
+    }
+
+    #[test]
+    fn solver_iterations_keep_stacked_boxes_apart() {
+        let expected = [0.5, 1.5, 2.5];
+        let single = settle_box_stack(1);
+        assert!(
+            single[0] < expected[0] - 0.01,
+            "bottom box at {}",
+            single[0]
+        );
+
+        let stacked = settle_box_stack(4);
+        for (y, expected) in stacked.iter().zip(expected) {
+            assert!(
+                (y - expected).abs() < 0.005,
+                "box at {y}, expected {expected}"
+            );
+        }
+        for pair in stacked.windows(2) {
+            assert!(pair[1] - pair[0] > 0.995);
+        }
+    }
+
+    #[test]
+    fn disabling_gravity_stops_downward_acceleration() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            restitution: 0.0,
+            ..Default::default()
+        });
+        let body = sim.create_rigid_body(&RigidBodyInfo {
+            material: frictionless,
+            initial_position: vec3(0.0, 100.0, 0.0),
+            has_gravity: 1,
+            ..Default::default()
+        });
+        assert_eq!(sim.rigid_body_has_gravity(body), Some(true));
+
+        for _ in 0..10 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+        let falling = sim.get_rigid_body_velocity(body).unwrap();
+        assert!(falling.y < 0.0);
+
+        assert!(sim.set_rigid_body_gravity(body, false));
+        assert_eq!(sim.rigid_body_has_gravity(body), Some(false));
+        for _ in 0..10 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+        assert_eq!(sim.get_rigid_body_velocity(body).unwrap(), falling);
+        assert_eq!(sim.rigid_body_has_gravity(Handle::default()), None);
+    }
+
+    #[test]
+    fn rigid_body_state_round_trips() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let body = sim.create_rigid_body(&RigidBodyInfo::default());
+        let state = RigidBodyState {
+            position: vec3(1.0, 2.0, 3.0),
+            rotation: Quat::from_rotation_y(0.5),
+            linear_velocity: vec3(-1.0, 0.5, 4.0),
+            angular_velocity: vec3(0.0, 2.0, 0.25),
+        };
+
+        assert!(sim.set_rigid_body_state(body, &state));
+        assert_eq!(sim.get_rigid_body_state(body), Some(state));
+        assert!(!sim.set_rigid_body_state(Handle::default(), &state));
+        assert!(sim.get_rigid_body_state(Handle::default()).is_none());
+    }
+
+    #[test]
+    fn density_derives_mass_from_shape_volume() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let light = sim.create_material(&MaterialInfo {
+            density: 1.0,
+            ..Default::default()
+        });
+        let heavy = sim.create_material(&MaterialInfo {
+            density: 3.0,
+            ..Default::default()
+        });
+        let sphere = CollisionShape {
+            radius: 0.5,
+            ..Default::default()
+        };
+        let body = |sim: &mut PhysicsSimulation, material, mass| {
+            sim.create_rigid_body(&RigidBodyInfo {
+                material,
+                collision_shape: sphere,
+                mass,
+                ..Default::default()
+            })
+        };
+        let a = body(&mut sim, light, 0.0);
+        let b = body(&mut sim, heavy, 0.0);
+        let explicit = body(&mut sim, heavy, 2.0);
+
+        let a_mass = sim.rigid_body_mass(a).unwrap();
+        assert!((a_mass - sphere.volume()).abs() < 1e-5);
+        assert!((sim.rigid_body_mass(b).unwrap() - a_mass * 3.0).abs() < 1e-5);
+        assert_eq!(sim.rigid_body_mass(explicit), Some(2.0));
+        let default = sim.create_rigid_body(&RigidBodyInfo::default());
+        assert_eq!(sim.rigid_body_mass(default), Some(1.0));
+    }
+
+    #[test]
+    fn long_box_collides_with_sphere_past_its_center_cell() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let plank = sim.create_static_box(Vec3::ZERO, vec3(20.0, 1.0, 1.0), Handle::default());
+        // Small bodies elsewhere keep the grid cells much smaller than the box.
+        for i in 0..4 {
+            sim.create_rigid_body(&RigidBodyInfo {
+                initial_position: vec3(i as f32 * 3.0, 20.0, 0.0),
+                collision_shape: CollisionShape {
+                    radius: 0.25,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        }
+        let sphere = sim.create_rigid_body(&RigidBodyInfo {
+            initial_position: vec3(9.5, 0.9, 0.0),
+            collision_shape: CollisionShape {
+                radius: 0.5,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        sim.update(1.0 / 60.0).unwrap();
+
+        assert!(sim
+            .get_contacts()
+            .iter()
+            .any(|c| [c.a, c.b].contains(&plank) && [c.a, c.b].contains(&sphere)));
+        assert!(sim.get_rigid_body_status(sphere).unwrap().position.y >= 1.0 - 1e-4);
+    }
+
+    #[test]
+    fn world_aabb_follows_rotation() {
+        let shape = CollisionShape {
+            shape_type: CollisionShapeType::Box,
+            dimensions: vec3(4.0, 1.0, 2.0),
+            ..Default::default()
+        };
+        let (min, max) = shape.world_aabb(vec3(1.0, 0.0, 0.0), Quat::IDENTITY);
+        assert_eq!((min, max), (vec3(-1.0, -0.5, -1.0), vec3(3.0, 0.5, 1.0)));
+
+        let turned = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let (min, max) = shape.world_aabb(Vec3::ZERO, turned);
+        assert!((max - vec3(1.0, 0.5, 2.0)).abs().max_element() < 1e-5);
+        assert!((min + vec3(1.0, 0.5, 2.0)).abs().max_element() < 1e-5);
+    }
+
+    #[test]
+    fn large_body_collides_with_small_body_cells_away() {
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let wall = sim.create_static_box(Vec3::ZERO, vec3(16.0, 16.0, 1.0), Handle::default());
+        let pebble_shape = CollisionShape {
+            radius: 0.25,
+            ..Default::default()
+        };
+        let mut pebbles = Vec::new();
+        for i in 0..7 {
+            pebbles.push(sim.create_rigid_body(&RigidBodyInfo {
+                initial_position: vec3(i as f32 * 2.0 - 6.0, 30.0, 0.0),
+                collision_shape: pebble_shape,
+                ..Default::default()
+            }));
+        }
+        let pebble = pebbles[0];
+        let touching = vec3(7.5, 7.5, 0.7);
+        assert!(sim.set_rigid_body_transform(
+            pebble,
+            &ActorStatus {
+                position: touching,
+                rotation: Quat::IDENTITY,
+            }
+        ));
+
+        let wall_shape = CollisionShape {
+            shape_type: CollisionShapeType::Box,
+            dimensions: vec3(16.0, 16.0, 1.0),
+            ..Default::default()
+        };
+        let bounds = std::iter::once(wall_shape.local_aabb())
+            .chain(pebbles.iter().map(|_| pebble_shape.local_aabb()));
+        let cell_size = broadphase_cell_size(bounds);
+        assert!(16.0 > cell_size * 4.0);
+        assert!((touching.x / cell_size).floor() >= 2.0);
+
+        sim.update(1.0 / 60.0).unwrap();
+
+        assert!(sim
+            .get_contacts()
+            .iter()
+            .any(|c| [c.a, c.b].contains(&wall) && [c.a, c.b].contains(&pebble)));
+    }
 }
@@ -1,7 +1,8 @@
 use glam::{Mat4, Vec3};
 pub use meshi_audio::AudioEngine;
 use meshi_audio::{
-    AudioEngineInfo, AudioSource, Bus, FinishedCallback, PlaybackState, StreamingSource,
+    AudioBackend, AudioEngineInfo, AudioSource, Bus, FinishedCallback, PlaybackState,
+    StreamingSource,
 };
 pub use meshi_ffi_structs::*;
 pub use meshi_graphics::RenderEngine;
@@ -11,12 +12,16 @@ use meshi_graphics::{
 };
 pub use meshi_physics::PhysicsSimulation;
 use meshi_physics::SimulationInfo;
-use meshi_physics::{CollisionShape, CollisionShapeType, ContactInfo, ForceApplyInfo, RigidBody};
+use meshi_physics::{
+    CollisionShape, CollisionShapeType, ContactCallback, ContactInfo, ForceApplyInfo, RigidBody,
+};
 use meshi_utils::timer::Timer;
+use meshi_utils::MeshiError;
+use noren::rdb::terrain::TerrainChunkArtifact;
 use noren::{meta::DeviceModel, DBInfo};
 use resource_pool::Handle;
 use std::ffi::*;
-use tracing::{info, Level};
+use tracing::{error, info, Level};
 use tracing_subscriber::FmtSubscriber;
 
 macro_rules! return_if_null {
@@ -27,7 +32,10 @@ macro_rules! return_if_null {
     };
 }
 
-pub const MESHI_PLUGIN_ABI_VERSION: u32 = 3;
+/// Version of [`MeshiPluginApi`]. New entries are only ever appended to the
+/// end of the table, and each addition bumps this version so hosts built
+/// against an older table keep calling the right functions.
+pub const MESHI_PLUGIN_ABI_VERSION: u32 = 21;
 
 #[repr(C)]
 pub struct MeshiPluginApi {
@@ -121,6 +129,54 @@ pub struct MeshiPluginApi {
         extern "C" fn(*mut MeshiEngine, Handle<RenderObject>, Handle<RigidBody>) -> i32,
     pub unpair_render_physics:
         extern "C" fn(*mut MeshiEngine, *const Handle<RenderObject>, *const Handle<RigidBody>),
+    pub gfx_try_create_mesh_object:
+        extern "C" fn(*mut MeshiEngine, *const MeshObjectInfo, *mut Handle<RenderObject>) -> i32,
+    pub gfx_try_create_render_object:
+        extern "C" fn(*mut MeshiEngine, *const RenderObjectInfo, *mut Handle<RenderObject>) -> i32,
+    pub gfx_set_light_position: extern "C" fn(*mut MeshiEngine, Handle<Light>, Vec3),
+    pub gfx_set_transforms_batch:
+        extern "C" fn(*mut MeshiEngine, *const Handle<RenderObject>, *const Mat4, usize),
+    pub physx_register_contact_callback:
+        extern "C" fn(*mut MeshiEngine, *mut c_void, ContactCallback),
+    pub gfx_frame_dump: extern "C" fn(*mut MeshiEngine, *mut FFIImage) -> i32,
+    pub audio_set_master_volume: extern "C" fn(*mut MeshiEngine, c_float),
+    pub physx_set_rigid_body_gravity:
+        extern "C" fn(*mut MeshiEngine, *const Handle<meshi_physics::RigidBody>, i32) -> i32,
+    pub audio_stream_finished: extern "C" fn(*mut MeshiEngine, Handle<StreamingSource>) -> i32,
+    pub audio_set_stream_looping: extern "C" fn(*mut MeshiEngine, Handle<StreamingSource>, i32),
+    pub audio_get_backend: extern "C" fn(*mut MeshiEngine) -> AudioBackend,
+    pub audio_get_info: extern "C" fn(*mut MeshiEngine, *mut AudioEngineInfo),
+    pub gfx_set_primary_camera: extern "C" fn(*mut MeshiEngine, Handle<Camera>),
+    pub gfx_get_primary_camera: extern "C" fn(*mut MeshiEngine) -> Handle<Camera>,
+    pub gfx_set_object_mesh:
+        extern "C" fn(*mut MeshiEngine, Handle<RenderObject>, *const c_char) -> i32,
+    pub gfx_set_object_material:
+        extern "C" fn(*mut MeshiEngine, Handle<RenderObject>, *const c_char) -> i32,
+    pub gfx_object_count: extern "C" fn(*mut MeshiEngine) -> usize,
+    pub gfx_light_count: extern "C" fn(*mut MeshiEngine) -> usize,
+    pub gfx_camera_count: extern "C" fn(*mut MeshiEngine) -> usize,
+    pub physx_get_rigid_body_state: extern "C" fn(
+        *mut MeshiEngine,
+        *const Handle<meshi_physics::RigidBody>,
+        *mut meshi_physics::RigidBodyState,
+    ) -> i32,
+    pub physx_set_rigid_body_state: extern "C" fn(
+        *mut MeshiEngine,
+        *const Handle<meshi_physics::RigidBody>,
+        *const meshi_physics::RigidBodyState,
+    ) -> i32,
+    pub audio_play_one_shot:
+        extern "C" fn(*mut MeshiEngine, *const c_char, Vec3) -> Handle<AudioSource>,
+    pub gfx_set_display_size: extern "C" fn(*mut MeshiEngine, Handle<Display>, u32, u32),
+    pub gfx_get_display_size:
+        extern "C" fn(*mut MeshiEngine, Handle<Display>, *mut u32, *mut u32) -> i32,
+    pub gfx_set_display_fullscreen: extern "C" fn(*mut MeshiEngine, Handle<Display>, i32),
+    pub physx_set_rigid_body_velocity: extern "C" fn(
+        *mut MeshiEngine,
+        *const Handle<meshi_physics::RigidBody>,
+        *const Vec3,
+    ) -> i32,
+    pub physx_set_paused: extern "C" fn(*mut MeshiEngine, i32),
 }
 
 pub static MESHI_PLUGIN_API: MeshiPluginApi = MeshiPluginApi {
@@ -178,6 +234,33 @@ pub static MESHI_PLUGIN_API: MeshiPluginApi = MeshiPluginApi {
     physx_collision_shape_capsule: meshi_physx_collision_shape_capsule,
     pair_render_physics: meshi_pair_render_physics,
     unpair_render_physics: meshi_unpair_render_physics,
+    gfx_try_create_mesh_object: meshi_gfx_try_create_mesh_object,
+    gfx_try_create_render_object: meshi_gfx_try_create_render_object,
+    gfx_set_light_position: meshi_gfx_set_light_position,
+    gfx_set_transforms_batch: meshi_gfx_set_transforms_batch,
+    physx_register_contact_callback: meshi_physx_register_contact_callback,
+    gfx_frame_dump: meshi_gfx_frame_dump,
+    audio_set_master_volume: meshi_audio_set_master_volume,
+    physx_set_rigid_body_gravity: meshi_physx_set_rigid_body_gravity,
+    audio_stream_finished: meshi_audio_stream_finished,
+    audio_set_stream_looping: meshi_audio_set_stream_looping,
+    audio_get_backend: meshi_audio_get_backend,
+    audio_get_info: meshi_audio_get_info,
+    gfx_set_primary_camera: meshi_gfx_set_primary_camera,
+    gfx_get_primary_camera: meshi_gfx_get_primary_camera,
+    gfx_set_object_mesh: meshi_gfx_set_object_mesh,
+    gfx_set_object_material: meshi_gfx_set_object_material,
+    gfx_object_count: meshi_gfx_object_count,
+    gfx_light_count: meshi_gfx_light_count,
+    gfx_camera_count: meshi_gfx_camera_count,
+    physx_get_rigid_body_state: meshi_physx_get_rigid_body_state,
+    physx_set_rigid_body_state: meshi_physx_set_rigid_body_state,
+    audio_play_one_shot: meshi_audio_play_one_shot,
+    gfx_set_display_size: meshi_gfx_set_display_size,
+    gfx_get_display_size: meshi_gfx_get_display_size,
+    gfx_set_display_fullscreen: meshi_gfx_set_display_fullscreen,
+    physx_set_rigid_body_velocity: meshi_physx_set_rigid_body_velocity,
+    physx_set_paused: meshi_physx_set_paused,
 };
 
 #[no_mangle]
@@ -202,6 +285,35 @@ pub struct MeshiEngineInfo {
     pub debug_mode: i32,
 }
 
+/// Status codes returned by the `meshi_*_try_*` entry points.
+///
+/// The values are part of the C API and must stay stable.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MeshiResult {
+    Success = 0,
+    NullPointer = 1,
+    GpuError = 2,
+    DatabaseError = 3,
+    AssetError = 4,
+    ShaderError = 5,
+    Unknown = 6,
+    Unavailable = 7,
+    DatabaseUnavailable = 8,
+}
+
+impl From<&MeshiError> for MeshiResult {
+    fn from(value: &MeshiError) -> Self {
+        match value {
+            MeshiError::Gpu(_) => MeshiResult::GpuError,
+            MeshiError::Database(_) => MeshiResult::DatabaseError,
+            MeshiError::Asset(_) => MeshiResult::AssetError,
+            MeshiError::Shader(_) => MeshiResult::ShaderError,
+            MeshiError::Other(_) => MeshiResult::Unknown,
+        }
+    }
+}
+
 /// Primary engine instance returned by [`meshi_make_engine`].
 ///
 /// This struct owns the rendering and physics systems and should be
@@ -211,14 +323,25 @@ pub struct MeshiEngine {
     name: String,
     render: Box<RenderEngine>,
     physics: Box<PhysicsSimulation>,
-    database: Box<noren::DB>,
+    /// Asset database under the application directory. `None` when it could
+    /// not be opened; meshes then fail to load but the engine still runs.
+    database: Option<Box<noren::DB>>,
     audio: AudioEngine,
     frame_timer: Timer,
     render_physics_pairs: Vec<RenderPhysicsPair>,
+    /// Camera targeted when a camera call is given a default handle. The
+    /// first registered camera becomes primary.
+    primary_camera: Handle<Camera>,
 }
 
 impl MeshiEngine {
     fn new(info: &MeshiEngineInfo) -> Option<Box<MeshiEngine>> {
+        Self::with_database(info, true)
+    }
+
+    /// Build the engine, opening the asset database only if `open_database`
+    /// is set. Lightweight tools and tests can run without one.
+    fn with_database(info: &MeshiEngineInfo, open_database: bool) -> Option<Box<MeshiEngine>> {
         if info.application_name.is_null() || info.application_location.is_null() {
             return None;
         }
@@ -252,21 +375,30 @@ impl MeshiEngine {
             .expect("failed to initialize render engine"),
         );
 
-        let mut database = Box::new(
-            noren::DB::new(&DBInfo {
+        let mut database = if open_database {
+            match noren::DB::new(&DBInfo {
                 base_dir: &appdir,
                 layout_file: None,
                 pooled_geometry_uploads: false,
-            })
-            .expect("failed to initialize database!"),
-        );
+            }) {
+                Ok(database) => Some(Box::new(database)),
+                Err(err) => {
+                    error!("Failed to initialize database, continuing without it: {err:?}");
+                    None
+                }
+            }
+        } else {
+            None
+        };
 
-        render.initialize_database(database.as_mut());
         let mut audio = AudioEngine::new(&AudioEngineInfo {
             debug_mode: info.debug_mode != 0,
             ..Default::default()
         });
-        audio.initialize_database(database.as_mut());
+        if let Some(database) = database.as_mut() {
+            render.initialize_database(database);
+            audio.initialize_database(database);
+        }
         Some(Box::new(MeshiEngine {
             database,
             render,
@@ -278,22 +410,58 @@ impl MeshiEngine {
             frame_timer: Timer::new(),
             name: appname.to_string(),
             render_physics_pairs: Vec::new(),
+            primary_camera: Handle::default(),
         }))
     }
 
+    fn camera_or_primary(&self, camera: Handle<Camera>) -> Handle<Camera> {
+        if camera.valid() {
+            camera
+        } else {
+            self.primary_camera
+        }
+    }
+
     fn update(&mut self) -> f32 {
         self.frame_timer.stop();
         let dt = self.frame_timer.elapsed_duration();
         self.frame_timer.start();
         let dt_secs = dt.as_secs_f32();
-        self.sync_render_physics_pairs();
         let _ = self.physics.update(dt_secs);
+        self.sync_render_physics_pairs();
+        self.render.draw_physics_debug(&self.physics);
         self.render.update(dt_secs);
         self.audio.update(dt_secs);
 
         dt_secs
     }
 
+    fn create_render_object(
+        &mut self,
+        info: &RenderObjectInfo,
+    ) -> Result<Handle<RenderObject>, MeshiError> {
+        let mesh = if info.mesh.is_null() {
+            "model/default"
+        } else {
+            unsafe { CStr::from_ptr(info.mesh) }
+                .to_str()
+                .unwrap_or("model/default")
+        };
+
+        let Some(database) = self.database.as_mut() else {
+            return Err(MeshiError::Other(format!(
+                "cannot load mesh '{mesh}' without a database"
+            )));
+        };
+        let model = database.fetch_gpu_model(mesh)?;
+        let h = self
+            .render
+            .register_object(&GfxRenderObjectInfo::Model(model.into()))?;
+        self.render.set_object_transform(h, &info.transform);
+
+        Ok(h)
+    }
+
     fn shut_down(mut self) {
         self.render.shut_down();
     }
@@ -303,13 +471,15 @@ impl MeshiEngine {
             return;
         }
 
+        // Blend between fixed steps so rendering doesn't stutter.
+        let alpha = self.physics.step_alpha();
         let mut remaining = Vec::with_capacity(self.render_physics_pairs.len());
         for pair in self.render_physics_pairs.drain(..) {
             if !pair.render_handle.valid() || !pair.physics_handle.valid() {
                 continue;
             }
 
-            if let Some(status) = self.physics.get_rigid_body_status(pair.physics_handle) {
+            if let Some(status) = self.physics.interpolated_status(pair.physics_handle, alpha) {
                 let transform = Mat4::from_rotation_translation(status.rotation, status.position);
                 self.render
                     .set_object_transform(pair.render_handle, &transform);
@@ -319,10 +489,12 @@ impl MeshiEngine {
         self.render_physics_pairs = remaining;
     }
 
-    fn register_render_physics_pair(
+    /// Copy `physics_handle`'s transform onto `render_handle` after every
+    /// physics step. Replaces any existing binding for either handle.
+    fn attach_body_to_object(
         &mut self,
-        render_handle: Handle<RenderObject>,
         physics_handle: Handle<RigidBody>,
+        render_handle: Handle<RenderObject>,
     ) -> bool {
         if !render_handle.valid() || !physics_handle.valid() {
             return false;
@@ -337,10 +509,10 @@ impl MeshiEngine {
         true
     }
 
-    fn unregister_render_physics_pair(
+    fn detach_body_from_object(
         &mut self,
-        render_handle: Handle<RenderObject>,
         physics_handle: Handle<RigidBody>,
+        render_handle: Handle<RenderObject>,
     ) {
         self.render_physics_pairs.retain(|pair| {
             pair.render_handle != render_handle || pair.physics_handle != physics_handle
@@ -381,7 +553,11 @@ pub extern "C" fn meshi_make_engine(info: *const MeshiEngineInfo) -> *mut MeshiE
         // completes the builder.
         .finish();
 
-    tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    // Hosts may create several engines (or install their own subscriber), so
+    // only the first call gets to set the global default.
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        info!("Tracing subscriber already set; keeping the existing one");
+    }
 
     if info.is_null() {
         return std::ptr::null_mut();
@@ -477,7 +653,7 @@ pub extern "C" fn meshi_pair_render_physics(
     if engine.is_null() {
         return 0;
     }
-    if unsafe { &mut *engine }.register_render_physics_pair(render_handle, physics_handle) {
+    if unsafe { &mut *engine }.attach_body_to_object(physics_handle, render_handle) {
         1
     } else {
         0
@@ -498,7 +674,7 @@ pub extern "C" fn meshi_unpair_render_physics(
         return;
     }
     unsafe { &mut *engine }
-        .unregister_render_physics_pair(unsafe { *render_handle }, unsafe { *physics_handle });
+        .detach_body_from_object(unsafe { *physics_handle }, unsafe { *render_handle });
 }
 
 ////////////////////////////////////////////
@@ -532,7 +708,10 @@ pub extern "C" fn meshi_gfx_create_mesh_object(
     meshi_gfx_create_render_object(render, info)
 }
 
-/// Register a new renderable object.
+/// Register a new renderable object. If `info.mesh` cannot be loaded the
+/// error is logged and the default mesh is used instead; a default (invalid)
+/// handle is only returned when even that fails. Use
+/// [`meshi_gfx_try_create_render_object`] to have failures reported.
 ///
 /// # Safety
 /// `render` must be a valid pointer obtained from [`meshi_get_graphics_system`]
@@ -544,25 +723,75 @@ pub extern "C" fn meshi_gfx_create_render_object(
 ) -> Handle<RenderObject> {
     return_if_null!(Handle::default(), render, info);
     let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    let info = unsafe { &*info };
 
-    let info: &RenderObjectInfo = unsafe { &(*info) };
-
-    let mesh = unsafe { CStr::from_ptr(info.mesh) }
-        .to_str()
-        .unwrap_or("model/default");
+    let result = engine.create_render_object(info).or_else(|err| {
+        if info.mesh.is_null() {
+            return Err(err);
+        }
+        error!("Failed to create render object, falling back to the default mesh: {err}");
+        engine.create_render_object(&RenderObjectInfo {
+            mesh: std::ptr::null(),
+            ..*info
+        })
+    });
+    match result {
+        Ok(h) => h,
+        Err(err) => {
+            error!("Failed to create render object: {err}");
+            Handle::default()
+        }
+    }
+}
 
-    let model = engine
-        .database
-        .fetch_gpu_model(mesh)
-        .expect("Failed to  load model!");
+/// Register a new renderable object using mesh-based info, reporting failures
+/// as a [`MeshiResult`] code.
+///
+/// # Safety
+/// `render` must be a valid pointer obtained from [`meshi_get_graphics_system`],
+/// `info` must point to a valid [`RenderObjectInfo`] and `out_handle` must be
+/// writable.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_try_create_mesh_object(
+    render: *mut MeshiEngine,
+    info: *const MeshObjectInfo,
+    out_handle: *mut Handle<RenderObject>,
+) -> i32 {
+    meshi_gfx_try_create_render_object(render, info, out_handle)
+}
 
-    let h = engine
-        .render
-        .register_object(&GfxRenderObjectInfo::Model(model))
-        .expect("Unable to register object");
-    meshi_gfx_set_transform(engine, h, &info.transform);
+/// Register a new renderable object, reporting failures as a [`MeshiResult`]
+/// code instead of panicking. `out_handle` is only written on success.
+/// Engines running without an asset database report
+/// [`MeshiResult::DatabaseUnavailable`] since no mesh can be loaded.
+///
+/// # Safety
+/// `render` must be a valid pointer obtained from [`meshi_get_graphics_system`],
+/// `info` must point to a valid [`RenderObjectInfo`] and `out_handle` must be
+/// writable.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_try_create_render_object(
+    render: *mut MeshiEngine,
+    info: *const RenderObjectInfo,
+    out_handle: *mut Handle<RenderObject>,
+) -> i32 {
+    return_if_null!(MeshiResult::NullPointer as i32, render, info, out_handle);
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    if engine.database.is_none() {
+        error!("Failed to create render object: no asset database is loaded");
+        return MeshiResult::DatabaseUnavailable as i32;
+    }
 
-    h
+    match engine.create_render_object(unsafe { &*info }) {
+        Ok(h) => {
+            unsafe { *out_handle = h };
+            MeshiResult::Success as i32
+        }
+        Err(err) => {
+            error!("Failed to create render object: {err}");
+            MeshiResult::from(&err) as i32
+        }
+    }
 }
 
 #[no_mangle]
@@ -579,6 +808,61 @@ pub extern "C" fn meshi_gfx_release_render_object(
     engine.render.release_object(unsafe { *h });
 }
 
+/// Rebind a model object to the mesh stored under `mesh`, keeping its handle
+/// and transform. Returns a [`MeshiResult`] code; the object is unchanged on
+/// failure.
+///
+/// # Safety
+/// `render` must be a valid pointer obtained from [`meshi_get_graphics_system`]
+/// and `mesh` must be a valid null-terminated string.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_set_object_mesh(
+    render: *mut MeshiEngine,
+    handle: Handle<RenderObject>,
+    mesh: *const c_char,
+) -> i32 {
+    return_if_null!(MeshiResult::NullPointer as i32, render, mesh);
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    let Ok(mesh) = unsafe { CStr::from_ptr(mesh) }.to_str() else {
+        return MeshiResult::Unknown as i32;
+    };
+
+    match engine.render.set_object_mesh(handle, mesh) {
+        Ok(()) => MeshiResult::Success as i32,
+        Err(err) => {
+            error!("Failed to set object mesh '{mesh}': {err}");
+            MeshiResult::from(&err) as i32
+        }
+    }
+}
+
+/// Rebind an object to the material stored under `material`. Returns a
+/// [`MeshiResult`] code; the object is unchanged on failure.
+///
+/// # Safety
+/// `render` must be a valid pointer obtained from [`meshi_get_graphics_system`]
+/// and `material` must be a valid null-terminated string.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_set_object_material(
+    render: *mut MeshiEngine,
+    handle: Handle<RenderObject>,
+    material: *const c_char,
+) -> i32 {
+    return_if_null!(MeshiResult::NullPointer as i32, render, material);
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    let Ok(material) = unsafe { CStr::from_ptr(material) }.to_str() else {
+        return MeshiResult::Unknown as i32;
+    };
+
+    match engine.render.set_object_material(handle, material) {
+        Ok(()) => MeshiResult::Success as i32,
+        Err(err) => {
+            error!("Failed to set object material '{material}': {err}");
+            MeshiResult::from(&err) as i32
+        }
+    }
+}
+
 /// Update the transformation matrix for a renderable object.
 ///
 /// # Safety
@@ -608,7 +892,38 @@ pub extern "C" fn meshi_gfx_set_transform(
         .set_object_transform(h, unsafe { &*transform });
 }
 
-/// Create a directional light for the scene.
+/// Update the transforms of `count` renderable objects in one call.
+///
+/// `handles[i]` receives `transforms[i]`. Invalid handles are skipped.
+///
+/// # Safety
+/// `render` must be obtained from [`meshi_get_graphics_system`], and `handles`
+/// and `transforms` must each point to at least `count` elements.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_set_transforms_batch(
+    render: *mut MeshiEngine,
+    handles: *const Handle<RenderObject>,
+    transforms: *const Mat4,
+    count: usize,
+) {
+    return_if_null!((), render, handles, transforms);
+    if count == 0 {
+        return;
+    }
+
+    let handles = unsafe { std::slice::from_raw_parts(handles, count) };
+    let transforms = unsafe { std::slice::from_raw_parts(transforms, count) };
+    let batch: Vec<(Handle<RenderObject>, Mat4)> = handles
+        .iter()
+        .copied()
+        .zip(transforms.iter().copied())
+        .collect();
+
+    unsafe { &mut *render }.render.set_object_transforms(&batch);
+}
+
+/// Create a light for the scene. Invalid light descriptions (see
+/// `LightInfo::validate`) return an invalid handle.
 ///
 /// # Safety
 /// `render` must be valid and `info` must not be null.
@@ -622,7 +937,13 @@ pub extern "C" fn meshi_gfx_create_light(
     }
 
     let engine: &mut MeshiEngine = unsafe { &mut (*render) };
-    engine.render.register_light(unsafe { &*info })
+    match engine.render.register_light(unsafe { &*info }) {
+        Ok(h) => h,
+        Err(err) => {
+            error!("Failed to create light: {err}");
+            Handle::default()
+        }
+    }
 }
 
 #[no_mangle]
@@ -653,7 +974,8 @@ pub extern "C" fn meshi_gfx_set_light_transform(
     engine.render.set_light_transform(h, unsafe { &*transform });
 }
 
-/// Update the properties for a directional light.
+/// Update the properties for a light. Position and direction are re-packed
+/// according to the light type.
 ///
 /// # Safety
 /// `render` and `info` must be valid pointers.
@@ -671,6 +993,24 @@ pub extern "C" fn meshi_gfx_set_light_info(
     engine.render.set_light_info(h, unsafe { &*info });
 }
 
+/// Move a point, spot or area light without changing its other properties.
+///
+/// # Safety
+/// `render` must be a valid pointer.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_set_light_position(
+    render: *mut MeshiEngine,
+    h: Handle<Light>,
+    position: Vec3,
+) {
+    if render.is_null() {
+        return;
+    }
+
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    engine.render.set_light_position(h, position);
+}
+
 /// Create a window display for rendering output.
 ///
 /// # Safety
@@ -702,7 +1042,58 @@ pub extern "C" fn meshi_gfx_register_display(
         },
         ..Default::default()
     };
-    engine.render.register_window_display(display_info)
+    if engine.render.headless() {
+        engine.render.register_cpu_display(display_info)
+    } else {
+        engine.render.register_window_display(display_info)
+    }
+}
+
+/// Copy the most recent frame of the primary display into `out_image`.
+///
+/// Only displays registered on a headless engine can be dumped. The pixel
+/// buffer is owned by the engine and stays valid until the next call to
+/// [`meshi_update`] or [`meshi_gfx_frame_dump`]. Returns a [`MeshiResult`]
+/// code, `0` on success.
+///
+/// # Safety
+/// `render` must be valid and `out_image` must be writable.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_frame_dump(render: *mut MeshiEngine, out_image: *mut FFIImage) -> i32 {
+    return_if_null!(MeshiResult::NullPointer as i32, render, out_image);
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+
+    let Some(display) = engine.render.primary_display() else {
+        return MeshiResult::Unavailable as i32;
+    };
+    match engine.render.frame_dump(display) {
+        Some(image) => {
+            unsafe { *out_image = image };
+            MeshiResult::Success as i32
+        }
+        None => MeshiResult::Unavailable as i32,
+    }
+}
+
+/// Number of live render objects, or zero if `render` is null.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_object_count(render: *mut MeshiEngine) -> usize {
+    return_if_null!(0, render);
+    unsafe { &*render }.render.object_count()
+}
+
+/// Number of live lights, or zero if `render` is null.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_light_count(render: *mut MeshiEngine) -> usize {
+    return_if_null!(0, render);
+    unsafe { &*render }.render.light_count()
+}
+
+/// Number of live cameras, or zero if `render` is null.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_camera_count(render: *mut MeshiEngine) -> usize {
+    return_if_null!(0, render);
+    unsafe { &*render }.render.camera_count()
 }
 
 /// Attach a camera to a display for rendering output.
@@ -737,12 +1128,47 @@ pub extern "C" fn meshi_gfx_register_camera(
     }
 
     let engine: &mut MeshiEngine = unsafe { &mut (*render) };
-    engine
+    let camera = engine
         .render
-        .register_camera(unsafe { &*initial_transform })
+        .register_camera(unsafe { &*initial_transform });
+    if !engine.primary_camera.valid() {
+        engine.primary_camera = camera;
+    }
+    camera
+}
+
+/// Make `camera` the primary camera and show it on the primary display.
+///
+/// # Safety
+/// `render` must be a valid pointer.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_set_primary_camera(render: *mut MeshiEngine, camera: Handle<Camera>) {
+    if render.is_null() || !camera.valid() {
+        return;
+    }
+
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    engine.primary_camera = camera;
+    if let Some(display) = engine.render.primary_display() {
+        engine.render.attach_camera_to_display(display, camera);
+    }
+}
+
+/// Camera targeted by camera calls given a default handle.
+///
+/// # Safety
+/// `render` must be a valid pointer.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_get_primary_camera(render: *mut MeshiEngine) -> Handle<Camera> {
+    if render.is_null() {
+        return Handle::default();
+    }
+
+    unsafe { &*render }.primary_camera
 }
 
-/// Set the world-to-camera transform used for rendering.
+/// Set the world-to-camera transform used for rendering. A default `camera`
+/// handle targets the primary camera.
 ///
 /// # Safety
 /// `render` and `transform` must be valid pointers.
@@ -757,12 +1183,14 @@ pub extern "C" fn meshi_gfx_set_camera_transform(
     }
 
     let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    let camera = engine.camera_or_primary(camera);
     engine
         .render
         .set_camera_transform(camera, unsafe { &*transform });
 }
 
-/// Set the projection matrix used for rendering.
+/// Set the projection matrix used for rendering. A default `camera` handle
+/// targets the primary camera.
 ///
 /// # Safety
 /// `render` and `transform` must be valid pointers.
@@ -777,6 +1205,7 @@ pub extern "C" fn meshi_gfx_set_camera_projection(
     }
 
     let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    let camera = engine.camera_or_primary(camera);
     engine
         .render
         .set_camera_projection(camera, unsafe { &*transform });
@@ -784,6 +1213,10 @@ pub extern "C" fn meshi_gfx_set_camera_projection(
 
 /// Enable or disable mouse capture for the renderer window.
 ///
+/// While captured the cursor is hidden and grabbed, and mouse movement is
+/// reported as relative `Motion2D` events from the `MouseMotion` source.
+/// Headless engines record the request but have no cursor to grab.
+///
 /// # Safety
 /// `render` must be a valid pointer.
 #[no_mangle]
@@ -796,6 +1229,68 @@ pub extern "C" fn meshi_gfx_capture_mouse(render: *mut MeshiEngine, value: i32)
     engine.render.set_capture_mouse(value != 0);
 }
 
+/// Resize a display. Resizing the primary display also resizes the renderer
+/// output and refits the aspect of its attached perspective camera.
+///
+/// # Safety
+/// `render` must be a valid pointer.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_set_display_size(
+    render: *mut MeshiEngine,
+    display: Handle<Display>,
+    width: u32,
+    height: u32,
+) {
+    return_if_null!((), render);
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    engine.render.set_display_size(display, [width, height]);
+}
+
+/// Write the current size of `display` into `out_width` and `out_height`.
+/// Returns a [`MeshiResult`] code, `0` on success.
+///
+/// # Safety
+/// `render` must be valid and both outputs must be writable.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_get_display_size(
+    render: *mut MeshiEngine,
+    display: Handle<Display>,
+    out_width: *mut u32,
+    out_height: *mut u32,
+) -> i32 {
+    return_if_null!(
+        MeshiResult::NullPointer as i32,
+        render,
+        out_width,
+        out_height
+    );
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    let [width, height] = engine.render.display_size(display);
+    if width == 0 || height == 0 {
+        return MeshiResult::Unavailable as i32;
+    }
+    unsafe {
+        *out_width = width;
+        *out_height = height;
+    }
+    MeshiResult::Success as i32
+}
+
+/// Enter (`1`) or leave (`0`) borderless fullscreen on a window display.
+///
+/// # Safety
+/// `render` must be a valid pointer.
+#[no_mangle]
+pub extern "C" fn meshi_gfx_set_display_fullscreen(
+    render: *mut MeshiEngine,
+    display: Handle<Display>,
+    value: i32,
+) {
+    return_if_null!((), render);
+    let engine: &mut MeshiEngine = unsafe { &mut (*render) };
+    engine.render.set_display_fullscreen(display, value != 0);
+}
+
 ////////////////////////////////////////////
 ///////////////////AUDIO////////////////////
 ////////////////////////////////////////////
@@ -814,7 +1309,8 @@ pub extern "C" fn meshi_get_audio_system(engine: *mut MeshiEngine) -> *mut Meshi
     engine
 }
 
-/// Create an audio source from a file path.
+/// Create an audio source from a file path. Returns a default handle if the
+/// clip can't be loaded; the reason is logged.
 #[no_mangle]
 pub extern "C" fn meshi_audio_create_source(
     engine: *mut MeshiEngine,
@@ -827,6 +1323,22 @@ pub extern "C" fn meshi_audio_create_source(
     unsafe { &mut (*engine).audio }.create_source(p)
 }
 
+/// Play a clip once at `position`. The source is destroyed when playback
+/// finishes, so the returned handle only stays valid until then. Returns a
+/// default handle if the clip can't be loaded; the reason is logged.
+#[no_mangle]
+pub extern "C" fn meshi_audio_play_one_shot(
+    engine: *mut MeshiEngine,
+    path: *const c_char,
+    position: Vec3,
+) -> Handle<AudioSource> {
+    if engine.is_null() || path.is_null() {
+        return Handle::default();
+    }
+    let p = unsafe { CStr::from_ptr(path) }.to_str().unwrap_or("");
+    unsafe { &mut (*engine).audio }.play_one_shot(p, position)
+}
+
 /// Destroy an audio source and free its resources.
 #[no_mangle]
 pub extern "C" fn meshi_audio_destroy_source(engine: *mut MeshiEngine, h: Handle<AudioSource>) {
@@ -949,7 +1461,8 @@ pub extern "C" fn meshi_audio_set_listener_transform(
     unsafe { &mut (*engine).audio }.set_listener_transform(unsafe { &*transform }, velocity);
 }
 
-/// Create a streaming audio source from a file path.
+/// Create a streaming audio source from a file path. The clip is resampled
+/// to the engine's sample rate.
 #[no_mangle]
 pub extern "C" fn meshi_audio_create_stream(
     engine: *mut MeshiEngine,
@@ -962,8 +1475,8 @@ pub extern "C" fn meshi_audio_create_stream(
     unsafe { &mut (*engine).audio }.create_stream(p)
 }
 
-/// Fill `out_samples` with data from the streaming source, returning the
-/// number of bytes written.
+/// Fill `out_samples` with interleaved 16-bit PCM from the streaming source,
+/// returning the number of bytes written.
 #[no_mangle]
 pub extern "C" fn meshi_audio_update_stream(
     engine: *mut MeshiEngine,
@@ -975,7 +1488,34 @@ pub extern "C" fn meshi_audio_update_stream(
         return 0;
     }
     let slice = unsafe { std::slice::from_raw_parts_mut(out_samples, max) };
-    unsafe { &mut (*engine).audio }.update_stream(h, slice)
+    unsafe { &mut (*engine).audio }
+        .update_stream(h, slice)
+        .bytes
+}
+
+/// Returns 1 once the streaming source has no more data to read, 0 otherwise.
+#[no_mangle]
+pub extern "C" fn meshi_audio_stream_finished(
+    engine: *mut MeshiEngine,
+    h: Handle<StreamingSource>,
+) -> i32 {
+    if engine.is_null() {
+        return 1;
+    }
+    unsafe { &(*engine).audio }.is_stream_finished(h) as i32
+}
+
+/// Loop a streaming source back to its start when it runs out of data.
+#[no_mangle]
+pub extern "C" fn meshi_audio_set_stream_looping(
+    engine: *mut MeshiEngine,
+    h: Handle<StreamingSource>,
+    looping: i32,
+) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe { &mut (*engine).audio }.set_stream_looping(h, looping != 0);
 }
 
 /// Set the volume for an audio bus.
@@ -991,6 +1531,37 @@ pub extern "C" fn meshi_audio_set_bus_volume(
     unsafe { &mut (*engine).audio }.set_bus_volume(h, volume as f32);
 }
 
+/// Set the master volume, scaling every bus and source.
+#[no_mangle]
+pub extern "C" fn meshi_audio_set_master_volume(engine: *mut MeshiEngine, volume: c_float) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe { &mut (*engine).audio }.set_master_volume(volume as f32);
+}
+
+/// Backend the audio engine is actually running on. Requesting Rodio falls
+/// back to `Dummy` when no output device could be opened.
+#[no_mangle]
+pub extern "C" fn meshi_audio_get_backend(engine: *mut MeshiEngine) -> AudioBackend {
+    if engine.is_null() {
+        return AudioBackend::Dummy;
+    }
+    unsafe { &(*engine).audio }.backend()
+}
+
+/// Copy the audio engine's active settings into `out_info`.
+///
+/// # Safety
+/// `engine` and `out_info` must be valid pointers.
+#[no_mangle]
+pub extern "C" fn meshi_audio_get_info(engine: *mut MeshiEngine, out_info: *mut AudioEngineInfo) {
+    if engine.is_null() || out_info.is_null() {
+        return;
+    }
+    unsafe { *out_info = (*engine).audio.info() };
+}
+
 /// Register a callback invoked when a source finishes playback.
 #[no_mangle]
 pub extern "C" fn meshi_audio_register_finished_callback(
@@ -1035,6 +1606,16 @@ pub extern "C" fn meshi_physx_set_gravity(engine: *mut MeshiEngine, gravity_mps:
     unsafe { &mut (*engine).physics }.set_gravity(gravity_mps);
 }
 
+/// Freeze or resume the physics simulation. Rendering and audio keep
+/// updating while physics is paused.
+#[no_mangle]
+pub extern "C" fn meshi_physx_set_paused(engine: *mut MeshiEngine, paused: i32) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe { &mut (*engine).physics }.set_paused(paused != 0);
+}
+
 /// Create a new material in the physics system.
 ///
 /// # Safety
@@ -1167,12 +1748,78 @@ pub extern "C" fn meshi_physx_get_rigid_body_velocity(
         .unwrap_or(Vec3::ZERO)
 }
 
-/// Set the collision shape for a rigid body.
+/// Retrieve the position, rotation and linear and angular velocity of a
+/// rigid body in one snapshot.
 ///
 /// # Safety
-/// `engine`, `h`, and `shape` must be valid pointers.
+/// `engine`, `h`, and `out_state` must all be valid pointers. The function
+/// returns immediately and leaves `out_state` untouched if any pointer is
+/// null.
 #[no_mangle]
-pub extern "C" fn meshi_physx_set_collision_shape(
+pub extern "C" fn meshi_physx_get_rigid_body_state(
+    engine: *mut MeshiEngine,
+    h: *const Handle<meshi_physics::RigidBody>,
+    out_state: *mut meshi_physics::RigidBodyState,
+) -> i32 {
+    if engine.is_null() || h.is_null() || out_state.is_null() {
+        return 0;
+    }
+    if let Some(state) = unsafe { &(*engine).physics }.get_rigid_body_state(unsafe { *h }) {
+        unsafe { *out_state = state };
+        1
+    } else {
+        0
+    }
+}
+
+/// Restore a snapshot taken with `meshi_physx_get_rigid_body_state`.
+///
+/// # Safety
+/// `engine`, `h`, and `state` must be valid, non-null pointers.
+#[no_mangle]
+pub extern "C" fn meshi_physx_set_rigid_body_state(
+    engine: *mut MeshiEngine,
+    h: *const Handle<meshi_physics::RigidBody>,
+    state: *const meshi_physics::RigidBodyState,
+) -> i32 {
+    if engine.is_null() || h.is_null() || state.is_null() {
+        return 0;
+    }
+    if unsafe { &mut (*engine).physics }.set_rigid_body_state(unsafe { *h }, unsafe { &*state }) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Set the linear velocity of a rigid body.
+///
+/// # Safety
+/// `engine`, `h`, and `velocity` must be valid, non-null pointers.
+#[no_mangle]
+pub extern "C" fn meshi_physx_set_rigid_body_velocity(
+    engine: *mut MeshiEngine,
+    h: *const Handle<meshi_physics::RigidBody>,
+    velocity: *const Vec3,
+) -> i32 {
+    if engine.is_null() || h.is_null() || velocity.is_null() {
+        return 0;
+    }
+    if unsafe { &mut (*engine).physics }
+        .set_rigid_body_velocity(unsafe { *h }, unsafe { *velocity })
+    {
+        1
+    } else {
+        0
+    }
+}
+
+/// Set the collision shape for a rigid body.
+///
+/// # Safety
+/// `engine`, `h`, and `shape` must be valid pointers.
+#[no_mangle]
+pub extern "C" fn meshi_physx_set_collision_shape(
     engine: *mut MeshiEngine,
     h: *const Handle<meshi_physics::RigidBody>,
     shape: *const CollisionShape,
@@ -1189,6 +1836,27 @@ pub extern "C" fn meshi_physx_set_collision_shape(
     }
 }
 
+/// Enable (`enabled != 0`) or disable gravity on a rigid body. The change
+/// applies from the next simulation step.
+///
+/// # Safety
+/// `engine` and `h` must be valid pointers.
+#[no_mangle]
+pub extern "C" fn meshi_physx_set_rigid_body_gravity(
+    engine: *mut MeshiEngine,
+    h: *const Handle<meshi_physics::RigidBody>,
+    enabled: i32,
+) -> i32 {
+    if engine.is_null() || h.is_null() {
+        return 0;
+    }
+    if unsafe { &mut (*engine).physics }.set_rigid_body_gravity(unsafe { *h }, enabled != 0) {
+        1
+    } else {
+        0
+    }
+}
+
 /// Retrieve collision contacts from the last simulation update.
 /// Returns the number of contacts written to `out_contacts`.
 ///
@@ -1212,6 +1880,22 @@ pub extern "C" fn meshi_physx_get_contacts(
     count
 }
 
+/// Register a callback invoked for each contact after every simulation step.
+///
+/// # Safety
+/// `engine` must be a valid pointer returned by [`meshi_make_engine`].
+#[no_mangle]
+pub extern "C" fn meshi_physx_register_contact_callback(
+    engine: *mut MeshiEngine,
+    user_data: *mut c_void,
+    cb: ContactCallback,
+) {
+    if engine.is_null() {
+        return;
+    }
+    unsafe { &mut (*engine).physics }.register_contact_callback(cb, user_data);
+}
+
 #[no_mangle]
 pub extern "C" fn meshi_physx_collision_shape_sphere(radius: f32) -> CollisionShape {
     CollisionShape {
@@ -1245,11 +1929,123 @@ pub extern "C" fn meshi_physx_collision_shape_capsule(
     }
 }
 
+/// Register a static heightfield matching a terrain chunk's height samples.
+/// `transform` is the chunk's render transform; only its translation is
+/// applied.
+pub fn physics_body_for_terrain_chunk(
+    sim: &mut PhysicsSimulation,
+    artifact: &TerrainChunkArtifact,
+    transform: &Mat4,
+) -> Handle<RigidBody> {
+    let [cols, rows] = artifact.grid_size;
+    let field = sim.create_heightfield(&artifact.heights, rows, cols, artifact.sample_spacing);
+    sim.create_static_heightfield(transform.w_axis.truncate(), field, Handle::default())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use glam::{Quat, Vec3};
-    use meshi_physics::{ActorStatus, PhysicsSimulation, RigidBodyInfo, SimulationInfo};
+    use glam::{Quat, Vec2, Vec3, Vec4};
+    use meshi_graphics::{
+        AnimationEvent, AnimationEventKind, AnimationState, BillboardInfo, BillboardType,
+        BloomSettings, FogSettings, ModelInfo, ShadowFilter, ShadowFilterMode, SkinnedModelInfo,
+        SpriteInfo, TonemapOperator, TonemapSettings,
+    };
+    use meshi_physics::{
+        ActorStatus, MaterialInfo, PhysicsSimulation, RigidBodyInfo, SimulationInfo,
+    };
+
+    fn headless_engine(location: &std::path::Path) -> *mut MeshiEngine {
+        headless_engine_with_debug(location, false)
+    }
+
+    fn headless_engine_with_debug(location: &std::path::Path, debug: bool) -> *mut MeshiEngine {
+        let name = CString::new("meshi-test").unwrap();
+        let location = CString::new(location.to_str().unwrap()).unwrap();
+        let engine = MeshiEngine::new(&MeshiEngineInfo {
+            application_name: name.as_ptr(),
+            application_location: location.as_ptr(),
+            headless: 1,
+            canvas_extent: std::ptr::null(),
+            debug_mode: debug as i32,
+        })
+        .expect("create headless engine");
+        Box::into_raw(engine)
+    }
+
+    fn headless_display(engine: *mut MeshiEngine) -> Handle<Display> {
+        headless_display_with_resizable(engine, false)
+    }
+
+    /// Registers a 64x48 display viewed through an identity camera.
+    fn headless_display_with_resizable(
+        engine: *mut MeshiEngine,
+        resizable: bool,
+    ) -> Handle<Display> {
+        let display = meshi_gfx_register_display(
+            engine,
+            &DisplayInfo {
+                vsync: 0,
+                window: WindowInfo {
+                    title: std::ptr::null(),
+                    width: 64,
+                    height: 48,
+                    resizable: resizable as i32,
+                },
+            },
+        );
+        let camera = meshi_gfx_register_camera(engine, &Mat4::IDENTITY);
+        meshi_gfx_attach_camera_to_display(engine, display, camera);
+        display
+    }
+
+    /// A copy of a dumped frame. CPU displays are BGRA8.
+    struct Frame {
+        width: u32,
+        height: u32,
+        pixels: Vec<u8>,
+    }
+
+    impl Frame {
+        fn rgb(&self, x: u32, y: u32) -> [u8; 3] {
+            let offset = ((y * self.width + x) * 4) as usize;
+            [
+                self.pixels[offset + 2],
+                self.pixels[offset + 1],
+                self.pixels[offset],
+            ]
+        }
+
+        fn center(&self) -> [u8; 3] {
+            self.rgb(self.width / 2, self.height / 2)
+        }
+
+        fn mean_brightness(&self) -> f32 {
+            self.pixels.iter().map(|&p| p as f32).sum::<f32>() / self.pixels.len() as f32
+        }
+    }
+
+    /// Renders one frame and reads it back through `meshi_gfx_frame_dump`.
+    fn read_pixels(engine: *mut MeshiEngine) -> Frame {
+        meshi_update(engine);
+        let mut image = FFIImage {
+            width: 0,
+            height: 0,
+            format: 0,
+            pixels: std::ptr::null(),
+        };
+        assert_eq!(
+            meshi_gfx_frame_dump(engine, &mut image),
+            MeshiResult::Success as i32
+        );
+        assert!(!image.pixels.is_null());
+        let len = (image.width * image.height * 4) as usize;
+        Frame {
+            width: image.width,
+            height: image.height,
+            pixels: unsafe { std::slice::from_raw_parts(image.pixels, len) }.to_vec(),
+        }
+    }
 
     #[test]
     fn rigid_body_transform_roundtrip() {
@@ -1267,4 +2063,1226 @@ mod tests {
         assert_eq!(out.position, transform.position);
         assert_eq!(out.rotation, transform.rotation);
     }
+
+    #[test]
+    fn make_engine_twice_does_not_panic_on_subscriber_setup() {
+        assert!(meshi_make_engine(std::ptr::null()).is_null());
+        assert!(meshi_make_engine(std::ptr::null()).is_null());
+    }
+
+    #[test]
+    fn try_create_render_object_reports_null_pointers() {
+        let mut handle = Handle::default();
+        let code =
+            meshi_gfx_try_create_render_object(std::ptr::null_mut(), std::ptr::null(), &mut handle);
+        assert_eq!(code, MeshiResult::NullPointer as i32);
+        assert!(!handle.valid());
+    }
+
+    #[test]
+    fn meshi_error_maps_to_stable_codes() {
+        let err = MeshiError::Other("missing".to_string());
+        assert_eq!(MeshiResult::from(&err) as i32, 6);
+        assert_eq!(MeshiResult::Success as i32, 0);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn try_create_mesh_object_reports_missing_mesh() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let mesh = CString::new("model/does-not-exist").unwrap();
+        let info = RenderObjectInfo {
+            mesh: mesh.as_ptr(),
+            material: std::ptr::null(),
+            transform: Mat4::IDENTITY,
+        };
+        let mut handle = Handle::default();
+        let code = meshi_gfx_try_create_mesh_object(engine, &info, &mut handle);
+
+        assert_ne!(code, MeshiResult::Success as i32);
+        assert!(!handle.valid());
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn engine_runs_without_a_database() {
+        let name = CString::new("meshi-test").unwrap();
+        let location = CString::new(".").unwrap();
+        let engine = Box::into_raw(
+            MeshiEngine::with_database(
+                &MeshiEngineInfo {
+                    application_name: name.as_ptr(),
+                    application_location: location.as_ptr(),
+                    headless: 1,
+                    canvas_extent: std::ptr::null(),
+                    debug_mode: 0,
+                },
+                false,
+            )
+            .expect("create engine without a database"),
+        );
+        assert!(unsafe { &*engine }.database.is_none());
+
+        let info = RenderObjectInfo {
+            mesh: std::ptr::null(),
+            material: std::ptr::null(),
+            transform: Mat4::IDENTITY,
+        };
+        let mut handle = Handle::default();
+        let code = meshi_gfx_try_create_render_object(engine, &info, &mut handle);
+        assert_eq!(code, MeshiResult::DatabaseUnavailable as i32);
+        assert!(!handle.valid());
+
+        unsafe { &mut *engine }.update();
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn create_render_object_falls_back_to_the_default_mesh() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let mesh = CString::new("model/does-not-exist").unwrap();
+        let info = RenderObjectInfo {
+            mesh: mesh.as_ptr(),
+            material: std::ptr::null(),
+            transform: Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0)),
+        };
+        let handle = meshi_gfx_create_render_object(engine, &info);
+        assert!(handle.valid());
+
+        let default = meshi_gfx_create_render_object(
+            engine,
+            &RenderObjectInfo {
+                mesh: std::ptr::null(),
+                ..info
+            },
+        );
+        let render = &unsafe { &*engine }.render;
+        assert_eq!(render.object_transform(handle), info.transform);
+        assert!(render.object_bounds(handle).is_some());
+        assert_eq!(render.object_bounds(handle), render.object_bounds(default));
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn set_object_mesh_swaps_in_place_and_rejects_unknown_keys() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let info = RenderObjectInfo {
+            mesh: std::ptr::null(),
+            material: std::ptr::null(),
+            transform: Mat4::from_translation(Vec3::new(1.0, 2.0, 3.0)),
+        };
+        let mut handle = Handle::default();
+        assert_eq!(
+            meshi_gfx_try_create_render_object(engine, &info, &mut handle),
+            MeshiResult::Success as i32
+        );
+
+        let mesh = CString::new("model/default").unwrap();
+        assert_eq!(
+            meshi_gfx_set_object_mesh(engine, handle, mesh.as_ptr()),
+            MeshiResult::Success as i32
+        );
+
+        let render = &unsafe { &*engine }.render;
+        let bounds = render.object_bounds(handle);
+        let material = render.object_material(handle).map(|m| m.slot);
+        assert_eq!(render.object_transform(handle), info.transform);
+
+        let missing_mesh = CString::new("model/does-not-exist").unwrap();
+        assert_ne!(
+            meshi_gfx_set_object_mesh(engine, handle, missing_mesh.as_ptr()),
+            MeshiResult::Success as i32
+        );
+        let missing_material = CString::new("material/does-not-exist").unwrap();
+        assert_ne!(
+            meshi_gfx_set_object_material(engine, handle, missing_material.as_ptr()),
+            MeshiResult::Success as i32
+        );
+
+        let render = &unsafe { &*engine }.render;
+        assert_eq!(render.object_bounds(handle), bounds);
+        assert_eq!(render.object_material(handle).map(|m| m.slot), material);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn set_transforms_batch_skips_invalid_handles() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let billboard = GfxRenderObjectInfo::Billboard(BillboardInfo {
+            texture_id: 0,
+            material: None,
+            billboard_type: BillboardType::ScreenAligned,
+            target: Vec3::ZERO,
+        });
+        let render = &mut unsafe { &mut *engine }.render;
+        let a = render.register_object(&billboard).expect("register a");
+        let b = render.register_object(&billboard).expect("register b");
+
+        let handles = [a, Handle::default(), b];
+        let transforms = [
+            Mat4::from_translation(Vec3::new(1.0, 0.0, 0.0)),
+            Mat4::from_translation(Vec3::new(2.0, 0.0, 0.0)),
+            Mat4::from_translation(Vec3::new(3.0, 0.0, 0.0)),
+        ];
+        meshi_gfx_set_transforms_batch(engine, handles.as_ptr(), transforms.as_ptr(), 3);
+
+        let render = &unsafe { &*engine }.render;
+        assert_eq!(render.object_transform(a), transforms[0]);
+        assert_eq!(render.object_transform(b), transforms[2]);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn set_object_material_swaps_the_material_of_a_default_model() {
+        let location = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../sample_database");
+        let engine = headless_engine(&location);
+
+        let info = RenderObjectInfo {
+            mesh: std::ptr::null(),
+            material: std::ptr::null(),
+            transform: Mat4::IDENTITY,
+        };
+        let object = meshi_gfx_create_render_object(engine, &info);
+        assert!(object.valid());
+
+        let render = &unsafe { &*engine }.render;
+        let before = render.object_material(object).expect("model material");
+        let bounds = render.object_bounds(object);
+        let draws = render.object_draw_count(object);
+        assert!(draws > 0);
+
+        let key = "material/witch/hat";
+        let (_, expected) = unsafe { &mut *engine }
+            .database
+            .as_mut()
+            .expect("database")
+            .fetch_host_material(key)
+            .expect("sample material");
+        let expected = expected.expect("material GPU handle");
+        assert_ne!(expected.slot, before.slot);
+
+        let material = CString::new(key).unwrap();
+        assert_eq!(
+            meshi_gfx_set_object_material(engine, object, material.as_ptr()),
+            MeshiResult::Success as i32
+        );
+
+        let render = &unsafe { &*engine }.render;
+        assert_eq!(
+            render.object_material(object).map(|m| m.slot),
+            Some(expected.slot)
+        );
+        assert_eq!(render.object_bounds(object), bounds);
+        assert_eq!(render.object_draw_count(object), draws);
+        meshi_update(engine);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn set_object_material_rejects_unknown_key() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let render = &mut unsafe { &mut *engine }.render;
+        let object = render
+            .register_object(&GfxRenderObjectInfo::Billboard(BillboardInfo {
+                texture_id: 0,
+                material: None,
+                billboard_type: BillboardType::ScreenAligned,
+                target: Vec3::ZERO,
+            }))
+            .expect("register billboard");
+        let before = render.object_material(object).expect("billboard material");
+
+        assert!(render
+            .set_object_material(object, "material/does-not-exist")
+            .is_err());
+        let after = render.object_material(object).expect("billboard material");
+        assert_eq!(after.slot, before.slot);
+        assert!(render.object_bounds(object).is_some());
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn pick_object_returns_the_nearer_of_two_objects() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let camera = meshi_gfx_register_camera(engine, &Mat4::IDENTITY);
+        let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 64.0 / 48.0, 0.1, 100.0);
+        meshi_gfx_set_camera_projection(engine, camera, &projection);
+        let object_at = |z: f32| {
+            let info = RenderObjectInfo {
+                mesh: std::ptr::null(),
+                material: std::ptr::null(),
+                transform: Mat4::from_translation(Vec3::new(0.0, 0.0, z)),
+            };
+            meshi_gfx_create_render_object(engine, &info)
+        };
+        // Register the far object first so order alone cannot pick the winner.
+        let far = object_at(-10.0);
+        let near = object_at(-5.0);
+
+        let render = &mut unsafe { &mut *engine }.render;
+        let viewport = Vec2::new(64.0, 48.0);
+        let center = viewport * 0.5;
+        assert_eq!(
+            render.pick_object(camera, center, viewport).map(|h| h.slot),
+            Some(near.slot)
+        );
+
+        render.set_object_transform(near, &Mat4::from_translation(Vec3::new(0.0, 0.0, -20.0)));
+        assert_eq!(
+            render.pick_object(camera, center, viewport).map(|h| h.slot),
+            Some(far.slot)
+        );
+
+        render.set_object_transform(near, &Mat4::from_translation(Vec3::new(0.0, 0.0, 5.0)));
+        render.set_object_transform(far, &Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0)));
+        assert!(render.pick_object(camera, center, viewport).is_none());
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn model_bounds_follow_the_object_transform() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let info = |transform| RenderObjectInfo {
+            mesh: std::ptr::null(),
+            material: std::ptr::null(),
+            transform,
+        };
+        let plain = meshi_gfx_create_render_object(engine, &info(Mat4::IDENTITY));
+        let offset = Vec3::new(5.0, -2.0, 1.0);
+        let moved = meshi_gfx_create_render_object(
+            engine,
+            &info(Mat4::from_scale_rotation_translation(
+                Vec3::splat(2.0),
+                Quat::IDENTITY,
+                offset,
+            )),
+        );
+
+        let render = &unsafe { &*engine }.render;
+        let (min, max) = render.object_bounds(plain).expect("plain bounds");
+        assert!(max.cmpgt(min).all(), "degenerate bounds {min} {max}");
+        let (moved_min, moved_max) = render.object_bounds(moved).expect("moved bounds");
+        assert!(moved_min.abs_diff_eq(min * 2.0 + offset, 1e-4));
+        assert!(moved_max.abs_diff_eq(max * 2.0 + offset, 1e-4));
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn model_bounds_override_is_reported_verbatim() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let engine_ref = unsafe { &mut *engine };
+        let model = engine_ref
+            .database
+            .as_mut()
+            .expect("database")
+            .fetch_gpu_model("model/default")
+            .expect("default model");
+        let bounds = (Vec3::new(-2.0, -1.0, -3.0), Vec3::new(4.0, 5.0, 6.0));
+        let object = engine_ref
+            .render
+            .register_object(&GfxRenderObjectInfo::Model(ModelInfo {
+                model,
+                bounds: Some(bounds),
+            }))
+            .expect("register model");
+
+        assert_eq!(engine_ref.render.object_bounds(object), Some(bounds));
+        meshi_destroy_engine(engine);
+    }
+
+    extern "C" fn count_animation_event(event: *const AnimationEvent, user_data: *mut c_void) {
+        let counts = unsafe { &mut *(user_data as *mut [u32; 3]) };
+        counts[unsafe { &*event }.kind as usize] += 1;
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn non_looping_clip_reports_completion_once() {
+        let location = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../sample_database");
+        let engine = headless_engine(&location);
+
+        let engine_ref = unsafe { &mut *engine };
+        let model = engine_ref
+            .database
+            .as_mut()
+            .expect("database")
+            .fetch_gpu_model("model/fox")
+            .expect("sample skinned model");
+        let object = engine_ref
+            .render
+            .register_object(&GfxRenderObjectInfo::SkinnedModel(SkinnedModelInfo {
+                model,
+                animation: AnimationState {
+                    looping: false,
+                    ..Default::default()
+                },
+                bounds: None,
+            }))
+            .expect("register skinned model");
+
+        let mut counts = [0u32; 3];
+        engine_ref.render.register_animation_callback(
+            object,
+            count_animation_event,
+            &mut counts as *mut [u32; 3] as *mut c_void,
+        );
+        for _ in 0..200 {
+            engine_ref.render.update(0.25);
+        }
+
+        assert_eq!(counts[AnimationEventKind::Completed as usize], 1);
+        assert_eq!(counts[AnimationEventKind::Looped as usize], 0);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn primary_camera_receives_default_handle_updates() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let first = meshi_gfx_register_camera(engine, &Mat4::IDENTITY);
+        let second = meshi_gfx_register_camera(engine, &Mat4::IDENTITY);
+        assert_eq!(meshi_gfx_get_primary_camera(engine).slot, first.slot);
+
+        meshi_gfx_set_primary_camera(engine, second);
+        assert_eq!(meshi_gfx_get_primary_camera(engine).slot, second.slot);
+
+        let moved = Mat4::from_translation(Vec3::new(4.0, 5.0, 6.0));
+        meshi_gfx_set_camera_transform(engine, Handle::default(), &moved);
+        let render = unsafe { &mut (*engine).render };
+        assert!(render.camera_transform(second).abs_diff_eq(moved, 1.0e-5));
+        assert!(render
+            .camera_transform(first)
+            .abs_diff_eq(Mat4::IDENTITY, 1.0e-5));
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn frame_dump_returns_headless_frame() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        headless_display(engine);
+
+        let frame = read_pixels(engine);
+        assert_eq!((frame.width, frame.height), (64, 48));
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn render_to_image_returns_requested_size_without_a_display() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let render = &mut unsafe { &mut *engine }.render;
+        let object = render
+            .register_object(&GfxRenderObjectInfo::Billboard(BillboardInfo {
+                texture_id: 0,
+                material: None,
+                billboard_type: BillboardType::ScreenAligned,
+                target: Vec3::ZERO,
+            }))
+            .expect("register billboard");
+        render.set_object_transform(object, &Mat4::from_translation(Vec3::new(0.0, 0.0, -3.0)));
+        let camera = render.register_camera(&Mat4::IDENTITY);
+        assert!(render.primary_display().is_none());
+
+        let image = render
+            .render_to_image(camera, [40, 30])
+            .expect("render to image");
+        assert_eq!((image.width, image.height), (40, 30));
+        assert!(!image.pixels.is_null());
+        let pixels = unsafe { std::slice::from_raw_parts(image.pixels, 40 * 30 * 4) };
+        assert!(pixels.iter().any(|&p| p != 0));
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn render_to_image_projects_with_the_capture_aspect() {
+        /// Covered pixels along the center row and center column.
+        fn covered_span(pixels: &[u8], [width, height]: [u32; 2]) -> (usize, usize) {
+            let at = |x: u32, y: u32| {
+                let offset = ((y * width + x) * 4) as usize;
+                &pixels[offset..offset + 4]
+            };
+            let background = at(0, 0);
+            let across = (0..width)
+                .filter(|&x| at(x, height / 2) != background)
+                .count();
+            let down = (0..height)
+                .filter(|&y| at(width / 2, y) != background)
+                .count();
+            (across, down)
+        }
+
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        let render = &mut unsafe { &mut *engine }.render;
+        let object = render
+            .register_object(&GfxRenderObjectInfo::Billboard(BillboardInfo {
+                texture_id: 0,
+                material: None,
+                billboard_type: BillboardType::ScreenAligned,
+                target: Vec3::ZERO,
+            }))
+            .expect("register billboard");
+        render.set_object_transform(object, &Mat4::from_translation(Vec3::new(0.0, 0.0, -3.0)));
+        let camera = render.register_camera(&Mat4::IDENTITY);
+        render.set_camera_perspective(camera, std::f32::consts::FRAC_PI_2, 1.0, 1.0, 0.1, 100.0);
+
+        for extent in [[80, 40], [40, 80]] {
+            let image = unsafe { &mut *engine }
+                .render
+                .render_to_image(camera, extent)
+                .expect("render to image");
+            let len = (extent[0] * extent[1] * 4) as usize;
+            let pixels = unsafe { std::slice::from_raw_parts(image.pixels, len) };
+            let (across, down) = covered_span(pixels, extent);
+            assert!(across > 0, "billboard missing from {extent:?} capture");
+            assert!(
+                across.abs_diff(down) <= 2,
+                "square billboard drawn {across}x{down} in a {extent:?} capture"
+            );
+        }
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn resizing_primary_display_resizes_viewport_and_frames() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        let display = headless_display(engine);
+
+        meshi_gfx_set_display_size(engine, display, 80, 60);
+        let (mut width, mut height) = (0, 0);
+        assert_eq!(
+            meshi_gfx_get_display_size(engine, display, &mut width, &mut height),
+            MeshiResult::Success as i32
+        );
+        assert_eq!((width, height), (80, 60));
+        assert_eq!(unsafe { &*engine }.render.viewport_size(), [80, 60]);
+
+        let frame = read_pixels(engine);
+        assert_eq!((frame.width, frame.height), (80, 60));
+
+        // Headless displays have no monitor, so fullscreen is only recorded.
+        meshi_gfx_set_display_fullscreen(engine, display, 1);
+        assert!(unsafe { &*engine }.render.display_fullscreen(display));
+        assert_eq!(unsafe { &*engine }.render.display_size(display), [80, 60]);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn resize_events_reach_the_callback_and_resize_the_viewport() {
+        extern "C" fn record_resizes(event: *mut event::Event, user_data: *mut c_void) {
+            let event = unsafe { &*event };
+            if event.event_type() == event::EventType::WindowResized {
+                let sizes = unsafe { &mut *(user_data as *mut Vec<Vec2>) };
+                sizes.push(unsafe { event.motion2d() });
+            }
+        }
+
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        let display = headless_display_with_resizable(engine, true);
+
+        let mut sizes: Vec<Vec2> = Vec::new();
+        meshi_register_event_callback(engine, &mut sizes as *mut _ as *mut c_void, record_resizes);
+        unsafe { &mut *engine }
+            .render
+            .queue_event(display, event::Event::window_resized(96, 72));
+        meshi_update(engine);
+
+        assert_eq!(sizes, vec![Vec2::new(96.0, 72.0)]);
+        let render = &unsafe { &*engine }.render;
+        assert_eq!(render.display_size(display), [96, 72]);
+        assert_eq!(render.viewport_size(), [96, 72]);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn capture_mouse_is_tracked_and_ignored_when_headless() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        assert!(!unsafe { &*engine }.render.mouse_captured());
+
+        meshi_gfx_capture_mouse(engine, 1);
+        assert!(unsafe { &*engine }.render.mouse_captured());
+        meshi_update(engine);
+
+        meshi_gfx_capture_mouse(engine, 0);
+        assert!(!unsafe { &*engine }.render.mouse_captured());
+        meshi_gfx_capture_mouse(std::ptr::null_mut(), 1);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn overlapping_sprites_draw_in_z_order() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        headless_display(engine);
+
+        // Register the front sprite first so only the z-order can put it on top.
+        let render = &mut unsafe { &mut *engine }.render;
+        let covering = Vec2::splat(100_000.0);
+        let red = render.register_sprite(&SpriteInfo {
+            size: covering,
+            color: Vec4::new(1.0, 0.0, 0.0, 1.0),
+            z: 1,
+            ..Default::default()
+        });
+        render.register_sprite(&SpriteInfo {
+            size: covering,
+            color: Vec4::new(0.0, 0.0, 1.0, 1.0),
+            z: 0,
+            ..Default::default()
+        });
+
+        let [r, _, b] = read_pixels(engine).center();
+        assert!(r > 200 && b < 50, "expected red on top, got r={r} b={b}");
+
+        unsafe { &mut *engine }.render.release_sprite(red);
+        let [r, _, b] = read_pixels(engine).center();
+        assert!(
+            b > 200 && r < 50,
+            "expected blue once red is released, got r={r} b={b}"
+        );
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn dense_fog_shifts_distant_pixels_toward_fog_color() {
+        fn brightness([r, g, b]: [u8; 3]) -> i32 {
+            r as i32 + g as i32 + b as i32
+        }
+
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        headless_display(engine);
+        let object = meshi_gfx_create_render_object(
+            engine,
+            &RenderObjectInfo {
+                mesh: std::ptr::null(),
+                material: std::ptr::null(),
+                transform: Mat4::IDENTITY,
+            },
+        );
+        assert!(object.valid());
+
+        let fog = FogSettings {
+            enabled: true,
+            color: Vec3::ONE,
+            density: 0.1,
+            ..Default::default()
+        };
+        // Scale with distance so the object covers the same pixels either way.
+        let fog_shift = |distance: f32| {
+            let render = &mut unsafe { &mut *engine }.render;
+            render.set_object_transform(
+                object,
+                &Mat4::from_scale_rotation_translation(
+                    Vec3::splat(distance / 3.0),
+                    Quat::IDENTITY,
+                    Vec3::new(0.0, 0.0, -distance),
+                ),
+            );
+            render.set_fog(FogSettings::default());
+            let clear = read_pixels(engine);
+            unsafe { &mut *engine }.render.set_fog(fog);
+            let fogged = read_pixels(engine);
+            assert_eq!(
+                clear.rgb(0, 0),
+                fogged.rgb(0, 0),
+                "fog changed a pixel no geometry covers"
+            );
+            brightness(fogged.center()) - brightness(clear.center())
+        };
+
+        let near = fog_shift(3.0);
+        let far = fog_shift(30.0);
+        assert!(
+            far > near,
+            "far object moved {far} toward the fog, near object {near}"
+        );
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn linear_falloff_lights_mid_range_brighter_than_inverse_square() {
+        fn lit_brightness(falloff: LightFalloff) -> f32 {
+            let dir = tempfile::tempdir().expect("create temp dir");
+            let engine = headless_engine(dir.path());
+            headless_display(engine);
+
+            let object = meshi_gfx_create_render_object(
+                engine,
+                &RenderObjectInfo {
+                    mesh: std::ptr::null(),
+                    material: std::ptr::null(),
+                    transform: Mat4::from_translation(Vec3::new(0.0, 0.0, -3.0)),
+                },
+            );
+            assert!(object.valid());
+            let light = LightInfo {
+                falloff,
+                ..LightInfo::point(Vec3::new(0.0, 0.0, -1.0), Vec3::ONE, 1.0, 4.0)
+            };
+            meshi_gfx_create_light(engine, &light);
+
+            let brightness = read_pixels(engine).mean_brightness();
+            meshi_destroy_engine(engine);
+            brightness
+        }
+
+        let inverse_square = lit_brightness(LightFalloff::InverseSquare);
+        let linear = lit_brightness(LightFalloff::Linear);
+        assert!(
+            linear > inverse_square + 0.5,
+            "linear {linear} vs inverse-square {inverse_square}"
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn aces_tonemapping_keeps_bright_scenes_below_white() {
+        fn brightest_center_channel(engine: *mut MeshiEngine, tonemap: TonemapSettings) -> u8 {
+            unsafe { &mut *engine }.render.set_tonemap(tonemap);
+            read_pixels(engine).center().into_iter().max().unwrap()
+        }
+
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        headless_display(engine);
+        let object = meshi_gfx_create_render_object(
+            engine,
+            &RenderObjectInfo {
+                mesh: std::ptr::null(),
+                material: std::ptr::null(),
+                transform: Mat4::from_translation(Vec3::new(0.0, 0.0, -3.0)),
+            },
+        );
+        assert!(object.valid());
+        let light = LightInfo::point(Vec3::new(0.0, 0.0, -1.0), Vec3::ONE, 1.0, 4.0);
+        meshi_gfx_create_light(engine, &light);
+        assert_eq!(
+            unsafe { &*engine }.render.tonemap().operator,
+            TonemapOperator::Aces
+        );
+
+        let lit = brightest_center_channel(
+            engine,
+            TonemapSettings {
+                operator: TonemapOperator::None,
+                exposure: 1.0,
+            },
+        );
+        assert!(
+            lit > 0 && lit < 255,
+            "object should be lit in range, got {lit}"
+        );
+
+        // Expose the lit object to four times display white.
+        let exposure = 4.0 * 255.0 / lit as f32;
+        let untonemapped = brightest_center_channel(
+            engine,
+            TonemapSettings {
+                operator: TonemapOperator::None,
+                exposure,
+            },
+        );
+        assert_eq!(untonemapped, 255);
+        let aces = brightest_center_channel(
+            engine,
+            TonemapSettings {
+                operator: TonemapOperator::Aces,
+                exposure,
+            },
+        );
+        assert!(aces < 255, "ACES saturated the lit object");
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn bloom_brightens_pixels_next_to_bright_objects() {
+        fn center_row(engine: *mut MeshiEngine) -> Vec<u32> {
+            let frame = read_pixels(engine);
+            let row = (frame.height / 2 * frame.width * 4) as usize;
+            frame.pixels[row..row + (frame.width * 4) as usize]
+                .chunks(4)
+                .map(|p| p[0] as u32 + p[1] as u32 + p[2] as u32)
+                .collect()
+        }
+
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        headless_display(engine);
+
+        let render = &mut unsafe { &mut *engine }.render;
+        let object = render
+            .register_object(&GfxRenderObjectInfo::Billboard(BillboardInfo {
+                texture_id: 0,
+                material: None,
+                billboard_type: BillboardType::ScreenAligned,
+                target: Vec3::ZERO,
+            }))
+            .expect("register billboard");
+        render.set_object_transform(object, &Mat4::from_translation(Vec3::new(0.0, 0.0, -3.0)));
+
+        let plain = center_row(engine);
+        unsafe { &mut *engine }.render.set_bloom(BloomSettings {
+            enabled: true,
+            threshold: 0.2,
+            intensity: 2.0,
+            radius: 8.0,
+        });
+        let bloomed = center_row(engine);
+
+        // Walk out from the center of the billboard to the first pixel past its edge.
+        let center = plain.len() / 2;
+        let edge = (center..plain.len())
+            .find(|&x| plain[x] != plain[center])
+            .expect("billboard should not cover the whole row");
+        let outside = (edge + 1).min(plain.len() - 1);
+        assert!(
+            bloomed[outside] > plain[outside],
+            "bloom did not brighten pixel {outside}: {} -> {}",
+            plain[outside],
+            bloomed[outside]
+        );
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn set_light_position_moves_point_light() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+
+        let info = LightInfo {
+            ty: LightType::Point,
+            flags: 0,
+            intensity: 1.0,
+            range: 10.0,
+            color_r: 1.0,
+            color_g: 1.0,
+            color_b: 1.0,
+            pos_x: 0.0,
+            pos_y: 0.0,
+            pos_z: 0.0,
+            dir_x: 0.0,
+            dir_y: -1.0,
+            dir_z: 0.0,
+            spot_inner_angle_rad: 0.0,
+            spot_outer_angle_rad: 0.0,
+            rect_half_width: 0.0,
+            rect_half_height: 0.0,
+            falloff: LightFalloff::InverseSquare,
+        };
+        let light = meshi_gfx_create_light(engine, &info);
+        meshi_gfx_set_light_position(engine, light, Vec3::new(1.0, 2.0, 3.0));
+
+        let stored = unsafe { &*engine }
+            .render
+            .light_info(light)
+            .expect("light is cached");
+        assert_eq!((stored.pos_x, stored.pos_y, stored.pos_z), (1.0, 2.0, 3.0));
+        assert_eq!(stored.range, info.range);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn resource_counts_follow_creation_and_release() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        let objects = meshi_gfx_object_count(engine);
+        let lights = meshi_gfx_light_count(engine);
+        let cameras = meshi_gfx_camera_count(engine);
+
+        let billboard = GfxRenderObjectInfo::Billboard(BillboardInfo {
+            texture_id: 0,
+            material: None,
+            billboard_type: BillboardType::ScreenAligned,
+            target: Vec3::ZERO,
+        });
+        let render = &mut unsafe { &mut *engine }.render;
+        let a = render.register_object(&billboard).expect("register a");
+        let b = render.register_object(&billboard).expect("register b");
+        let light =
+            meshi_gfx_create_light(engine, &LightInfo::point(Vec3::ZERO, Vec3::ONE, 1.0, 10.0));
+        let camera = meshi_gfx_register_camera(engine, &Mat4::IDENTITY);
+        assert_eq!(meshi_gfx_object_count(engine), objects + 2);
+        assert_eq!(meshi_gfx_light_count(engine), lights + 1);
+        assert_eq!(meshi_gfx_camera_count(engine), cameras + 1);
+
+        meshi_gfx_release_render_object(engine, &a);
+        meshi_gfx_release_render_object(engine, &b);
+        meshi_gfx_release_light(engine, &light);
+        unsafe { &mut *engine }.render.release_camera(camera);
+        assert_eq!(meshi_gfx_object_count(engine), objects);
+        assert_eq!(meshi_gfx_light_count(engine), lights);
+        assert_eq!(meshi_gfx_camera_count(engine), cameras);
+        assert_eq!(meshi_gfx_object_count(std::ptr::null_mut()), 0);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn debug_lines_accumulate_until_update() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine_with_debug(dir.path(), true);
+        let render = &mut unsafe { &mut *engine }.render;
+
+        render.debug_line(Vec3::ZERO, Vec3::X, Vec4::ONE);
+        render.debug_box(Vec3::splat(-1.0), Vec3::ONE, Vec4::ONE);
+        assert_eq!(render.debug_vertex_count(), 2 + 24);
+
+        meshi_update(engine);
+        assert_eq!(unsafe { &*engine }.render.debug_vertex_count(), 0);
+        meshi_destroy_engine(engine);
+
+        let engine = headless_engine(dir.path());
+        let render = &mut unsafe { &mut *engine }.render;
+        render.debug_sphere(Vec3::ZERO, 1.0, Vec4::ONE);
+        assert_eq!(render.debug_vertex_count(), 0);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn physics_debug_draws_each_body_shape() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine_with_debug(dir.path(), true);
+        let meshi = unsafe { &mut *engine };
+        meshi.physics.create_rigid_body(&RigidBodyInfo::default());
+        meshi.physics.create_rigid_body(&RigidBodyInfo {
+            initial_position: Vec3::new(3.0, 0.0, 0.0),
+            collision_shape: CollisionShape {
+                shape_type: CollisionShapeType::Box,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        meshi.render.draw_physics_debug(&meshi.physics);
+        // Three 24-segment circles for the sphere, twelve edges for the box.
+        let sphere_segments = 3 * 24;
+        let box_segments = 12;
+        assert_eq!(
+            meshi.render.debug_vertex_count(),
+            (sphere_segments + box_segments) * 2
+        );
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn only_shadow_casting_spot_lights_allocate_shadow_maps() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        headless_display(engine);
+        unsafe { &mut *engine }.render.set_shadow_resolution(256);
+
+        let spot = LightInfo::spot(
+            Vec3::new(0.0, 5.0, 0.0),
+            Vec3::NEG_Y,
+            Vec3::ONE,
+            1.0,
+            20.0,
+            0.3,
+            0.5,
+        );
+        let light = meshi_gfx_create_light(engine, &spot);
+        meshi_update(engine);
+        assert_eq!(unsafe { &*engine }.render.local_shadow_map_count(), 1);
+
+        unsafe { &mut *engine }
+            .render
+            .set_light_casts_shadows(light, false);
+        meshi_update(engine);
+        assert_eq!(unsafe { &*engine }.render.local_shadow_map_count(), 0);
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn requested_anisotropy_is_clamped_and_reported_by_samplers() {
+        fn applied_and_limit(anisotropy: u32) -> (u32, u32) {
+            let render = RenderEngine::new(&RenderEngineInfo {
+                headless: true,
+                anisotropy,
+                ..Default::default()
+            })
+            .expect("create render engine");
+            (
+                render.sampler_anisotropy(),
+                render.sampler_anisotropy_limit(),
+            )
+        }
+
+        let (applied, limit) = applied_and_limit(8);
+        assert!(limit >= 1);
+        assert_eq!(applied, 8.min(limit));
+
+        let (applied, limit) = applied_and_limit(1024);
+        assert_eq!(applied, limit);
+
+        let (applied, _) = applied_and_limit(0);
+        assert_eq!(applied, 1);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn pcf_softens_shadow_edges_without_touching_lit_or_shadowed_areas() {
+        // A 90 degree camera over the 64x48 display, looking down -Z.
+        const ASPECT: f32 = 64.0 / 48.0;
+        let light = Vec3::new(3.0, 0.0, -1.0);
+        let occluder_center = Vec3::new(0.0, 0.0, -4.0);
+        let occluder_half = Vec3::new(0.5, 1.5, 0.5);
+        let receiver_front = -5.95;
+
+        fn shadowed_frame(filter: ShadowFilter, light: Vec3, occluder: (Vec3, Vec3)) -> Frame {
+            let dir = tempfile::tempdir().expect("create temp dir");
+            let engine = headless_engine(dir.path());
+            let display = headless_display(engine);
+            let render = &mut unsafe { &mut *engine }.render;
+            let camera = render.register_camera(&Mat4::IDENTITY);
+            render.set_camera_perspective(
+                camera,
+                std::f32::consts::FRAC_PI_2,
+                64.0,
+                48.0,
+                0.1,
+                100.0,
+            );
+            meshi_gfx_attach_camera_to_display(engine, display, camera);
+            // A coarse map gives blocky edges for the kernel to blend.
+            let render = &mut unsafe { &mut *engine }.render;
+            render.set_shadow_resolution(32);
+            render.set_shadow_filtering(filter);
+
+            let objects = [
+                (Vec3::new(0.0, 0.0, -6.0), Vec3::new(4.0, 4.0, 0.05)),
+                occluder,
+            ];
+            for (center, half) in objects {
+                let object = meshi_gfx_create_render_object(
+                    engine,
+                    &RenderObjectInfo {
+                        mesh: std::ptr::null(),
+                        material: std::ptr::null(),
+                        transform: Mat4::IDENTITY,
+                    },
+                );
+                assert!(object.valid());
+                // Fit the default mesh to the requested box whatever its size.
+                let render = &mut unsafe { &mut *engine }.render;
+                let (min, max) = render.object_bounds(object).expect("object bounds");
+                let scale = half / ((max - min) * 0.5);
+                let offset = center - scale * (min + max) * 0.5;
+                render.set_object_transform(
+                    object,
+                    &Mat4::from_scale_rotation_translation(scale, Quat::IDENTITY, offset),
+                );
+            }
+            let spot = LightInfo::spot(
+                light,
+                (Vec3::new(0.0, 0.0, -6.0) - light).normalize(),
+                Vec3::ONE,
+                2.0,
+                20.0,
+                0.6,
+                0.8,
+            );
+            meshi_gfx_create_light(engine, &spot);
+
+            let frame = read_pixels(engine);
+            meshi_destroy_engine(engine);
+            frame
+        }
+
+        let screen_x = |point: Vec3| ((1.0 + point.x / (-point.z * ASPECT)) * 32.0) as u32;
+        // Where the ray from the light past `point` lands on the receiver.
+        let on_receiver = |point: Vec3| {
+            let t = (receiver_front - light.z) / (point.z - light.z);
+            light + (point - light) * t
+        };
+        let shadow_left = on_receiver(occluder_center + Vec3::new(-0.5, 0.0, 0.5));
+        let shadow_right = on_receiver(occluder_center + Vec3::new(0.5, 0.0, -0.5));
+        let umbra = screen_x((shadow_left + shadow_right) * 0.5);
+        let edge = screen_x(shadow_left);
+        let lit = screen_x(Vec3::new(2.0, 0.0, receiver_front));
+        let occluder_front = occluder_center + Vec3::new(-0.5, 0.0, 0.5);
+        assert!(
+            umbra + 2 < screen_x(occluder_front),
+            "occluder hides the umbra"
+        );
+
+        let occluder = (occluder_center, occluder_half);
+        let hard = shadowed_frame(
+            ShadowFilter {
+                mode: ShadowFilterMode::Hard,
+                kernel_size: 1,
+            },
+            light,
+            occluder,
+        );
+        let pcf = shadowed_frame(
+            ShadowFilter {
+                mode: ShadowFilterMode::Pcf,
+                kernel_size: 5,
+            },
+            light,
+            occluder,
+        );
+        let row = hard.height / 2;
+        let brightness = |[r, g, b]: [u8; 3]| r as u32 + g as u32 + b as u32;
+        assert!(
+            brightness(hard.rgb(umbra, row)) < brightness(hard.rgb(lit, row)),
+            "umbra sample is not shadowed"
+        );
+
+        assert_eq!(
+            hard.rgb(lit, row),
+            pcf.rgb(lit, row),
+            "PCF changed a lit pixel"
+        );
+        assert_eq!(
+            hard.rgb(umbra, row),
+            pcf.rgb(umbra, row),
+            "PCF changed an umbra pixel"
+        );
+        let softened = (edge.saturating_sub(3)..=edge + 3)
+            .filter(|&x| hard.rgb(x, row) != pcf.rgb(x, row))
+            .count();
+        assert!(
+            softened > 0,
+            "PCF left the shadow edge at x={edge} unchanged"
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn attached_body_drives_render_transform() {
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let engine = headless_engine(dir.path());
+        let meshi = unsafe { &mut *engine };
+        let object = meshi
+            .render
+            .register_object(&GfxRenderObjectInfo::Billboard(BillboardInfo {
+                texture_id: 0,
+                material: None,
+                billboard_type: BillboardType::ScreenAligned,
+                target: Vec3::ZERO,
+            }))
+            .expect("register billboard");
+        let body = meshi.physics.create_rigid_body(&RigidBodyInfo {
+            initial_position: Vec3::new(0.0, 10.0, 0.0),
+            has_gravity: 1,
+            ..Default::default()
+        });
+        assert!(meshi.attach_body_to_object(body, object));
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        meshi_update(engine);
+        let meshi = unsafe { &mut *engine };
+        let position = meshi.physics.get_rigid_body_status(body).unwrap().position;
+        assert!(position.y < 10.0);
+        assert_eq!(
+            meshi.render.object_transform(object).w_axis.truncate(),
+            position
+        );
+
+        meshi.detach_body_from_object(body, object);
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        meshi_update(engine);
+        let meshi = unsafe { &*engine };
+        assert_eq!(
+            meshi.render.object_transform(object).w_axis.truncate(),
+            position
+        );
+        assert_ne!(
+            meshi.physics.get_rigid_body_status(body).unwrap().position,
+            position
+        );
+        meshi_destroy_engine(engine);
+    }
+
+    #[test]
+    fn sphere_rests_on_flat_terrain_chunk() {
+        let artifact = TerrainChunkArtifact {
+            project_key: "terrain/test".to_string(),
+            chunk_coords: [0, 0],
+            lod: 0,
+            bounds_min: [0.0, 3.0, 0.0],
+            bounds_max: [4.0, 3.0, 4.0],
+            grid_size: [5, 5],
+            sample_spacing: 1.0,
+            heights: vec![3.0; 25],
+            normals: vec![[0.0, 1.0, 0.0]; 25],
+            hole_masks: vec![0; 25],
+            material_ids: None,
+            material_weights: None,
+            content_hash: 0,
+            material_blend_texture: Default::default(),
+        };
+        let mut sim = PhysicsSimulation::new(&SimulationInfo::default());
+        let transform = Mat4::from_translation(Vec3::new(-2.0, 0.0, -2.0));
+        physics_body_for_terrain_chunk(&mut sim, &artifact, &transform);
+
+        let frictionless = sim.create_material(&MaterialInfo {
+            dynamic_friction_m: 0.0,
+            static_friction_m: 0.0,
+            restitution: 0.0,
+            ..Default::default()
+        });
+        let sphere = sim.create_rigid_body(&RigidBodyInfo {
+            material: frictionless,
+            initial_position: Vec3::new(0.0, 6.0, 0.0),
+            has_gravity: 1,
+            collision_shape: meshi_physx_collision_shape_sphere(0.5),
+            ..Default::default()
+        });
+        for _ in 0..240 {
+            sim.update(1.0 / 60.0).unwrap();
+        }
+
+        let position = sim.get_rigid_body_status(sphere).unwrap().position;
+        assert!((position.y - 3.5).abs() < 0.01);
+    }
 }
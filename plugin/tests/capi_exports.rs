@@ -105,14 +105,8 @@ fn capi_headers_match_rust_exports() {
         header_exports.extend(declared_functions_from_header(&contents));
     }
 
-    let missing_in_headers: Vec<_> = rust_exports
-        .difference(&header_exports)
-        .cloned()
-        .collect();
-    let extra_in_headers: Vec<_> = header_exports
-        .difference(&rust_exports)
-        .cloned()
-        .collect();
+    let missing_in_headers: Vec<_> = rust_exports.difference(&header_exports).cloned().collect();
+    let extra_in_headers: Vec<_> = header_exports.difference(&rust_exports).cloned().collect();
 
     assert!(
         rust_exports.contains("meshi_plugin_get_api")
@@ -125,3 +119,58 @@ fn capi_headers_match_rust_exports() {
         "C headers do not match Rust exports.\nMissing in headers: {missing_in_headers:?}\nExtra in headers: {extra_in_headers:?}"
     );
 }
+
+fn struct_body<'a>(contents: &'a str, start: &str, end: &str) -> &'a str {
+    let start = contents
+        .find(start)
+        .unwrap_or_else(|| panic!("missing `{start}`"));
+    let body = &contents[start..];
+    let body = &body[body.find('{').expect("missing struct body") + 1..];
+    &body[..body.find(end).unwrap_or_else(|| panic!("missing `{end}`"))]
+}
+
+fn plugin_api_fields_from_rust(contents: &str) -> Vec<String> {
+    struct_body(contents, "pub struct MeshiPluginApi", "\n}")
+        .lines()
+        .filter_map(|line| line.strip_prefix("    pub "))
+        .filter_map(parse_identifier_at_start)
+        .collect()
+}
+
+fn plugin_api_fields_from_header(contents: &str) -> Vec<String> {
+    struct_body(
+        contents,
+        "typedef struct MeshiPluginApi",
+        "} MeshiPluginApi;",
+    )
+    .split(';')
+    .map(str::trim)
+    .filter(|entry| !entry.is_empty())
+    .filter_map(|entry| match entry.split("(*").nth(1) {
+        Some(pointer) => parse_identifier_at_start(pointer),
+        None => parse_last_identifier(entry),
+    })
+    .collect()
+}
+
+#[test]
+fn plugin_api_table_order_matches_header() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let rust = fs::read_to_string(manifest_dir.join("src").join("lib.rs"))
+        .expect("failed to read plugin source");
+    let header_path = manifest_dir
+        .join("..")
+        .join("capi")
+        .join("meshi-rs")
+        .join("meshi.h");
+    let header = fs::read_to_string(&header_path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", header_path.display()));
+
+    let rust_fields = plugin_api_fields_from_rust(&rust);
+    assert!(rust_fields.len() > 1, "no plugin API entries found");
+    assert_eq!(
+        rust_fields,
+        plugin_api_fields_from_header(&header),
+        "MeshiPluginApi entries must appear in the same order in Rust and C"
+    );
+}
@@ -1,42 +1,124 @@
 pub mod timer;
 
 use bento::BentoError;
+use dashi::GPUError;
 use furikake::error::FurikakeError;
 use noren::NorenError;
 
 #[derive(Debug)]
-pub struct MeshiError {
+pub enum MeshiError {
+    Gpu(GPUError),
+    Database(NorenError),
+    Asset(BentoError),
+    Shader(FurikakeError),
+    Other(String),
+}
 
+impl std::fmt::Display for MeshiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MeshiError::Gpu(err) => write!(f, "GPU error: {err:?}"),
+            MeshiError::Database(err) => write!(f, "Database error: {err:?}"),
+            MeshiError::Asset(err) => write!(f, "Asset error: {err:?}"),
+            MeshiError::Shader(err) => write!(f, "Shader error: {err:?}"),
+            MeshiError::Other(message) => write!(f, "{message}"),
+        }
+    }
 }
 
+impl std::error::Error for MeshiError {}
 
-impl From<dashi::GPUError> for MeshiError {
-    fn from(value: dashi::GPUError) -> Self {
-        todo!()
+impl From<GPUError> for MeshiError {
+    fn from(value: GPUError) -> Self {
+        MeshiError::Gpu(value)
     }
 }
 
 impl From<NorenError> for MeshiError {
     fn from(value: NorenError) -> Self {
-        todo!()
+        MeshiError::Database(value)
     }
 }
 
 impl From<BentoError> for MeshiError {
     fn from(value: BentoError) -> Self {
-        todo!()
+        MeshiError::Asset(value)
     }
 }
 
 impl From<FurikakeError> for MeshiError {
     fn from(value: FurikakeError) -> Self {
-        todo!()
+        MeshiError::Shader(value)
+    }
+}
+
+impl From<String> for MeshiError {
+    fn from(value: String) -> Self {
+        MeshiError::Other(value)
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asset_error_converts_to_asset_variant() {
+        let err = MeshiError::from(BentoError::InvalidInput("bad shader".to_string()));
+        assert!(matches!(err, MeshiError::Asset(_)));
+        assert!(!err.to_string().is_empty());
+    }
+
+    #[test]
+    fn question_mark_propagates_asset_error() {
+        fn compile() -> Result<(), MeshiError> {
+            Err(BentoError::InvalidInput("missing entry".to_string()))?;
+            Ok(())
+        }
+
+        assert!(matches!(compile(), Err(MeshiError::Asset(_))));
+    }
+
+    #[test]
+    fn gpu_error_converts_to_gpu_variant() {
+        let err = MeshiError::from(GPUError::Unimplemented("headless present"));
+        assert!(matches!(err, MeshiError::Gpu(_)));
+        assert!(err.to_string().starts_with("GPU error"));
+    }
+
+    #[test]
+    fn database_error_converts_to_database_variant() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut db = noren::DB::new(&noren::DBInfo {
+            base_dir: dir.path().to_str().unwrap(),
+            layout_file: None,
+            pooled_geometry_uploads: false,
+        })
+        .unwrap();
+
+        fn fetch(db: &mut noren::DB) -> Result<(), MeshiError> {
+            db.audio_mut().fetch_clip("audio/missing.wav")?;
+            Ok(())
+        }
+
+        let err = fetch(&mut db).unwrap_err();
+        assert!(matches!(err, MeshiError::Database(_)));
+        assert!(err.to_string().starts_with("Database error"));
+    }
+
+    #[test]
+    fn shader_error_converts_to_shader_variant() {
+        let err = MeshiError::from(FurikakeError::MissingReserved {
+            name: "meshi_bindless_skeletons".to_string(),
+        });
+        assert!(matches!(err, MeshiError::Shader(_)));
+        assert!(err.to_string().starts_with("Shader error"));
+    }
 
-//impl From<dashi::GPUError> for MeshiError {
-//    fn from(value: dashi::GPUError) -> Self {
-//        todo!()
-//    }
-//}
+    #[test]
+    fn other_error_displays_message() {
+        let err = MeshiError::from("mesh not found".to_string());
+        assert!(matches!(err, MeshiError::Other(_)));
+        assert_eq!(err.to_string(), "mesh not found");
+    }
+}
@@ -3,6 +3,7 @@ pub struct Timer {
     start_time: Option<Instant>,
     elapsed: Duration,
     is_paused: bool,
+    last_lap: Duration,
 }
 
 impl Timer {
@@ -12,6 +13,7 @@ impl Timer {
             start_time: None,
             elapsed: Duration::new(0, 0),
             is_paused: false,
+            last_lap: Duration::new(0, 0),
         }
     }
 
@@ -25,6 +27,7 @@ impl Timer {
             // Start or restart the timer
             self.start_time = Some(Instant::now());
             self.elapsed = Duration::new(0, 0);
+            self.last_lap = Duration::new(0, 0);
         }
     }
 
@@ -46,11 +49,32 @@ impl Timer {
         }
     }
 
+    // Resume a paused timer; does nothing if the timer isn't paused
+    pub fn resume(&mut self) {
+        if self.is_paused {
+            self.start();
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.is_paused
+    }
+
+    // Get the elapsed time since the previous lap (or start) without stopping.
+    // Paused time is excluded, as with elapsed_duration.
+    pub fn lap(&mut self) -> Duration {
+        let now = self.elapsed_duration();
+        let lap = now.saturating_sub(self.last_lap);
+        self.last_lap = now;
+        lap
+    }
+
     // Reset the timer
     pub fn reset(&mut self) {
         self.start_time = None;
         self.elapsed = Duration::new(0, 0);
         self.is_paused = false;
+        self.last_lap = Duration::new(0, 0);
     }
 
     // Get the current elapsed duration
@@ -90,7 +114,10 @@ impl Timer {
 #[cfg(test)]
 mod tests {
     use super::Timer;
-    use std::{thread, time::Duration};
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
 
     #[test]
     fn start_records_elapsed_time() {
@@ -126,6 +153,47 @@ mod tests {
         assert!(timer.elapsed_ms() > paused);
     }
 
+    #[test]
+    fn paused_span_is_excluded_from_elapsed() {
+        let mut timer = Timer::new();
+        timer.start();
+        thread::sleep(Duration::from_millis(10));
+        timer.pause();
+        assert!(timer.is_paused());
+        let paused = timer.elapsed_duration();
+        thread::sleep(Duration::from_millis(50));
+
+        let resumed_at = Instant::now();
+        timer.resume();
+        assert!(!timer.is_paused());
+        thread::sleep(Duration::from_millis(10));
+        timer.stop();
+        let running = resumed_at.elapsed();
+
+        // Only the time spent running after the resume is added on top of
+        // what had accumulated before the pause.
+        let elapsed = timer.elapsed_duration();
+        assert!(elapsed >= paused + Duration::from_millis(10));
+        assert!(elapsed <= paused + running);
+    }
+
+    #[test]
+    fn laps_sum_to_total() {
+        let mut timer = Timer::new();
+        timer.start();
+        thread::sleep(Duration::from_millis(5));
+        let first = timer.lap();
+        thread::sleep(Duration::from_millis(5));
+        let second = timer.lap();
+        thread::sleep(Duration::from_millis(5));
+        timer.pause();
+        let third = timer.lap();
+
+        assert!(first >= Duration::from_millis(5));
+        assert!(second >= Duration::from_millis(5));
+        assert_eq!(first + second + third, timer.elapsed_duration());
+    }
+
     #[test]
     fn stop_stops_elapsed_time() {
         let mut timer = Timer::new();